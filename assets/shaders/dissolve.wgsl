@@ -0,0 +1,56 @@
+// Ein Brick brennt weg: Alles, wo das Rauschen unter dem Fortschritt liegt, ist schon verschwunden,
+// knapp darüber glüht ein Rand in der Farbe der Brick-Stufe.
+struct DissolveMaterial {
+    color: vec4<f32>,
+    edge_color: vec4<f32>,
+    progress: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: DissolveMaterial;
+@group(1) @binding(1)
+var base_color_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var base_color_sampler: sampler;
+
+struct FragmentInput {
+    @builtin(front_facing) is_front: bool,
+    @builtin(position) frag_coord: vec4<f32>,
+    #import bevy_pbr::mesh_vertex_output
+};
+
+// Breite des glühenden Randes, gemessen am Rauschwert.
+const EDGE_WIDTH: f32 = 0.08;
+// Wie fein das Rauschen über die Oberfläche verteilt ist.
+const NOISE_SCALE: f32 = 6.0;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Einfaches Value-Noise, zwischen den Gitterpunkten weich interpoliert.
+fn noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash(cell);
+    let b = hash(cell + vec2<f32>(1.0, 0.0));
+    let c = hash(cell + vec2<f32>(0.0, 1.0));
+    let d = hash(cell + vec2<f32>(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    // In Weltkoordinaten, damit das Muster über alle Seiten des Würfels zusammenhängt.
+    let p = in.world_position.xy * NOISE_SCALE + in.world_position.z * 1.7;
+    let n = 0.65 * noise(p) + 0.35 * noise(p * 2.3);
+    if n < material.progress {
+        discard;
+    }
+    let texel = textureSample(base_color_texture, base_color_sampler, in.uv);
+    let edge = 1.0 - smoothstep(material.progress, material.progress + EDGE_WIDTH, n);
+    // Vor dem Verbrennen hat der Brick noch keinen Rand.
+    let glow = edge * step(0.001, material.progress);
+    return mix(material.color * texel, material.edge_color, glow);
+}
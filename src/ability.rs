@@ -0,0 +1,120 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::format;
+use crate::{phase_set, GameState, LevelEntity, Phase, TimeScale};
+
+const COOLDOWN_FONT_SIZE: f32 = 24.0;
+const COOLDOWN_TEXT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const COOLDOWN_READY_COLOR: Color = Color::rgb(0.1, 0.5, 0.1);
+
+// Jede Fähigkeit ist ein eigener Marker-Typ. Cooldown und Anzeige übernimmt das AbilityPlugin,
+// die Fähigkeit selbst muss nur noch entscheiden, was beim Auslösen passiert.
+pub trait AbilityKind: Send + Sync + 'static {
+    const NAME: &'static str;
+    const COOLDOWN: f32;
+    // Zeile in der Cooldown-Anzeige unten links, damit sich mehrere Fähigkeiten nicht überdecken.
+    const HUD_SLOT: usize;
+}
+
+#[derive(Component)]
+pub struct Ability<K: AbilityKind> {
+    cooldown: Timer,
+    marker: PhantomData<K>,
+}
+
+impl<K: AbilityKind> Default for Ability<K> {
+    // Eine neue Fähigkeit ist sofort einsatzbereit.
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(K::COOLDOWN, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+        Ability {
+            cooldown,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: AbilityKind> Ability<K> {
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+
+    // Löst die Fähigkeit aus, falls sie bereit ist, und startet dann den Cooldown.
+    pub fn try_activate(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        self.cooldown.reset();
+        true
+    }
+}
+
+#[derive(Component)]
+struct CooldownText<K: AbilityKind>(PhantomData<K>);
+
+pub struct AbilityPlugin<K: AbilityKind>(PhantomData<K>);
+
+impl<K: AbilityKind> Default for AbilityPlugin<K> {
+    fn default() -> Self {
+        AbilityPlugin(PhantomData)
+    }
+}
+
+impl<K: AbilityKind> Plugin for AbilityPlugin<K> {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_cooldown_text::<K>))
+            .add_system_set(phase_set(Phase::Scoring).with_system(tick_cooldowns::<K>))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_cooldown_text::<K>));
+    }
+}
+
+fn spawn_cooldown_text<K: AbilityKind>(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: COOLDOWN_FONT_SIZE,
+                color: COOLDOWN_TEXT_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(5.0 + K::HUD_SLOT as f32 * COOLDOWN_FONT_SIZE),
+                left: Val::Px(5.0),
+                ..default()
+            },
+            ..default()
+        }),
+        CooldownText::<K>(PhantomData),
+        LevelEntity,
+    ));
+}
+
+// Cooldowns laufen im festen Takt mit dem globalen Zeitfaktor, damit sie in Zeitlupe oder Pause ebenfalls langsamer
+// werden und nicht von der Bildrate abhängen.
+fn tick_cooldowns<K: AbilityKind>(mut query: Query<&mut Ability<K>>, time_scale: Res<TimeScale>) {
+    let delta = Duration::from_secs_f32(time_scale.step());
+    for mut ability in &mut query {
+        ability.cooldown.tick(delta);
+    }
+}
+
+fn update_cooldown_text<K: AbilityKind>(
+    ability_query: Query<&Ability<K>>,
+    mut text_query: Query<&mut Text, With<CooldownText<K>>>,
+) {
+    let (Ok(ability), Ok(mut text)) = (ability_query.get_single(), text_query.get_single_mut()) else { return };
+    let section = &mut text.sections[0];
+    if ability.is_ready() {
+        section.value = format!("{}: bereit", K::NAME);
+        section.style.color = COOLDOWN_READY_COLOR;
+    } else {
+        section.value = format!("{}: {}", K::NAME, format::seconds(ability.cooldown.remaining_secs()));
+        section.style.color = COOLDOWN_TEXT_COLOR;
+    }
+}
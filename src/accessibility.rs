@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::danger::DangerWarning;
+use crate::powerup::PowerUpKind;
+use crate::save::SaveData;
+
+// Farben aus der Palette von Okabe und Ito, die bei allen gängigen Farbsehschwächen unterscheidbar bleiben.
+const ORANGE: Color = Color::rgb(0.9, 0.62, 0.0);
+const SKY_BLUE: Color = Color::rgb(0.34, 0.71, 0.91);
+const BLUISH_GREEN: Color = Color::rgb(0.0, 0.62, 0.45);
+const YELLOW: Color = Color::rgb(0.94, 0.89, 0.26);
+const BLUE: Color = Color::rgb(0.0, 0.45, 0.7);
+const VERMILLION: Color = Color::rgb(0.84, 0.37, 0.0);
+const REDDISH_PURPLE: Color = Color::rgb(0.8, 0.47, 0.65);
+// Strafbricks heben sich in allen Paletten über die Helligkeit ab statt über den Farbton.
+const DARK_GRAY: Color = Color::rgb(0.15, 0.15, 0.15);
+const GRAY: Color = Color::rgb(0.5, 0.5, 0.5);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    const ALL: [ColorblindMode; 4] = [
+        ColorblindMode::Off,
+        ColorblindMode::Deuteranopia,
+        ColorblindMode::Protanopia,
+        ColorblindMode::Tritanopia,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorblindMode::Off => "Aus",
+            ColorblindMode::Deuteranopia => "Deuteranopie",
+            ColorblindMode::Protanopia => "Protanopie",
+            ColorblindMode::Tritanopia => "Tritanopie",
+        }
+    }
+
+    pub fn next(self) -> ColorblindMode {
+        let current = ColorblindMode::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        ColorblindMode::ALL[(current + 1) % ColorblindMode::ALL.len()]
+    }
+}
+
+// Alle Farben, an denen man im Spiel etwas erkennen muss, laufen über diese Ressource.
+// Ohne Farbsehschwäche-Modus bleiben die Farben der Skins, des Themes und der Power-Ups unverändert.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Palette {
+    pub mode: ColorblindMode,
+}
+
+impl Palette {
+    pub fn ball(&self, skin_color: Color) -> Color {
+        match self.mode {
+            ColorblindMode::Off => skin_color,
+            ColorblindMode::Deuteranopia | ColorblindMode::Protanopia => BLUE,
+            ColorblindMode::Tritanopia => Color::rgb(0.9, 0.1, 0.1),
+        }
+    }
+
+    pub fn piercing_ball(&self) -> Color {
+        match self.mode {
+            ColorblindMode::Off => crate::PIERCING_BALL_COLOR,
+            ColorblindMode::Deuteranopia | ColorblindMode::Protanopia => ORANGE,
+            ColorblindMode::Tritanopia => REDDISH_PURPLE,
+        }
+    }
+
+    // None heißt, die Farbe aus dem Theme gilt.
+    pub fn penalty_bricks(&self) -> Option<Color> {
+        (self.mode != ColorblindMode::Off).then_some(DARK_GRAY)
+    }
+
+    // Nützliche und gefährliche Power-Ups unterscheiden sich in jeder Palette deutlich.
+    pub fn powerup(&self, kind: PowerUpKind) -> Color {
+        match (self.mode, kind) {
+            // Mods bringen ihre eigenen Farben mit.
+            (ColorblindMode::Off, _) | (_, PowerUpKind::Mod(_)) => kind.color(),
+            (_, PowerUpKind::Coin) => Color::WHITE,
+            (ColorblindMode::Tritanopia, PowerUpKind::Smash) => VERMILLION,
+            (_, PowerUpKind::Smash) => YELLOW,
+            (_, PowerUpKind::Magnet) => REDDISH_PURPLE,
+            (_, PowerUpKind::Shrink) if self.mode == ColorblindMode::Tritanopia => Color::BLACK,
+            (_, PowerUpKind::Shrink) => YELLOW,
+            (_, PowerUpKind::SpeedUp) if self.mode == ColorblindMode::Tritanopia => GRAY,
+            (_, PowerUpKind::SpeedUp) => Color::BLACK,
+            (ColorblindMode::Deuteranopia, PowerUpKind::ReverseControls) => VERMILLION,
+            (ColorblindMode::Protanopia, PowerUpKind::ReverseControls) => GRAY,
+            (_, PowerUpKind::ReverseControls) => Color::rgb(0.55, 0.0, 0.3),
+            (ColorblindMode::Tritanopia, PowerUpKind::Fireball) => REDDISH_PURPLE,
+            (_, PowerUpKind::Fireball) => ORANGE,
+            (ColorblindMode::Tritanopia, PowerUpKind::Shield) => Color::rgb(0.0, 0.8, 0.8),
+            (_, PowerUpKind::Shield) => SKY_BLUE,
+            (_, PowerUpKind::Expand) => BLUISH_GREEN,
+            (_, PowerUpKind::GiantBall) => BLUE,
+            (ColorblindMode::Deuteranopia, PowerUpKind::MiniBall) => GRAY,
+            (_, PowerUpKind::MiniBall) => VERMILLION,
+        }
+    }
+}
+
+// Jedes System mit Bewegungs- oder Blinkeffekten fragt hier nach, bevor es den Effekt abspielt.
+// Das gilt auch für künftige Effekte wie Kamerawackeln oder pulsierendes Leuchten.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccessibilitySettings {
+    // Keine Trümmer, keine Spur, keine drehenden oder hüpfenden Objekte und kein schnelles Blinken.
+    pub reduced_motion: bool,
+    // Wichtige Töne werden zusätzlich als kleine Bildunterschrift mit Richtungspfeil angezeigt, siehe captions.rs.
+    #[serde(default)]
+    pub captions: bool,
+    // Wie früh Bildrand, Ton und Paddle vor einem tief fallenden Ball warnen, siehe danger.rs.
+    #[serde(default)]
+    pub danger_warning: DangerWarning,
+}
+
+impl AccessibilitySettings {
+    pub fn allows_motion(&self) -> bool {
+        !self.reduced_motion
+    }
+}
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        // Wie die Skins kommen auch diese Einstellungen aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let save = app.world.resource::<SaveData>();
+        let palette = Palette { mode: save.colorblind_mode };
+        let settings = save.accessibility;
+        app.insert_resource(palette)
+            .insert_resource(settings)
+            .add_system(store_accessibility);
+    }
+}
+
+fn store_accessibility(palette: Res<Palette>, settings: Res<AccessibilitySettings>, mut save: ResMut<SaveData>) {
+    if palette.is_changed() && save.colorblind_mode != palette.mode {
+        save.colorblind_mode = palette.mode;
+    }
+    if settings.is_changed() && save.accessibility != *settings {
+        save.accessibility = *settings;
+    }
+}
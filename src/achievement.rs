@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::mode::GameMode;
+use crate::save::SaveData;
+use crate::toast::ShowToast;
+use crate::{BrickDestroyed, GameState, LevelCleared};
+
+const BRICK_COLLECTOR_GOAL: u32 = 500;
+
+// Erfolge bleiben im Spielstand erhalten und schalten unter anderem neue Skins frei.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Achievement {
+    FirstClear,
+    CampaignComplete,
+    BrickCollector,
+}
+
+impl Achievement {
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::FirstClear => "Erstes Level geschafft",
+            Achievement::CampaignComplete => "Kampagne beendet",
+            Achievement::BrickCollector => "500 Bricks zerstört",
+        }
+    }
+}
+
+impl SaveData {
+    pub fn has_achievement(&self, achievement: Achievement) -> bool {
+        self.achievements.contains(&achievement)
+    }
+
+    // Gibt true zurück, wenn der Erfolg neu ist.
+    pub fn unlock(&mut self, achievement: Achievement) -> bool {
+        if self.has_achievement(achievement) {
+            return false;
+        }
+        info!("Erfolg freigeschaltet: {}", achievement.name());
+        self.achievements.push(achievement);
+        true
+    }
+}
+
+// Zerstörte Bricks des laufenden Levels. Sie werden erst beim Verlassen des Levels in den Spielstand übernommen,
+// damit nicht bei jedem Treffer die Datei geschrieben wird.
+#[derive(Resource, Default)]
+struct PendingBricks(u32);
+
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingBricks>()
+            .add_system(count_bricks)
+            .add_system(level_achievements)
+            .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(flush_bricks));
+    }
+}
+
+fn count_bricks(mut events: EventReader<BrickDestroyed>, mut pending: ResMut<PendingBricks>) {
+    pending.0 += events.iter().count() as u32;
+}
+
+// Jeder neu freigeschaltete Erfolg wird kurz eingeblendet.
+fn announce(achievement: Achievement) -> ShowToast {
+    ShowToast(format!("Erfolg: {}", achievement.name()))
+}
+
+// Was die Demo von selbst zerstört, zählt nicht.
+fn flush_bricks(
+    mut pending: ResMut<PendingBricks>,
+    mut save: ResMut<SaveData>,
+    mode: Res<GameMode>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    if pending.0 == 0 || *mode == GameMode::Demo {
+        pending.0 = 0;
+        return;
+    }
+    save.bricks_destroyed += pending.0;
+    pending.0 = 0;
+    if save.bricks_destroyed >= BRICK_COLLECTOR_GOAL && save.unlock(Achievement::BrickCollector) {
+        toasts.send(announce(Achievement::BrickCollector));
+    }
+}
+
+fn level_achievements(
+    mut events: EventReader<LevelCleared>,
+    mut save: ResMut<SaveData>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for event in events.iter() {
+        let mut unlocked = vec![Achievement::FirstClear];
+        if event.last {
+            unlocked.push(Achievement::CampaignComplete);
+        }
+        for achievement in unlocked {
+            if save.unlock(achievement) {
+                toasts.send(announce(achievement));
+            }
+        }
+    }
+}
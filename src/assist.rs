@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::resume::PendingResume;
+use crate::save::SaveData;
+use crate::shield::spawn_shield_entity;
+use crate::{Arena, GameState};
+
+const SLOW_BALL_FACTOR: f32 = 0.8;
+const WIDE_PADDLE_FACTOR: f32 = 1.5;
+// Der Schild der Hilfe hält praktisch das ganze Level, bis er den Ball einmal gerettet hat.
+const SAVE_SHIELD_DURATION: f32 = 3600.0;
+
+// Hilfen, die sich einzeln einschalten lassen. Ergebnisse mit Hilfen werden als solche gekennzeichnet.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Assist {
+    // Zu Beginn jedes Levels liegt ein Schild unter dem Paddle, der den Ball einmal auffängt.
+    SaveShield,
+    SlowBall,
+    WidePaddle,
+    InfiniteLives,
+    // Ball und Power-Ups richten sich nach verlorenen Bällen und langen Kombos, siehe difficulty.rs.
+    Adaptive,
+}
+
+impl Assist {
+    pub const ALL: [Assist; 5] =
+        [Assist::SaveShield, Assist::SlowBall, Assist::WidePaddle, Assist::InfiniteLives, Assist::Adaptive];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Assist::SaveShield => "Rettungsschild",
+            Assist::SlowBall => "Langsamer Ball",
+            Assist::WidePaddle => "Breites Paddle",
+            Assist::InfiniteLives => "Unendlich Leben",
+            Assist::Adaptive => "Anpassende Schwierigkeit",
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct Assists {
+    enabled: Vec<Assist>,
+}
+
+impl Assists {
+    pub fn is_enabled(&self, assist: Assist) -> bool {
+        self.enabled.contains(&assist)
+    }
+
+    pub fn toggle(&mut self, assist: Assist) {
+        if self.is_enabled(assist) {
+            self.enabled.retain(|enabled| *enabled != assist);
+        } else {
+            self.enabled.push(assist);
+        }
+    }
+
+    // Die Namen aller eingeschalteten Hilfen für die Kennzeichnung, leer ohne Hilfen.
+    pub fn label(&self) -> String {
+        Assist::ALL
+            .into_iter()
+            .filter(|assist| self.is_enabled(*assist))
+            .map(Assist::name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn ball_speed_factor(&self) -> f32 {
+        if self.is_enabled(Assist::SlowBall) { SLOW_BALL_FACTOR } else { 1.0 }
+    }
+
+    pub fn paddle_width_factor(&self) -> f32 {
+        if self.is_enabled(Assist::WidePaddle) { WIDE_PADDLE_FACTOR } else { 1.0 }
+    }
+}
+
+pub struct AssistPlugin;
+
+impl Plugin for AssistPlugin {
+    fn build(&self, app: &mut App) {
+        // Die Hilfen kommen aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let assists = app.world.resource::<SaveData>().assists.clone();
+        app.insert_resource(assists)
+            .add_system(store_assists)
+            .add_system_set(
+                SystemSet::on_enter(GameState::Playing).with_system(spawn_save_shield.after(crate::spawn_level)),
+            );
+    }
+}
+
+fn store_assists(assists: Res<Assists>, mut save: ResMut<SaveData>) {
+    if assists.is_changed() && save.assists != *assists {
+        save.assists = assists.clone();
+    }
+}
+
+// Ein fortgesetzter Durchlauf bringt seinen eigenen Schild aus dem Spielstand mit.
+fn spawn_save_shield(
+    mut commands: Commands,
+    assists: Res<Assists>,
+    pending: Res<PendingResume>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    arena: Res<Arena>,
+) {
+    if !assists.is_enabled(Assist::SaveShield) || pending.0.is_some() {
+        return;
+    }
+    spawn_shield_entity(&mut commands, &mut meshes, &mut materials, &arena, SAVE_SHIELD_DURATION);
+}
@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::config::GameConfig;
+use crate::level::Campaign;
+use crate::mode::GameMode;
+use crate::rng::GameRng;
+use crate::transition::ScreenTransition;
+use crate::{phase_set, Arena, Ball, GameState, LevelEntity, Mutators, Paddle, Phase, TimeScale, PADDLE_Y};
+
+// So lange muss das Hauptmenü unberührt bleiben, bis die Demo startet, und so lange läuft sie höchstens.
+const IDLE_SECONDS: f32 = 30.0;
+const DEMO_SECONDS: f32 = 60.0;
+const OVERLAY_FONT_SIZE: f32 = 40.0;
+const OVERLAY_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+
+// Wie lange im Hauptmenü nichts gedrückt wurde, und in der Demo, wie lange sie schon läuft.
+#[derive(Resource, Default)]
+struct AttractClock(f32);
+
+#[derive(Component)]
+struct DemoOverlay;
+
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractClock>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_clock))
+            .add_system_set(SystemSet::on_update(GameState::Menu).with_system(start_demo))
+            .add_system_set(
+                SystemSet::on_enter(GameState::Playing)
+                    .with_system(reset_clock)
+                    .with_system(spawn_overlay),
+            )
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(end_demo))
+            .add_system_set(phase_set(Phase::Input).with_system(steer_paddle.after(crate::move_object)));
+    }
+}
+
+fn reset_clock(mut clock: ResMut<AttractClock>) {
+    clock.0 = 0.0;
+}
+
+// Jede Taste, ein Mausklick, eine Mausbewegung oder ein Gamepad-Knopf.
+fn any_input(
+    keyboard_input: &Input<KeyCode>,
+    mouse_input: &Input<MouseButton>,
+    gamepad_buttons: &Input<GamepadButton>,
+    cursor_events: &mut EventReader<CursorMoved>,
+) -> bool {
+    let moved = cursor_events.iter().count() > 0;
+    moved
+        || keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_buttons.get_just_pressed().next().is_some()
+}
+
+// Bleibt das Hauptmenü lange unberührt, spielt die Demo ein zufälliges Level der Kampagne.
+#[allow(clippy::too_many_arguments)]
+fn start_demo(
+    mut clock: ResMut<AttractClock>,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut cursor_events: EventReader<CursorMoved>,
+    mut campaign: ResMut<Campaign>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut rng: ResMut<GameRng>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    if any_input(&keyboard_input, &mouse_input, &gamepad_buttons, &mut cursor_events) || transition.is_active() {
+        clock.0 = 0.0;
+        return;
+    }
+    clock.0 += time.delta_seconds();
+    if clock.0 < IDLE_SECONDS || campaign.levels.is_empty() {
+        return;
+    }
+    campaign.current = rng.gen_range(0..campaign.levels.len());
+    campaign.custom_level = None;
+    *mutators = Mutators::default();
+    *mode = GameMode::Demo;
+    transition.to(GameState::Playing);
+}
+
+fn spawn_overlay(mut commands: Commands, asset_server: Res<AssetServer>, mode: Res<GameMode>) {
+    if *mode != GameMode::Demo {
+        return;
+    }
+    commands.spawn((
+        TextBundle::from_section(
+            "Demo - beliebige Taste drücken",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: OVERLAY_FONT_SIZE,
+                color: OVERLAY_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(20.0),
+                left: Val::Px(20.0),
+                ..default()
+            },
+            ..default()
+        }),
+        DemoOverlay,
+        LevelEntity,
+    ));
+}
+
+// Eine Eingabe oder das Ende der Spielzeit bringt die Demo zurück ins Hauptmenü. Die Schrift blinkt dabei langsam.
+#[allow(clippy::too_many_arguments)]
+fn end_demo(
+    mode: Res<GameMode>,
+    mut clock: ResMut<AttractClock>,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut cursor_events: EventReader<CursorMoved>,
+    mut overlay_query: Query<&mut Text, With<DemoOverlay>>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    if *mode != GameMode::Demo {
+        cursor_events.clear();
+        return;
+    }
+    clock.0 += time.delta_seconds();
+    for mut text in &mut overlay_query {
+        text.sections[0].style.color.set_a(if clock.0.fract() < 0.5 { 1.0 } else { 0.3 });
+    }
+    let touched = any_input(&keyboard_input, &mouse_input, &gamepad_buttons, &mut cursor_events);
+    if (touched || clock.0 >= DEMO_SECONDS) && !transition.is_active() {
+        transition.to(GameState::Menu);
+    }
+}
+
+// In der Demo folgt das Paddle der Stelle, an der der fallende Ball die Höhe des Paddles erreichen wird.
+// Abpraller an den Seitenwänden werden dabei mitgerechnet. Schneller als ein Spieler ist es nicht.
+#[allow(clippy::type_complexity)]
+fn steer_paddle(
+    mode: Res<GameMode>,
+    mut paddle_query: Query<&mut Transform, With<Paddle>>,
+    ball_query: Query<(&Transform, &crate::Kinematic), (With<Ball>, Without<Paddle>)>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    time_scale: Res<TimeScale>,
+) {
+    if *mode != GameMode::Demo {
+        return;
+    }
+    let Ok(mut paddle_transform) = paddle_query.get_single_mut() else { return };
+    // Bei mehreren Bällen zählt der, der dem Paddle am nächsten ist.
+    let lowest = ball_query.iter().min_by(|(a, _), (b, _)| a.translation.y.total_cmp(&b.translation.y));
+    let Some((ball_transform, body)) = lowest else { return };
+
+    let ball = ball_transform.translation;
+    let target = if body.velocity.y < 0.0 {
+        let radius = ball_transform.scale.x / 2.0;
+        let left = arena.left() + radius;
+        let width = (arena.right() - radius - left).max(f32::EPSILON);
+        let fall_time = (ball.y - PADDLE_Y).max(0.0) / -body.velocity.y;
+        // Die gerade Flugbahn wird an den Wänden zurückgefaltet.
+        let unfolded = (ball.x + body.velocity.x * fall_time - left).rem_euclid(2.0 * width);
+        left + if unfolded > width { 2.0 * width - unfolded } else { unfolded }
+    } else {
+        ball.x
+    };
+    let max_step = config.paddle_speed * time_scale.step();
+    let x = paddle_transform.translation.x + (target - paddle_transform.translation.x).clamp(-max_step, max_step);
+    paddle_transform.translation.x = arena.clamp_paddle_x(x, paddle_transform.scale.x);
+}
@@ -0,0 +1,106 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+// Ohne Angabe im Level fliegt dieser Ball.
+pub const DEFAULT_BALL: &str = "default";
+
+// Wie sich ein Ball beim Abprallen verhält, so wie es in assets/balls/<name>.ball.ron steht. Ein Level wählt seinen
+// Ball über den Namen der Datei, so entstehen neue Bälle wie ein schwerer oder ein springender ohne neuen Code.
+// Fehlt ein Wert in der Datei, gilt der Standardwert von hier.
+#[derive(Deserialize, TypeUuid, Debug, Clone, Copy, PartialEq)]
+#[uuid = "8e2f4a61-5c3d-4b7e-9f10-2a6d8c4e7b35"]
+#[serde(default)]
+pub struct BallPhysics {
+    // Anteil der Geschwindigkeit, den der Ball bei jedem Abprall behält. Das Material eines Bricks kommt dazu.
+    pub restitution: f32,
+    // Wie schnell der Ball nach einem bremsenden oder beschleunigenden Abprall mindestens und höchstens sein darf,
+    // gemessen an der Geschwindigkeit des Levels. Sonst würde er an einer Metallwand immer schneller oder an Stein
+    // fast stehen bleiben.
+    pub min_speed: f32,
+    pub max_speed: f32,
+    // Um diesen Anteil der Geschwindigkeit des Levels wird der Ball bei jedem Treffer auf dem Paddle schneller,
+    // bis max_speed erreicht ist.
+    pub ramp_rate: f32,
+    // So viel Drall (Radiant pro Sekunde) gibt eine Einheit Paddle-Geschwindigkeit beim Abprall mit.
+    pub spin_response: f32,
+}
+
+impl Default for BallPhysics {
+    fn default() -> Self {
+        BallPhysics {
+            restitution: 1.0,
+            min_speed: 0.6,
+            max_speed: 1.5,
+            ramp_rate: 0.0,
+            spin_response: 0.6,
+        }
+    }
+}
+
+impl BallPhysics {
+    // Der Pfad der Datei zu einem Ball, wie ihn ein Level nennt.
+    pub fn path(name: &str) -> String {
+        format!("balls/{name}.ball.ron")
+    }
+
+    // Die Geschwindigkeit nach einem Abprall, bei dem das Material `surface_restitution` behält. Die Richtung
+    // bleibt, nur der Betrag ändert sich. Ändert sich nichts, bleibt auch ein Ball außerhalb der Grenzen so schnell,
+    // wie er ist, etwa nach einem Bumper.
+    pub fn respond(&self, velocity: Vec3, surface_restitution: f32, ball_speed: f32) -> Vec3 {
+        let restitution = self.restitution * surface_restitution;
+        if restitution == 1.0 {
+            return velocity;
+        }
+        let speed = (velocity.length() * restitution).clamp(ball_speed * self.min_speed, ball_speed * self.max_speed);
+        velocity.normalize_or_zero() * speed
+    }
+
+    // Die Geschwindigkeit nach einem Treffer auf dem Paddle. Ein Ball, der schon schneller ist, wird nicht gebremst.
+    pub fn ramp(&self, velocity: Vec3, ball_speed: f32) -> Vec3 {
+        let speed = velocity.length();
+        let cap = ball_speed * self.max_speed;
+        if self.ramp_rate <= 0.0 || speed >= cap {
+            return velocity;
+        }
+        velocity.normalize_or_zero() * (speed + ball_speed * self.ramp_rate).min(cap)
+    }
+}
+
+// Bis die Datei geladen ist, und für Bälle ohne Datei, etwa im Lasttest, gelten die Standardwerte.
+pub fn resolve(handle: Option<&Handle<BallPhysics>>, assets: &Assets<BallPhysics>) -> BallPhysics {
+    handle.and_then(|handle| assets.get(handle)).copied().unwrap_or_default()
+}
+
+#[derive(Default)]
+struct BallPhysicsLoader;
+
+impl AssetLoader for BallPhysicsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let physics = ron::de::from_bytes::<BallPhysics>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(physics));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ball.ron"]
+    }
+}
+
+pub struct BallPhysicsPlugin;
+
+impl Plugin for BallPhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<BallPhysics>().init_asset_loader::<BallPhysicsLoader>();
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::config::GameConfig;
+use crate::effect::{Stacking, TimedEffect, TimedEffectPlugin};
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::run::RunModifiers;
+use crate::{phase_set, Ball, Phase, TimeScale};
+
+const GIANT_FACTOR: f32 = 1.8;
+const MINI_FACTOR: f32 = 0.5;
+const RESIZE_DURATION: f32 = 10.0;
+// Größenänderung pro Sekunde, damit der Ball nicht schlagartig springt.
+const RESIZE_SPEED: f32 = 0.5;
+// Der kleine Ball ist schwerer zu treffen, dafür zählt jeder Brick, den er direkt trifft, doppelt.
+const MINI_SCORE_FACTOR: usize = 2;
+
+// Eine zeitlich begrenzte Größenänderung des Balls. Riesen- und Miniball heben sich auf, siehe Stacking.
+#[derive(Component)]
+pub struct BallResize {
+    factor: f32,
+    duration: Timer,
+}
+
+impl BallResize {
+    pub fn score_factor(&self) -> usize {
+        if self.factor < 1.0 { MINI_SCORE_FACTOR } else { 1 }
+    }
+}
+
+impl TimedEffect for BallResize {
+    fn timer(&self) -> &Timer {
+        &self.duration
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.duration
+    }
+
+    fn kind(&self) -> PowerUpKind {
+        if self.factor > 1.0 { PowerUpKind::GiantBall } else { PowerUpKind::MiniBall }
+    }
+}
+
+pub struct BallSizePlugin;
+
+impl Plugin for BallSizePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(TimedEffectPlugin::<BallResize>::default())
+            .add_system(apply_ball_powerups)
+            .add_system_set(phase_set(Phase::Input).with_system(tween_ball_size));
+    }
+}
+
+fn apply_ball_powerups(
+    mut commands: Commands,
+    mut events: EventReader<PowerUpCollected>,
+    ball_query: Query<(Entity, Option<&BallResize>), With<Ball>>,
+) {
+    for PowerUpCollected(kind) in events.iter() {
+        let factor = match kind {
+            PowerUpKind::GiantBall => GIANT_FACTOR,
+            PowerUpKind::MiniBall => MINI_FACTOR,
+            _ => continue,
+        };
+        for (ball, resize) in &ball_query {
+            if Stacking::resize(resize.map(|resize| resize.factor), factor) == Stacking::Cancel {
+                commands.entity(ball).remove::<BallResize>();
+                continue;
+            }
+            commands.entity(ball).insert(BallResize {
+                factor,
+                duration: Timer::from_seconds(RESIZE_DURATION, TimerMode::Once),
+            });
+        }
+    }
+}
+
+// Die Größe läuft gleichmäßig auf den Zielwert zu. Ohne Effekt ist das die Größe aus Balancing und Modifikatoren.
+// Die Kollisionen lesen die Größe aus dem Transform, sie gilt also in jedem Schritt so, wie der Ball gerade aussieht.
+fn tween_ball_size(
+    mut query: Query<(&mut Transform, Option<&BallResize>), With<Ball>>,
+    time_scale: Res<TimeScale>,
+    config: Res<GameConfig>,
+    run_modifiers: Res<RunModifiers>,
+) {
+    let max_change = RESIZE_SPEED * time_scale.step();
+    for (mut transform, resize) in &mut query {
+        let target = config.ball_size * run_modifiers.ball_size_factor() * resize.map_or(1.0, |resize| resize.factor);
+        let change = (target - transform.scale).clamp_length_max(max_change);
+        transform.scale += change;
+    }
+}
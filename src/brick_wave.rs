@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::easing::Ease;
+use crate::tween::{ScaleLens, TranslationLens, Tween};
+use crate::{Brick, GameState};
+
+// Die Reihen steigen von unten nach oben nacheinander auf, in jeder Reihe läuft die Welle von links nach rechts.
+const ROW_DELAY: f32 = 0.08;
+const COLUMN_DELAY: f32 = 0.02;
+const RISE_DURATION: f32 = 0.45;
+// So weit unter ihrem Platz beginnen die Bricks.
+const RISE_DISTANCE: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+enum WaveState {
+    // Das Level ist gerade betreten, die Bricks kommen erst mit dem nächsten Abgleich des Rasters in die Welt.
+    Waiting,
+    Running,
+    #[default]
+    Done,
+}
+
+// Die Welle, mit der die Bricks zu Beginn eines Levels an ihren Platz steigen. Solange sie läuft, bleibt der Ball
+// auf dem Paddle. Bricks, die später im Level dazukommen, etwa nachwachsende, erscheinen sofort.
+#[derive(Resource, Default)]
+pub struct BrickWave(WaveState);
+
+impl BrickWave {
+    pub fn is_running(&self) -> bool {
+        self.0 == WaveState::Running
+    }
+}
+
+pub struct BrickWavePlugin;
+
+impl Plugin for BrickWavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BrickWave>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_wave))
+            // Erst nach dem Update sind die Bricks aus dem Abgleich beim Betreten des Levels in der Welt.
+            .add_system_to_stage(CoreStage::PostUpdate, attach_spawn_animations)
+            .add_system(finish_wave);
+    }
+}
+
+// Mit weniger Bewegung stehen die Bricks sofort an ihrem Platz.
+fn start_wave(mut wave: ResMut<BrickWave>, settings: Res<AccessibilitySettings>) {
+    wave.0 = if settings.allows_motion() { WaveState::Waiting } else { WaveState::Done };
+}
+
+// Kommen nach dem Betreten keine Bricks, etwa in einem leeren Level, gibt es keine Welle. Die Bricks wachsen beim
+// Aufsteigen auf ihre Größe und schwingen leicht über ihren Platz hinaus. Bis ihre Verzögerung abgelaufen ist,
+// haben sie noch keine Größe.
+fn attach_spawn_animations(
+    mut commands: Commands,
+    mut wave: ResMut<BrickWave>,
+    mut query: Query<(Entity, &Brick, &mut Transform), Added<Brick>>,
+) {
+    if wave.0 != WaveState::Waiting {
+        return;
+    }
+    wave.0 = if query.is_empty() { WaveState::Done } else { WaveState::Running };
+    for (entity, brick, mut transform) in &mut query {
+        let delay = brick.row as f32 * ROW_DELAY + brick.column as f32 * COLUMN_DELAY;
+        let rise = TranslationLens {
+            start: transform.translation - Vec3::Y * RISE_DISTANCE,
+            end: transform.translation,
+        };
+        let grow = ScaleLens {
+            start: Vec3::ZERO,
+            end: transform.scale,
+        };
+        commands.entity(entity).insert((
+            Tween::new(rise, RISE_DURATION).with_ease(Ease::OutBack).with_delay(delay),
+            Tween::new(grow, RISE_DURATION).with_ease(Ease::OutCubic).with_delay(delay),
+        ));
+        transform.scale = Vec3::ZERO;
+    }
+}
+
+// Die Welle ist vorbei, sobald kein Brick mehr aufsteigt.
+fn finish_wave(mut wave: ResMut<BrickWave>, query: Query<(), (With<Brick>, With<Tween<TranslationLens>>)>) {
+    if wave.0 == WaveState::Running && query.is_empty() {
+        wave.0 = WaveState::Done;
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::AccessibilitySettings;
+use crate::level::{Campaign, Level};
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, Ball, GameState, Kinematic, LevelEntity, Phase, Scoreboard};
+
+const BUMPER_SCORE: usize = 5;
+// Faktor, um den der Ball bei jedem Treffer schneller wird, und die Obergrenze dafür.
+const BUMPER_BOOST: f32 = 1.2;
+const BUMPER_MAX_SPEED: f32 = 14.0;
+const BUMPER_POP_DURATION: f32 = 0.2;
+const BUMPER_POP_SCALE: f32 = 0.35;
+
+fn default_radius() -> f32 {
+    0.5
+}
+
+// Ein Bumper, wie er in der Level-Datei angegeben wird.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BumperDefinition {
+    pub position: Vec2,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+}
+
+#[derive(Component)]
+struct Bumper {
+    radius: f32,
+    // Läuft nach jedem Treffer einmal ab und treibt die "Pop"-Animation.
+    pop: Timer,
+}
+
+pub struct BumperPlugin;
+
+impl Plugin for BumperPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_bumpers))
+            .add_system_set(phase_set(Phase::Collision).with_system(bumper_collision))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(animate_bumpers));
+    }
+}
+
+fn spawn_bumpers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if level.bumpers.is_empty() {
+        return;
+    }
+
+    let mesh = meshes.add(shape::UVSphere::default().into());
+    let material = materials.add(StandardMaterial {
+        base_color: Color::GOLD,
+        metallic: 0.8,
+        perceptual_roughness: 0.3,
+        ..default()
+    });
+    for definition in &level.bumpers {
+        // Der Timer startet abgelaufen, damit der Bumper zu Beginn nicht "poppt".
+        let mut pop = Timer::from_seconds(BUMPER_POP_DURATION, TimerMode::Once);
+        pop.tick(pop.duration());
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(definition.position.extend(0.0))
+                    .with_scale(Vec3::splat(definition.radius)),
+                ..default()
+            },
+            Bumper {
+                radius: definition.radius,
+                pop,
+            },
+            LevelEntity,
+        ));
+    }
+}
+
+// Bumper sind rund, deshalb wird hier nicht collide_aabb genutzt, sondern der Abstand der Mittelpunkte.
+// Der Ball wird an der Normalen gespiegelt, beschleunigt und aus dem Bumper herausgeschoben.
+fn bumper_collision(
+    mut ball_query: Query<(&mut Transform, &mut Kinematic), With<Ball>>,
+    mut bumper_query: Query<(&Transform, &mut Bumper), Without<Ball>>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for (mut ball_transform, mut body) in &mut ball_query {
+        let ball_radius = ball_transform.scale.x / 2.0;
+        for (bumper_transform, mut bumper) in &mut bumper_query {
+            let offset = (ball_transform.translation - bumper_transform.translation).truncate();
+            let min_distance = bumper.radius + ball_radius;
+            if offset.length_squared() >= min_distance * min_distance {
+                continue;
+            }
+            let normal = offset.normalize_or_zero().extend(0.0);
+            // Nur reflektieren, wenn sich der Ball auf den Bumper zu bewegt.
+            if body.velocity.dot(normal) < 0.0 {
+                let reflected = body.velocity - 2.0 * body.velocity.dot(normal) * normal;
+                body.velocity = (reflected * BUMPER_BOOST).clamp_length_max(BUMPER_MAX_SPEED);
+            }
+            ball_transform.translation = bumper_transform.translation + normal * min_distance;
+
+            scoreboard.score += BUMPER_SCORE;
+            bumper.pop.reset();
+            sounds.send(PlaySound(Sfx::Bumper));
+        }
+    }
+}
+
+fn animate_bumpers(
+    mut query: Query<(&mut Transform, &mut Bumper)>,
+    time: Res<Time>,
+    settings: Res<AccessibilitySettings>,
+) {
+    for (mut transform, mut bumper) in &mut query {
+        bumper.pop.tick(time.delta());
+        let pop = if settings.allows_motion() { 1.0 + BUMPER_POP_SCALE * bumper.pop.percent_left() } else { 1.0 };
+        transform.scale = Vec3::splat(bumper.radius * pop);
+    }
+}
@@ -0,0 +1,171 @@
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::sound::{PlaySound, PlaySoundAt, Sfx};
+use crate::MainCamera;
+
+const CAPTION_DURATION: f32 = 2.0;
+const FADE_DURATION: f32 = 0.5;
+const CAPTION_FONT_SIZE: f32 = 24.0;
+const CAPTION_TEXT_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
+const CAPTION_BACKGROUND: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
+// Die Pfeile der acht Richtungen, gegen den Uhrzeigersinn von rechts aus.
+const ARROWS: [char; 8] = ['→', '↗', '↑', '↖', '←', '↙', '↓', '↘'];
+// Liegt die Quelle näher an der Bildmitte als dieser Anteil der kürzeren Bildseite, gibt es keinen Pfeil.
+const CENTER_RADIUS: f32 = 0.1;
+
+// Die Spalte unten in der Mitte, in der die Bildunterschriften stehen.
+#[derive(Component)]
+struct CaptionStack;
+
+// Jeder Ton hat höchstens eine Bildunterschrift. Kommt er wieder, bevor sie verschwunden ist, steht sie länger.
+#[derive(Component)]
+struct Caption {
+    sfx: Sfx,
+    timer: Timer,
+}
+
+// Bildunterschriften für wichtige Töne, für alle, die schlecht oder gar nicht hören. Sie hängen an denselben
+// Events wie der Ton und zeigen mit einem Pfeil, woher er kommt.
+pub struct CaptionsPlugin;
+
+impl Plugin for CaptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_caption_stack)
+            .add_system(show_captions)
+            .add_system(fade_captions.after(show_captions));
+    }
+}
+
+fn spawn_caption_stack(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                // Über dem Paddle, damit der Blick nicht weit wandern muss.
+                position: UiRect {
+                    left: Val::Px(0.0),
+                    bottom: Val::Px(80.0),
+                    ..default()
+                },
+                size: Size::new(Val::Percent(100.0), Val::Auto),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        CaptionStack,
+    ));
+}
+
+// Zeigt von der Bildmitte zur Stelle des Tons. Außerhalb des Bildes gilt die Richtung zur projizierten Stelle.
+fn direction_arrow(camera: &Camera, camera_transform: &GlobalTransform, position: Vec3) -> Option<char> {
+    let size = camera.logical_viewport_size()?;
+    let offset = camera.world_to_viewport(camera_transform, position)? - size / 2.0;
+    if offset.length() < CENTER_RADIUS * size.min_element() {
+        return None;
+    }
+    let sector = (offset.y.atan2(offset.x) / FRAC_PI_4).round() as i32;
+    Some(ARROWS[sector.rem_euclid(ARROWS.len() as i32) as usize])
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_captions(
+    mut commands: Commands,
+    mut sounds: EventReader<PlaySound>,
+    mut positioned: EventReader<PlaySoundAt>,
+    settings: Res<AccessibilitySettings>,
+    stack_query: Query<Entity, With<CaptionStack>>,
+    mut caption_query: Query<(&mut Caption, &Children)>,
+    mut text_query: Query<&mut Text>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !settings.captions {
+        sounds.clear();
+        positioned.clear();
+        return;
+    }
+    let camera = camera_query.get_single().ok();
+    let mut cues: Vec<(Sfx, String)> = Vec::new();
+    let events = sounds
+        .iter()
+        .map(|PlaySound(sfx)| (*sfx, None))
+        .chain(positioned.iter().map(|PlaySoundAt(sfx, position)| (*sfx, Some(*position))));
+    for (sfx, position) in events {
+        let Some(caption) = sfx.caption() else { continue };
+        let arrow = position.zip(camera).and_then(|(position, (camera, transform))| {
+            direction_arrow(camera, transform, position)
+        });
+        let label = arrow.map_or(caption.to_string(), |arrow| format!("{arrow} {caption}"));
+        // Mehrmals im selben Frame zählt der letzte, etwa bei mehreren Geschützen.
+        match cues.iter_mut().find(|(cued, _)| *cued == sfx) {
+            Some(cue) => cue.1 = label,
+            None => cues.push((sfx, label)),
+        }
+    }
+
+    let Ok(stack) = stack_query.get_single() else { return };
+    for (sfx, label) in cues {
+        if let Some((mut caption, children)) = caption_query.iter_mut().find(|(caption, _)| caption.sfx == sfx) {
+            caption.timer.reset();
+            if let Ok(mut text) = text_query.get_mut(children[0]) {
+                text.sections[0].value = label;
+            }
+            continue;
+        }
+        let caption = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(4.0)),
+                        padding: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    background_color: CAPTION_BACKGROUND.into(),
+                    ..default()
+                },
+                Caption {
+                    sfx,
+                    timer: Timer::from_seconds(CAPTION_DURATION, TimerMode::Once),
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                        font_size: CAPTION_FONT_SIZE,
+                        color: CAPTION_TEXT_COLOR,
+                    },
+                ));
+            })
+            .id();
+        commands.entity(stack).add_child(caption);
+    }
+}
+
+// Wie die Meldungen laufen auch die Bildunterschriften in echter Zeit und blenden am Ende aus.
+fn fade_captions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut caption_query: Query<(Entity, &mut Caption, &mut BackgroundColor, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, mut caption, mut background, children) in &mut caption_query {
+        if caption.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let alpha = (caption.timer.remaining_secs() / FADE_DURATION).min(1.0);
+        background.0.set_a(CAPTION_BACKGROUND.a() * alpha);
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].style.color.set_a(alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::config::GameConfig;
+use crate::grid::BrickGrid;
+use crate::level::BrickKind;
+use crate::run::RunModifiers;
+use crate::theme::{ThemeMaterials, CHAIN_BRICK_COLOR};
+use crate::{
+    brick_points, phase_set, Arena, Brick, BrickDestroyed, GridGeometry, LevelEntity, Mutators, Phase, Scoreboard,
+    TimeScale, BRICK_SIZE,
+};
+
+// So lange bleibt ein getroffenes Glied markiert. Ist bis dahin nicht die ganze Kette getroffen, schließt es sich wieder.
+const CHAIN_WINDOW: f32 = 4.0;
+const STRUCK_EMISSIVE: Color = Color::rgb(0.9, 0.6, 0.1);
+const LINK_THICKNESS: f32 = 0.06;
+
+// Ein getroffenes Glied, das auf den Rest seiner Kette wartet.
+#[derive(Component)]
+struct Struck(Timer);
+
+// Die sichtbare Verbindung zwischen zwei benachbarten Gliedern, von der unteren oder linken Zelle aus.
+#[derive(Component)]
+struct ChainLine {
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+pub struct ChainPlugin;
+
+impl Plugin for ChainPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            phase_set(Phase::Scoring)
+                .with_system(strike_links)
+                .with_system(break_chains.after(strike_links)),
+        )
+        .add_system_set(phase_set(Phase::Vfx).with_system(draw_links));
+    }
+}
+
+// Die Nachbarzellen rechts und oben, in denen ebenfalls ein Kettenglied liegt.
+fn neighbours(brick_grid: &BrickGrid, row: usize, column: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    [(row + 1, column), (row, column + 1)]
+        .into_iter()
+        .filter(|&(row, column)| brick_grid.get(row, column).is_some_and(|cell| cell.kind == BrickKind::Chain))
+}
+
+// Die Ketten des Rasters: Glieder, die waagerecht oder senkrecht aneinander grenzen, gehören zusammen.
+fn chains(brick_grid: &BrickGrid) -> Vec<Vec<(usize, usize)>> {
+    let links: Vec<(usize, usize)> = brick_grid
+        .bricks()
+        .filter(|(_, _, cell)| cell.kind == BrickKind::Chain)
+        .map(|(row, column, _)| (row, column))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut chains = Vec::new();
+    for &start in &links {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut chain = vec![start];
+        let mut index = 0;
+        while let Some(&(row, column)) = chain.get(index) {
+            index += 1;
+            // Unter 0 läuft der Index über und trifft kein Glied.
+            let adjacent =
+                [(row + 1, column), (row, column + 1), (row.wrapping_sub(1), column), (row, column.wrapping_sub(1))];
+            for cell in adjacent {
+                if links.contains(&cell) && seen.insert(cell) {
+                    chain.push(cell);
+                }
+            }
+        }
+        chains.push(chain);
+    }
+    chains
+}
+
+// Ein Glied ohne Leben wurde getroffen und leuchtet auf. Läuft seine Zeit ab, bekommt es sein Leben zurück.
+#[allow(clippy::type_complexity)]
+fn strike_links(
+    mut commands: Commands,
+    mut brick_grid: ResMut<BrickGrid>,
+    mut query: Query<(Entity, &Brick, &mut Handle<StandardMaterial>, Option<&mut Struck>)>,
+    theme_materials: Res<ThemeMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time_scale: Res<TimeScale>,
+    mut struck_material: Local<Option<Handle<StandardMaterial>>>,
+) {
+    let step = Duration::from_secs_f32(time_scale.step());
+    for (entity, brick, mut material, struck) in &mut query {
+        let Some(cell) = brick_grid.get(brick.row, brick.column) else { continue };
+        if cell.kind != BrickKind::Chain {
+            continue;
+        }
+        if let Some(mut struck) = struck {
+            if struck.0.tick(step).finished() {
+                brick_grid.heal(brick.row, brick.column);
+                *material = theme_materials.brick(BrickKind::Chain);
+                commands.entity(entity).remove::<Struck>();
+            }
+        } else if cell.health == 0 {
+            *material = struck_material
+                .get_or_insert_with(|| {
+                    materials.add(StandardMaterial {
+                        base_color: CHAIN_BRICK_COLOR,
+                        emissive: STRUCK_EMISSIVE,
+                        ..default()
+                    })
+                })
+                .clone();
+            commands.entity(entity).insert(Struck(Timer::from_seconds(CHAIN_WINDOW, TimerMode::Once)));
+        }
+    }
+}
+
+// Sind alle Glieder einer Kette getroffen, zerbricht sie auf einmal. Jedes Glied zählt wie ein zerstörter Brick.
+#[allow(clippy::too_many_arguments)]
+fn break_chains(
+    mut brick_grid: ResMut<BrickGrid>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut brick_destroyed_events: EventWriter<BrickDestroyed>,
+    theme_materials: Res<ThemeMaterials>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    run_modifiers: Res<RunModifiers>,
+    mutators: Res<Mutators>,
+) {
+    let geometry = GridGeometry::new(&arena);
+    for chain in chains(&brick_grid) {
+        let complete = chain
+            .iter()
+            .all(|&(row, column)| brick_grid.get(row, column).is_some_and(|cell| cell.health == 0));
+        if !complete {
+            continue;
+        }
+        for (row, column) in chain {
+            brick_grid.remove(row, column);
+            scoreboard.score += brick_points(BrickKind::Chain, &config, &run_modifiers, &mutators);
+            scoreboard.combo += 1;
+            brick_destroyed_events.send(BrickDestroyed {
+                position: geometry.position(row, column).extend(0.0),
+                material: theme_materials.brick(BrickKind::Chain),
+                kind: BrickKind::Chain,
+            });
+        }
+    }
+}
+
+// Hält für jedes Paar benachbarter Glieder eine dünne Stange vor den Bricks bereit. Zerbricht eine Kette,
+// verschwinden ihre Stangen mit ihr.
+fn draw_links(
+    mut commands: Commands,
+    brick_grid: Res<BrickGrid>,
+    arena: Res<Arena>,
+    query: Query<(Entity, &ChainLine)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut link_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    if !brick_grid.is_changed() {
+        return;
+    }
+    let wanted: Vec<((usize, usize), (usize, usize))> = brick_grid
+        .bricks()
+        .filter(|(_, _, cell)| cell.kind == BrickKind::Chain)
+        .flat_map(|(row, column, _)| neighbours(&brick_grid, row, column).map(move |to| ((row, column), to)))
+        .collect();
+    let mut existing = HashSet::new();
+    for (entity, line) in &query {
+        if wanted.contains(&(line.from, line.to)) {
+            existing.insert((line.from, line.to));
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let (mesh, material) = link_assets.get_or_insert_with(|| {
+        (
+            meshes.add(shape::Cube::default().into()),
+            materials.add(StandardMaterial {
+                base_color: CHAIN_BRICK_COLOR,
+                metallic: 1.0,
+                ..default()
+            }),
+        )
+    });
+    let geometry = GridGeometry::new(&arena);
+    for (from, to) in wanted.into_iter().filter(|link| !existing.contains(link)) {
+        let start = geometry.position(from.0, from.1);
+        let end = geometry.position(to.0, to.1);
+        let length = start.distance(end);
+        let scale = if from.0 == to.0 {
+            Vec3::new(length, LINK_THICKNESS, LINK_THICKNESS)
+        } else {
+            Vec3::new(LINK_THICKNESS, length, LINK_THICKNESS)
+        };
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(((start + end) / 2.0).extend(BRICK_SIZE.z / 2.0)).with_scale(scale),
+                ..default()
+            },
+            ChainLine { from, to },
+            LevelEntity,
+        ));
+    }
+}
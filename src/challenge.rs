@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+// Jede weitere Herausforderung legt noch so viel auf das Produkt der einzelnen Faktoren drauf.
+const COMBINATION_BONUS: f32 = 0.1;
+const TINY_PADDLE_FACTOR: f32 = 0.5;
+const DOUBLE_SPEED_FACTOR: f32 = 2.0;
+
+// Herausforderungen, die im Menü "Eigenes Spiel" beliebig kombiniert werden. Jede macht das Spiel schwerer
+// und bringt dafür mehr Punkte pro Brick.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Challenge {
+    TinyPaddle,
+    // Ein Brick ist erst zu sehen, wenn er getroffen wurde.
+    InvisibleBricks,
+    MirroredControls,
+    DoubleSpeed,
+    // Die ganze Arena dreht sich langsam um die Blickachse, siehe rotation.rs.
+    RotatingArena,
+}
+
+impl Challenge {
+    pub const ALL: [Challenge; 5] = [
+        Challenge::TinyPaddle,
+        Challenge::InvisibleBricks,
+        Challenge::MirroredControls,
+        Challenge::DoubleSpeed,
+        Challenge::RotatingArena,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Challenge::TinyPaddle => "Winziges Paddle",
+            Challenge::InvisibleBricks => "Unsichtbare Bricks",
+            Challenge::MirroredControls => "Gespiegelte Steuerung",
+            Challenge::DoubleSpeed => "Doppeltes Tempo",
+            Challenge::RotatingArena => "Drehende Arena",
+        }
+    }
+
+    fn score_factor(self) -> f32 {
+        match self {
+            Challenge::TinyPaddle => 1.5,
+            Challenge::InvisibleBricks => 1.5,
+            Challenge::MirroredControls => 1.25,
+            Challenge::DoubleSpeed => 1.5,
+            Challenge::RotatingArena => 1.75,
+        }
+    }
+}
+
+// Die gewählten Herausforderungen. Paddlebreite, Ballgeschwindigkeit und Steuerung lesen ihre Faktoren hier ab,
+// so wie bei den Hilfen. Unsichtbare Bricks werden wie Geisterbricks aufgedeckt, siehe ghost.rs.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Challenges(Vec<Challenge>);
+
+impl Challenges {
+    pub fn is_active(&self, challenge: Challenge) -> bool {
+        self.0.contains(&challenge)
+    }
+
+    pub fn toggle(&mut self, challenge: Challenge) {
+        if let Some(index) = self.0.iter().position(|active| *active == challenge) {
+            self.0.remove(index);
+        } else {
+            self.0.push(challenge);
+        }
+    }
+
+    // Das Produkt der einzelnen Faktoren. Wer mehrere kombiniert, bekommt zusätzlich einen Bonus.
+    pub fn score_factor(&self) -> f32 {
+        let product: f32 = self.0.iter().map(|challenge| challenge.score_factor()).product();
+        product * (1.0 + COMBINATION_BONUS * self.0.len().saturating_sub(1) as f32)
+    }
+
+    pub fn paddle_width_factor(&self) -> f32 {
+        if self.is_active(Challenge::TinyPaddle) { TINY_PADDLE_FACTOR } else { 1.0 }
+    }
+
+    pub fn ball_speed_factor(&self) -> f32 {
+        if self.is_active(Challenge::DoubleSpeed) { DOUBLE_SPEED_FACTOR } else { 1.0 }
+    }
+
+    // Faktor für die Eingaberichtung des Paddles, zusätzlich zu vertauschten Steuerungen durch Gefahren.
+    pub fn control_sign(&self) -> f32 {
+        if self.is_active(Challenge::MirroredControls) { -1.0 } else { 1.0 }
+    }
+}
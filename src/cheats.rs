@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::toast::ShowToast;
+use crate::{Ball, Brick, GameState, PenaltyBrick};
+
+const GIANT_BALL_FACTOR: f32 = 3.0;
+
+// Versteckte Modifikatoren, die es nur über Cheat-Codes gibt.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cheat {
+    GiantBall,
+    RainbowBricks,
+}
+
+impl Cheat {
+    fn name(self) -> &'static str {
+        match self {
+            Cheat::GiantBall => "Riesenball",
+            Cheat::RainbowBricks => "Regenbogen-Bricks",
+        }
+    }
+}
+
+// Eine Tastenfolge und der Cheat, den sie ein- oder ausschaltet.
+pub struct CheatCode {
+    pub sequence: Vec<KeyCode>,
+    pub cheat: Cheat,
+}
+
+// Die bekannten Codes. Weitere lassen sich einfach in die Ressource eintragen.
+#[derive(Resource)]
+pub struct CheatCodes(pub Vec<CheatCode>);
+
+impl Default for CheatCodes {
+    fn default() -> Self {
+        use KeyCode::*;
+        CheatCodes(vec![
+            CheatCode {
+                sequence: vec![Up, Up, Down, Down, Left, Right, Left, Right, B, A],
+                cheat: Cheat::GiantBall,
+            },
+            CheatCode {
+                sequence: vec![R, A, I, N, B, O, W],
+                cheat: Cheat::RainbowBricks,
+            },
+        ])
+    }
+}
+
+// Die aktiven Cheats. Sie gelten bis zum Beenden des Spiels.
+#[derive(Resource, Default)]
+pub struct Cheats {
+    active: Vec<Cheat>,
+    // Wurde im laufenden Durchlauf geschummelt, landet das Ergebnis in keiner Bestenliste.
+    pub used_this_run: bool,
+}
+
+impl Cheats {
+    fn is_active(&self, cheat: Cheat) -> bool {
+        self.active.contains(&cheat)
+    }
+}
+
+pub struct CheatPlugin;
+
+impl Plugin for CheatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CheatCodes>()
+            .init_resource::<Cheats>()
+            .add_system(detect_cheat_codes)
+            .add_system(apply_giant_ball)
+            .add_system(apply_rainbow_bricks)
+            .add_system_set(SystemSet::on_exit(GameState::Menu).with_system(start_run));
+    }
+}
+
+// Ein neuer Durchlauf beginnt nur dann sauber, wenn gerade kein Cheat aktiv ist.
+fn start_run(mut cheats: ResMut<Cheats>) {
+    cheats.used_this_run = !cheats.active.is_empty();
+}
+
+// Merkt sich die zuletzt gedrückten Tasten und vergleicht das Ende mit allen Codes.
+fn detect_cheat_codes(
+    keyboard_input: Res<Input<KeyCode>>,
+    codes: Res<CheatCodes>,
+    mut cheats: ResMut<Cheats>,
+    mut toasts: EventWriter<ShowToast>,
+    mut recent: Local<VecDeque<KeyCode>>,
+) {
+    let longest = codes.0.iter().map(|code| code.sequence.len()).max().unwrap_or(0);
+    for key in keyboard_input.get_just_pressed() {
+        recent.push_back(*key);
+        while recent.len() > longest {
+            recent.pop_front();
+        }
+        let matches = |code: &&CheatCode| recent.iter().rev().take(code.sequence.len()).eq(code.sequence.iter().rev());
+        let Some(code) = codes.0.iter().find(matches) else { continue };
+        recent.clear();
+        let message = if cheats.is_active(code.cheat) {
+            cheats.active.retain(|cheat| *cheat != code.cheat);
+            format!("Cheat aus: {}", code.cheat.name())
+        } else {
+            cheats.active.push(code.cheat);
+            cheats.used_this_run = true;
+            format!("Cheat an: {} (keine Bestenliste)", code.cheat.name())
+        };
+        toasts.send(ShowToast(message));
+    }
+}
+
+fn apply_giant_ball(cheats: Res<Cheats>, mut query: Query<&mut Transform, Added<Ball>>) {
+    if !cheats.is_active(Cheat::GiantBall) {
+        return;
+    }
+    for mut transform in &mut query {
+        transform.scale *= GIANT_BALL_FACTOR;
+    }
+}
+
+// Jeder Brick bekommt einen Farbton nach seiner Position, so läuft der Regenbogen schräg durch das Raster.
+// Strafbricks bleiben erkennbar.
+#[allow(clippy::type_complexity)]
+fn apply_rainbow_bricks(
+    cheats: Res<Cheats>,
+    mut query: Query<(&Transform, &mut Handle<StandardMaterial>), (Added<Brick>, Without<PenaltyBrick>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !cheats.is_active(Cheat::RainbowBricks) {
+        return;
+    }
+    for (transform, mut material) in &mut query {
+        let hue = ((transform.translation.x + transform.translation.y) * 30.0).rem_euclid(360.0);
+        *material = materials.add(Color::hsl(hue, 0.8, 0.55).into());
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use clap::{Parser, ValueEnum};
+
+use crate::daily::DailyChallenge;
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::rng::GameRng;
+use crate::save::SaveData;
+use crate::snapshot::{self, PendingSnapshot};
+use crate::stress::StressTest;
+use crate::transition::ScreenTransition;
+use crate::tutorial::TutorialLevel;
+use crate::{GameState, Mutators};
+
+// Die Startoptionen von der Kommandozeile, etwa `--mode daily --seed 42 --mute` oder `--stress 2000 50`. Ist ein Modus oder Level
+// angegeben, geht es nach dem Laden direkt ins Spiel statt ins Hauptmenü. Gedacht zum Testen und für Skripte.
+#[derive(Parser, Resource, Clone, Debug)]
+#[command(about = "Breakout in 3D")]
+pub struct LaunchOptions {
+    #[arg(long, value_name = "N", help = "Level der Kampagne, ab 1 gezählt. Ohne --mode wird die Kampagne gespielt")]
+    level: Option<usize>,
+    #[arg(long, value_name = "U64", requires = "mode", help = "Seed des Durchlaufs, in der täglichen Herausforderung auch des Levels")]
+    seed: Option<u64>,
+    #[arg(long, value_enum, help = "Startet direkt in diesem Modus")]
+    mode: Option<LaunchMode>,
+    #[arg(long, conflicts_with = "fullscreen", help = "Im Fenster starten (Standard)")]
+    windowed: bool,
+    #[arg(long, help = "Im Vollbild starten")]
+    fullscreen: bool,
+    #[arg(long, help = "Alle Soundeffekte abschalten")]
+    pub mute: bool,
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["BRICKS", "BALLS"],
+        conflicts_with_all = ["mode", "level"],
+        help = "Lasttest: füllt die Arena mit so vielen Bricks und Bällen und misst 30 Sekunden lang die Frame-Zeiten"
+    )]
+    stress: Option<Vec<usize>>,
+    #[arg(
+        long,
+        value_name = "DATEI",
+        conflicts_with_all = ["mode", "level", "stress"],
+        help = "Stellt den Stand aus einem Schnappschuss (F11) nach, etwa aus einem Fehlerbericht"
+    )]
+    snapshot: Option<PathBuf>,
+}
+
+// Die Modi, die sich direkt starten lassen. Probespiel braucht den Editor und fehlt deshalb.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum LaunchMode {
+    Campaign,
+    TimeAttack,
+    Daily,
+    Tutorial,
+}
+
+impl From<LaunchMode> for GameMode {
+    fn from(mode: LaunchMode) -> Self {
+        match mode {
+            LaunchMode::Campaign => GameMode::Campaign,
+            LaunchMode::TimeAttack => GameMode::TimeAttack,
+            LaunchMode::Daily => GameMode::Daily,
+            LaunchMode::Tutorial => GameMode::Tutorial,
+        }
+    }
+}
+
+impl LaunchOptions {
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        }
+    }
+
+    pub fn stress_test(&self) -> Option<StressTest> {
+        let stress = self.stress.as_ref()?;
+        Some(StressTest { bricks: stress[0], balls: stress[1].max(1) })
+    }
+
+    fn game_mode(&self) -> Option<GameMode> {
+        if self.stress.is_some() {
+            return Some(GameMode::TestPlay);
+        }
+        match (self.mode, self.level) {
+            (Some(mode), _) => Some(mode.into()),
+            (None, Some(_)) => Some(GameMode::Campaign),
+            (None, None) => None,
+        }
+    }
+}
+
+pub struct CliPlugin(pub LaunchOptions);
+
+impl Plugin for CliPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(stress) = self.0.stress_test() {
+            app.insert_resource(stress);
+        }
+        if let Some(path) = &self.0.snapshot {
+            match snapshot::load(path) {
+                Ok(snapshot) => {
+                    app.insert_resource(PendingSnapshot(Some(snapshot)));
+                }
+                Err(error) => warn!("Schnappschuss {} konnte nicht geladen werden: {error}", path.display()),
+            }
+        }
+        // Erst nach clear_custom_level, sonst ginge das Level für Tutorial und Lasttest gleich wieder verloren.
+        app.insert_resource(self.0.clone()).add_system_set(
+            SystemSet::on_enter(GameState::Menu).with_system(launch_from_cli.after(crate::level::clear_custom_level)),
+        );
+    }
+}
+
+// Beim ersten Erreichen des Hauptmenüs sind alle Level geladen. Von dort geht es einmalig so weiter,
+// als wäre der Modus im Menü gewählt worden.
+#[allow(clippy::too_many_arguments)]
+pub fn launch_from_cli(
+    options: Res<LaunchOptions>,
+    mut launched: Local<bool>,
+    mut campaign: ResMut<Campaign>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut daily: ResMut<DailyChallenge>,
+    mut rng: ResMut<GameRng>,
+    mut save: ResMut<SaveData>,
+    tutorial: Res<TutorialLevel>,
+    (stress, mut levels): (Option<Res<StressTest>>, ResMut<Assets<Level>>),
+    mut transition: ResMut<ScreenTransition>,
+) {
+    if *launched {
+        return;
+    }
+    *launched = true;
+    let Some(game_mode) = options.game_mode() else { return };
+
+    save.suspended_run = None;
+    *mutators = Mutators::default();
+    campaign.current = 0;
+    if let Some(seed) = options.seed {
+        rng.start_run(seed);
+    }
+    match game_mode {
+        GameMode::Daily => {
+            if let Some(seed) = options.seed {
+                daily.seed = seed;
+            }
+            campaign.current = daily.level_index(campaign.levels.len());
+            *mutators = daily.mutators();
+        }
+        GameMode::Tutorial => campaign.custom_level = Some(tutorial.0.clone()),
+        // Der Lasttest spielt sein erzeugtes Level wie ein Probespiel aus dem Editor.
+        GameMode::TestPlay => campaign.custom_level = stress.map(|stress| levels.add(stress.level())),
+        _ => {
+            if let Some(level) = options.level {
+                let last = campaign.levels.len();
+                if !(1..=last).contains(&level) {
+                    warn!("Level {level} gibt es nicht, die Kampagne hat {last} Level");
+                }
+                campaign.current = level.clamp(1, last) - 1;
+            }
+        }
+    }
+    *mode = game_mode;
+    transition.to(GameState::Playing);
+}
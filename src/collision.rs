@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+// Die Ebenen, auf denen Objekte zusammenstoßen können. Jede Ebene belegt ein Bit in den Masken.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layer {
+    Ball,
+    Paddle,
+    Brick,
+    Wall,
+    Pickup,
+    // Für Geschosse wie Laser, die Bricks und Wände treffen, aber nie das Paddle.
+    Projectile,
+    // Schüsse von Geschütz-Bricks, sie treffen nur das Paddle.
+    EnemyShot,
+}
+
+impl Layer {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    // Womit ein Objekt dieser Ebene normalerweise zusammenstößt.
+    fn default_mask(self) -> &'static [Layer] {
+        match self {
+            Layer::Ball => &[Layer::Paddle, Layer::Brick, Layer::Wall],
+            Layer::Paddle => &[Layer::Ball, Layer::Pickup, Layer::EnemyShot],
+            Layer::Brick => &[Layer::Ball, Layer::Projectile],
+            Layer::Wall => &[Layer::Ball, Layer::Projectile],
+            Layer::Pickup => &[Layer::Paddle],
+            Layer::Projectile => &[Layer::Brick, Layer::Wall],
+            Layer::EnemyShot => &[Layer::Paddle],
+        }
+    }
+}
+
+// Auf welcher Ebene ein Objekt liegt und mit welchen Ebenen es zusammenstoßen darf.
+// Zwei Objekte stoßen nur zusammen, wenn jedes in der Maske des anderen steht.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CollisionLayers {
+    membership: u8,
+    mask: u8,
+}
+
+impl CollisionLayers {
+    pub fn new(layer: Layer, mask: &[Layer]) -> Self {
+        CollisionLayers {
+            membership: layer.bit(),
+            mask: mask.iter().fold(0, |mask, layer| mask | layer.bit()),
+        }
+    }
+
+    pub fn interacts_with(&self, other: &CollisionLayers) -> bool {
+        self.mask & other.membership != 0 && other.mask & self.membership != 0
+    }
+}
+
+impl From<Layer> for CollisionLayers {
+    fn from(layer: Layer) -> Self {
+        CollisionLayers::new(layer, layer.default_mask())
+    }
+}
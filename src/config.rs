@@ -0,0 +1,138 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+const CONFIG_PATH: &str = "game.config.ron";
+
+// Die Stellschrauben für das Balancing, so wie sie in assets/game.config.ron stehen. Die Datei wird wie ein Theme
+// neu geladen, sobald sie sich ändert. Fehlt ein Wert dort, gilt der Standardwert von hier.
+// Die Maße des Rasters (Bricks und Abstände) bleiben Konstanten, da die Level-Dateien gegen sie gebaut sind.
+#[derive(Resource, Deserialize, TypeUuid, Reflect, Clone)]
+#[uuid = "3c9a5e17-0d4b-4f6e-8a21-7b5c9e2d4f80"]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameConfig {
+    pub ball_speed: f32,
+    pub ball_size: Vec3,
+    pub paddle_speed: f32,
+    // Die Breite ohne Upgrades aus dem Shop.
+    pub paddle_size: Vec3,
+    pub gravity: f32,
+    pub starting_lives: u32,
+    // Punkte pro zerstörtem Brick, bevor die Modifikatoren sie vervielfachen.
+    pub brick_score: usize,
+    // Chance auf ein Power-Up pro Brick, und wie viel jede Stufe des Upgrades im Shop dazugibt.
+    pub drop_chance: f64,
+    pub drop_chance_per_level: f64,
+    // Chance auf eine Münze, wenn kein Power-Up fällt.
+    pub coin_drop_chance: f64,
+    // Rutscht der Ball höchstens so weit unter die Unterkante des Paddles und fällt dabei langsamer als
+    // rescue_max_fall_speed, wird er einmal pro Level zurückgestupst. Mit 0 gibt es keine Rettung.
+    pub rescue_margin: f32,
+    pub rescue_max_fall_speed: f32,
+    // Ein getroffener Brick blitzt so viele Frames weiß auf, und das Spiel steht so viele Sekunden still.
+    // Mit 0 fällt der jeweilige Effekt weg.
+    pub hit_flash_frames: u32,
+    pub hit_stop: f32,
+    // Bis zu diesem Abstand in Metern gilt ein Ball an der Kante des Paddles oder der Ecke eines Bricks als knapp.
+    // Danach läuft das Spiel so viele Sekunden in Zeitlupe. Mit 0 fällt die Zeitlupe weg.
+    pub near_miss_margin: f32,
+    pub near_miss_slow_motion: f32,
+    // Grenzen der anpassenden Schwierigkeit: Faktoren auf die Ballgeschwindigkeit und auf die Chance für Power-Ups,
+    // jeweils am leichten und am schweren Ende.
+    pub adaptive_speed_min: f32,
+    pub adaptive_speed_max: f32,
+    pub adaptive_drop_max: f64,
+    pub adaptive_drop_min: f64,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            ball_speed: 7.0,
+            ball_size: Vec3::new(0.2, 0.2, 0.2),
+            paddle_speed: 8.0,
+            paddle_size: Vec3::new(1.0, 0.2, 1.0),
+            gravity: 4.0,
+            starting_lives: 3,
+            brick_score: 1,
+            drop_chance: 0.15,
+            drop_chance_per_level: 0.05,
+            coin_drop_chance: 0.25,
+            rescue_margin: 0.3,
+            rescue_max_fall_speed: 3.0,
+            hit_flash_frames: 2,
+            hit_stop: 0.02,
+            near_miss_margin: 0.06,
+            near_miss_slow_motion: 0.15,
+            adaptive_speed_min: 0.8,
+            adaptive_speed_max: 1.2,
+            adaptive_drop_max: 1.5,
+            adaptive_drop_min: 0.75,
+        }
+    }
+}
+
+#[derive(Default)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let config = ron::de::from_bytes::<GameConfig>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+// Hält die Datei geladen, damit Änderungen daran ankommen.
+#[derive(Resource)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+pub struct GameConfigPlugin;
+
+impl Plugin for GameConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<GameConfig>()
+            .init_asset_loader::<GameConfigLoader>()
+            .init_resource::<GameConfig>()
+            .add_startup_system(load_config)
+            .add_system(apply_config);
+    }
+}
+
+fn load_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle(asset_server.load(CONFIG_PATH)));
+}
+
+// Bis die Datei geladen ist, gelten die Standardwerte. Danach wird die Ressource bei jeder Änderung ersetzt.
+// Was beim Spawnen gelesen wird, etwa die Ballgeschwindigkeit, gilt ab dem nächsten Level.
+fn apply_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    mut config: ResMut<GameConfig>,
+) {
+    let changed = events.iter().any(|event| {
+        matches!(event, AssetEvent::Created { handle: loaded } | AssetEvent::Modified { handle: loaded } if *loaded == handle.0)
+    });
+    if !changed {
+        return;
+    }
+    if let Some(loaded) = configs.get(&handle.0) {
+        *config = loaded.clone();
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::input::gamepad::{GamepadEvent, GamepadEventType};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use crate::toast::ShowToast;
+use crate::{GameState, LevelEntity};
+
+// Kleinere Ausschläge des Sticks zählen nicht, sonst wandert das Paddle bei leicht verzogenen Sticks.
+const STICK_DEADZONE: f32 = 0.25;
+const NOTICE_FONT_SIZE: f32 = 40.0;
+const NOTICE_COLOR: Color = Color::rgb(0.6, 0.1, 0.1);
+
+// Womit gerade gespielt wird. Gewechselt wird automatisch mit der letzten Eingabe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ControlScheme {
+    #[default]
+    Keyboard,
+    Gamepad(Gamepad),
+}
+
+#[derive(Resource, Default)]
+pub struct ActiveControls {
+    scheme: ControlScheme,
+    // Die Richtung, in die der Spieler das Paddle gerade steuert, von -1 bis 1.
+    direction: f32,
+    // Das aktive Gamepad wurde getrennt. Bis zur nächsten Eingabe steht das Spiel still.
+    waiting: bool,
+}
+
+impl ActiveControls {
+    pub fn direction(&self) -> f32 {
+        self.direction
+    }
+
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self.scheme, ControlScheme::Gamepad(_))
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
+    }
+}
+
+// Text, der während der Pause nach dem Trennen des Gamepads in der Mitte steht.
+#[derive(Component)]
+struct DisconnectNotice;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveControls>()
+            .add_system_to_stage(CoreStage::PreUpdate, switch_scheme.after(InputSystem))
+            .add_system_to_stage(CoreStage::PreUpdate, read_direction.after(switch_scheme))
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_notice))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_notice));
+    }
+}
+
+// Ein neu angeschlossenes Gamepad übernimmt sofort, danach gewinnt jeweils das Gerät mit der letzten Eingabe.
+// Wird das aktive Gamepad getrennt, geht es mit der Tastatur weiter, aber erst nach einem Tastendruck.
+fn switch_scheme(
+    mut events: EventReader<GamepadEvent>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut controls: ResMut<ActiveControls>,
+    state: Res<State<GameState>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for event in events.iter() {
+        match event.event_type {
+            GamepadEventType::Connected(_) => {
+                controls.scheme = ControlScheme::Gamepad(event.gamepad);
+                toasts.send(ShowToast("Gamepad verbunden".to_string()));
+            }
+            GamepadEventType::Disconnected if controls.scheme == ControlScheme::Gamepad(event.gamepad) => {
+                controls.scheme = ControlScheme::Keyboard;
+                controls.waiting = *state.current() == GameState::Playing;
+                toasts.send(ShowToast("Gamepad getrennt".to_string()));
+            }
+            _ => {}
+        }
+    }
+    if keyboard_input.get_just_pressed().next().is_some() {
+        controls.scheme = ControlScheme::Keyboard;
+        controls.waiting = false;
+    } else if let Some(button) = gamepad_buttons.get_just_pressed().next() {
+        controls.scheme = ControlScheme::Gamepad(button.gamepad);
+        controls.waiting = false;
+    }
+}
+
+// Mit der Tastatur steuern Hoch und Runter, mit dem Gamepad der linke Stick oder das Steuerkreuz.
+fn read_direction(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut controls: ResMut<ActiveControls>,
+) {
+    let mut direction = 0.0;
+    match controls.scheme {
+        ControlScheme::Keyboard => {
+            if keyboard_input.pressed(KeyCode::Up) {
+                direction += 1.0;
+            }
+            if keyboard_input.pressed(KeyCode::Down) {
+                direction -= 1.0;
+            }
+        }
+        ControlScheme::Gamepad(gamepad) => {
+            if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadRight)) {
+                direction += 1.0;
+            }
+            if gamepad_buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadLeft)) {
+                direction -= 1.0;
+            }
+            let stick = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.0);
+            if stick.abs() > STICK_DEADZONE {
+                direction += stick;
+            }
+        }
+    }
+    controls.direction = direction.clamp(-1.0, 1.0);
+}
+
+fn spawn_notice(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "Gamepad getrennt\nWeiter mit beliebiger Taste",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: NOTICE_FONT_SIZE,
+                color: NOTICE_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Percent(40.0),
+                left: Val::Percent(30.0),
+                ..default()
+            },
+            ..default()
+        }),
+        DisconnectNotice,
+        LevelEntity,
+    ));
+}
+
+fn update_notice(controls: Res<ActiveControls>, mut query: Query<&mut Visibility, With<DisconnectNotice>>) {
+    for mut visibility in &mut query {
+        visibility.is_visible = controls.is_waiting();
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+
+use crate::config::GameConfig;
+use crate::format;
+use crate::mode::GameMode;
+use crate::toast::ShowToast;
+use crate::{collision, phase_set, Arena, Collider, GameState, LevelEntity, Phase, TimeScale, WallBundle, WallLocation};
+
+// So breit ist die Lücke in der Decke, durch die der Ball entkommen kann, und so breit das Paddle darüber.
+const GAP_WIDTH: f32 = 3.0;
+const CEILING_PADDLE_SIZE: Vec3 = Vec3::new(1.2, 0.2, 1.0);
+// So weit über der Decke liegt das obere Paddle.
+const CEILING_PADDLE_OFFSET: f32 = 0.3;
+// Fliegt der Ball so weit über das obere Paddle hinaus, ist er verloren.
+const ESCAPE_MARGIN: f32 = 0.5;
+const LEFT_KEY: KeyCode = KeyCode::A;
+const RIGHT_KEY: KeyCode = KeyCode::D;
+const TIMER_FONT_SIZE: f32 = 48.0;
+const HINT_FONT_SIZE: f32 = 24.0;
+const TIMER_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+
+// Das Paddle des zweiten Spielers. Es ist bewusst kein Paddle, damit Kombo, Power-Ups und Fähigkeiten
+// weiter nur am unteren hängen.
+#[derive(Component)]
+pub struct CeilingPaddle;
+
+// Wie lange beide Spieler den Ball schon gemeinsam im Spiel halten. Die Zeit läuft über alle Level weiter.
+#[derive(Resource, Default)]
+struct SurvivalTime(f32);
+
+#[derive(Component)]
+struct SurvivalText;
+
+pub struct CoopPlugin;
+
+impl Plugin for CoopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurvivalTime>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(report_survival))
+            .add_system_set(
+                SystemSet::on_enter(GameState::Playing)
+                    .with_system(spawn_ceiling_paddle)
+                    .with_system(spawn_survival_text),
+            )
+            .add_system_set(phase_set(Phase::Input).with_system(move_ceiling_paddle))
+            .add_system_set(phase_set(Phase::Scoring).with_system(tick_survival))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_survival_text));
+    }
+}
+
+// Im Koop-Modus hat die Decke in der Mitte eine Lücke, sonst ist sie durchgehend.
+pub fn spawn_ceiling(
+    commands: &mut Commands,
+    arena: &Arena,
+    mode: GameMode,
+    material: Handle<StandardMaterial>,
+    mesh: Handle<Mesh>,
+) {
+    if mode != GameMode::Coop {
+        commands.spawn((WallBundle::new(WallLocation::Top, arena, material, mesh), LevelEntity));
+        return;
+    }
+    for side in [-1.0, 1.0] {
+        let mut segment = WallBundle::new(WallLocation::Top, arena, material.clone(), mesh.clone());
+        let transform = &mut segment.pbr_bundle.transform;
+        transform.scale.x = (transform.scale.x - GAP_WIDTH) / 2.0;
+        transform.translation.x = side * (GAP_WIDTH + transform.scale.x) / 2.0;
+        commands.spawn((segment, LevelEntity));
+    }
+}
+
+// Oberhalb davon ist der Ball durch die Lücke in der Decke entkommen.
+pub fn escape_height(arena: &Arena) -> f32 {
+    ceiling_paddle_y(arena) + CEILING_PADDLE_SIZE.y / 2.0 + ESCAPE_MARGIN
+}
+
+fn ceiling_paddle_y(arena: &Arena) -> f32 {
+    arena.top() + arena.wall_thickness / 2.0 + CEILING_PADDLE_OFFSET + CEILING_PADDLE_SIZE.y / 2.0
+}
+
+fn spawn_ceiling_paddle(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    arena: Res<Arena>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if *mode != GameMode::Coop {
+        return;
+    }
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Cube::default().into()),
+            material: materials.add(Color::rgb(0.2, 0.4, 0.9).into()),
+            transform: Transform::from_xyz(0.0, ceiling_paddle_y(&arena), 0.0).with_scale(CEILING_PADDLE_SIZE),
+            ..default()
+        },
+        CeilingPaddle,
+        collision::CollisionLayers::from(collision::Layer::Paddle),
+        Collider,
+        LevelEntity,
+    ));
+}
+
+// Der zweite Spieler steuert mit A und D. Das Paddle bleibt über der Lücke, weiter kann der Ball nicht entkommen.
+fn move_ceiling_paddle(
+    mut query: Query<&mut Transform, With<CeilingPaddle>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    time_scale: Res<TimeScale>,
+    config: Res<GameConfig>,
+) {
+    let Ok(mut transform) = query.get_single_mut() else { return };
+    let mut direction = 0.0;
+    if keyboard_input.pressed(LEFT_KEY) {
+        direction -= 1.0;
+    }
+    if keyboard_input.pressed(RIGHT_KEY) {
+        direction += 1.0;
+    }
+    let bound = (GAP_WIDTH - transform.scale.x) / 2.0;
+    let x = transform.translation.x + direction * config.paddle_speed * time_scale.step();
+    transform.translation.x = x.clamp(-bound, bound);
+}
+
+fn tick_survival(mode: Res<GameMode>, mut survival: ResMut<SurvivalTime>, time_scale: Res<TimeScale>) {
+    if *mode == GameMode::Coop {
+        survival.0 += time_scale.step();
+    }
+}
+
+fn spawn_survival_text(mut commands: Commands, asset_server: Res<AssetServer>, mode: Res<GameMode>) {
+    if *mode != GameMode::Coop {
+        return;
+    }
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: TIMER_FONT_SIZE,
+                    color: TIMER_COLOR,
+                },
+            ),
+            TextSection::new(
+                "\nOben: A / D\nKamera: V",
+                TextStyle {
+                    font,
+                    font_size: HINT_FONT_SIZE,
+                    color: TIMER_COLOR,
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(85.0),
+                right: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        SurvivalText,
+        LevelEntity,
+    ));
+}
+
+fn update_survival_text(survival: Res<SurvivalTime>, mut query: Query<&mut Text, With<SurvivalText>>) {
+    let Ok(mut text) = query.get_single_mut() else { return };
+    text.sections[0].value = format::timer(survival.0);
+}
+
+// Zurück im Hauptmenü ist der Koop-Durchlauf vorbei. Die gemeinsame Zeit wird noch einmal gezeigt.
+fn report_survival(mode: Res<GameMode>, mut survival: ResMut<SurvivalTime>, mut toasts: EventWriter<ShowToast>) {
+    if *mode == GameMode::Coop && survival.0 > 0.0 {
+        toasts.send(ShowToast(format!("Gemeinsam durchgehalten: {}", format::timer(survival.0))));
+    }
+    survival.0 = 0.0;
+}
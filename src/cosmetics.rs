@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::achievement::Achievement;
+use crate::save::SaveData;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BallSkin {
+    #[default]
+    Classic,
+    Glowing,
+    Metal,
+    Cube,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PaddleSkin {
+    #[default]
+    Classic,
+    Gold,
+    Glass,
+}
+
+impl BallSkin {
+    const ALL: [BallSkin; 4] = [BallSkin::Classic, BallSkin::Glowing, BallSkin::Metal, BallSkin::Cube];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BallSkin::Classic => "Klassisch",
+            BallSkin::Glowing => "Glühend",
+            BallSkin::Metal => "Metall",
+            BallSkin::Cube => "Würfel",
+        }
+    }
+
+    // Welcher Erfolg den Skin freischaltet. Der klassische Skin ist immer verfügbar.
+    fn required_achievement(self) -> Option<Achievement> {
+        match self {
+            BallSkin::Classic => None,
+            BallSkin::Glowing => Some(Achievement::FirstClear),
+            BallSkin::Metal => Some(Achievement::BrickCollector),
+            BallSkin::Cube => Some(Achievement::CampaignComplete),
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            BallSkin::Classic | BallSkin::Cube => crate::BALL_COLOR,
+            BallSkin::Glowing => Color::rgb(1.0, 0.9, 0.3),
+            BallSkin::Metal => Color::rgb(0.75, 0.75, 0.8),
+        }
+    }
+
+    pub fn material(self) -> StandardMaterial {
+        let color = self.color();
+        match self {
+            BallSkin::Classic | BallSkin::Cube => color.into(),
+            BallSkin::Glowing => StandardMaterial {
+                base_color: color,
+                emissive: color * 0.6,
+                ..default()
+            },
+            BallSkin::Metal => StandardMaterial {
+                base_color: color,
+                metallic: 1.0,
+                perceptual_roughness: 0.2,
+                ..default()
+            },
+        }
+    }
+
+    pub fn mesh(self) -> Mesh {
+        match self {
+            BallSkin::Cube => shape::Cube::default().into(),
+            _ => shape::UVSphere::default().into(),
+        }
+    }
+}
+
+impl PaddleSkin {
+    const ALL: [PaddleSkin; 3] = [PaddleSkin::Classic, PaddleSkin::Gold, PaddleSkin::Glass];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PaddleSkin::Classic => "Klassisch",
+            PaddleSkin::Gold => "Gold",
+            PaddleSkin::Glass => "Glas",
+        }
+    }
+
+    fn required_achievement(self) -> Option<Achievement> {
+        match self {
+            PaddleSkin::Classic => None,
+            PaddleSkin::Gold => Some(Achievement::CampaignComplete),
+            PaddleSkin::Glass => Some(Achievement::FirstClear),
+        }
+    }
+
+    pub fn material(self) -> StandardMaterial {
+        match self {
+            PaddleSkin::Classic => Color::BLUE.into(),
+            PaddleSkin::Gold => StandardMaterial {
+                base_color: Color::GOLD,
+                metallic: 1.0,
+                perceptual_roughness: 0.3,
+                ..default()
+            },
+            PaddleSkin::Glass => StandardMaterial {
+                base_color: Color::rgba(0.6, 0.9, 1.0, 0.5),
+                alpha_mode: AlphaMode::Blend,
+                perceptual_roughness: 0.1,
+                ..default()
+            },
+        }
+    }
+}
+
+// Die gewählten Skins. Sie werden im Spielstand gespeichert und von spawn_level beim Aufbau eines Levels benutzt.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CosmeticsConfig {
+    pub ball: BallSkin,
+    pub paddle: PaddleSkin,
+}
+
+pub struct CosmeticsPlugin;
+
+impl Plugin for CosmeticsPlugin {
+    fn build(&self, app: &mut App) {
+        // Die Auswahl kommt aus dem Spielstand, deshalb muss das SavePlugin vorher hinzugefügt werden.
+        let config = app.world.resource::<SaveData>().cosmetics;
+        app.insert_resource(config).add_system(store_cosmetics);
+    }
+}
+
+// Ändert sich die Auswahl im Menü, wird sie in den Spielstand übernommen.
+fn store_cosmetics(config: Res<CosmeticsConfig>, mut save: ResMut<SaveData>) {
+    if config.is_changed() && save.cosmetics != *config {
+        save.cosmetics = *config;
+    }
+}
+
+fn is_unlocked(required: Option<Achievement>, save: &SaveData) -> bool {
+    required.is_none_or(|achievement| save.has_achievement(achievement))
+}
+
+// Wählt den nächsten freigeschalteten Skin. Gesperrte Skins werden übersprungen.
+pub fn cycle_ball_skin(config: &mut CosmeticsConfig, save: &SaveData) {
+    let current = BallSkin::ALL.iter().position(|skin| *skin == config.ball).unwrap_or(0);
+    for offset in 1..=BallSkin::ALL.len() {
+        let skin = BallSkin::ALL[(current + offset) % BallSkin::ALL.len()];
+        if is_unlocked(skin.required_achievement(), save) {
+            config.ball = skin;
+            return;
+        }
+    }
+}
+
+pub fn cycle_paddle_skin(config: &mut CosmeticsConfig, save: &SaveData) {
+    let current = PaddleSkin::ALL.iter().position(|skin| *skin == config.paddle).unwrap_or(0);
+    for offset in 1..=PaddleSkin::ALL.len() {
+        let skin = PaddleSkin::ALL[(current + offset) % PaddleSkin::ALL.len()];
+        if is_unlocked(skin.required_achievement(), save) {
+            config.paddle = skin;
+            return;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+
+use crate::accessibility::{AccessibilitySettings, Palette};
+use crate::assist::Assists;
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::rng::GameRng;
+use crate::{Arena, GameState};
+
+// Was beim Absturz zuletzt über das laufende Spiel bekannt war. Der Panic-Hook hat keinen Zugriff auf die Welt,
+// deshalb schreibt update_crash_context hier jeden Frame mit.
+#[derive(Default)]
+struct CrashContext {
+    state: String,
+    mode: String,
+    level: String,
+    seed: u64,
+    settings: String,
+    entities: usize,
+}
+
+static CRASH_CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        install_panic_hook();
+        app.add_system_to_stage(CoreStage::Last, update_crash_context);
+    }
+}
+
+// Bei einem Absturz landet ein Bericht mit Backtrace und dem letzten Spielstand in einer Datei neben dem Spiel.
+// Die normale Ausgabe der Panic bleibt erhalten.
+fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+        // Stürzt das Spiel ausgerechnet beim Mitschreiben ab, fehlt der Kontext eben.
+        let context = match CRASH_CONTEXT.try_lock() {
+            Ok(context) => context.as_ref().map_or_else(|| "unbekannt".to_string(), describe),
+            Err(_) => "nicht verfügbar".to_string(),
+        };
+        let report = format!(
+            "KuerteilCG Absturzbericht\n\
+             Version: {}\n\
+             Zeit: {seconds}\n\n\
+             Fehler: {info}\n\n\
+             {context}\n\n\
+             Letzte Ereignisse:\n{}\n\
+             Backtrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            crate::gamelog::recent_events(),
+            Backtrace::force_capture(),
+        );
+        let path = format!("crash-report-{seconds}.txt");
+        match fs::write(&path, report) {
+            Ok(()) => eprintln!(
+                "\nKuerteilCG ist leider abgestürzt. Ein Bericht liegt unter {path}.\n\
+                 Bitte häng ihn an, wenn du den Fehler meldest. Danke!"
+            ),
+            Err(error) => eprintln!("\nKuerteilCG ist leider abgestürzt. Der Bericht konnte nicht gespeichert werden: {error}"),
+        }
+    }));
+}
+
+fn describe(context: &CrashContext) -> String {
+    format!(
+        "Zustand: {}\nModus: {}\nLevel: {}\nSeed des Levels: {}\nEinstellungen: {}\nEntities: {}",
+        context.state, context.mode, context.level, context.seed, context.settings, context.entities,
+    )
+}
+
+// Die Texte werden nur neu gebaut, wenn sich etwas geändert hat. Gezählt werden die Entities aber in jedem Frame.
+fn update_crash_context(
+    state: Res<State<GameState>>,
+    mode: Res<GameMode>,
+    (campaign, levels, rng): (Res<Campaign>, Res<Assets<Level>>, Res<GameRng>),
+    (palette, settings, assists, arena): (Res<Palette>, Res<AccessibilitySettings>, Res<Assists>, Res<Arena>),
+    entities: Query<()>,
+) {
+    let Ok(mut guard) = CRASH_CONTEXT.try_lock() else { return };
+    let first = guard.is_none();
+    let context = guard.get_or_insert_with(CrashContext::default);
+    if first || state.is_changed() || mode.is_changed() || campaign.is_changed() || rng.is_changed() {
+        context.state = format!("{:?}", state.current());
+        context.mode = mode.name().to_string();
+        let name = levels.get(campaign.current_handle()).map_or("?", |level| level.name.as_str());
+        context.level = format!("{} ({name})", campaign.current + 1);
+        context.seed = rng.level_seed();
+    }
+    if first || palette.is_changed() || settings.is_changed() || assists.is_changed() || arena.is_changed() {
+        context.settings = format!(
+            "Farbsehschwäche {}, weniger Bewegung {}, Hilfen [{}], Arena {}x{}",
+            palette.mode.name(),
+            settings.reduced_motion,
+            assists.label(),
+            arena.width,
+            arena.height,
+        );
+    }
+    context.entities = entities.iter().count();
+}
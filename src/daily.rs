@@ -0,0 +1,85 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::Mutators;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+// Ältere Tage fliegen aus der Bestenliste, damit der Spielstand nicht endlos wächst.
+const KEPT_DAYS: u64 = 30;
+const DAILY_SCORE_ENTRIES: usize = 5;
+
+// Die Herausforderung des Tages. Level und Modifikatoren folgen allein aus dem Datum (UTC),
+// damit alle Spieler am selben Tag dasselbe spielen und die Einstellungen nicht verändert werden können.
+#[derive(Resource, Clone, Copy)]
+pub struct DailyChallenge {
+    pub day: u64,
+    pub seed: u64,
+}
+
+impl Default for DailyChallenge {
+    fn default() -> Self {
+        DailyChallenge::today()
+    }
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+        let day = seconds / SECONDS_PER_DAY;
+        DailyChallenge { day, seed: splitmix64(day) }
+    }
+
+    pub fn level_index(&self, level_count: usize) -> usize {
+        (self.seed % level_count as u64) as usize
+    }
+
+    pub fn mutators(&self) -> Mutators {
+        Mutators {
+            gravity: (self.seed >> 32) & 1 == 1,
+            ..Mutators::default()
+        }
+    }
+
+    // Das Datum als JJJJ-MM-TT, umgerechnet aus den Tagen seit 1970 (Algorithmus von Howard Hinnant).
+    pub fn date_label(&self) -> String {
+        let z = self.day as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+// Verteilt aufeinanderfolgende Tage gleichmäßig auf den ganzen Zahlenbereich.
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// Eigene Bestenliste je Tag, getrennt von den Bestenlisten der anderen Modi.
+#[derive(Serialize, Deserialize, Default)]
+pub struct DailyScores(HashMap<u64, Vec<usize>>);
+
+impl DailyScores {
+    pub fn best(&self, day: u64) -> Option<usize> {
+        self.0.get(&day).and_then(|scores| scores.first().copied())
+    }
+
+    pub fn insert(&mut self, day: u64, score: usize) {
+        let scores = self.0.entry(day).or_default();
+        scores.push(score);
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        scores.truncate(DAILY_SCORE_ENTRIES);
+        self.0.retain(|kept_day, _| kept_day + KEPT_DAYS > day);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::AccessibilitySettings;
+use crate::launch::HeldBall;
+use crate::sound::{PlaySoundAt, Sfx};
+use crate::{Ball, GameState, Kinematic, Paddle, PADDLE_Y};
+
+// Die Breite der roten Streifen am Bildrand, in Pixeln.
+const EDGE_WIDTH: f32 = 24.0;
+const EDGE_COLOR: Color = Color::rgba(0.9, 0.0, 0.0, 0.0);
+// So deckend werden die Streifen, wenn der Ball auf Höhe des Paddles ist.
+const MAX_EDGE_ALPHA: f32 = 0.45;
+const PADDLE_GLOW: Color = Color::rgb(0.8, 0.0, 0.0);
+// Deutlich unter drei Pulsen pro Sekunde, ab denen Blinken für Menschen mit Epilepsie gefährlich wird.
+const PULSE_FREQUENCY: f32 = 2.0;
+
+// Wie früh vor einem fallenden Ball gewarnt wird, in den Einstellungen zur Barrierefreiheit.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DangerWarning {
+    Off,
+    Late,
+    #[default]
+    Normal,
+    Early,
+}
+
+impl DangerWarning {
+    const ALL: [DangerWarning; 4] = [
+        DangerWarning::Off,
+        DangerWarning::Late,
+        DangerWarning::Normal,
+        DangerWarning::Early,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DangerWarning::Off => "Aus",
+            DangerWarning::Late => "Spät",
+            DangerWarning::Normal => "Normal",
+            DangerWarning::Early => "Früh",
+        }
+    }
+
+    pub fn next(self) -> DangerWarning {
+        let current = DangerWarning::ALL.iter().position(|warning| *warning == self).unwrap_or(0);
+        DangerWarning::ALL[(current + 1) % DangerWarning::ALL.len()]
+    }
+
+    // Ab dieser Höhe über dem Paddle gilt ein fallender Ball als gefährdet.
+    fn height(self) -> Option<f32> {
+        match self {
+            DangerWarning::Off => None,
+            DangerWarning::Late => Some(1.0),
+            DangerWarning::Normal => Some(2.0),
+            DangerWarning::Early => Some(3.5),
+        }
+    }
+}
+
+// Einer der vier Streifen am Bildrand.
+#[derive(Component)]
+struct DangerEdge;
+
+// Fällt ein Ball tief über dem Paddle, färbt sich der Bildrand rot, ein Warnton erklingt und das Paddle glüht im
+// Takt. Je näher der Ball dem Paddle kommt, desto kräftiger. Mit weniger Bewegung glüht alles gleichmäßig.
+pub struct DangerPlugin;
+
+impl Plugin for DangerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_danger_edges)
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(warn_danger))
+            .add_system_set(SystemSet::on_pause(GameState::Playing).with_system(clear_danger))
+            .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(clear_danger));
+    }
+}
+
+fn spawn_danger_edges(mut commands: Commands) {
+    let horizontal = Size::new(Val::Percent(100.0), Val::Px(EDGE_WIDTH));
+    let vertical = Size::new(Val::Px(EDGE_WIDTH), Val::Percent(100.0));
+    let edges = [
+        (UiRect { left: Val::Px(0.0), top: Val::Px(0.0), ..default() }, horizontal),
+        (UiRect { left: Val::Px(0.0), bottom: Val::Px(0.0), ..default() }, horizontal),
+        (UiRect { left: Val::Px(0.0), top: Val::Px(0.0), ..default() }, vertical),
+        (UiRect { right: Val::Px(0.0), top: Val::Px(0.0), ..default() }, vertical),
+    ];
+    for (position, size) in edges {
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position,
+                    size,
+                    ..default()
+                },
+                background_color: EDGE_COLOR.into(),
+                // Die Streifen liegen über dem Spiel, sollen aber keine Klicks abfangen.
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            },
+            DangerEdge,
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn warn_danger(
+    settings: Res<AccessibilitySettings>,
+    time: Res<Time>,
+    ball_query: Query<(Entity, &Transform, &Kinematic), (With<Ball>, Without<HeldBall>)>,
+    paddle_query: Query<&Handle<StandardMaterial>, With<Paddle>>,
+    mut edge_query: Query<&mut BackgroundColor, With<DangerEdge>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut sounds: EventWriter<PlaySoundAt>,
+    mut endangered: Local<HashSet<Entity>>,
+) {
+    let Some(height) = settings.danger_warning.height() else {
+        endangered.clear();
+        show_danger(0.0, &paddle_query, &mut edge_query, &mut materials);
+        return;
+    };
+    let mut danger: f32 = 0.0;
+    for (ball, transform, body) in &ball_query {
+        let above_paddle = transform.translation.y - PADDLE_Y;
+        if body.velocity.y >= 0.0 || above_paddle > height {
+            endangered.remove(&ball);
+            continue;
+        }
+        // Unter dem Paddle ist der Ball schon verloren, die Warnung bleibt dann auf voller Stärke.
+        danger = danger.max((1.0 - above_paddle / height).min(1.0));
+        // Der Ton nur einmal, wenn der Ball in die Gefahrenzone kommt.
+        if endangered.insert(ball) {
+            sounds.send(PlaySoundAt(Sfx::Danger, transform.translation));
+        }
+    }
+    endangered.retain(|ball| ball_query.contains(*ball));
+
+    let pulse = if settings.allows_motion() {
+        0.7 + 0.3 * (time.elapsed_seconds() * PULSE_FREQUENCY * TAU).sin()
+    } else {
+        1.0
+    };
+    show_danger(danger * pulse, &paddle_query, &mut edge_query, &mut materials);
+}
+
+fn clear_danger(
+    paddle_query: Query<&Handle<StandardMaterial>, With<Paddle>>,
+    mut edge_query: Query<&mut BackgroundColor, With<DangerEdge>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    show_danger(0.0, &paddle_query, &mut edge_query, &mut materials);
+}
+
+// Setzt Rand und Paddle auf die Stärke zwischen 0 und 1. get_mut meldet das Material als geändert, deshalb nur bei
+// einem echten Wechsel.
+fn show_danger(
+    strength: f32,
+    paddle_query: &Query<&Handle<StandardMaterial>, With<Paddle>>,
+    edge_query: &mut Query<&mut BackgroundColor, With<DangerEdge>>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let alpha = MAX_EDGE_ALPHA * strength;
+    for mut edge in edge_query.iter_mut() {
+        if edge.0.a() != alpha {
+            edge.0.set_a(alpha);
+        }
+    }
+    let glow = PADDLE_GLOW * strength;
+    for handle in paddle_query {
+        if materials.get(handle).is_some_and(|material| material.emissive != glow) {
+            materials.get_mut(handle).unwrap().emissive = glow;
+        }
+    }
+}
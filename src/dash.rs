@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::ability::{Ability, AbilityKind, AbilityPlugin};
+use crate::controls::ActiveControls;
+use crate::hazard::{control_sign, ReversedControls};
+use crate::input_buffer::{Action, InputBuffer};
+use crate::{phase_set, Arena, Mutators, Paddle, Phase};
+
+const DASH_DISTANCE: f32 = 2.0;
+
+// Mit Shift oder B am Gamepad springt das Paddle sofort ein Stück in die gehaltene Richtung.
+pub struct Dash;
+
+impl AbilityKind for Dash {
+    const NAME: &'static str = "Dash";
+    const COOLDOWN: f32 = 1.5;
+    const HUD_SLOT: usize = 0;
+}
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(AbilityPlugin::<Dash>::default())
+            .add_system_set(phase_set(Phase::Input).with_system(dash.after(crate::move_object)));
+    }
+}
+
+// Der Druck auf Shift bleibt kurz gemerkt. Kommt die Richtung oder das Ende des Cooldowns knapp danach, gibt es den
+// Dash trotzdem.
+#[allow(clippy::type_complexity)]
+fn dash(
+    controls: Res<ActiveControls>,
+    mut buffer: ResMut<InputBuffer>,
+    mut query: Query<(&mut Transform, &mut Ability<Dash>, Option<&ReversedControls>), With<Paddle>>,
+    arena: Res<Arena>,
+    mutators: Res<Mutators>,
+) {
+    if !buffer.is_pending(Action::Dash) {
+        return;
+    }
+    // Ohne gehaltene Richtung gibt es keinen Dash, und der Cooldown wird nicht verbraucht.
+    if controls.direction() == 0.0 {
+        return;
+    }
+    let direction = controls.direction().signum();
+    for (mut transform, mut ability, reversed) in &mut query {
+        if ability.try_activate() {
+            buffer.take(Action::Dash);
+            let direction = direction * control_sign(reversed) * mutators.challenges.control_sign();
+            transform.translation.x = arena.clamp_paddle_x(transform.translation.x + direction * DASH_DISTANCE, transform.scale.x);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::pool::{Pool, PoolPlugin};
+use crate::{phase_set, BrickDestroyed, Kinematic, LevelEntity, Phase, TimeScale};
+
+const FRAGMENTS_PER_BRICK: usize = 6;
+// Obergrenze für alle gleichzeitig existierenden Trümmer, damit Kettenreaktionen die Framerate nicht einbrechen lassen.
+const MAX_DEBRIS: usize = 120;
+const DEBRIS_LIFETIME: f32 = 2.0;
+const DEBRIS_SIZE: f32 = 0.15;
+const DEBRIS_GRAVITY: f32 = 9.81;
+const DEBRIS_SPEED: f32 = 4.0;
+// Anteil der Geschwindigkeit, der beim Aufprall auf den Boden erhalten bleibt.
+const DEBRIS_BOUNCINESS: f32 = 0.45;
+
+// Bewegt werden die Trümmer von apply_velocity, hier steht nur, wie lange sie noch sichtbar sind.
+#[derive(Component)]
+struct Debris {
+    lifetime: Timer,
+}
+
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(PoolPlugin::<Debris>::default())
+            .add_system(spawn_debris)
+            .add_system_set(phase_set(Phase::Vfx).with_system(update_debris));
+    }
+}
+
+// Für jeden zerstörten Brick fliegen ein paar kleine Würfel in zufällige Richtungen auseinander.
+// Jeder Splitter hat eine eigene Kopie des Brick-Materials, damit er unabhängig ausgeblendet werden kann.
+// Wird ein Splitter aus dem Vorrat wiederverwendet, wird seine Kopie nur überschrieben.
+#[allow(clippy::too_many_arguments)]
+fn spawn_debris(
+    mut commands: Commands,
+    mut events: EventReader<BrickDestroyed>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    debris_query: Query<&Handle<StandardMaterial>, With<Debris>>,
+    mut pool: ResMut<Pool<Debris>>,
+    settings: Res<AccessibilitySettings>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    if !settings.allows_motion() {
+        events.clear();
+        return;
+    }
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(shape::Cube::default().into()))
+        .clone();
+    let mut rng = rand::thread_rng();
+    let mut count = debris_query.iter().count() - pool.free_count();
+
+    for event in events.iter() {
+        let Some(brick_material) = materials.get(&event.material).cloned() else { continue };
+        let fragment_material = StandardMaterial {
+            alpha_mode: AlphaMode::Blend,
+            ..brick_material
+        };
+        for _ in 0..FRAGMENTS_PER_BRICK {
+            if count >= MAX_DEBRIS {
+                return;
+            }
+            count += 1;
+
+            let direction = Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(0.0..1.0), rng.gen_range(-0.5..1.0));
+            let offset = Vec3::new(rng.gen_range(-0.4..0.4), rng.gen_range(-0.15..0.15), 0.0);
+            let transform = Transform::from_translation(event.position + offset).with_scale(Vec3::splat(DEBRIS_SIZE));
+            let debris = Debris {
+                lifetime: Timer::from_seconds(DEBRIS_LIFETIME, TimerMode::Once),
+            };
+            let body = Kinematic {
+                velocity: direction.normalize_or_zero() * DEBRIS_SPEED * rng.gen_range(0.5..1.0),
+                gravity: Vec3::NEG_Y * DEBRIS_GRAVITY,
+                bounciness: DEBRIS_BOUNCINESS,
+            };
+
+            let recycled = pool.take().and_then(|entity| Some((entity, debris_query.get(entity).ok()?)));
+            if let Some((entity, handle)) = recycled {
+                if let Some(material) = materials.get_mut(handle) {
+                    *material = fragment_material.clone();
+                }
+                commands.entity(entity).insert((transform, debris, body, Visibility::VISIBLE));
+                continue;
+            }
+            commands.spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: materials.add(fragment_material.clone()),
+                    transform,
+                    ..default()
+                },
+                debris,
+                body,
+                LevelEntity,
+            ));
+        }
+    }
+}
+
+// Die Trümmer taumeln in Flugrichtung und werden über ihre Lebenszeit ausgeblendet. Danach kommen sie
+// angehalten zurück in den Vorrat.
+#[allow(clippy::type_complexity)]
+fn update_debris(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Debris, &mut Kinematic, &Handle<StandardMaterial>, &Visibility)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut pool: ResMut<Pool<Debris>>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = time_scale.step();
+    for (entity, mut transform, mut debris, mut body, material, visibility) in &mut query {
+        if !visibility.is_visible {
+            continue;
+        }
+        if debris.lifetime.tick(Duration::from_secs_f32(step)).finished() {
+            body.velocity = Vec3::ZERO;
+            body.gravity = Vec3::ZERO;
+            pool.release(&mut commands, entity);
+            continue;
+        }
+
+        transform.rotate_x(body.velocity.z * step);
+        transform.rotate_z(-body.velocity.x * step);
+
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color.set_a(debris.lifetime.percent_left());
+        }
+    }
+}
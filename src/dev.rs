@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use bevy::render::camera::{RenderTarget, ScalingMode};
+use bevy::render::render_graph::RenderGraph;
+use bevy::render::RenderApp;
+use bevy::window::{CreateWindow, WindowClosed, WindowId};
+use bevy_inspector_egui::bevy_egui::{self, EguiContext, EguiPlugin};
+use bevy_inspector_egui::{bevy_inspector, egui, DefaultInspectorConfigPlugin};
+
+use crate::config::GameConfig;
+use crate::gamelog;
+use crate::{Arena, BallSpeed, TimeScale};
+
+const DEBUG_WINDOW_KEY: KeyCode = KeyCode::F8;
+const DEBUG_WINDOW_SIZE: Vec2 = Vec2::new(1100.0, 750.0);
+const DEBUG_EGUI_PASS: &str = "debug_window_egui_pass";
+// So viel Rand bleibt in der Draufsicht um die Arena herum.
+const DEBUG_VIEW_MARGIN: f32 = 1.3;
+const INSPECTOR_SIZE: (f32, f32) = (320.0, 160.0);
+
+// Das zweite Fenster für Tests mit Aufnahme. Die Kennung steht schon beim Start fest, weil egui seinen
+// Render-Durchgang für das Fenster vor dem ersten Frame braucht.
+#[derive(Resource)]
+struct DebugWindow {
+    id: WindowId,
+    open: bool,
+}
+
+// Schaut ohne Perspektive senkrecht auf die Arena und zeigt nur das Debug-Fenster, ohne HUD.
+#[derive(Component)]
+struct DebugCamera;
+
+// Nur mit dem Feature "dev": Ein Fenster zeigt alle Entities mit ihren Komponenten, darüber lässt sich etwa
+// die Helligkeit der Lichter ändern. Dazu kommt je ein Fenster für die Werte, an denen beim Testen am
+// häufigsten gedreht wird, darunter alles aus game.config.ron. Die Ballgeschwindigkeit gilt ab dem nächsten Anstoß.
+// F8 öffnet ein zweites Fenster mit einer Draufsicht, dem Protokoll der Ereignisse und diesen Inspektoren. Solange
+// es offen ist, bleibt das Spielfenster frei davon und lässt sich sauber aufnehmen.
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        let window = WindowId::new();
+        app.register_type::<BallSpeed>()
+            .register_type::<TimeScale>()
+            .register_type::<GameConfig>()
+            .add_plugin(EguiPlugin)
+            .add_plugin(DefaultInspectorConfigPlugin)
+            .insert_resource(DebugWindow { id: window, open: false })
+            .add_system(toggle_debug_window)
+            .add_system(frame_debug_camera.after(toggle_debug_window))
+            .add_system(inspector_ui);
+
+        let mut graph = app.sub_app_mut(RenderApp).world.resource_mut::<RenderGraph>();
+        bevy_egui::setup_pipeline(
+            &mut graph,
+            bevy_egui::RenderGraphConfig {
+                window_id: window,
+                egui_pass: DEBUG_EGUI_PASS,
+            },
+        );
+    }
+}
+
+// F8 öffnet das Fenster oder schließt es wieder. Wird es über das Betriebssystem geschlossen, verschwindet die
+// Kamera dazu ebenfalls.
+fn toggle_debug_window(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut debug_window: ResMut<DebugWindow>,
+    mut windows: ResMut<Windows>,
+    mut create_window: EventWriter<CreateWindow>,
+    mut closed_windows: EventReader<WindowClosed>,
+    camera_query: Query<Entity, With<DebugCamera>>,
+) {
+    if closed_windows.iter().any(|closed| closed.id == debug_window.id) {
+        debug_window.open = false;
+        for camera in &camera_query {
+            commands.entity(camera).despawn();
+        }
+    }
+    if !keyboard_input.just_pressed(DEBUG_WINDOW_KEY) {
+        return;
+    }
+    if debug_window.open {
+        if let Some(window) = windows.get_mut(debug_window.id) {
+            window.close();
+        }
+        return;
+    }
+    create_window.send(CreateWindow {
+        id: debug_window.id,
+        descriptor: WindowDescriptor {
+            width: DEBUG_WINDOW_SIZE.x,
+            height: DEBUG_WINDOW_SIZE.y,
+            title: "KuerteilCG – Debug".to_string(),
+            ..default()
+        },
+    });
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                target: RenderTarget::Window(debug_window.id),
+                ..default()
+            },
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical(1.0),
+                ..default()
+            }
+            .into(),
+            ..default()
+        },
+        UiCameraConfig { show_ui: false },
+        DebugCamera,
+    ));
+    debug_window.open = true;
+}
+
+// Die Draufsicht folgt der Arena, auch wenn sie sich während eines Levels ändert.
+fn frame_debug_camera(
+    arena: Res<Arena>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<DebugCamera>>,
+) {
+    for (mut transform, mut projection) in &mut camera_query {
+        let center = Vec3::new((arena.left() + arena.right()) / 2.0, (arena.bottom() + arena.top()) / 2.0, 0.0);
+        *transform = Transform::from_translation(center + Vec3::Z * 2.0 * arena.extent()).looking_at(center, Vec3::Y);
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scaling_mode = ScalingMode::FixedVertical(arena.extent() * DEBUG_VIEW_MARGIN);
+        }
+    }
+}
+
+// Die Inspektoren liegen im Debug-Fenster, solange es offen ist, sonst wie bisher über dem Spiel.
+// Das Protokoll der Ereignisse gibt es nur im Debug-Fenster.
+fn inspector_ui(world: &mut World) {
+    let debug_window = world.resource::<DebugWindow>();
+    let (id, open) = (debug_window.id, debug_window.open);
+    let mut egui_context = world.resource_mut::<EguiContext>();
+    let debug_context = if open { egui_context.try_ctx_for_window_mut(id).cloned() } else { None };
+    let in_debug_window = debug_context.is_some();
+    let context = debug_context.unwrap_or_else(|| egui_context.ctx_mut().clone());
+
+    egui::Window::new("World Inspector").default_size(INSPECTOR_SIZE).show(&context, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            bevy_inspector::ui_for_world(world, ui);
+            ui.allocate_space(ui.available_size());
+        });
+    });
+    egui::Window::new("BallSpeed").show(&context, |ui| bevy_inspector::ui_for_resource::<BallSpeed>(world, ui));
+    egui::Window::new("TimeScale").show(&context, |ui| bevy_inspector::ui_for_resource::<TimeScale>(world, ui));
+    egui::Window::new("GameConfig").show(&context, |ui| bevy_inspector::ui_for_resource::<GameConfig>(world, ui));
+    if in_debug_window {
+        egui::Window::new("Ereignisse").default_size(INSPECTOR_SIZE).show(&context, |ui| {
+            egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                ui.monospace(gamelog::recent_events());
+            });
+        });
+    }
+}
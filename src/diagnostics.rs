@@ -0,0 +1,151 @@
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+// Wie viele Objekte check_for_collision im letzten Schritt gegen den Ball geprüft hat.
+pub const COLLISION_CHECKS: DiagnosticId = DiagnosticId::from_u128(209_385_541_207_817_602_411_736_409_174_022_158_031);
+// So viele Frames zeigt der Graph, so viele Messwerte hebt die Frame-Zeit ohnehin auf.
+const GRAPH_FRAMES: usize = 20;
+// Bei dieser Frame-Zeit in Millisekunden ist ein Balken voll, das entspricht 30 FPS.
+const GRAPH_MAX_FRAME_TIME: f64 = 33.3;
+const GRAPH_HEIGHT: f32 = 40.0;
+const GRAPH_BAR_WIDTH: f32 = 4.0;
+const HUD_FONT_SIZE: f32 = 18.0;
+const HUD_TEXT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const HUD_BACKGROUND: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+const BAR_COLOR: Color = Color::rgb(0.2, 0.6, 0.2);
+const SLOW_BAR_COLOR: Color = Color::rgb(0.8, 0.2, 0.1);
+
+// Das Fenster mit FPS, Frame-Zeit, Entities und Kollisionsprüfungen oben rechts. F3 blendet es ein und aus.
+#[derive(Component)]
+struct DiagnosticsHud;
+
+#[derive(Component)]
+struct DiagnosticsText;
+
+// Ein Balken im Graphen der Frame-Zeiten, der älteste hat Index 0.
+#[derive(Component)]
+struct FrameTimeBar(usize);
+
+pub struct DiagnosticsHudPlugin;
+
+impl Plugin for DiagnosticsHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(FrameTimeDiagnosticsPlugin)
+            .add_plugin(EntityCountDiagnosticsPlugin)
+            .add_startup_system(setup_diagnostics)
+            .add_system(toggle_hud)
+            .add_system(update_hud_text)
+            .add_system(update_frame_time_graph);
+    }
+}
+
+fn setup_diagnostics(mut commands: Commands, mut diagnostics: ResMut<Diagnostics>, asset_server: Res<AssetServer>) {
+    diagnostics.add(Diagnostic::new(COLLISION_CHECKS, "collision_checks", 20));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(5.0),
+                        right: Val::Px(5.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                background_color: HUD_BACKGROUND.into(),
+                ..default()
+            },
+            DiagnosticsHud,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                        font_size: HUD_FONT_SIZE,
+                        color: HUD_TEXT_COLOR,
+                    },
+                ),
+                DiagnosticsText,
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(GRAPH_FRAMES as f32 * GRAPH_BAR_WIDTH), Val::Px(GRAPH_HEIGHT)),
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|graph| {
+                    for index in 0..GRAPH_FRAMES {
+                        graph.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(GRAPH_BAR_WIDTH), Val::Px(0.0)),
+                                    ..default()
+                                },
+                                background_color: BAR_COLOR.into(),
+                                ..default()
+                            },
+                            FrameTimeBar(index),
+                        ));
+                    }
+                });
+        });
+}
+
+fn toggle_hud(keyboard_input: Res<Input<KeyCode>>, mut query: Query<&mut Style, With<DiagnosticsHud>>) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+    for mut style in &mut query {
+        style.display = match style.display {
+            Display::None => Display::Flex,
+            Display::Flex => Display::None,
+        };
+    }
+}
+
+fn update_hud_text(
+    diagnostics: Res<Diagnostics>,
+    hud_query: Query<&Style, With<DiagnosticsHud>>,
+    mut text_query: Query<&mut Text, With<DiagnosticsText>>,
+) {
+    // Solange das Fenster versteckt ist, muss der Text nicht neu gesetzt werden.
+    if hud_query.iter().all(|style| style.display == Display::None) {
+        return;
+    }
+    let value = |id| diagnostics.get(id).and_then(|diagnostic| diagnostic.smoothed()).unwrap_or(0.0);
+    let Ok(mut text) = text_query.get_single_mut() else { return };
+    text.sections[0].value = format!(
+        "FPS: {:.0}\nFrame: {:.1} ms\nEntities: {:.0}\nKollisionen/Schritt: {:.0}",
+        value(FrameTimeDiagnosticsPlugin::FPS),
+        value(FrameTimeDiagnosticsPlugin::FRAME_TIME),
+        value(EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+        value(COLLISION_CHECKS),
+    );
+}
+
+// Jeder Balken zeigt die Zeit eines der letzten Frames. Zu langsame Frames werden rot.
+fn update_frame_time_graph(
+    diagnostics: Res<Diagnostics>,
+    mut bar_query: Query<(&FrameTimeBar, &mut Style, &mut BackgroundColor)>,
+) {
+    let Some(frame_time) = diagnostics.get(FrameTimeDiagnosticsPlugin::FRAME_TIME) else { return };
+    let values: Vec<f64> = frame_time.values().copied().collect();
+    // Gibt es noch nicht genug Messwerte, bleiben die ersten Balken leer.
+    let offset = GRAPH_FRAMES.saturating_sub(values.len());
+    for (bar, mut style, mut color) in &mut bar_query {
+        let value = bar.0.checked_sub(offset).and_then(|index| values.get(index)).copied().unwrap_or(0.0);
+        let fraction = (value / GRAPH_MAX_FRAME_TIME).min(1.0) as f32;
+        style.size.height = Val::Px(fraction * GRAPH_HEIGHT);
+        *color = if value > GRAPH_MAX_FRAME_TIME { SLOW_BAR_COLOR } else { BAR_COLOR }.into();
+    }
+}
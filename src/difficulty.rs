@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::assist::{Assist, Assists};
+use crate::config::GameConfig;
+use crate::mode::GameMode;
+use crate::{phase_set, Ball, BallLost, Kinematic, Phase, Scoreboard};
+
+// Ein verlorener Ball macht das Spiel deutlich leichter, eine lange Kombo nur ein wenig schwerer.
+const LOSS_STEP: f32 = 0.25;
+const COMBO_STEP: f32 = 0.1;
+// Ab dieser Kombo gilt der Spieler als sicher. Sie zählt einmal, bis die Kombo wieder abreißt.
+const COMBO_THRESHOLD: u32 = 8;
+
+// Die angepasste Schwierigkeit von -1 (so leicht wie erlaubt) bis 1 (so schwer wie erlaubt). Sie wandert mit dem
+// Spieler über Level und Durchläufe hinweg und gilt nur mit der Hilfe "Anpassende Schwierigkeit".
+// Die Grenzen der Faktoren stehen in game.config.ron.
+#[derive(Resource, Default)]
+pub struct Difficulty(f32);
+
+impl Difficulty {
+    pub fn ball_speed_factor(&self, config: &GameConfig) -> f32 {
+        if self.0 < 0.0 {
+            1.0 + self.0 * (1.0 - config.adaptive_speed_min)
+        } else {
+            1.0 + self.0 * (config.adaptive_speed_max - 1.0)
+        }
+    }
+
+    // Wer sich schwer tut, bekommt öfter Power-Ups, wer sicher spielt, seltener.
+    pub fn drop_chance_factor(&self, config: &GameConfig) -> f64 {
+        let level = self.0 as f64;
+        if level < 0.0 {
+            1.0 - level * (config.adaptive_drop_max - 1.0)
+        } else {
+            1.0 - level * (1.0 - config.adaptive_drop_min)
+        }
+    }
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Difficulty>()
+            .add_system_set(phase_set(Phase::Scoring).with_system(adapt_difficulty.after(crate::check_ball_lost)));
+    }
+}
+
+// Ändert sich die Geschwindigkeit, wird auch der fliegende Ball im selben Verhältnis schneller oder langsamer,
+// sonst würde die Änderung erst beim nächsten Abschuss spürbar.
+#[allow(clippy::too_many_arguments)]
+fn adapt_difficulty(
+    mut difficulty: ResMut<Difficulty>,
+    mut lost_events: EventReader<BallLost>,
+    scoreboard: Res<Scoreboard>,
+    mut previous_combo: Local<u32>,
+    assists: Res<Assists>,
+    mode: Res<GameMode>,
+    config: Res<GameConfig>,
+    mut ball_query: Query<&mut Kinematic, With<Ball>>,
+) {
+    let losses = lost_events.iter().count();
+    let combo_reached = *previous_combo < COMBO_THRESHOLD && scoreboard.combo >= COMBO_THRESHOLD;
+    *previous_combo = scoreboard.combo;
+    if !assists.is_enabled(Assist::Adaptive) || *mode == GameMode::Demo {
+        return;
+    }
+    let mut level = difficulty.0 - losses as f32 * LOSS_STEP;
+    if combo_reached {
+        level += COMBO_STEP;
+    }
+    let level = level.clamp(-1.0, 1.0);
+    if level == difficulty.0 {
+        return;
+    }
+    let before = difficulty.ball_speed_factor(&config);
+    difficulty.0 = level;
+    let ratio = difficulty.ball_speed_factor(&config) / before;
+    for mut body in &mut ball_query {
+        body.velocity *= ratio;
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+use crate::level::BrickKind;
+use crate::theme::{Theme, ThemeMaterials};
+use crate::{phase_set, BrickDestroyed, LevelEntity, Phase, TimeScale, BRICK_SIZE};
+
+const DISSOLVE_DURATION: f32 = 0.4;
+// Falls die Theme-Datei noch nicht geladen ist.
+const DEFAULT_EDGE: Color = Color::rgb(1.0, 0.6, 0.1);
+
+// Ein Brick, der wegbrennt. Der Fortschritt läuft von 0 (ganz da) bis 1 (ganz verschwunden),
+// die Form des Randes bestimmt ein Rauschen im Shader.
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "3c9e7a41-6d2f-4b85-a1e0-7f4c2d9b5e18"]
+pub struct DissolveMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    edge_color: Color,
+    #[uniform(0)]
+    progress: f32,
+    #[texture(1)]
+    #[sampler(2)]
+    texture: Option<Handle<Image>>,
+}
+
+impl Material for DissolveMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/dissolve.wgsl".into()
+    }
+}
+
+// Der eigentliche Brick ist beim Treffer schon weg und blockiert nichts mehr, übrig bleibt nur dieses Abbild.
+#[derive(Component)]
+struct Dissolving(Timer);
+
+pub struct DissolvePlugin;
+
+impl Plugin for DissolvePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(MaterialPlugin::<DissolveMaterial>::default())
+            .add_system(spawn_dissolving)
+            .add_system_set(phase_set(Phase::Vfx).with_system(burn_away));
+    }
+}
+
+// Das Abbild übernimmt Farbe und Textur des Brick-Materials, der Rand kommt je nach Stufe aus dem Theme.
+#[allow(clippy::too_many_arguments)]
+fn spawn_dissolving(
+    mut commands: Commands,
+    mut events: EventReader<BrickDestroyed>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut dissolve_materials: ResMut<Assets<DissolveMaterial>>,
+    theme_materials: Res<ThemeMaterials>,
+    themes: Res<Assets<Theme>>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(shape::Cube::default().into()))
+        .clone();
+    let theme = theme_materials.theme(&themes);
+
+    for event in events.iter() {
+        let Some(brick_material) = standard_materials.get(&event.material) else { continue };
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: mesh.clone(),
+                material: dissolve_materials.add(DissolveMaterial {
+                    color: brick_material.base_color,
+                    edge_color: theme.map_or(DEFAULT_EDGE, |theme| theme.dissolve_edge(event.kind == BrickKind::Penalty)),
+                    progress: 0.0,
+                    texture: brick_material.base_color_texture.clone(),
+                }),
+                transform: Transform::from_translation(event.position)
+                    .with_scale(Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0)),
+                ..default()
+            },
+            Dissolving(Timer::from_seconds(DISSOLVE_DURATION, TimerMode::Once)),
+            LevelEntity,
+        ));
+    }
+}
+
+fn burn_away(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Dissolving, &Handle<DissolveMaterial>)>,
+    mut materials: ResMut<Assets<DissolveMaterial>>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = Duration::from_secs_f32(time_scale.step());
+    for (entity, mut dissolving, handle) in &mut query {
+        if dissolving.0.tick(step).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle) {
+            material.progress = dissolving.0.percent();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Kurven für Animationen. Jede bildet den Fortschritt t von 0 bis 1 auf den Anteil der Bewegung ab, am Anfang 0
+// und am Ende 1.
+
+// Stärke des Überschwingens bei ease_out_back, der übliche Wert aus den Kurven von Robert Penner.
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+// Schießt etwas über das Ziel hinaus und setzt sich dann, wie etwas, das mit Schwung ankommt.
+pub fn ease_out_back(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0) - 1.0;
+    1.0 + (BACK_OVERSHOOT + 1.0) * t * t * t + BACK_OVERSHOOT * t * t
+}
+
+// Schnell am Anfang, weich am Ende.
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t.clamp(0.0, 1.0)).powi(3)
+}
+
+// Die Kurven als Wert, damit ein Tween sie mitführen kann.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Ease {
+    #[default]
+    Linear,
+    OutCubic,
+    OutBack,
+}
+
+impl Ease {
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Ease::Linear => t.clamp(0.0, 1.0),
+            Ease::OutCubic => ease_out_cubic(t),
+            Ease::OutBack => ease_out_back(t),
+        }
+    }
+}
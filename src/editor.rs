@@ -0,0 +1,349 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::level::{BrickKind, Campaign, Level};
+use crate::mode::GameMode;
+use crate::sharing;
+use crate::theme::ThemeMaterials;
+use crate::toast::ShowToast;
+use crate::transition::ScreenTransition;
+use crate::{Arena, GameState, GridGeometry, MainCamera, Mutators, Scoreboard, BRICK_SIZE};
+
+// Hierhin speichert der Editor. Die Datei liegt bei den anderen Leveln und wird beim nächsten Öffnen wieder geladen.
+const EDITOR_LEVEL_PATH: &str = "assets/levels/custom.level.ron";
+const EDITOR_LEVEL_NAME: &str = "Eigenes Level";
+// So viele Schritte lassen sich höchstens rückgängig machen.
+const UNDO_LIMIT: usize = 100;
+const BALL_SPEED_STEP: f32 = 0.1;
+const MIN_BALL_SPEED: f32 = 0.5;
+const MAX_BALL_SPEED: f32 = 2.0;
+// Leere Zellen sind nur angedeutet, damit man sieht, wohin man malen kann.
+const EMPTY_CELL_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.15);
+const HUD_FONT_SIZE: f32 = 24.0;
+const HUD_TEXT_COLOR: Color = Color::rgb(0.0, 0.0, 0.0);
+
+// cells[row][column], die unterste Reihe zuerst.
+type Cells = Vec<Vec<Option<BrickKind>>>;
+
+// Der Stand des Editors. Er bleibt erhalten, während das Level probegespielt wird.
+#[derive(Resource, Default)]
+struct LevelEditor {
+    cells: Cells,
+    brush: BrickKind,
+    ball_speed: f32,
+    undo: Vec<Cells>,
+    empty_material: Handle<StandardMaterial>,
+}
+
+// Die Bricks eines Levels im Raster der Standardarena.
+fn cells_from_level(level: Option<&Level>, grid: &GridGeometry) -> Cells {
+    (0..grid.rows)
+        .map(|row| {
+            (0..grid.columns)
+                .map(|column| level.and_then(|level| level.brick_at(row, column, grid)))
+                .collect()
+        })
+        .collect()
+}
+
+impl LevelEditor {
+    fn level(&self) -> Level {
+        Level {
+            name: EDITOR_LEVEL_NAME.to_string(),
+            gravity: false,
+            force_fields: Vec::new(),
+            bumpers: Vec::new(),
+            portals: Vec::new(),
+            obstacles: Vec::new(),
+            floor: Vec::new(),
+            objectives: Vec::new(),
+            par: None,
+            ball_speed: self.ball_speed,
+            layout: Some(Level::layout_from_cells(&self.cells)),
+            arena: None,
+            camera: None,
+            ball: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct EditorCell {
+    row: usize,
+    column: usize,
+}
+
+// Alles, was der Editor in die Welt setzt, wird beim Verlassen wieder entfernt.
+#[derive(Component)]
+struct EditorEntity;
+
+#[derive(Component)]
+struct EditorText;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelEditor>()
+            .add_system_set(SystemSet::on_enter(GameState::Editor).with_system(enter_editor))
+            .add_system_set(
+                SystemSet::on_update(GameState::Editor)
+                    .with_system(paint_cells)
+                    .with_system(editor_keys)
+                    .with_system(update_cells.after(paint_cells).after(editor_keys))
+                    .with_system(update_editor_text.after(editor_keys)),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Editor).with_system(despawn_editor));
+    }
+}
+
+// Beim ersten Öffnen startet der Editor mit dem zuletzt gespeicherten eigenen Level oder, falls es keines gibt,
+// mit dem im Menü gewählten Level der Kampagne.
+#[allow(clippy::too_many_arguments)]
+fn enter_editor(
+    mut commands: Commands,
+    mut editor: ResMut<LevelEditor>,
+    mut scoreboard: ResMut<Scoreboard>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut arena: ResMut<Arena>,
+) {
+    // Punkte aus einem Probespiel zählen nicht.
+    scoreboard.score = 0;
+    // Der Editor malt immer in der Standardarena, eigene Level haben keine eigenen Maße.
+    *arena = Arena::default();
+    let grid = GridGeometry::new(&arena);
+    if editor.cells.is_empty() {
+        let saved = fs::read_to_string(EDITOR_LEVEL_PATH).ok().and_then(|contents| ron::from_str::<Level>(&contents).ok());
+        let level = saved.as_ref().or_else(|| levels.get(&campaign.levels[campaign.current]));
+        editor.cells = cells_from_level(level, &grid);
+        editor.ball_speed = level.map_or(1.0, |level| level.ball_speed);
+        editor.empty_material = materials.add(StandardMaterial {
+            base_color: EMPTY_CELL_COLOR,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        });
+    }
+
+    let mesh = meshes.add(shape::Cube::default().into());
+    for row in 0..grid.rows {
+        for column in 0..grid.columns {
+            commands.spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: editor.empty_material.clone(),
+                    transform: Transform {
+                        translation: grid.position(row, column).extend(0.0),
+                        scale: Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                EditorCell { row, column },
+                EditorEntity,
+            ));
+        }
+    }
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: HUD_FONT_SIZE,
+                color: HUD_TEXT_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(5.0),
+                left: Val::Px(5.0),
+                ..default()
+            },
+            ..default()
+        }),
+        EditorText,
+        EditorEntity,
+    ));
+}
+
+// Die linke Maustaste malt mit dem gewählten Brick, die rechte radiert. Jeder Strich ist ein Schritt zum Rückgängigmachen.
+fn paint_cells(
+    mut editor: ResMut<LevelEditor>,
+    mouse: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    arena: Res<Arena>,
+) {
+    let paint = if mouse.pressed(MouseButton::Left) {
+        Some(editor.brush)
+    } else if mouse.pressed(MouseButton::Right) {
+        None
+    } else {
+        return;
+    };
+    if mouse.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        let snapshot = editor.cells.clone();
+        editor.undo.push(snapshot);
+        if editor.undo.len() > UNDO_LIMIT {
+            editor.undo.remove(0);
+        }
+    }
+
+    let Some(cursor) = windows.get_primary().and_then(|window| window.cursor_position()) else { return };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else { return };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else { return };
+    // Die Bricks liegen in der Ebene z = 0.
+    if ray.direction.z.abs() < f32::EPSILON {
+        return;
+    }
+    let point = ray.origin + ray.direction * (-ray.origin.z / ray.direction.z);
+    let Some((row, column)) = GridGeometry::new(&arena).cell_at(point.truncate()) else { return };
+    if editor.cells[row][column] != paint {
+        editor.cells[row][column] = paint;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn editor_keys(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut editor: ResMut<LevelEditor>,
+    arena: Res<Arena>,
+    mut campaign: ResMut<Campaign>,
+    mut levels: ResMut<Assets<Level>>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut transition: ResMut<ScreenTransition>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let control = keyboard_input.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        editor.brush = BrickKind::Normal;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key2) {
+        editor.brush = BrickKind::Penalty;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key3) {
+        editor.brush = BrickKind::Ghost;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key4) {
+        editor.brush = BrickKind::Regenerating;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key5) {
+        editor.brush = BrickKind::Chain;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key6) {
+        editor.brush = BrickKind::Turret;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key7) {
+        editor.brush = BrickKind::Glass;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key8) {
+        editor.brush = BrickKind::Metal;
+    }
+    if keyboard_input.just_pressed(KeyCode::Key9) {
+        editor.brush = BrickKind::Stone;
+    }
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        editor.ball_speed = (editor.ball_speed + BALL_SPEED_STEP).min(MAX_BALL_SPEED);
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        editor.ball_speed = (editor.ball_speed - BALL_SPEED_STEP).max(MIN_BALL_SPEED);
+    }
+    if control && keyboard_input.just_pressed(KeyCode::Z) {
+        if let Some(cells) = editor.undo.pop() {
+            editor.cells = cells;
+        }
+    }
+    if control && keyboard_input.just_pressed(KeyCode::S) {
+        let result = ron::ser::to_string_pretty(&editor.level(), ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|contents| fs::write(EDITOR_LEVEL_PATH, contents).map_err(|error| error.to_string()));
+        match result {
+            Ok(()) => info!("Level gespeichert unter {EDITOR_LEVEL_PATH}"),
+            Err(error) => warn!("Level konnte nicht gespeichert werden: {error}"),
+        }
+    }
+    // Strg+C legt das Level als Code in die Zwischenablage, Strg+V übernimmt einen Code von dort in den Editor.
+    if control && keyboard_input.just_pressed(KeyCode::C) {
+        let result = sharing::level_code(&editor.level()).and_then(sharing::copy_to_clipboard);
+        toasts.send(ShowToast(match result {
+            Ok(()) => "Level-Code kopiert".to_string(),
+            Err(error) => format!("Level-Code konnte nicht kopiert werden: {error}"),
+        }));
+    }
+    if control && keyboard_input.just_pressed(KeyCode::V) {
+        match sharing::paste_from_clipboard().and_then(|code| sharing::level_from_code(&code)) {
+            Ok(level) => {
+                let snapshot = editor.cells.clone();
+                editor.undo.push(snapshot);
+                if editor.undo.len() > UNDO_LIMIT {
+                    editor.undo.remove(0);
+                }
+                editor.cells = cells_from_level(Some(&level), &GridGeometry::new(&arena));
+                editor.ball_speed = level.ball_speed.clamp(MIN_BALL_SPEED, MAX_BALL_SPEED);
+                toasts.send(ShowToast(format!("Level übernommen: {}", level.name)));
+            }
+            Err(error) => toasts.send(ShowToast(error)),
+        }
+    }
+    // Strg+E legt eine Kopie bei den eigenen Leveln ab.
+    if control && keyboard_input.just_pressed(KeyCode::E) {
+        toasts.send(ShowToast(match sharing::save_user_level(&editor.level()) {
+            Ok(path) => format!("Gespeichert unter {}", path.display()),
+            Err(error) => format!("Level konnte nicht gespeichert werden: {error}"),
+        }));
+    }
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        campaign.custom_level = Some(levels.add(editor.level()));
+        *mode = GameMode::TestPlay;
+        *mutators = Mutators::default();
+        transition.to(GameState::Playing);
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        // Wie in back_to_menu, sonst würde close_on_esc im Menü im selben Frame das Fenster schließen.
+        keyboard_input.reset(KeyCode::Escape);
+        transition.to(GameState::Menu);
+    }
+}
+
+fn update_cells(
+    editor: Res<LevelEditor>,
+    theme_materials: Res<ThemeMaterials>,
+    mut query: Query<(&EditorCell, &mut Handle<StandardMaterial>)>,
+    added: Query<(), Added<EditorCell>>,
+) {
+    if !editor.is_changed() && added.is_empty() {
+        return;
+    }
+    for (cell, mut material) in &mut query {
+        *material = match editor.cells[cell.row][cell.column] {
+            Some(kind) => theme_materials.brick(kind),
+            None => editor.empty_material.clone(),
+        };
+    }
+}
+
+fn update_editor_text(editor: Res<LevelEditor>, mut query: Query<&mut Text, With<EditorText>>) {
+    let Ok(mut text) = query.get_single_mut() else { return };
+    text.sections[0].value = format!(
+        "Brick: {}  Ball: {:.1}x  Rückgängig: {}\n\
+         Links malen, rechts löschen, 1-9 Brick wählen, Hoch/Runter Ballgeschwindigkeit\n\
+         Strg+Z rückgängig, Strg+S speichern, Strg+E zu eigenen Leveln, Enter testen, Esc Menü\n\
+         Strg+C Code kopieren, Strg+V Code einfügen",
+        editor.brush.name(),
+        editor.ball_speed,
+        editor.undo.len(),
+    );
+}
+
+fn despawn_editor(mut commands: Commands, query: Query<Entity, With<EditorEntity>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
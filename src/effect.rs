@@ -0,0 +1,115 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::powerup::PowerUpKind;
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, Phase, TimeScale};
+
+// So viele Sekunden vor dem Ende eines hilfreichen Effekts kommt ein Warnton.
+const ENDING_WARNING: f32 = 2.0;
+
+// Zeitlich begrenzte Effekte sind Komponenten mit einem Timer. Das TimedEffectPlugin lässt den Timer
+// im festen Spieltakt ablaufen und entfernt die Komponente danach wieder. Solange er läuft, steht der Effekt
+// unter dem Power-Up, das ihn ausgelöst hat, in ActiveEffects.
+pub trait TimedEffect: Component {
+    fn timer(&self) -> &Timer;
+    fn timer_mut(&mut self) -> &mut Timer;
+    fn kind(&self) -> PowerUpKind;
+}
+
+// Was ein neu eingesammeltes Power-Up mit einem laufenden Effekt an derselben Stelle macht.
+// Derselbe Effekt frischt nur die Dauer auf, statt sich zu stapeln. Zwei gegensätzliche Effekte, etwa ein breites
+// und ein schmales Paddle, heben sich auf und keiner bleibt übrig.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stacking {
+    Start,
+    Refresh,
+    Cancel,
+}
+
+impl Stacking {
+    // Für Effekte, die eine Größe mit einem Faktor ändern. Vergrößern und Verkleinern sind Gegensätze.
+    pub fn resize(active: Option<f32>, incoming: f32) -> Stacking {
+        match active {
+            None => Stacking::Start,
+            Some(factor) if (factor > 1.0) == (incoming > 1.0) => Stacking::Refresh,
+            Some(_) => Stacking::Cancel,
+        }
+    }
+}
+
+// Alle gerade laufenden Effekte mit dem Anteil ihrer Dauer, der noch übrig ist. Jeder Effekt steht höchstens
+// einmal darin, auch wenn mehrere Bälle ihn haben. Wird in jedem Frame neu gefüllt, die Anzeige steht in effect_hud.rs.
+#[derive(Resource, Default)]
+pub struct ActiveEffects(Vec<(PowerUpKind, f32)>);
+
+impl ActiveEffects {
+    pub fn report(&mut self, kind: PowerUpKind, timer: &Timer) {
+        let remaining = timer.percent_left();
+        match self.0.iter_mut().find(|(active, _)| *active == kind) {
+            Some((_, fraction)) => *fraction = fraction.max(remaining),
+            None => self.0.push((kind, remaining)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PowerUpKind, f32)> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+// Füllt ActiveEffects in jedem Frame neu. Die einzelnen Effekte melden sich im Update-Stage.
+pub struct ActiveEffectsPlugin;
+
+impl Plugin for ActiveEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveEffects>()
+            .add_system_to_stage(CoreStage::PreUpdate, clear_active_effects);
+    }
+}
+
+fn clear_active_effects(mut active: ResMut<ActiveEffects>) {
+    active.0.clear();
+}
+
+pub struct TimedEffectPlugin<T: TimedEffect>(PhantomData<T>);
+
+impl<T: TimedEffect> Default for TimedEffectPlugin<T> {
+    fn default() -> Self {
+        TimedEffectPlugin(PhantomData)
+    }
+}
+
+impl<T: TimedEffect> Plugin for TimedEffectPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(phase_set(Phase::Scoring).with_system(tick_effect::<T>))
+            .add_system(report_effect::<T>);
+    }
+}
+
+fn report_effect<T: TimedEffect>(query: Query<&T>, mut active: ResMut<ActiveEffects>) {
+    for effect in &query {
+        active.report(effect.kind(), effect.timer());
+    }
+}
+
+// Läuft eine Gefahr aus, ist das eine gute Nachricht, davor wird nicht gewarnt.
+fn tick_effect<T: TimedEffect>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut T)>,
+    time_scale: Res<TimeScale>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for (entity, mut effect) in &mut query {
+        let before = effect.timer().remaining_secs();
+        if effect.timer_mut().tick(Duration::from_secs_f32(time_scale.step())).finished() {
+            commands.entity(entity).remove::<T>();
+        } else if before > ENDING_WARNING
+            && effect.timer().remaining_secs() <= ENDING_WARNING
+            && !effect.kind().is_hazard()
+        {
+            sounds.send(PlaySound(Sfx::EffectEnding));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::accessibility::Palette;
+use crate::effect::ActiveEffects;
+use crate::powerup::PowerUpKind;
+use crate::{GameState, LevelEntity};
+
+const ICON_SIZE: f32 = 36.0;
+const ICON_SPACING: f32 = 24.0;
+const SYMBOL_FONT_SIZE: f32 = 20.0;
+const SYMBOL_COLOR: Color = Color::WHITE;
+// Der Countdown ist ein Ring aus Punkten um das Symbol. Mit der Restdauer verschwindet einer nach dem anderen.
+const RING_DOTS: usize = 12;
+const DOT_SIZE: f32 = 5.0;
+const RING_RADIUS: f32 = 25.0;
+const DOT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+
+// Die Leiste unten rechts, in der die Symbole der laufenden Effekte stehen.
+#[derive(Component)]
+struct EffectBar;
+
+#[derive(Component)]
+struct EffectIcon(PowerUpKind);
+
+// Punkt `0` sitzt oben, die übrigen folgen im Uhrzeigersinn.
+#[derive(Component)]
+struct CountdownDot(usize);
+
+pub struct EffectHudPlugin;
+
+impl Plugin for EffectHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_effect_bar))
+            .add_system_to_stage(CoreStage::PostUpdate, rebuild_icons)
+            .add_system_to_stage(CoreStage::PostUpdate, update_countdowns.after(rebuild_icons));
+    }
+}
+
+fn spawn_effect_bar(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(20.0),
+                    right: Val::Px(20.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ..default()
+        },
+        EffectBar,
+        LevelEntity,
+    ));
+}
+
+// Kommt ein Effekt hinzu oder läuft einer ab, wird die Leiste neu gebaut. Sonst ändern sich nur die Ringe.
+fn rebuild_icons(
+    mut commands: Commands,
+    active: Res<ActiveEffects>,
+    bar_query: Query<Entity, With<EffectBar>>,
+    icon_query: Query<&EffectIcon>,
+    palette: Res<Palette>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(bar) = bar_query.get_single() else { return };
+    let shown: Vec<_> = icon_query.iter().map(|icon| icon.0).collect();
+    let wanted: Vec<_> = active.iter().map(|(kind, _)| kind).collect();
+    // Die Reihenfolge der Abfrage ist nicht festgelegt, verglichen wird deshalb nur, welche Effekte es sind.
+    if shown.len() == wanted.len() && wanted.iter().all(|kind| shown.contains(kind)) {
+        return;
+    }
+    commands.entity(bar).despawn_descendants();
+    commands.entity(bar).with_children(|parent| {
+        for kind in wanted {
+            spawn_icon(parent, kind, &palette, &asset_server);
+        }
+    });
+}
+
+fn spawn_icon(parent: &mut ChildBuilder, kind: PowerUpKind, palette: &Palette, asset_server: &AssetServer) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Px(ICON_SIZE), Val::Px(ICON_SIZE)),
+                    margin: UiRect::horizontal(Val::Px(ICON_SPACING / 2.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: palette.powerup(kind).into(),
+                ..default()
+            },
+            EffectIcon(kind),
+        ))
+        .with_children(|icon| {
+            icon.spawn(TextBundle::from_section(
+                kind.symbol(),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                    font_size: SYMBOL_FONT_SIZE,
+                    color: SYMBOL_COLOR,
+                },
+            ));
+            for index in 0..RING_DOTS {
+                let angle = index as f32 / RING_DOTS as f32 * TAU;
+                let center = ICON_SIZE / 2.0;
+                icon.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            position: UiRect {
+                                left: Val::Px(center + angle.sin() * RING_RADIUS - DOT_SIZE / 2.0),
+                                top: Val::Px(center - angle.cos() * RING_RADIUS - DOT_SIZE / 2.0),
+                                ..default()
+                            },
+                            size: Size::new(Val::Px(DOT_SIZE), Val::Px(DOT_SIZE)),
+                            ..default()
+                        },
+                        background_color: DOT_COLOR.into(),
+                        ..default()
+                    },
+                    CountdownDot(index),
+                ));
+            }
+        });
+}
+
+// Ein voller Ring heißt volle Dauer. Die Punkte verschwinden gegen den Uhrzeigersinn, der oberste zuletzt.
+fn update_countdowns(
+    active: Res<ActiveEffects>,
+    icon_query: Query<&EffectIcon>,
+    mut dot_query: Query<(&CountdownDot, &Parent, &mut Visibility)>,
+) {
+    for (dot, parent, mut visibility) in &mut dot_query {
+        let Ok(icon) = icon_query.get(parent.get()) else { continue };
+        let remaining = active.iter().find(|(kind, _)| *kind == icon.0).map_or(0.0, |(_, fraction)| fraction);
+        let visible = (dot.0 as f32) < (remaining * RING_DOTS as f32).ceil();
+        if visibility.is_visible != visible {
+            visibility.is_visible = visible;
+        }
+    }
+}
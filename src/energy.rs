@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::powerup::PowerUpKind;
+use crate::sound::{PlaySound, Sfx};
+use crate::{Ball, BrickDestroyed, GameState, LevelEntity, Piercing};
+
+const ENERGY_PER_BRICK: f32 = 0.1;
+const SMASH_DURATION: f32 = 3.0;
+const SMASH_KEY: KeyCode = KeyCode::X;
+const METER_WIDTH: f32 = 200.0;
+const METER_HEIGHT: f32 = 20.0;
+const METER_BACKGROUND: Color = Color::rgba(0.0, 0.0, 0.0, 0.4);
+const METER_CHARGING: Color = Color::rgb(0.2, 0.6, 1.0);
+const METER_FULL: Color = Color::rgb(1.0, 0.35, 0.0);
+
+// Füllt sich mit jedem zerstörten Brick. Ist sie voll, kann der Spieler einen "Smash" auslösen.
+#[derive(Resource, Default)]
+pub struct Energy(pub f32);
+
+impl Energy {
+    fn is_full(&self) -> bool {
+        self.0 >= 1.0
+    }
+}
+
+#[derive(Component)]
+struct EnergyFill;
+
+pub struct EnergyPlugin;
+
+impl Plugin for EnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Energy>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_energy_meter))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(collect_energy)
+                    .with_system(smash.after(collect_energy))
+                    .with_system(update_energy_meter.after(smash)),
+            );
+    }
+}
+
+fn spawn_energy_meter(mut commands: Commands, mut energy: ResMut<Energy>) {
+    energy.0 = 0.0;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    // Unter der Lebensanzeige am rechten Rand.
+                    position: UiRect {
+                        top: Val::Px(55.0),
+                        right: Val::Px(10.0),
+                        ..default()
+                    },
+                    size: Size::new(Val::Px(METER_WIDTH), Val::Px(METER_HEIGHT)),
+                    ..default()
+                },
+                background_color: METER_BACKGROUND.into(),
+                ..default()
+            },
+            LevelEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                        ..default()
+                    },
+                    background_color: METER_CHARGING.into(),
+                    ..default()
+                },
+                EnergyFill,
+            ));
+        });
+}
+
+fn collect_energy(mut events: EventReader<BrickDestroyed>, mut energy: ResMut<Energy>) {
+    let destroyed = events.iter().count();
+    if destroyed > 0 {
+        energy.0 = (energy.0 + destroyed as f32 * ENERGY_PER_BRICK).min(1.0);
+    }
+}
+
+// Der Smash macht alle Bälle für ein paar Sekunden durchschlagend und leert die Energie.
+fn smash(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut energy: ResMut<Energy>,
+    ball_query: Query<Entity, With<Ball>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    if !energy.is_full() || !keyboard_input.just_pressed(SMASH_KEY) {
+        return;
+    }
+    energy.0 = 0.0;
+    for ball in &ball_query {
+        commands
+            .entity(ball)
+            .insert(Piercing(Timer::from_seconds(SMASH_DURATION, TimerMode::Once), PowerUpKind::Smash));
+    }
+    sounds.send(PlaySound(Sfx::Smash));
+}
+
+fn update_energy_meter(energy: Res<Energy>, mut query: Query<(&mut Style, &mut BackgroundColor), With<EnergyFill>>) {
+    if !energy.is_changed() {
+        return;
+    }
+    for (mut style, mut color) in &mut query {
+        style.size.width = Val::Percent(energy.0 * 100.0);
+        *color = if energy.is_full() { METER_FULL } else { METER_CHARGING }.into();
+    }
+}
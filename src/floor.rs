@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{Campaign, Level};
+use crate::sound::{PlaySound, Sfx};
+use crate::{Arena, GameState, LevelEntity};
+
+// Die Streifen liegen knapp vor der unteren Wand, damit sie sie ganz überdecken.
+const ZONE_SCALE: f32 = 1.05;
+const LAVA_COLOR: Color = Color::rgb(1.0, 0.3, 0.05);
+const BUMPER_COLOR: Color = Color::rgb(1.0, 0.8, 0.2);
+// So sieht ein Bumper aus, der seinen Abpraller schon hergegeben hat.
+const USED_BUMPER_COLOR: Color = Color::rgb(0.35, 0.3, 0.2);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloorKind {
+    // Verschluckt den Ball, sobald er unter das Paddle gerät. Die Rettung greift hier nicht.
+    Lava,
+    // Wirft den Ball einmal pro Level zurück nach oben, danach ist er ein normaler Boden.
+    Bumper,
+}
+
+// Ein Abschnitt des Bodens, wie er in der Level-Datei angegeben wird, von links nach rechts in Weltkoordinaten:
+// (from: -3.0, to: -1.0, kind: Lava)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct FloorZoneDefinition {
+    pub from: f32,
+    pub to: f32,
+    pub kind: FloorKind,
+}
+
+// Was der Ball an einer Stelle des Bodens vorfindet.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FloorSurface {
+    // Der Ball fällt durch und geht am unteren Rand verloren, wie ohne Zonen überall.
+    Open,
+    Lava,
+    // Ein Bumper, der noch abprallen lässt. Die Nummer gibt consume_bumper zurück.
+    Bumper(usize),
+}
+
+struct FloorZone {
+    from: f32,
+    to: f32,
+    kind: FloorKind,
+    used: bool,
+    strip: Entity,
+}
+
+// Die Zonen am Boden des laufenden Levels. check_ball_lost fragt hier nach, was unter dem Ball liegt.
+#[derive(Resource, Default)]
+pub struct FloorMap {
+    zones: Vec<FloorZone>,
+}
+
+impl FloorMap {
+    // Überlappen sich Zonen, gilt die zuerst angegebene.
+    pub fn surface_at(&self, x: f32) -> FloorSurface {
+        let zone = self.zones.iter().enumerate().find(|(_, zone)| x >= zone.from && x <= zone.to);
+        match zone {
+            Some((_, zone)) if zone.kind == FloorKind::Lava => FloorSurface::Lava,
+            Some((index, zone)) if !zone.used => FloorSurface::Bumper(index),
+            _ => FloorSurface::Open,
+        }
+    }
+
+    pub fn consume_bumper(&mut self, index: usize) {
+        if let Some(zone) = self.zones.get_mut(index) {
+            zone.used = true;
+        }
+    }
+}
+
+// Wird gesendet, wenn ein Bumper im Boden den Ball zurückgeworfen hat.
+pub struct FloorBounce;
+
+// Die Materialien der Streifen, eines je Art und eines für verbrauchte Bumper.
+#[derive(Resource)]
+struct FloorMaterials {
+    lava: Handle<StandardMaterial>,
+    bumper: Handle<StandardMaterial>,
+    used: Handle<StandardMaterial>,
+}
+
+pub struct FloorPlugin;
+
+impl Plugin for FloorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FloorMap>()
+            .add_event::<FloorBounce>()
+            .add_startup_system(create_materials)
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_floor_zones))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(show_used_bumpers));
+    }
+}
+
+fn create_materials(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
+    let glowing = |color: Color| StandardMaterial {
+        base_color: color,
+        emissive: color,
+        ..default()
+    };
+    commands.insert_resource(FloorMaterials {
+        lava: materials.add(glowing(LAVA_COLOR)),
+        bumper: materials.add(glowing(BUMPER_COLOR)),
+        used: materials.add(USED_BUMPER_COLOR.into()),
+    });
+}
+
+// Jedes Level beginnt mit frischen Bumpern. Jede Zone liegt als leuchtender Streifen auf der unteren Wand.
+fn spawn_floor_zones(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    floor_materials: Res<FloorMaterials>,
+    mut floor: ResMut<FloorMap>,
+    arena: Res<Arena>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    floor.zones.clear();
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if level.floor.is_empty() {
+        return;
+    }
+    let mesh = meshes.add(shape::Cube::default().into());
+    for definition in &level.floor {
+        let material = match definition.kind {
+            FloorKind::Lava => floor_materials.lava.clone(),
+            FloorKind::Bumper => floor_materials.bumper.clone(),
+        };
+        let width = (definition.to - definition.from).max(0.0);
+        let size = Vec3::new(width, arena.wall_thickness * ZONE_SCALE, arena.depth * ZONE_SCALE);
+        let center = (definition.from + definition.to) / 2.0;
+        let strip = commands
+            .spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material,
+                    transform: Transform::from_xyz(center, arena.bottom(), 0.0).with_scale(size),
+                    ..default()
+                },
+                LevelEntity,
+            ))
+            .id();
+        floor.zones.push(FloorZone {
+            from: definition.from,
+            to: definition.to,
+            kind: definition.kind,
+            used: false,
+            strip,
+        });
+    }
+}
+
+fn show_used_bumpers(
+    mut events: EventReader<FloorBounce>,
+    floor: Res<FloorMap>,
+    floor_materials: Res<FloorMaterials>,
+    mut strip_query: Query<&mut Handle<StandardMaterial>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    sounds.send(PlaySound(Sfx::Bumper));
+    for zone in floor.zones.iter().filter(|zone| zone.used) {
+        if let Ok(mut material) = strip_query.get_mut(zone.strip) {
+            *material = floor_materials.used.clone();
+        }
+    }
+}
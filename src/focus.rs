@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+// Ab dieser Auslenkung zählt der linke Stick als gedrückte Richtung.
+const STICK_THRESHOLD: f32 = 0.5;
+
+// Der Button, der gerade ausgewählt ist. Auf jedem Bildschirm trägt höchstens einer diese Komponente.
+#[derive(Component)]
+pub struct Focused;
+
+// Wird gesendet, wenn der ausgewählte Button mit Enter, Leertaste oder der A-Taste am Gamepad bestätigt wird.
+// Ein Mausklick läuft weiter über Interaction.
+pub struct Activated(pub Entity);
+
+// Jeder Bildschirm mit Buttons lässt sich so auch ohne Maus bedienen, ohne dass er selbst etwas dafür tun muss.
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Activated>()
+            .add_system(move_focus)
+            .add_system(activate_focused.after(move_focus));
+    }
+}
+
+// -1 für nach oben, 1 für nach unten, 0 ohne Eingabe. Der Stick zählt nur beim Überschreiten der Schwelle,
+// sonst würde die Auswahl bei gehaltenem Stick in jedem Frame weiterspringen.
+fn navigation_input(
+    keyboard_input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_buttons: &Input<GamepadButton>,
+    axes: &Axis<GamepadAxis>,
+    stick_held: &mut bool,
+) -> i32 {
+    let mut direction = 0;
+    if keyboard_input.any_just_pressed([KeyCode::Up, KeyCode::Left]) {
+        direction -= 1;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Down, KeyCode::Right]) {
+        direction += 1;
+    }
+    for gamepad in gamepads.iter() {
+        let pressed = |button_type| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+        if pressed(GamepadButtonType::DPadUp) || pressed(GamepadButtonType::DPadLeft) {
+            direction -= 1;
+        }
+        if pressed(GamepadButtonType::DPadDown) || pressed(GamepadButtonType::DPadRight) {
+            direction += 1;
+        }
+    }
+    // Bei mehreren Gamepads zählt der am weitesten ausgelenkte Stick.
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.0))
+        .fold(0.0, |strongest: f32, y| if y.abs() > strongest.abs() { y } else { strongest });
+    // Der Stick zeigt nach oben positiv, die Liste läuft von oben nach unten.
+    if !*stick_held && stick.abs() > STICK_THRESHOLD {
+        direction -= stick.signum() as i32;
+    }
+    *stick_held = stick.abs() > STICK_THRESHOLD;
+    direction.signum()
+}
+
+// Die Reihenfolge ergibt sich aus der Lage auf dem Bildschirm, von oben nach unten und von links nach rechts.
+// Am Ende der Liste geht es oben wieder los. Fährt die Maus über einen Button, wird er ausgewählt,
+// damit Maus und Tastatur nie zwei verschiedene Buttons hervorheben.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn move_focus(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    query: Query<(Entity, &GlobalTransform, Option<&Focused>), With<Button>>,
+    hovered: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    mut stick_held: Local<bool>,
+) {
+    let direction = navigation_input(&keyboard_input, &gamepads, &gamepad_buttons, &axes, &mut stick_held);
+    // Frisch gespawnte Buttons stehen bis zum ersten Layout noch im Ursprung und zählen erst danach mit.
+    let mut buttons: Vec<_> = query
+        .iter()
+        .filter(|(_, transform, _)| transform.translation().truncate() != Vec2::ZERO)
+        .collect();
+    if buttons.is_empty() {
+        return;
+    }
+    buttons.sort_by(|(_, a, _), (_, b, _)| {
+        let (a, b) = (a.translation(), b.translation());
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    });
+    let current = buttons.iter().position(|(_, _, focused)| focused.is_some());
+
+    let target = if let Some((entity, _)) = hovered.iter().find(|(_, interaction)| **interaction == Interaction::Hovered) {
+        Some(entity)
+    } else {
+        match current {
+            None => Some(buttons[0].0),
+            Some(index) if direction != 0 => {
+                let next = (index as i32 + direction).rem_euclid(buttons.len() as i32) as usize;
+                Some(buttons[next].0)
+            }
+            Some(_) => None,
+        }
+    };
+    let Some(target) = target else { return };
+    if let Some(index) = current {
+        if buttons[index].0 == target {
+            return;
+        }
+        commands.entity(buttons[index].0).remove::<Focused>();
+    }
+    commands.entity(target).insert(Focused);
+}
+
+fn activate_focused(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    query: Query<Entity, (With<Focused>, With<Button>)>,
+    mut events: EventWriter<Activated>,
+) {
+    let confirmed = keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::Space])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South)));
+    if !confirmed {
+        return;
+    }
+    if let Ok(entity) = query.get_single() {
+        events.send(Activated(entity));
+    }
+}
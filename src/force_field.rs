@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{Campaign, Level};
+use crate::{phase_set, GameState, Kinematic, LevelEntity, Phase, TimeScale};
+
+// Wie ein Kraftfeld auf die Objekte in seinem Inneren wirkt.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum ForceKind {
+    // Gleichmäßige Beschleunigung in eine Richtung, z.B. Wind oder eine Strömung.
+    Directional(Vec2),
+    // Beschleunigung zur Mitte des Feldes (positive Stärke) oder von ihr weg (negative Stärke).
+    // Mit `swirl` kommt eine Beschleunigung quer dazu hinzu, wodurch ein Wirbel entsteht.
+    Radial {
+        strength: f32,
+        #[serde(default)]
+        swirl: f32,
+    },
+}
+
+// Ein Kraftfeld, wie es in der Level-Datei angegeben wird. Position und Größe sind in Arena-Koordinaten.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForceFieldDefinition {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub kind: ForceKind,
+}
+
+#[derive(Component)]
+pub struct ForceField {
+    half_size: Vec2,
+    kind: ForceKind,
+}
+
+impl ForceField {
+    // Die Beschleunigung, die ein Objekt an `position` erfährt, oder None, wenn es außerhalb des Feldes liegt.
+    fn acceleration(&self, center: Vec2, position: Vec2) -> Option<Vec2> {
+        let offset = position - center;
+        if offset.x.abs() > self.half_size.x || offset.y.abs() > self.half_size.y {
+            return None;
+        }
+        match self.kind {
+            ForceKind::Directional(direction) => Some(direction),
+            ForceKind::Radial { strength, swirl } => {
+                let inward = -offset.normalize_or_zero();
+                Some(inward * strength + inward.perp() * swirl)
+            }
+        }
+    }
+}
+
+pub struct ForceFieldPlugin;
+
+impl Plugin for ForceFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_force_fields))
+            .add_system_set(phase_set(Phase::Forces).with_system(apply_force_fields));
+    }
+}
+
+// Die Felder werden als durchsichtige Quader dargestellt, damit man ihre Grenzen im Spiel sieht.
+fn spawn_force_fields(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if level.force_fields.is_empty() {
+        return;
+    }
+
+    let mesh = meshes.add(shape::Cube::default().into());
+    for definition in &level.force_fields {
+        let color = match definition.kind {
+            ForceKind::Directional(_) => Color::rgba(0.3, 0.7, 1.0, 0.15),
+            ForceKind::Radial { .. } => Color::rgba(0.8, 0.3, 1.0, 0.15),
+        };
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: color,
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation(definition.position.extend(0.0))
+                    .with_scale(definition.size.extend(1.0)),
+                ..default()
+            },
+            ForceField {
+                half_size: definition.size / 2.0,
+                kind: definition.kind,
+            },
+            LevelEntity,
+        ));
+    }
+}
+
+// Jedes bewegte Objekt, das in einem Feld liegt, wird pro Schritt von diesem beschleunigt: Ball, Power-Ups,
+// Trümmer und Geschosse.
+fn apply_force_fields(
+    field_query: Query<(&ForceField, &Transform)>,
+    mut body_query: Query<(&Transform, &mut Kinematic)>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = time_scale.step();
+    for (field, field_transform) in &field_query {
+        let center = field_transform.translation.truncate();
+        for (transform, mut body) in &mut body_query {
+            if let Some(acceleration) = field.acceleration(center, transform.translation.truncate()) {
+                body.velocity += acceleration.extend(0.0) * step;
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Zahlen und Zeiten für die Oberfläche. Alle Anzeigen von Punkten und Uhren gehen hier durch, damit sie überall
+// gleich aussehen. Das Spiel gibt es bisher nur auf Deutsch, die Trennzeichen stehen deshalb fest. Kommen
+// Übersetzungen dazu, werden sie hier aus der gewählten Sprache gelesen.
+const THOUSANDS_SEPARATOR: char = '.';
+const DECIMAL_SEPARATOR: char = ',';
+
+// Ganze Zahlen mit Tausendertrennzeichen, etwa 12.345.
+pub fn number(value: usize) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(THOUSANDS_SEPARATOR);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// Uhren als Minuten, Sekunden und Hundertstel, etwa 01:23,45.
+pub fn timer(seconds: f32) -> String {
+    let hundredths = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{:02}:{:02}{}{:02}",
+        hundredths / 6000,
+        hundredths / 100 % 60,
+        DECIMAL_SEPARATOR,
+        hundredths % 100
+    )
+}
+
+// Kurze Dauern wie Abklingzeiten mit einer Nachkommastelle, etwa 2,5s.
+pub fn seconds(seconds: f32) -> String {
+    decimal(format!("{seconds:.1}s"))
+}
+
+// Abstände zu einer Bestzeit mit Vorzeichen, etwa +1,25.
+pub fn signed_seconds(seconds: f32) -> String {
+    decimal(format!("{seconds:+.2}"))
+}
+
+// Kleine Abstände in Zentimetern mit einer Nachkommastelle, etwa 1,5 cm. Die Welt misst in Metern.
+pub fn centimeters(meters: f32) -> String {
+    decimal(format!("{:.1} cm", meters * 100.0))
+}
+
+fn decimal(formatted: String) -> String {
+    formatted.replace('.', &DECIMAL_SEPARATOR.to_string())
+}
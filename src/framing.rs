@@ -0,0 +1,126 @@
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::AccessibilitySettings;
+use crate::easing::Ease;
+use crate::tween::{Lens, LensPlugin, TransformLens, Tween};
+use crate::{Arena, MainCamera};
+
+// Das Sichtfeld ohne Angabe im Level, wie bei Bevys Standardkamera.
+pub const DEFAULT_FOV: f32 = FRAC_PI_4;
+// Engere oder weitere Sichtfelder verzerren das Bild zu stark, Angaben außerhalb werden darauf begrenzt. In Grad.
+const MIN_FOV: f32 = 20.0;
+const MAX_FOV: f32 = 100.0;
+// So weit muss die Kamera mindestens vor der Vorderkante der Wände stehen.
+const MIN_CAMERA_GAP: f32 = 1.0;
+// So lange gleitet die Kamera beim Wechsel in ein Level mit anderem Ausschnitt.
+const GLIDE_DURATION: f32 = 0.8;
+
+// Der Blick auf die Arena, wie ihn eine Level-Datei vorgibt, in Weltkoordinaten.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CameraFraming {
+    pub position: Vec3,
+    pub look_at: Vec3,
+    // In Grad. Ohne Angabe gilt das Sichtfeld der Standardkamera.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fov: Option<f32>,
+}
+
+impl CameraFraming {
+    // Prüft die Angaben gegen die Maße der Arena. Der Blickpunkt wird in die Arena gezogen, das Sichtfeld begrenzt.
+    // Steht die Kamera in oder hinter den Wänden, gilt die Angabe nicht und die Kamera richtet sich nach der Größe.
+    pub fn fit(self, arena: &Arena) -> Option<CameraFraming> {
+        let front = arena.depth / 2.0 + MIN_CAMERA_GAP;
+        if self.position.z < front {
+            let z = self.position.z;
+            warn!("Die Kamera des Levels steht bei z = {z} hinter der Vorderkante {front} und wird ignoriert");
+            return None;
+        }
+        let look_at = self.look_at.clamp(
+            Vec3::new(arena.left(), arena.bottom(), -arena.depth / 2.0),
+            Vec3::new(arena.right(), arena.top(), arena.depth / 2.0),
+        );
+        if look_at != self.look_at {
+            let wanted = self.look_at;
+            warn!("Der Blickpunkt {wanted} der Kamera liegt außerhalb der Arena und wird auf {look_at} verschoben");
+        }
+        Some(CameraFraming {
+            position: self.position,
+            look_at,
+            fov: self.fov.map(|fov| fov.clamp(MIN_FOV, MAX_FOV)),
+        })
+    }
+
+    pub fn scaled(self, factor: f32) -> CameraFraming {
+        CameraFraming {
+            position: self.position * factor,
+            look_at: self.look_at * factor,
+            ..self
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        Transform::from_translation(self.position).looking_at(self.look_at, Vec3::Y)
+    }
+
+    // Das Sichtfeld in Bogenmaß.
+    pub fn fov(&self) -> Option<f32> {
+        self.fov.map(f32::to_radians)
+    }
+}
+
+struct FovLens {
+    start: f32,
+    end: f32,
+}
+
+impl Lens for FovLens {
+    type Target = Projection;
+
+    fn apply(&self, projection: &mut Projection, ratio: f32) {
+        if let Projection::Perspective(perspective) = projection {
+            perspective.fov = self.start + (self.end - self.start) * ratio;
+        }
+    }
+}
+
+pub struct FramingPlugin;
+
+impl Plugin for FramingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(LensPlugin::<FovLens>::default()).add_system(glide_camera);
+    }
+}
+
+// Ändert sich die Arena, gleitet die Kamera zum neuen Ausschnitt. Mit weniger Bewegung springt sie. Ändert sich
+// nur etwas, das den Ausschnitt nicht betrifft, etwa das Zusammenrücken der Wände, bleibt die Kamera, wo sie ist.
+fn glide_camera(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    settings: Res<AccessibilitySettings>,
+    mut camera_query: Query<(Entity, &mut Transform, &mut Projection), With<MainCamera>>,
+    mut framed: Local<Option<(Transform, f32)>>,
+) {
+    if !arena.is_changed() {
+        return;
+    }
+    let (target, fov) = (arena.camera_transform(), arena.camera_fov());
+    if *framed == Some((target, fov)) {
+        return;
+    }
+    *framed = Some((target, fov));
+    for (entity, mut transform, mut projection) in &mut camera_query {
+        let Projection::Perspective(perspective) = &mut *projection else { continue };
+        if !settings.allows_motion() {
+            *transform = target;
+            perspective.fov = fov;
+            continue;
+        }
+        commands.entity(entity).insert((
+            Tween::new(TransformLens { start: *transform, end: target }, GLIDE_DURATION).with_ease(Ease::OutCubic),
+            Tween::new(FovLens { start: perspective.fov, end: fov }, GLIDE_DURATION).with_ease(Ease::OutCubic),
+        ));
+    }
+}
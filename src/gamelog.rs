@@ -0,0 +1,291 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::fs;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+
+use crate::level::BrickKind;
+use crate::near_miss::{NearMiss, NearMissKind};
+use crate::powerup::PowerUpCollected;
+use crate::toast::ShowToast;
+use crate::{BallLost, BrickDestroyed, CollisionEvent, GameState, LevelCleared, Lives};
+
+// So viele Ereignisse bleiben aufgehoben, die ältesten fallen heraus.
+const LOG_CAPACITY: usize = 512;
+const MARKER_SIZE: f32 = 0.5;
+const MARKER_COLOR: Color = Color::rgb(1.0, 0.9, 0.0);
+
+#[derive(Clone)]
+enum GameEvent {
+    Collision(Vec3),
+    BrickDestroyed { position: Vec3, penalty: bool },
+    PowerUp(&'static str),
+    LivesChanged(u32),
+    BallLost,
+    LevelCleared(usize),
+    NearMiss { position: Vec3, kind: NearMissKind, distance: f32 },
+}
+
+impl GameEvent {
+    fn describe(&self) -> String {
+        match self {
+            GameEvent::Collision(position) => format!("Kollision bei ({:.2}, {:.2})", position.x, position.y),
+            GameEvent::BrickDestroyed { position, penalty } => format!(
+                "{} zerstört bei ({:.2}, {:.2})",
+                if *penalty { "Strafbrick" } else { "Brick" },
+                position.x,
+                position.y
+            ),
+            GameEvent::PowerUp(name) => format!("Power-Up eingesammelt: {name}"),
+            GameEvent::LivesChanged(lives) => format!("Leben: {lives}"),
+            GameEvent::BallLost => "Ball verloren".to_string(),
+            GameEvent::LevelCleared(index) => format!("Level {} geschafft", index + 1),
+            GameEvent::NearMiss { position, kind, distance } => format!(
+                "{} bei ({:.2}, {:.2}), {:.3} m",
+                match kind {
+                    NearMissKind::CloseSave => "Knapp gerettet",
+                    NearMissKind::BrickCorner => "Knapp an Ecke vorbei",
+                },
+                position.x,
+                position.y,
+                distance
+            ),
+        }
+    }
+
+    // Die Stelle in der Arena, an der das Ereignis passiert ist, falls es eine gibt.
+    fn position(&self) -> Option<Vec3> {
+        match self {
+            GameEvent::Collision(position)
+            | GameEvent::BrickDestroyed { position, .. }
+            | GameEvent::NearMiss { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct LogEntry {
+    // Sekunden seit dem Start des Spiels.
+    time: f32,
+    event: GameEvent,
+}
+
+impl LogEntry {
+    fn describe(&self) -> String {
+        format!("[{:8.3}] {}", self.time, self.event.describe())
+    }
+}
+
+// Das Protokoll liegt außerhalb der Welt, damit der Panic-Hook es auch beim Absturz noch lesen kann.
+static GAME_LOG: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+// Die letzten Ereignisse als Text, eines pro Zeile, für den Absturzbericht.
+pub fn recent_events() -> String {
+    let Ok(log) = GAME_LOG.try_lock() else { return "nicht verfügbar".to_string() };
+    log.iter().fold(String::new(), |mut text, entry| {
+        let _ = writeln!(text, "{}", entry.describe());
+        text
+    })
+}
+
+// Eine Kopie des Protokolls, in der der Inspektor Schritt für Schritt blättert. Das Spiel steht so lange still.
+#[derive(Resource, Default)]
+struct InspectedLog {
+    entries: Vec<LogEntry>,
+    index: usize,
+}
+
+#[derive(Component)]
+struct InspectorEntity;
+
+#[derive(Component)]
+struct InspectorText;
+
+#[derive(Component)]
+struct InspectorMarker;
+
+pub struct GameLogPlugin;
+
+impl Plugin for GameLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectedLog>()
+            .add_system(record_events)
+            .add_system(dump_log)
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(open_inspector))
+            .add_system_set(SystemSet::on_enter(GameState::LogInspector).with_system(spawn_inspector))
+            .add_system_set(SystemSet::on_update(GameState::LogInspector).with_system(step_inspector))
+            .add_system_set(SystemSet::on_exit(GameState::LogInspector).with_system(despawn_inspector));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_events(
+    time: Res<Time>,
+    lives: Res<Lives>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut bricks: EventReader<BrickDestroyed>,
+    mut powerups: EventReader<PowerUpCollected>,
+    mut balls_lost: EventReader<BallLost>,
+    mut levels_cleared: EventReader<LevelCleared>,
+    mut near_misses: EventReader<NearMiss>,
+    mut last_lives: Local<Option<u32>>,
+) {
+    let mut events: Vec<GameEvent> = Vec::new();
+    events.extend(collisions.iter().map(|event| GameEvent::Collision(event.position)));
+    events.extend(bricks.iter().map(|event| GameEvent::BrickDestroyed {
+        position: event.position,
+        penalty: event.kind == BrickKind::Penalty,
+    }));
+    events.extend(powerups.iter().map(|PowerUpCollected(kind)| GameEvent::PowerUp(kind.name())));
+    events.extend(balls_lost.iter().map(|_| GameEvent::BallLost));
+    if *last_lives != Some(lives.0) {
+        *last_lives = Some(lives.0);
+        events.push(GameEvent::LivesChanged(lives.0));
+    }
+    events.extend(levels_cleared.iter().map(|event| GameEvent::LevelCleared(event.index)));
+    events.extend(near_misses.iter().map(|event| GameEvent::NearMiss {
+        position: event.position,
+        kind: event.kind,
+        distance: event.distance,
+    }));
+    if events.is_empty() {
+        return;
+    }
+
+    let Ok(mut log) = GAME_LOG.lock() else { return };
+    let time = time.elapsed_seconds();
+    for event in events {
+        if log.len() == LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(LogEntry { time, event });
+    }
+}
+
+// Mit F9 landet das Protokoll in einer Datei neben dem Spiel.
+fn dump_log(keyboard_input: Res<Input<KeyCode>>, mut toasts: EventWriter<ShowToast>) {
+    if !keyboard_input.just_pressed(KeyCode::F9) {
+        return;
+    }
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let path = format!("game-log-{seconds}.txt");
+    let message = match fs::write(&path, recent_events()) {
+        Ok(()) => format!("Protokoll gespeichert: {path}"),
+        Err(error) => {
+            warn!("Protokoll konnte nicht gespeichert werden: {error}");
+            "Protokoll konnte nicht gespeichert werden".to_string()
+        }
+    };
+    toasts.send(ShowToast(message));
+}
+
+// F10 hält das Spiel an und öffnet den Inspektor über dem laufenden Level.
+fn open_inspector(keyboard_input: Res<Input<KeyCode>>, mut state: ResMut<State<GameState>>) {
+    if keyboard_input.just_pressed(KeyCode::F10) {
+        let _ = state.push(GameState::LogInspector);
+    }
+}
+
+fn spawn_inspector(
+    mut commands: Commands,
+    mut inspected: ResMut<InspectedLog>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let entries: Vec<_> = GAME_LOG.lock().map(|log| log.iter().cloned().collect()).unwrap_or_default();
+    *inspected = InspectedLog {
+        index: entries.len().saturating_sub(1),
+        entries,
+    };
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: 24.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        InspectorText,
+        InspectorEntity,
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::UVSphere::default().into()),
+            material: materials.add(StandardMaterial {
+                base_color: MARKER_COLOR,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_scale(Vec3::splat(MARKER_SIZE)),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        InspectorMarker,
+        InspectorEntity,
+    ));
+}
+
+// Links und rechts blättern durch die Ereignisse, die Markierung zeigt, wo in der Arena es passiert ist.
+fn step_inspector(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut state: ResMut<State<GameState>>,
+    mut inspected: ResMut<InspectedLog>,
+    mut text_query: Query<&mut Text, With<InspectorText>>,
+    mut marker_query: Query<(&mut Transform, &mut Visibility), With<InspectorMarker>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F10) {
+        let _ = state.pop();
+        return;
+    }
+    let last = inspected.entries.len().saturating_sub(1);
+    if keyboard_input.just_pressed(KeyCode::Left) {
+        inspected.index = inspected.index.saturating_sub(1);
+    }
+    if keyboard_input.just_pressed(KeyCode::Right) {
+        inspected.index = (inspected.index + 1).min(last);
+    }
+    if !inspected.is_changed() {
+        return;
+    }
+
+    let entry = inspected.entries.get(inspected.index);
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = match entry {
+            Some(entry) => format!(
+                "Ereignis {}/{}  {}\nLinks/Rechts blättern, F10 schließen",
+                inspected.index + 1,
+                inspected.entries.len(),
+                entry.describe()
+            ),
+            None => "Noch keine Ereignisse\nF10 schließen".to_string(),
+        };
+    }
+    if let Ok((mut transform, mut visibility)) = marker_query.get_single_mut() {
+        let position = entry.and_then(|entry| entry.event.position());
+        visibility.is_visible = position.is_some();
+        if let Some(position) = position {
+            transform.translation = position;
+        }
+    }
+}
+
+fn despawn_inspector(mut commands: Commands, query: Query<Entity, With<InspectorEntity>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
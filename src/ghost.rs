@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::challenge::Challenge;
+use crate::grid::BrickGrid;
+use crate::level::BrickKind;
+use crate::{phase_set, Ball, Brick, Mutators, Phase, TimeScale};
+
+// Kommt der Ball einem Geisterbrick so nah, wird er aufgedeckt. Gemessen wird von Mitte zu Mitte.
+const REVEAL_DISTANCE: f32 = 1.2;
+const FADE_IN_DURATION: f32 = 0.6;
+
+// Ein Brick, der erst unsichtbar ist. Er hat ein eigenes Material, dessen Deckkraft nach dem Aufdecken
+// langsam auf 1 steigt. Getroffen werden kann er auch vorher.
+#[derive(Component)]
+struct GhostBrick {
+    revealed: bool,
+    alpha: f32,
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        // Nach dem Update, damit ein Brick, den sync_bricks gerade erst gespawnt hat, nie sichtbar aufblitzt.
+        app.add_system_to_stage(CoreStage::PostUpdate, haunt_bricks)
+            .add_system_set(phase_set(Phase::Vfx).with_system(reveal_ghosts).with_system(fade_in_ghosts));
+    }
+}
+
+// Geisterbricks sind alle Bricks dieser Art, mit der Herausforderung "Unsichtbare Bricks" einfach alle.
+fn haunt_bricks(
+    mut commands: Commands,
+    mutators: Res<Mutators>,
+    brick_grid: Res<BrickGrid>,
+    mut query: Query<(Entity, &Brick, &mut Handle<StandardMaterial>), Added<Brick>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let everything = mutators.challenges.is_active(Challenge::InvisibleBricks);
+    for (entity, brick, mut material) in &mut query {
+        let Some(cell) = brick_grid.get(brick.row, brick.column) else { continue };
+        if !everything && cell.kind != BrickKind::Ghost {
+            continue;
+        }
+        let Some(mut ghost_material) = materials.get(&material).cloned() else { continue };
+        ghost_material.base_color.set_a(0.0);
+        ghost_material.alpha_mode = AlphaMode::Blend;
+        *material = materials.add(ghost_material);
+        commands.entity(entity).insert(GhostBrick { revealed: false, alpha: 0.0 });
+    }
+}
+
+// Aufgedeckt wird, wenn ein Ball in der Nähe ist oder der Brick schon ein Leben verloren hat.
+fn reveal_ghosts(
+    brick_grid: Res<BrickGrid>,
+    ball_query: Query<&Transform, With<Ball>>,
+    mut query: Query<(&Brick, &Transform, &mut GhostBrick)>,
+) {
+    for (brick, transform, mut ghost) in &mut query {
+        if ghost.revealed {
+            continue;
+        }
+        let near = ball_query
+            .iter()
+            .any(|ball| ball.translation.truncate().distance(transform.translation.truncate()) < REVEAL_DISTANCE);
+        let hit = brick_grid
+            .get(brick.row, brick.column)
+            .is_some_and(|cell| cell.health < cell.kind.health());
+        if near || hit {
+            ghost.revealed = true;
+        }
+    }
+}
+
+fn fade_in_ghosts(
+    mut query: Query<(&mut GhostBrick, &Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time_scale: Res<TimeScale>,
+) {
+    for (mut ghost, handle) in &mut query {
+        if !ghost.revealed || ghost.alpha >= 1.0 {
+            continue;
+        }
+        ghost.alpha = (ghost.alpha + time_scale.step() / FADE_IN_DURATION).min(1.0);
+        let Some(material) = materials.get_mut(handle) else { continue };
+        material.base_color.set_a(ghost.alpha);
+        // Ganz sichtbar braucht der Brick keine Transparenz mehr und wird wieder wie die anderen gezeichnet.
+        if ghost.alpha >= 1.0 {
+            material.alpha_mode = AlphaMode::Opaque;
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+// Unter so viel Prozent der Sollpunkte gibt es nur noch ein C.
+const B_SCORE_RATIO: f32 = 0.6;
+// Ab so viel Prozent der Sollpunkte reicht für ein A die Sollzeit.
+const A_SCORE_RATIO: f32 = 0.8;
+
+// Die Sollwerte eines Levels, etwa par: (score: 450, time: 60.0). Gemeint sind die Punkte der ganzen Abrechnung
+// samt Boni und die Zeit in Sekunden bis zum letzten Brick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Par {
+    pub score: usize,
+    pub time: f32,
+}
+
+// Die Note eines geschafften Levels. Die Reihenfolge der Varianten ist die Rangfolge, S ist die beste.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Grade {
+    C,
+    B,
+    A,
+    S,
+}
+
+impl Grade {
+    // S gibt es für die Sollpunkte in der Sollzeit. Ein A bringen die Sollpunkte allein oder knapp darunter in der
+    // Sollzeit, ein B mehr als die Hälfte der Punkte.
+    pub fn from_result(score: usize, time: f32, par: Par) -> Grade {
+        let ratio = score as f32 / par.score.max(1) as f32;
+        let in_time = time <= par.time;
+        if ratio >= 1.0 && in_time {
+            Grade::S
+        } else if ratio >= 1.0 || ratio >= A_SCORE_RATIO && in_time {
+            Grade::A
+        } else if ratio >= B_SCORE_RATIO {
+            Grade::B
+        } else {
+            Grade::C
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::save::SaveData;
+use crate::CollisionEvent;
+
+// Mehr Lichter als diese leuchten nie gleichzeitig auf. Sie werden einmal erzeugt und immer wieder verwendet,
+// denn jedes Punktlicht kostet beim Rendern, ganz gleich, wie kurz es lebt.
+const MAX_IMPACT_LIGHTS: usize = 6;
+const IMPACT_LIGHT_DURATION: f32 = 0.15;
+const IMPACT_LIGHT_INTENSITY: f32 = 1500.0;
+const IMPACT_LIGHT_RANGE: f32 = 4.0;
+const IMPACT_LIGHT_COLOR: Color = Color::rgb(1.0, 0.85, 0.6);
+// Das Licht sitzt etwas vor der Kontaktstelle, damit es nicht im Brick oder der Wand steckt.
+const IMPACT_LIGHT_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 0.8);
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GraphicsSettings {
+    pub impact_lights: bool,
+    // Die Anzeige oben tritt zurück, solange der Ball in der oberen Hälfte ist, siehe hud.rs.
+    #[serde(default)]
+    pub adaptive_hud: bool,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        GraphicsSettings { impact_lights: true, adaptive_hud: false }
+    }
+}
+
+// Ein Licht aus dem Vorrat. Ist es gerade unsichtbar, darf es für den nächsten Aufprall genommen werden.
+#[derive(Component)]
+pub struct ImpactLight(Timer);
+
+pub struct GraphicsPlugin;
+
+impl Plugin for GraphicsPlugin {
+    fn build(&self, app: &mut App) {
+        // Die Einstellung kommt aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let settings = app.world.resource::<SaveData>().graphics;
+        app.insert_resource(settings)
+            .add_startup_system(spawn_impact_lights)
+            .add_system(store_graphics)
+            .add_system(flash_impact_lights)
+            .add_system(fade_impact_lights.after(flash_impact_lights));
+    }
+}
+
+fn store_graphics(settings: Res<GraphicsSettings>, mut save: ResMut<SaveData>) {
+    if settings.is_changed() && save.graphics != *settings {
+        save.graphics = *settings;
+    }
+}
+
+// Zu Beginn gelten alle Lichter als schon verglüht, damit sie vor jedem laufenden Blitz genommen werden.
+fn spawn_impact_lights(mut commands: Commands) {
+    for _ in 0..MAX_IMPACT_LIGHTS {
+        let mut timer = Timer::from_seconds(IMPACT_LIGHT_DURATION, TimerMode::Once);
+        timer.tick(Duration::from_secs_f32(IMPACT_LIGHT_DURATION));
+        commands.spawn((
+            PointLightBundle {
+                point_light: PointLight {
+                    intensity: 0.0,
+                    range: IMPACT_LIGHT_RANGE,
+                    color: IMPACT_LIGHT_COLOR,
+                    ..default()
+                },
+                visibility: Visibility::INVISIBLE,
+                ..default()
+            },
+            ImpactLight(timer),
+        ));
+    }
+}
+
+// Sind alle Lichter belegt, übernimmt der nächste Aufprall das, dessen Blitz schon am weitesten abgeklungen ist.
+fn flash_impact_lights(
+    settings: Res<GraphicsSettings>,
+    mut events: EventReader<CollisionEvent>,
+    mut query: Query<(&mut ImpactLight, &mut Transform, &mut Visibility)>,
+) {
+    if !settings.impact_lights {
+        events.clear();
+        return;
+    }
+    for event in events.iter() {
+        let Some((mut light, mut transform, mut visibility)) = query
+            .iter_mut()
+            .max_by(|(a, _, _), (b, _, _)| a.0.elapsed_secs().total_cmp(&b.0.elapsed_secs()))
+        else {
+            return;
+        };
+        light.0.reset();
+        transform.translation = event.position + IMPACT_LIGHT_OFFSET;
+        visibility.is_visible = true;
+    }
+}
+
+// Die Helligkeit fällt in der kurzen Zeit quadratisch ab, ein harter Blitz mit weichem Nachglühen.
+fn fade_impact_lights(
+    time: Res<Time>,
+    settings: Res<GraphicsSettings>,
+    mut query: Query<(&mut ImpactLight, &mut PointLight, &mut Visibility)>,
+) {
+    for (mut light, mut point_light, mut visibility) in &mut query {
+        if !visibility.is_visible {
+            continue;
+        }
+        if !settings.impact_lights || light.0.tick(time.delta()).finished() {
+            visibility.is_visible = false;
+            continue;
+        }
+        point_light.intensity = IMPACT_LIGHT_INTENSITY * light.0.percent_left().powi(2);
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use crate::level::{BrickKind, Level};
+use crate::surface::Surface;
+use crate::theme::ThemeMaterials;
+use crate::{
+    collision, phase_set, Arena, Brick, Collider, GameState, GridGeometry, LevelEntity, PenaltyBrick, Phase, BRICK_SIZE,
+};
+
+// Ein besetztes Feld im Raster. Mit jedem Treffer verliert der Brick ein Leben, bei 0 ist er zerstört.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub kind: BrickKind,
+    pub health: u32,
+}
+
+// Die Bricks des laufenden Levels, Zeile für Zeile von unten. Diese Ressource ist maßgeblich: Systeme ändern
+// nur die Zellen, und sync_bricks sorgt danach dafür, dass zu jeder besetzten Zelle genau ein Brick in der Welt steht.
+#[derive(Resource, Default)]
+pub struct BrickGrid {
+    columns: usize,
+    cells: Vec<Option<Cell>>,
+    // Die Entity zu jeder Zelle, so wie sie zuletzt gespawnt wurde. Nur sync_bricks ändert sie.
+    entities: Vec<Option<Entity>>,
+}
+
+impl BrickGrid {
+    pub fn from_level(level: &Level, geometry: &GridGeometry) -> Self {
+        let cells: Vec<_> = (0..geometry.rows)
+            .flat_map(|row| (0..geometry.columns).map(move |column| (row, column)))
+            .map(|(row, column)| {
+                level.brick_at(row, column, geometry).map(|kind| Cell {
+                    kind,
+                    health: kind.health(),
+                })
+            })
+            .collect();
+        BrickGrid {
+            columns: geometry.columns,
+            entities: vec![None; cells.len()],
+            cells,
+        }
+    }
+
+    fn index(&self, row: usize, column: usize) -> Option<usize> {
+        let index = row * self.columns + column;
+        (column < self.columns && index < self.cells.len()).then_some(index)
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<Cell> {
+        self.index(row, column).and_then(|index| self.cells[index])
+    }
+
+    // Ein Treffer kostet den Brick ein Leben. Wird er dabei zerstört, wird die Zelle geleert und er zurückgegeben.
+    pub fn hit(&mut self, row: usize, column: usize) -> Option<Cell> {
+        let index = self.index(row, column)?;
+        let cell = self.cells[index].as_mut()?;
+        cell.health = cell.health.saturating_sub(1);
+        // Ein Kettenglied bleibt ohne Leben stehen, bis die ganze Kette getroffen ist. Das entscheidet chain.rs.
+        if cell.health > 0 || cell.kind == BrickKind::Chain {
+            return None;
+        }
+        self.cells[index].take()
+    }
+
+    // Setzt einen neuen Brick mit vollen Leben in eine leere Zelle. Ist sie besetzt, passiert nichts.
+    pub fn restore(&mut self, row: usize, column: usize, kind: BrickKind) -> bool {
+        let Some(index) = self.index(row, column) else { return false };
+        if self.cells[index].is_some() {
+            return false;
+        }
+        self.cells[index] = Some(Cell { kind, health: kind.health() });
+        true
+    }
+
+    // Gibt dem Brick wieder alle Leben.
+    pub fn heal(&mut self, row: usize, column: usize) {
+        if let Some(cell) = self.index(row, column).and_then(|index| self.cells[index].as_mut()) {
+            cell.health = cell.kind.health();
+        }
+    }
+
+    pub fn remove(&mut self, row: usize, column: usize) {
+        if let Some(index) = self.index(row, column) {
+            self.cells[index] = None;
+        }
+    }
+
+    // Leert das Raster und setzt danach genau die angegebenen Zellen, etwa aus einem Schnappschuss.
+    pub fn replace(&mut self, cells: impl IntoIterator<Item = (usize, usize, Cell)>) {
+        self.cells.fill(None);
+        for (row, column, cell) in cells {
+            if let Some(index) = self.index(row, column) {
+                self.cells[index] = Some(cell);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(Option::is_none)
+    }
+
+    // Alle besetzten Zellen mit Reihe und Spalte.
+    pub fn bricks(&self) -> impl Iterator<Item = (usize, usize, Cell)> + '_ {
+        let columns = self.columns;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, cell)| cell.map(|cell| (index / columns, index % columns, cell)))
+    }
+}
+
+pub struct BrickGridPlugin;
+
+impl Plugin for BrickGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BrickGrid>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(sync_bricks.after(crate::spawn_level)))
+            .add_system_set(phase_set(Phase::Collision).with_system(sync_bricks.after(crate::check_for_collision)));
+    }
+}
+
+// Gleicht die Welt mit dem Raster ab: Für neue Zellen wird ein Brick gespawnt, der Brick einer geleerten Zelle verschwindet.
+fn sync_bricks(
+    mut commands: Commands,
+    mut grid: ResMut<BrickGrid>,
+    arena: Res<Arena>,
+    theme_materials: Res<ThemeMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    if !grid.is_changed() {
+        return;
+    }
+    // Alle Bricks teilen sich ein Mesh, statt dass für jeden ein eigener Würfel auf die Grafikkarte geladen wird.
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(shape::Cube::default().into()))
+        .clone();
+    let geometry = GridGeometry::new(&arena);
+    // Die Entities zu merken ist keine Änderung am Raster, sonst liefe der Abgleich jeden Schritt erneut.
+    let grid = grid.bypass_change_detection();
+
+    for (index, (cell, entity)) in grid.cells.iter().zip(grid.entities.iter_mut()).enumerate() {
+        match (cell, *entity) {
+            (None, Some(brick)) => {
+                commands.entity(brick).despawn();
+                *entity = None;
+            }
+            (Some(cell), None) => {
+                let (row, column) = (index / grid.columns, index % grid.columns);
+                let penalty = cell.kind == BrickKind::Penalty;
+                let mut brick = commands.spawn((
+                    PbrBundle {
+                        mesh: mesh.clone(),
+                        material: theme_materials.brick(cell.kind),
+                        transform: Transform {
+                            translation: geometry.position(row, column).extend(0.0),
+                            scale: Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    Brick { row, column },
+                    Collider,
+                    collision::CollisionLayers::from(collision::Layer::Brick),
+                    LevelEntity,
+                ));
+                if penalty {
+                    brick.insert(PenaltyBrick);
+                }
+                if let Some(surface) = Surface::of(cell.kind) {
+                    brick.insert(surface);
+                }
+                *entity = Some(brick.id());
+            }
+            _ => {}
+        }
+    }
+}
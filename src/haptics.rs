@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use gilrs::ff::{BaseEffect, BaseEffectType, EffectBuilder, Envelope, Repeat, Replay, Ticks};
+use gilrs::Gilrs;
+use serde::{Deserialize, Serialize};
+
+use crate::save::SaveData;
+use crate::near_miss::NearMiss;
+use crate::{BallLost, LevelCleared, PaddleHit};
+
+// Ein Puls: Beginn und Länge in Millisekunden und wie stark der Motor dabei läuft.
+type Pulse = (u32, u32, BaseEffectType);
+
+// Was gerade am Gamepad zu spüren sein soll. Passiert in einem Frame mehreres, gewinnt das Wichtigste.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Rumble {
+    NearMiss,
+    PaddleHit,
+    BallLost,
+    LevelCleared,
+}
+
+impl Rumble {
+    fn pulses(self) -> &'static [Pulse] {
+        match self {
+            // Ein ganz leichtes Zucken, schwächer als ein Treffer am Paddle.
+            Rumble::NearMiss => &[(0, 40, BaseEffectType::Weak { magnitude: 14_000 })],
+            Rumble::PaddleHit => &[(0, 60, BaseEffectType::Weak { magnitude: 24_000 })],
+            Rumble::BallLost => &[(0, 350, BaseEffectType::Strong { magnitude: 60_000 })],
+            // Drei kurze, immer stärkere Stöße wie ein kleiner Trommelwirbel.
+            Rumble::LevelCleared => &[
+                (0, 90, BaseEffectType::Weak { magnitude: 30_000 }),
+                (180, 90, BaseEffectType::Weak { magnitude: 45_000 }),
+                (360, 200, BaseEffectType::Strong { magnitude: 60_000 }),
+            ],
+        }
+    }
+
+    fn duration_ms(self) -> u32 {
+        self.pulses().iter().map(|(after, length, _)| after + length).max().unwrap_or(0)
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct HapticsSettings {
+    pub rumble: bool,
+}
+
+impl Default for HapticsSettings {
+    fn default() -> Self {
+        HapticsSettings { rumble: true }
+    }
+}
+
+pub struct HapticsPlugin;
+
+impl Plugin for HapticsPlugin {
+    fn build(&self, app: &mut App) {
+        // Die Einstellung kommt aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let settings = app.world.resource::<SaveData>().haptics;
+        app.insert_resource(settings)
+            .add_system(store_haptics)
+            .add_system(rumble);
+    }
+}
+
+fn store_haptics(settings: Res<HapticsSettings>, mut save: ResMut<SaveData>) {
+    if settings.is_changed() && save.haptics != *settings {
+        save.haptics = *settings;
+    }
+}
+
+// Gespielt wird auf allen angeschlossenen Gamepads, die Vibration können. Ein Effekt vibriert nur,
+// solange sein Handle lebt, deshalb werden die laufenden bis zu ihrem Ende aufgehoben.
+#[allow(clippy::too_many_arguments)]
+fn rumble(
+    settings: Res<HapticsSettings>,
+    gilrs: Option<NonSendMut<Gilrs>>,
+    time: Res<Time>,
+    mut paddle_hits: EventReader<PaddleHit>,
+    mut balls_lost: EventReader<BallLost>,
+    mut levels_cleared: EventReader<LevelCleared>,
+    mut near_misses: EventReader<NearMiss>,
+    mut playing: Local<Vec<(gilrs::ff::Effect, Timer)>>,
+) {
+    playing.retain_mut(|(_, timer)| !timer.tick(time.delta()).finished());
+
+    let rumble = [
+        (near_misses.iter().count() > 0).then_some(Rumble::NearMiss),
+        (paddle_hits.iter().count() > 0).then_some(Rumble::PaddleHit),
+        (balls_lost.iter().count() > 0).then_some(Rumble::BallLost),
+        (levels_cleared.iter().count() > 0).then_some(Rumble::LevelCleared),
+    ]
+    .into_iter()
+    .flatten()
+    .max();
+    let (Some(rumble), Some(mut gilrs)) = (rumble, gilrs) else { return };
+    if !settings.rumble {
+        return;
+    }
+    let gamepads: Vec<_> = gilrs
+        .gamepads()
+        .filter(|(_, gamepad)| gamepad.is_ff_supported())
+        .map(|(id, _)| id)
+        .collect();
+    if gamepads.is_empty() {
+        return;
+    }
+
+    let duration = rumble.duration_ms();
+    let mut builder = EffectBuilder::new();
+    for &(after, length, kind) in rumble.pulses() {
+        builder.add_effect(BaseEffect {
+            kind,
+            // Die Pause nach einem Puls ist so lang wie der ganze Effekt, damit keiner wiederholt wird.
+            scheduling: Replay {
+                after: Ticks::from_ms(after),
+                play_for: Ticks::from_ms(length),
+                with_delay: Ticks::from_ms(duration),
+            },
+            envelope: Envelope::default(),
+        });
+    }
+    let effect = builder
+        .gamepads(&gamepads)
+        .repeat(Repeat::For(Ticks::from_ms(duration)))
+        .finish(&mut gilrs)
+        .and_then(|effect| effect.play().map(|()| effect));
+    match effect {
+        Ok(effect) => playing.push((effect, Timer::from_seconds(duration as f32 / 1000.0, TimerMode::Once))),
+        Err(error) => warn!("Vibration konnte nicht abgespielt werden: {error}"),
+    }
+}
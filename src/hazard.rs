@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::effect::{TimedEffect, TimedEffectPlugin};
+use crate::paddle_size::ResizePaddle;
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::{Ball, Kinematic, Paddle};
+
+const SHRINK_FACTOR: f32 = 0.6;
+const SHRINK_DURATION: f32 = 8.0;
+// Auch die Grenzen aus BallPhysics wachsen solange mit, sonst ginge ein Teil davon beim Abprallen verloren und das
+// Drosseln am Ende bremste den Ball unter die Geschwindigkeit des Levels.
+pub const SPEED_UP_FACTOR: f32 = 1.4;
+const SPEED_UP_DURATION: f32 = 6.0;
+const REVERSE_CONTROLS_DURATION: f32 = 5.0;
+
+// Der Ball ist vorübergehend schneller. Läuft der Effekt ab, wird die Geschwindigkeit wieder gedrosselt.
+#[derive(Component)]
+pub struct SpeedUp(pub Timer);
+
+impl TimedEffect for SpeedUp {
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+
+    fn kind(&self) -> PowerUpKind {
+        PowerUpKind::SpeedUp
+    }
+}
+
+// Solange das Paddle diese Komponente hat, sind Hoch und Runter vertauscht.
+#[derive(Component)]
+pub struct ReversedControls(pub Timer);
+
+impl TimedEffect for ReversedControls {
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+
+    fn kind(&self) -> PowerUpKind {
+        PowerUpKind::ReverseControls
+    }
+}
+
+// Faktor für die Eingaberichtung des Paddles.
+pub fn control_sign(reversed: Option<&ReversedControls>) -> f32 {
+    if reversed.is_some() { -1.0 } else { 1.0 }
+}
+
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(TimedEffectPlugin::<SpeedUp>::default())
+            .add_plugin(TimedEffectPlugin::<ReversedControls>::default())
+            .add_system(apply_hazards)
+            // Entfernte Komponenten sind erst nach dem Update-Stage sichtbar, deshalb läuft das Drosseln danach.
+            .add_system_to_stage(CoreStage::PostUpdate, end_speed_up);
+    }
+}
+
+fn apply_hazards(
+    mut commands: Commands,
+    mut events: EventReader<PowerUpCollected>,
+    mut ball_query: Query<(Entity, &mut Kinematic, Option<&mut SpeedUp>), With<Ball>>,
+    paddle_query: Query<Entity, With<Paddle>>,
+    mut resize_events: EventWriter<ResizePaddle>,
+) {
+    for PowerUpCollected(kind) in events.iter() {
+        match kind {
+            PowerUpKind::Shrink => resize_events.send(ResizePaddle {
+                factor: SHRINK_FACTOR,
+                duration: SHRINK_DURATION,
+            }),
+            PowerUpKind::SpeedUp => {
+                for (ball, mut body, speed_up) in &mut ball_query {
+                    // Ein zweites Einsammeln verlängert nur den Effekt, der Ball wird nicht noch schneller.
+                    if let Some(mut speed_up) = speed_up {
+                        speed_up.0.reset();
+                        continue;
+                    }
+                    body.velocity *= SPEED_UP_FACTOR;
+                    commands
+                        .entity(ball)
+                        .insert(SpeedUp(Timer::from_seconds(SPEED_UP_DURATION, TimerMode::Once)));
+                }
+            }
+            PowerUpKind::ReverseControls => {
+                for paddle in &paddle_query {
+                    commands
+                        .entity(paddle)
+                        .insert(ReversedControls(Timer::from_seconds(REVERSE_CONTROLS_DURATION, TimerMode::Once)));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn end_speed_up(removed: RemovedComponents<SpeedUp>, mut ball_query: Query<&mut Kinematic, With<Ball>>) {
+    for ball in removed.iter() {
+        if let Ok(mut body) = ball_query.get_mut(ball) {
+            body.velocity /= SPEED_UP_FACTOR;
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::format;
+use crate::graphics::GraphicsSettings;
+use crate::save::SaveData;
+use crate::shop::Wallet;
+use crate::{Arena, Ball, GameState, Kinematic, LevelEntity, Lives, Scoreboard};
+
+const HUD_FONT_SIZE: f32 = 40.0;
+const HUD_PADDING: Val = Val::Px(5.0);
+const HUD_TEXT_COLOR: Color = Color::rgb(0.0, 0.0, 0.0);
+const COMBO_COLOR: Color = Color::rgb(0.8, 0.3, 0.0);
+// Ab so vielen Bricks ohne Paddle-Kontakt erscheint die Kombo in der Mitte.
+const MIN_COMBO: u32 = 2;
+// So deckend bleibt die ausgeblendete Anzeige, damit man sie noch findet.
+const FADED_OPACITY: f32 = 0.2;
+// So lange dauert das Aus- und Einblenden von ganz sichtbar zu ausgeblendet und zurück.
+const FADE_DURATION: f32 = 0.4;
+// So lange bleibt die Anzeige nach einer Änderung der Punkte sichtbar.
+const SCORE_HOLD: f32 = 1.5;
+
+// Wie groß die gesamte Oberfläche gezeichnet wird. Der Faktor kommt zum Skalierungsfaktor des Fensters hinzu,
+// auf einem Bildschirm mit hoher Pixeldichte bleibt die Oberfläche also gleich groß.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum UiSize {
+    Small,
+    #[default]
+    Normal,
+    Large,
+    Huge,
+}
+
+impl UiSize {
+    const ALL: [UiSize; 4] = [UiSize::Small, UiSize::Normal, UiSize::Large, UiSize::Huge];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            UiSize::Small => "Klein",
+            UiSize::Normal => "Normal",
+            UiSize::Large => "Groß",
+            UiSize::Huge => "Sehr groß",
+        }
+    }
+
+    pub fn next(self) -> UiSize {
+        let current = UiSize::ALL.iter().position(|size| *size == self).unwrap_or(0);
+        UiSize::ALL[(current + 1) % UiSize::ALL.len()]
+    }
+
+    fn scale(self) -> f64 {
+        match self {
+            UiSize::Small => 0.75,
+            UiSize::Normal => 1.0,
+            UiSize::Large => 1.25,
+            UiSize::Huge => 1.5,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ScoreText;
+
+#[derive(Component)]
+struct ComboText;
+
+#[derive(Component)]
+struct LivesText;
+
+// Ob die Anzeige gerade zurücktritt. Sie wird ausgeblendet, sobald alle Bälle in der oberen Hälfte sind, und kommt
+// zurück, wenn ein Ball in die untere Hälfte fällt oder sich die Punkte ändern. Dazwischen bleibt sie, wie sie ist,
+// damit sie nicht bei jedem Abprall an einem Brick flackert.
+#[derive(Resource)]
+struct HudVisibility {
+    faded: bool,
+    opacity: f32,
+    score: usize,
+    hold: Timer,
+}
+
+impl Default for HudVisibility {
+    fn default() -> Self {
+        HudVisibility {
+            faded: false,
+            opacity: 1.0,
+            score: 0,
+            hold: Timer::from_seconds(SCORE_HOLD, TimerMode::Once),
+        }
+    }
+}
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        // Die Größe kommt aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let size = app.world.resource::<SaveData>().ui_size;
+        app.insert_resource(size)
+            .add_system(apply_ui_size)
+            .add_system(store_ui_size)
+            .init_resource::<HudVisibility>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_hud))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(fade_hud.after(update_hud)))
+            .add_system(update_hud);
+    }
+}
+
+fn apply_ui_size(size: Res<UiSize>, mut ui_scale: ResMut<UiScale>) {
+    if ui_scale.scale != size.scale() {
+        ui_scale.scale = size.scale();
+    }
+}
+
+fn store_ui_size(size: Res<UiSize>, mut save: ResMut<SaveData>) {
+    if size.is_changed() && save.ui_size != *size {
+        save.ui_size = *size;
+    }
+}
+
+// Die Anzeige ist ein Baum aus drei gleich breiten Spalten über die ganze Fensterbreite: Punkte links, Kombo in der
+// Mitte, Leben rechts. Die Spalten richten sich nach dem Fenster, nicht nach festen Pixelpositionen.
+fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut visibility: ResMut<HudVisibility>,
+) {
+    // Eine Kombo reicht nie über ein Level hinaus.
+    scoreboard.combo = 0;
+    // Jedes Level beginnt mit voll sichtbarer Anzeige.
+    *visibility = HudVisibility { score: scoreboard.score, ..default() };
+    let style = TextStyle {
+        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+        font_size: HUD_FONT_SIZE,
+        color: HUD_TEXT_COLOR,
+    };
+    let column = |justify_content| NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0 / 3.0), Val::Auto),
+            justify_content,
+            ..default()
+        },
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(0.0),
+                        left: Val::Px(0.0),
+                        ..default()
+                    },
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    padding: UiRect::all(HUD_PADDING),
+                    align_items: AlignItems::FlexStart,
+                    ..default()
+                },
+                ..default()
+            },
+            LevelEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn(column(JustifyContent::FlexStart)).with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_sections([
+                        TextSection::new("Score: ", style.clone()),
+                        TextSection::from_style(style.clone()),
+                        TextSection::new("  Münzen: ", style.clone()),
+                        TextSection::from_style(style.clone()),
+                    ]),
+                    ScoreText,
+                ));
+            });
+            parent.spawn(column(JustifyContent::Center)).with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section("", TextStyle { color: COMBO_COLOR, ..style.clone() }),
+                    ComboText,
+                ));
+            });
+            parent.spawn(column(JustifyContent::FlexEnd)).with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_sections([
+                        TextSection::new("Leben: ", style.clone()),
+                        TextSection::from_style(style.clone()),
+                    ]),
+                    LivesText,
+                ));
+            });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn update_hud(
+    scoreboard: Res<Scoreboard>,
+    lives: Res<Lives>,
+    wallet: Res<Wallet>,
+    mut score_query: Query<&mut Text, (With<ScoreText>, Without<ComboText>, Without<LivesText>)>,
+    mut combo_query: Query<&mut Text, (With<ComboText>, Without<LivesText>)>,
+    mut lives_query: Query<&mut Text, With<LivesText>>,
+) {
+    if let Ok(mut text) = score_query.get_single_mut() {
+        text.sections[1].value = format::number(scoreboard.score);
+        text.sections[3].value = format::number(wallet.coins as usize);
+    }
+    if let Ok(mut text) = combo_query.get_single_mut() {
+        let combo = if scoreboard.combo >= MIN_COMBO { format!("Kombo x{}", scoreboard.combo) } else { String::new() };
+        if text.sections[0].value != combo {
+            text.sections[0].value = combo;
+        }
+    }
+    if let Ok(mut text) = lives_query.get_single_mut() {
+        text.sections[1].value = lives.0.to_string();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn fade_hud(
+    settings: Res<GraphicsSettings>,
+    arena: Res<Arena>,
+    scoreboard: Res<Scoreboard>,
+    time: Res<Time>,
+    mut visibility: ResMut<HudVisibility>,
+    ball_query: Query<(&Transform, &Kinematic), With<Ball>>,
+    mut text_query: Query<&mut Text, Or<(With<ScoreText>, With<ComboText>, With<LivesText>)>>,
+) {
+    let midline = (arena.bottom() + arena.top()) / 2.0;
+    let scored = visibility.score != scoreboard.score;
+    visibility.score = scoreboard.score;
+    if scored {
+        visibility.hold.reset();
+    }
+    let holding = !visibility.hold.tick(time.delta()).finished();
+
+    let balls: Vec<(f32, f32)> =
+        ball_query.iter().map(|(transform, body)| (transform.translation.y, body.velocity.y)).collect();
+    let descending = balls.iter().any(|(height, velocity)| *height < midline && *velocity < 0.0);
+    let all_above = !balls.is_empty() && balls.iter().all(|(height, _)| *height >= midline);
+    if !settings.adaptive_hud || holding || descending {
+        visibility.faded = false;
+    } else if all_above {
+        visibility.faded = true;
+    }
+
+    let target = if visibility.faded { FADED_OPACITY } else { 1.0 };
+    let step = (1.0 - FADED_OPACITY) / FADE_DURATION * time.delta_seconds();
+    let opacity = visibility.opacity;
+    visibility.opacity = if opacity < target { (opacity + step).min(target) } else { (opacity - step).max(target) };
+    for mut text in &mut text_query {
+        // Nur bei einem echten Wechsel, sonst würde der Text in jedem Frame neu gesetzt.
+        if text.sections.first().is_some_and(|section| section.style.color.a() != visibility.opacity) {
+            for section in &mut text.sections {
+                section.style.color.set_a(visibility.opacity);
+            }
+        }
+    }
+}
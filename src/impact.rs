@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::config::GameConfig;
+use crate::near_miss::NearMiss;
+use crate::{BrickHit, LevelEntity, TimeScale};
+
+// Der Blitz liegt knapp über dem Brick, damit er ihn ganz verdeckt.
+const FLASH_SCALE: f32 = 1.05;
+// So langsam läuft das Spiel nach einem knappen Moment, gemessen am Zeitfaktor von davor.
+const SLOW_MOTION_SCALE: f32 = 0.35;
+
+// Ein weißer Würfel über einem getroffenen Brick. Er ist ein eigenes Objekt, damit er auch über einem gerade
+// zerstörten Brick noch zu sehen ist und die Materialien der Bricks unberührt bleiben.
+#[derive(Component)]
+struct HitFlash(u32);
+
+// Solange der Timer läuft, steht das Spiel still oder läuft in Zeitlupe. Danach gilt wieder der Zeitfaktor von davor.
+#[derive(Resource, Default)]
+struct HitStop {
+    timer: Option<Timer>,
+    resume_scale: f32,
+}
+
+pub struct ImpactPlugin;
+
+impl Plugin for ImpactPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HitStop>()
+            .add_system(flash_bricks)
+            .add_system(fade_flashes.before(flash_bricks))
+            .add_system(start_hit_stop)
+            .add_system(release_hit_stop.before(start_hit_stop))
+            .add_system(start_slow_motion.after(release_hit_stop).before(start_hit_stop));
+    }
+}
+
+// Mit weniger Bewegung blitzt nichts, der Stillstand bleibt.
+fn flash_bricks(
+    mut commands: Commands,
+    mut events: EventReader<BrickHit>,
+    config: Res<GameConfig>,
+    settings: Res<AccessibilitySettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    if config.hit_flash_frames == 0 || !settings.allows_motion() {
+        events.clear();
+        return;
+    }
+    for event in events.iter() {
+        let (mesh, material) = assets.get_or_insert_with(|| {
+            (
+                meshes.add(shape::Cube::default().into()),
+                materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    unlit: true,
+                    ..default()
+                }),
+            )
+        });
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(event.position).with_scale(event.size * FLASH_SCALE),
+                ..default()
+            },
+            HitFlash(config.hit_flash_frames),
+            LevelEntity,
+        ));
+    }
+}
+
+// Der Blitz zählt Frames statt Zeit, damit er auch bei hoher Bildrate zu sehen ist.
+fn fade_flashes(mut commands: Commands, mut query: Query<(Entity, &mut HitFlash)>) {
+    for (entity, mut flash) in &mut query {
+        flash.0 = flash.0.saturating_sub(1);
+        if flash.0 == 0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Ein Treffer hält das Spiel ganz kurz an, dadurch fühlt er sich schwerer an. Trifft der Ball während des
+// Stillstands erneut, beginnt die Pause von vorn, der Zeitfaktor von davor bleibt gemerkt.
+fn start_hit_stop(
+    mut events: EventReader<BrickHit>,
+    config: Res<GameConfig>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time_scale: ResMut<TimeScale>,
+) {
+    if events.iter().count() == 0 || config.hit_stop <= 0.0 {
+        return;
+    }
+    if hit_stop.timer.is_none() {
+        hit_stop.resume_scale = time_scale.factor;
+    }
+    time_scale.factor = 0.0;
+    hit_stop.timer = Some(Timer::from_seconds(config.hit_stop, TimerMode::Once));
+}
+
+// Ein knapper Moment bremst das Spiel kurz ab. Ein laufender Stillstand nach einem Treffer geht vor.
+fn start_slow_motion(
+    mut events: EventReader<NearMiss>,
+    config: Res<GameConfig>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time_scale: ResMut<TimeScale>,
+) {
+    if events.iter().count() == 0 || config.near_miss_slow_motion <= 0.0 {
+        return;
+    }
+    if hit_stop.timer.is_some() && time_scale.factor == 0.0 {
+        return;
+    }
+    if hit_stop.timer.is_none() {
+        hit_stop.resume_scale = time_scale.factor;
+    }
+    time_scale.factor = hit_stop.resume_scale * SLOW_MOTION_SCALE;
+    hit_stop.timer = Some(Timer::from_seconds(config.near_miss_slow_motion, TimerMode::Once));
+}
+
+// Die Pause läuft in echter Zeit ab, der Zeitfaktor steht ja gerade auf null oder fast.
+fn release_hit_stop(time: Res<Time>, mut hit_stop: ResMut<HitStop>, mut time_scale: ResMut<TimeScale>) {
+    let Some(timer) = &mut hit_stop.timer else { return };
+    if timer.tick(time.delta()).finished() {
+        time_scale.factor = hit_stop.resume_scale;
+        hit_stop.timer = None;
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+// So lange bleibt ein Tastendruck gemerkt, falls er im nächsten Spielschritt noch nicht gilt.
+const BUFFER_WINDOW: f64 = 0.1;
+
+// Aktionen, die ein paar Frames zu früh gedrückt werden dürfen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Launch,
+    Dash,
+}
+
+impl Action {
+    const ALL: [Action; 2] = [Action::Launch, Action::Dash];
+
+    fn keys(self) -> &'static [KeyCode] {
+        match self {
+            Action::Launch => &[KeyCode::Space],
+            Action::Dash => &[KeyCode::LShift, KeyCode::RShift],
+        }
+    }
+
+    fn button(self) -> GamepadButtonType {
+        match self {
+            Action::Launch => GamepadButtonType::South,
+            Action::Dash => GamepadButtonType::East,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Launch => "Abschießen",
+            Action::Dash => "Dash",
+        }
+    }
+
+    // Die Beschriftung der ersten belegten Taste beziehungsweise des Knopfs, so wie sie auf dem Gerät steht.
+    pub fn binding_label(self, gamepad: bool) -> &'static str {
+        if gamepad {
+            return match self.button() {
+                GamepadButtonType::South => "A",
+                GamepadButtonType::East => "B",
+                GamepadButtonType::West => "X",
+                GamepadButtonType::North => "Y",
+                _ => "?",
+            };
+        }
+        match self.keys()[0] {
+            KeyCode::Space => "Leertaste",
+            KeyCode::LShift | KeyCode::RShift => "Shift",
+            _ => "?",
+        }
+    }
+
+    // Die Farbe des Knopfs auf dem Gamepad, damit das Symbol wie der echte Knopf aussieht.
+    pub fn button_color(self) -> Color {
+        match self.button() {
+            GamepadButtonType::South => Color::rgb(0.2, 0.6, 0.2),
+            GamepadButtonType::East => Color::rgb(0.7, 0.2, 0.2),
+            GamepadButtonType::West => Color::rgb(0.2, 0.3, 0.7),
+            _ => Color::rgb(0.7, 0.6, 0.1),
+        }
+    }
+}
+
+// Die Tastendrücke der letzten Zeit. Gesammelt wird in jedem Frame, verbraucht im festen Takt. Ein Druck zwischen
+// zwei Schritten oder kurz bevor die Aktion wieder möglich ist, geht dadurch nicht verloren.
+#[derive(Resource, Default)]
+pub struct InputBuffer {
+    presses: Vec<(Action, f64)>,
+}
+
+impl InputBuffer {
+    pub fn is_pending(&self, action: Action) -> bool {
+        self.presses.iter().any(|&(pressed, _)| pressed == action)
+    }
+
+    // Verbraucht den gemerkten Druck, damit er nur einmal auslöst.
+    pub fn take(&mut self, action: Action) -> bool {
+        let pending = self.is_pending(action);
+        self.presses.retain(|&(pressed, _)| pressed != action);
+        pending
+    }
+}
+
+pub struct InputBufferPlugin;
+
+impl Plugin for InputBufferPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBuffer>()
+            .add_system_to_stage(CoreStage::PreUpdate, record_presses.after(InputSystem));
+    }
+}
+
+// Die Zeit zählt in echten Sekunden, damit der Druck auch in Zeitlupe nach derselben Spanne verfällt.
+fn record_presses(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut buffer: ResMut<InputBuffer>,
+) {
+    let now = time.elapsed_seconds_f64();
+    buffer.presses.retain(|&(_, pressed_at)| now - pressed_at <= BUFFER_WINDOW);
+    for action in Action::ALL {
+        let button_pressed = gamepad_buttons.get_just_pressed().any(|button| button.button_type == action.button());
+        if keyboard_input.any_just_pressed(action.keys().iter().copied()) || button_pressed {
+            buffer.presses.push((action, now));
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::mode::GameMode;
+use crate::resume::PendingResume;
+use crate::tween::{TransformLens, Tween, TweenSystem};
+use crate::{Arena, GameState, MainCamera};
+
+const INTRO_DURATION: f32 = 2.0;
+// So weit unter der Decke liegt der Punkt auf der Brickwand, auf den die Kamera zu Beginn schaut.
+const BRICK_WALL_DEPTH: f32 = 1.5;
+
+// Die Kamerafahrt zu Beginn eines Levels. Sie läuft als eigener Zustand über GameState::Playing,
+// das Spiel darunter ist so lange angehalten.
+#[derive(Resource)]
+struct LevelIntro {
+    timer: Timer,
+}
+
+pub struct IntroPlugin;
+
+impl Plugin for IntroPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LevelIntro {
+            timer: Timer::from_seconds(INTRO_DURATION, TimerMode::Once),
+        })
+        .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_intro.after(crate::spawn_level)))
+        .add_system_set(
+            SystemSet::on_update(GameState::LevelIntro).with_system(fly_camera.after(TweenSystem)),
+        )
+        .add_system_set(SystemSet::on_exit(GameState::LevelIntro).with_system(finish_intro));
+    }
+}
+
+// Im Zeitangriff zählt jede Sekunde, ein fortgesetzter Durchlauf soll sofort weitergehen, und mit weniger
+// Bewegung fliegt die Kamera gar nicht.
+fn start_intro(
+    mut intro: ResMut<LevelIntro>,
+    mut state: ResMut<State<GameState>>,
+    mode: Res<GameMode>,
+    pending: Res<PendingResume>,
+    settings: Res<AccessibilitySettings>,
+) {
+    if *mode == GameMode::TimeAttack || pending.0.is_some() || !settings.allows_motion() {
+        return;
+    }
+    intro.timer.reset();
+    // Steht schon ein anderer Wechsel an, fällt der Anflug aus.
+    let _ = state.push(GameState::LevelIntro);
+}
+
+// Ein quadratischer Bézier-Bogen von hoch über der Brickwand nach vorn und hinunter auf die Spielposition.
+// Der Blick wandert dabei von der Brickwand zur Mitte der Arena.
+fn intro_transform(arena: &Arena, t: f32) -> Transform {
+    let end = arena.camera_transform();
+    let center_x = (arena.left() + arena.right()) / 2.0;
+    let start = Vec3::new(center_x, arena.top() + arena.extent(), 0.5 * arena.extent());
+    let control = Vec3::new(center_x, arena.top() + 0.5 * arena.extent(), 1.5 * arena.extent());
+    let eased = t * t * (3.0 - 2.0 * t);
+    let position = start.lerp(control, eased).lerp(control.lerp(end.translation, eased), eased);
+
+    let wall = Vec3::new(center_x, arena.top() - BRICK_WALL_DEPTH, 0.0);
+    let center = Vec3::new(center_x, (arena.bottom() + arena.top()) / 2.0, 0.0);
+    Transform::from_translation(position).looking_at(wall.lerp(center, eased), Vec3::Y)
+}
+
+// Jede Taste, ein Mausklick oder ein Gamepad-Knopf überspringt die Fahrt.
+#[allow(clippy::too_many_arguments)]
+fn fly_camera(
+    time: Res<Time>,
+    arena: Res<Arena>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut intro: ResMut<LevelIntro>,
+    mut state: ResMut<State<GameState>>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    let skipped = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_buttons.get_just_pressed().next().is_some();
+    if skipped || intro.timer.tick(time.delta()).finished() {
+        // Zurück in GameState::Playing, das Level läuft genau dort weiter, wo es angehalten wurde.
+        let _ = state.pop();
+        return;
+    }
+    for mut transform in &mut camera_query {
+        *transform = intro_transform(&arena, intro.timer.percent());
+    }
+}
+
+// Wurde die Fahrt übersprungen, während die Kamera noch zum Ausschnitt des Levels glitt, steht sie gleich dort.
+fn finish_intro(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    mut camera_query: Query<(Entity, &mut Transform), With<MainCamera>>,
+) {
+    for (entity, mut transform) in &mut camera_query {
+        *transform = arena.camera_transform();
+        commands.entity(entity).remove::<Tween<TransformLens>>();
+    }
+}
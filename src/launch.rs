@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::assist::{Assist, Assists};
+use crate::brick_wave::BrickWave;
+use crate::config::GameConfig;
+use crate::controls::ActiveControls;
+use crate::difficulty::Difficulty;
+use crate::hazard::{self, ReversedControls};
+use crate::input_buffer::{Action, InputBuffer};
+use crate::mode::GameMode;
+use crate::rng::GameRng;
+use crate::{phase_set, Ball, BallSpeed, GameState, Kinematic, LevelEntity, Mutators, Paddle, Phase, TimeScale};
+
+// Der Abschuss liegt in einem Kegel nach oben, so weit darf der Pfeil zu jeder Seite kippen.
+const MAX_AIM_ANGLE: f32 = 1.1;
+// Drehung des Pfeils in Bogenmaß pro Sekunde.
+const AIM_SPEED: f32 = 1.5;
+const ARROW_SIZE: Vec3 = Vec3::new(0.08, 1.2, 0.08);
+const ARROW_COLOR: Color = Color::rgb(1.0, 1.0, 0.6);
+
+// Ein Ball, der noch auf dem Paddle liegt. Das gilt zu Beginn eines Levels und nach jedem verlorenen Ball.
+// Der Winkel zählt von der Senkrechten aus, positiv zeigt er in Richtung der positiven x-Achse.
+#[derive(Component, Default)]
+pub struct HeldBall {
+    angle: f32,
+}
+
+impl HeldBall {
+    fn direction(&self) -> Vec3 {
+        Vec3::new(self.angle.sin(), self.angle.cos(), 0.0)
+    }
+}
+
+// Der Pfeil über dem gehaltenen Ball, er zeigt die Abschussrichtung.
+#[derive(Component)]
+struct AimArrow;
+
+pub struct LaunchPlugin;
+
+impl Plugin for LaunchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_arrow))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_arrow))
+            .add_system_set(
+                phase_set(Phase::Input)
+                    .with_system(aim_held_ball.after(crate::move_object))
+                    .with_system(launch_ball.after(aim_held_ball)),
+            );
+    }
+}
+
+// Der gehaltene Ball liegt mittig auf dem Paddle.
+pub fn held_position(paddle: &Transform, ball_size: Vec3) -> Vec3 {
+    paddle.translation + Vec3::Y * (paddle.scale.y + ball_size.y) / 2.0
+}
+
+fn spawn_arrow(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Cube::default().into()),
+            material: materials.add(StandardMaterial {
+                base_color: ARROW_COLOR,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_scale(ARROW_SIZE),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        AimArrow,
+        LevelEntity,
+    ));
+}
+
+// Solange der Ball gehalten wird, steht das Paddle still und die Eingabe für das Paddle dreht stattdessen den Pfeil.
+// Vertauschte Steuerung gilt dabei genauso.
+#[allow(clippy::type_complexity)]
+fn aim_held_ball(
+    mut ball_query: Query<(&mut Transform, &mut HeldBall), With<Ball>>,
+    paddle_query: Query<(&Transform, Option<&ReversedControls>), (With<Paddle>, Without<Ball>)>,
+    controls: Res<ActiveControls>,
+    time_scale: Res<TimeScale>,
+    mutators: Res<Mutators>,
+) {
+    let Ok((paddle, reversed)) = paddle_query.get_single() else { return };
+    let direction = controls.direction() * hazard::control_sign(reversed) * mutators.challenges.control_sign();
+    for (mut transform, mut held) in &mut ball_query {
+        held.angle = (held.angle + direction * AIM_SPEED * time_scale.step()).clamp(-MAX_AIM_ANGLE, MAX_AIM_ANGLE);
+        transform.translation = held_position(paddle, transform.scale);
+    }
+}
+
+// Der Druck auf die Leertaste kommt aus dem Eingabepuffer. Wer kurz vor dem Zurücklegen des Balls drückt, schießt
+// ihn gleich wieder ab. In der Demo fliegt der Ball sofort in eine zufällige Richtung los.
+fn launch_ball(
+    mut commands: Commands,
+    mut ball_query: Query<(Entity, &mut Kinematic, &HeldBall)>,
+    mut buffer: ResMut<InputBuffer>,
+    (mode, wave): (Res<GameMode>, Res<BrickWave>),
+    ball_speed: Res<BallSpeed>,
+    mut game_rng: ResMut<GameRng>,
+    (assists, difficulty, config): (Res<Assists>, Res<Difficulty>, Res<GameConfig>),
+) {
+    let demo = *mode == GameMode::Demo;
+    // Solange die Bricks noch an ihren Platz steigen, bleibt der Ball liegen. Ein Druck in der Zeit wird
+    // dabei nicht verbraucht.
+    if ball_query.is_empty() || wave.is_running() || !demo && !buffer.take(Action::Launch) {
+        return;
+    }
+    for (entity, mut body, held) in &mut ball_query {
+        let direction = if demo {
+            let rng = &mut *game_rng;
+            HeldBall { angle: rng.gen_range(-MAX_AIM_ANGLE..MAX_AIM_ANGLE) }.direction()
+        } else {
+            held.direction()
+        };
+        let adaptive = if assists.is_enabled(Assist::Adaptive) { difficulty.ball_speed_factor(&config) } else { 1.0 };
+        body.velocity = direction * ball_speed.0 * adaptive;
+        commands.entity(entity).remove::<HeldBall>();
+    }
+}
+
+// Der Pfeil beginnt am Ball und zeigt in die Abschussrichtung. Ohne gehaltenen Ball ist er versteckt.
+fn update_arrow(
+    ball_query: Query<(&Transform, &HeldBall), Without<AimArrow>>,
+    mut arrow_query: Query<(&mut Transform, &mut Visibility), With<AimArrow>>,
+) {
+    let Ok((mut arrow_transform, mut visibility)) = arrow_query.get_single_mut() else { return };
+    let Some((ball_transform, held)) = ball_query.iter().next() else {
+        visibility.is_visible = false;
+        return;
+    };
+    visibility.is_visible = true;
+    arrow_transform.translation = ball_transform.translation + held.direction() * ARROW_SIZE.y / 2.0;
+    arrow_transform.rotation = Quat::from_rotation_z(-held.angle);
+}
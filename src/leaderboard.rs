@@ -0,0 +1,144 @@
+use std::cmp::Reverse;
+use std::env;
+
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task};
+use futures_lite::future;
+
+use crate::format;
+use crate::menu::{button_text, spawn_button, spawn_screen, MenuButton};
+use crate::mode::{RunFinished, ScoreEntry};
+use crate::save::SaveData;
+use crate::GameState;
+
+// Die Adresse der Bestenliste kann über diese Umgebungsvariable gesetzt werden.
+const ENDPOINT_VARIABLE: &str = "KUERTEIL_LEADERBOARD_URL";
+const DEFAULT_ENDPOINT: &str = "http://localhost:8080/scores";
+// Nach einem Fehlschlag wird erst nach dieser Zeit erneut gesendet, bis dahin bleiben die Ergebnisse in der Warteschlange.
+const RETRY_SECONDS: f32 = 30.0;
+const TOP_ENTRIES: usize = 10;
+
+#[derive(Resource)]
+struct LeaderboardConfig {
+    endpoint: String,
+}
+
+// Die laufende Übertragung. Sie liefert die Anzahl der erfolgreich gesendeten Ergebnisse.
+#[derive(Resource, Default)]
+struct Submission {
+    task: Option<Task<Result<usize, String>>>,
+    retry: Option<Timer>,
+}
+
+// Die Spitze der Bestenliste für den Menübildschirm, solange sie noch geladen wird ist `task` gesetzt.
+#[derive(Resource, Default)]
+struct TopEntries {
+    task: Option<Task<Result<Vec<ScoreEntry>, String>>>,
+}
+
+#[derive(Component)]
+struct TopEntriesText;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        let endpoint = env::var(ENDPOINT_VARIABLE).unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        app.insert_resource(LeaderboardConfig { endpoint })
+            .init_resource::<Submission>()
+            .init_resource::<TopEntries>()
+            .add_system(queue_results)
+            .add_system(submit_pending.after(queue_results))
+            .add_system_set(SystemSet::on_enter(GameState::Leaderboard).with_system(spawn_leaderboard))
+            .add_system_set(SystemSet::on_update(GameState::Leaderboard).with_system(show_top_entries));
+    }
+}
+
+// Jedes Ergebnis kommt erst in die Warteschlange im Spielstand. So geht ohne Verbindung nichts verloren.
+fn queue_results(mut events: EventReader<RunFinished>, mut save: ResMut<SaveData>) {
+    for RunFinished(entry) in events.iter() {
+        save.pending_submissions.push(*entry);
+    }
+}
+
+fn submit_pending(
+    mut submission: ResMut<Submission>,
+    mut save: ResMut<SaveData>,
+    config: Res<LeaderboardConfig>,
+    time: Res<Time>,
+) {
+    if let Some(task) = &mut submission.task {
+        let Some(result) = future::block_on(future::poll_once(task)) else { return };
+        submission.task = None;
+        match result {
+            Ok(sent) => {
+                save.pending_submissions.drain(..sent);
+            }
+            Err(error) => {
+                warn!("Bestenliste nicht erreichbar, neuer Versuch später: {error}");
+                submission.retry = Some(Timer::from_seconds(RETRY_SECONDS, TimerMode::Once));
+            }
+        }
+        return;
+    }
+    if let Some(retry) = &mut submission.retry {
+        if !retry.tick(time.delta()).finished() {
+            return;
+        }
+        submission.retry = None;
+    }
+    if save.pending_submissions.is_empty() {
+        return;
+    }
+
+    let entries = save.pending_submissions.clone();
+    let endpoint = config.endpoint.clone();
+    submission.task = Some(IoTaskPool::get().spawn(async move {
+        let request = surf::post(&endpoint).body_json(&entries).map_err(|error| error.to_string())?;
+        let response = request.await.map_err(|error| error.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(entries.len())
+    }));
+}
+
+fn spawn_leaderboard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config: Res<LeaderboardConfig>,
+    mut top_entries: ResMut<TopEntries>,
+) {
+    let endpoint = config.endpoint.clone();
+    top_entries.task = Some(IoTaskPool::get().spawn(async move {
+        let mut entries: Vec<ScoreEntry> = surf::get(&endpoint).recv_json().await.map_err(|error| error.to_string())?;
+        entries.sort_unstable_by_key(|entry| Reverse(entry.score));
+        entries.truncate(TOP_ENTRIES);
+        Ok(entries)
+    }));
+
+    spawn_screen(&mut commands, &asset_server, "Bestenliste", |parent| {
+        parent.spawn((button_text("Lade ...", &asset_server), TopEntriesText));
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+fn show_top_entries(
+    mut top_entries: ResMut<TopEntries>,
+    mut text_query: Query<&mut Text, With<TopEntriesText>>,
+) {
+    let Some(task) = &mut top_entries.task else { return };
+    let Some(result) = future::block_on(future::poll_once(task)) else { return };
+    top_entries.task = None;
+    let Ok(mut text) = text_query.get_single_mut() else { return };
+    text.sections[0].value = match result {
+        Ok(entries) if entries.is_empty() => "Noch keine Einträge".to_string(),
+        Ok(entries) => entries
+            .iter()
+            .enumerate()
+            .map(|(rank, entry)| format!("{:>2}. {:>7}  {}", rank + 1, format::number(entry.score), entry.mode.name()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(error) => format!("Nicht erreichbar: {error}"),
+    };
+}
@@ -0,0 +1,308 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::bumper::BumperDefinition;
+use crate::floor::FloorZoneDefinition;
+use crate::framing::CameraFraming;
+use crate::force_field::ForceFieldDefinition;
+use crate::grade::Par;
+use crate::modding;
+use crate::objective::Objective;
+use crate::obstacle::ObstacleDefinition;
+use crate::portal::PortalPairDefinition;
+use crate::transition::ScreenTransition;
+use crate::{Arena, GameState, GridGeometry, PENALTY_BRICK_INTERVAL};
+
+// Die Level der Kampagne in der Reihenfolge, in der sie gespielt werden.
+const CAMPAIGN: [&str; 10] = [
+    "levels/01.level.ron",
+    "levels/02.level.ron",
+    "levels/03.level.ron",
+    "levels/04.level.ron",
+    "levels/05.level.ron",
+    "levels/06.level.ron",
+    "levels/07.level.ron",
+    "levels/08.level.ron",
+    "levels/09.level.ron",
+    "levels/10.level.ron",
+];
+
+// Bonuslevel gehören nicht zur Kampagne. Sie öffnen sich, sobald genug Sterne aus Nebenzielen gesammelt sind.
+const BONUS_LEVELS: [(&str, u32); 1] = [("levels/bonus-01.level.ron", 5)];
+
+fn default_ball_speed() -> f32 {
+    1.0
+}
+
+// Die Arten von Bricks, die ein Level-Layout enthalten kann.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BrickKind {
+    #[default]
+    Normal,
+    // Lässt beim Zerstören eine Gefahr fallen.
+    Penalty,
+    // Bleibt unsichtbar, bis der Ball ihm nahe kommt oder ihn trifft.
+    Ghost,
+    // Wächst einige Sekunden nach dem Zerstören an seiner Stelle nach.
+    Regenerating,
+    // Benachbarte Kettenglieder bilden eine Kette, die erst zerbricht, wenn alle kurz nacheinander getroffen wurden.
+    Chain,
+    // Schießt in regelmäßigen Abständen langsame Geschosse auf das Paddle.
+    Turret,
+    // Bricks aus einem Material, das den Ball beim Abprallen bremst oder beschleunigt, siehe surface.rs.
+    Glass,
+    Metal,
+    Stone,
+    // Ein Brick aus einem Mod, der Index zeigt in die Bricks der Mod-Registry.
+    Mod(u8),
+}
+
+impl BrickKind {
+    pub const ALL: [BrickKind; 9] = [
+        BrickKind::Normal,
+        BrickKind::Penalty,
+        BrickKind::Ghost,
+        BrickKind::Regenerating,
+        BrickKind::Chain,
+        BrickKind::Turret,
+        BrickKind::Glass,
+        BrickKind::Metal,
+        BrickKind::Stone,
+    ];
+
+    // So viele Treffer hält ein Brick aus. Die meisten eingebauten gehen beim ersten kaputt, Stein und Metall
+    // halten mehr aus, Mods können eigene Werte vorgeben.
+    pub fn health(self) -> u32 {
+        match self {
+            BrickKind::Stone => 2,
+            BrickKind::Metal => 3,
+            BrickKind::Mod(index) => modding::registry().brick(index).health,
+            _ => 1,
+        }
+    }
+
+    // Punkte für das Zerstören, bevor Balancing und Modifikatoren sie vervielfachen.
+    pub fn score(self) -> usize {
+        match self {
+            BrickKind::Stone => 2,
+            BrickKind::Metal => 3,
+            BrickKind::Mod(index) => modding::registry().brick(index).score,
+            _ => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BrickKind::Normal => "Normal",
+            BrickKind::Penalty => "Strafe",
+            BrickKind::Ghost => "Geist",
+            BrickKind::Regenerating => "Nachwachsend",
+            BrickKind::Chain => "Kette",
+            BrickKind::Turret => "Geschütz",
+            BrickKind::Glass => "Glas",
+            BrickKind::Metal => "Metall",
+            BrickKind::Stone => "Stein",
+            BrickKind::Mod(index) => &modding::registry().brick(index).name,
+        }
+    }
+
+    // Das Zeichen, mit dem der Brick im Layout der Level-Datei steht.
+    pub fn symbol(self) -> char {
+        match self {
+            BrickKind::Normal => '#',
+            BrickKind::Penalty => 'X',
+            BrickKind::Ghost => 'G',
+            BrickKind::Regenerating => 'R',
+            BrickKind::Chain => 'K',
+            BrickKind::Turret => 'T',
+            BrickKind::Glass => 'L',
+            BrickKind::Metal => 'M',
+            BrickKind::Stone => 'S',
+            BrickKind::Mod(index) => modding::registry().brick(index).symbol,
+        }
+    }
+
+    // Zeichen, die ein Mod nicht für eigene Bricks verwenden darf. Der Punkt steht für eine Lücke.
+    pub fn is_reserved_symbol(symbol: char) -> bool {
+        symbol == '.' || BrickKind::ALL.into_iter().any(|kind| kind.symbol() == symbol)
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<BrickKind> {
+        BrickKind::ALL
+            .into_iter()
+            .find(|kind| kind.symbol() == symbol)
+            .or_else(|| modding::registry().brick_kind(symbol))
+    }
+}
+
+// Beschreibung eines Levels, so wie sie in assets/levels/*.level.ron abgelegt ist.
+// Felder, die in der Datei fehlen, bekommen über serde ihren Standardwert.
+#[derive(Serialize, Deserialize, TypeUuid, Debug, Clone)]
+#[uuid = "3c7b3a52-5f0e-4d55-9a2e-1f6d2b9c8e41"]
+pub struct Level {
+    pub name: String,
+    // Ist das Flag gesetzt, wird der Ball in diesem Level ständig nach unten beschleunigt.
+    #[serde(default)]
+    pub gravity: bool,
+    #[serde(default)]
+    pub force_fields: Vec<ForceFieldDefinition>,
+    #[serde(default)]
+    pub bumpers: Vec<BumperDefinition>,
+    #[serde(default)]
+    pub portals: Vec<PortalPairDefinition>,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleDefinition>,
+    // Zonen am Boden hinter dem Paddle. Wo keine liegt, fällt der Ball wie bisher durch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floor: Vec<FloorZoneDefinition>,
+    // Freiwillige Nebenziele, etwa [TimeLimit(60.0), NoPowerUps, MinCombo(5)]. Sie zählen nur in der Kampagne.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objectives: Vec<Objective>,
+    // Sollpunkte und Sollzeit für die Note in der Abrechnung. Ohne Angabe gibt es keine Note.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub par: Option<Par>,
+    // Faktor auf die normale Geschwindigkeit des Balls.
+    #[serde(default = "default_ball_speed")]
+    pub ball_speed: f32,
+    // Eine Zeile je Reihe von oben nach unten, '#' ist ein normaler Brick, 'X' ein Strafbrick, 'G' ein Geisterbrick,
+    // 'R' ein nachwachsender Brick, 'K' ein Kettenglied, 'T' ein Geschütz, 'L' Glas, 'M' Metall und 'S' Stein, dazu
+    // kommen die Zeichen der Bricks aus Mods. Jedes andere Zeichen ist eine Lücke.
+    // Ohne Layout ist das ganze Raster gefüllt und jeder siebte Brick ein Strafbrick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Vec<String>>,
+    // Eigene Maße der Arena, etwa Some((width: 14.0, height: 12.0, depth: 1.5)). Ohne Angabe gilt die Standardgröße.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arena: Option<Arena>,
+    // Eigener Blick auf die Arena für große oder tiefe Level, etwa
+    // Some((position: (0.0, 9.0, 22.0), look_at: (0.0, 5.0, 0.0), fov: Some(50.0))). Das Sichtfeld ist in Grad.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraFraming>,
+    // Der Ball aus assets/balls, etwa Some("heavy") für balls/heavy.ball.ron. Ohne Angabe fliegt der normale Ball.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ball: Option<String>,
+}
+
+impl Level {
+    // Der Brick in einer Zelle des Rasters. Die Reihen zählen hier wie beim Aufbau von unten.
+    pub fn brick_at(&self, row: usize, column: usize, grid: &GridGeometry) -> Option<BrickKind> {
+        let Some(layout) = &self.layout else {
+            let penalty = (row * grid.columns + column) % PENALTY_BRICK_INTERVAL == PENALTY_BRICK_INTERVAL / 2;
+            return Some(if penalty { BrickKind::Penalty } else { BrickKind::Normal });
+        };
+        let line = layout.get(grid.rows.checked_sub(row + 1)?)?;
+        line.chars().nth(column).and_then(BrickKind::from_symbol)
+    }
+
+    // Baut das Layout aus einem Raster von Zellen, cells[row][column] mit der untersten Reihe zuerst.
+    pub fn layout_from_cells(cells: &[Vec<Option<BrickKind>>]) -> Vec<String> {
+        cells
+            .iter()
+            .rev()
+            .map(|row| row.iter().map(|cell| cell.map_or('.', BrickKind::symbol)).collect())
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let level = ron::de::from_bytes::<Level>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+// Alle geladenen Level und der Index des Levels, das als nächstes gespielt wird.
+#[derive(Resource)]
+pub struct Campaign {
+    pub levels: Vec<Handle<Level>>,
+    pub current: usize,
+    // Ein Level außerhalb der Kampagne, etwa aus dem Editor oder das Tutorial. Es hat Vorrang vor dem Level der Kampagne.
+    pub custom_level: Option<Handle<Level>>,
+    // Die Bonuslevel mit der Zahl an Sternen aus Nebenzielen, die sie freischaltet.
+    pub bonus_levels: Vec<(Handle<Level>, u32)>,
+}
+
+impl Campaign {
+    pub fn current_handle(&self) -> &Handle<Level> {
+        self.custom_level.as_ref().unwrap_or(&self.levels[self.current])
+    }
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .add_startup_system(load_campaign)
+            .add_system_set(SystemSet::on_update(GameState::Loading).with_system(wait_for_levels))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(reload_modified_level))
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(clear_custom_level));
+    }
+}
+
+fn load_campaign(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let levels = CAMPAIGN.iter().map(|path| asset_server.load(*path)).collect();
+    let bonus_levels = BONUS_LEVELS.iter().map(|(path, stars)| (asset_server.load(*path), *stars)).collect();
+    commands.insert_resource(Campaign {
+        levels,
+        current: 0,
+        custom_level: None,
+        bonus_levels,
+    });
+}
+
+// Erst wenn alle Level-Dateien geladen sind, geht es weiter ins Hauptmenü.
+fn wait_for_levels(
+    campaign: Res<Campaign>,
+    asset_server: Res<AssetServer>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    let bonus_levels = campaign.bonus_levels.iter().map(|(handle, _)| handle);
+    match asset_server.get_group_load_state(campaign.levels.iter().chain(bonus_levels).map(|handle| handle.id())) {
+        LoadState::Loaded => {
+            transition.to(GameState::Menu);
+        }
+        LoadState::Failed => panic!("Die Level-Dateien konnten nicht geladen werden"),
+        _ => {}
+    }
+}
+
+// Wird die Datei des laufenden Levels gespeichert, baut ein Neustart des Zustands das Level mit den neuen Werten auf.
+// So sieht man Änderungen sofort, ohne das Spiel neu zu starten.
+fn reload_modified_level(
+    mut events: EventReader<AssetEvent<Level>>,
+    campaign: Res<Campaign>,
+    mut state: ResMut<State<GameState>>,
+) {
+    let modified = events
+        .iter()
+        .any(|event| matches!(event, AssetEvent::Modified { handle } if handle == campaign.current_handle()));
+    // Steht schon ein anderer Wechsel an, geht der vor und die Änderung zeigt sich beim nächsten Speichern.
+    if modified {
+        let _ = state.restart();
+    }
+}
+
+// Zurück im Hauptmenü wird wieder die Kampagne gespielt.
+pub fn clear_custom_level(mut campaign: ResMut<Campaign>) {
+    campaign.custom_level = None;
+}
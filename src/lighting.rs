@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::level::Campaign;
+use crate::theme::{Theme, ThemeMaterials};
+use crate::{Arena, MainLight};
+
+// So viel des Tagesverlaufs wird pro Sekunde nachgeholt. Nach einem Levelwechsel dämmert es also langsam weiter.
+const DAYLIGHT_SPEED: f32 = 0.05;
+
+// Wie sich das Licht über die Kampagne verändert, vom ersten bis zum letzten Level. Gehört zum Theme,
+// fehlt die Angabe dort, gelten diese Standardwerte.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Daylight {
+    // Die Farbe der Lichtquelle am Anfang und am Ende, als RGB-Tripel.
+    morning: (f32, f32, f32),
+    evening: (f32, f32, f32),
+    // Die Helligkeit des Umgebungslichts am Anfang und am Ende.
+    morning_ambient: f32,
+    evening_ambient: f32,
+    // Um so viel Grad wandert die Lichtquelle insgesamt von links nach rechts über die Arena.
+    sweep: f32,
+}
+
+impl Default for Daylight {
+    fn default() -> Self {
+        Daylight {
+            morning: (1.0, 0.95, 0.85),
+            evening: (0.55, 0.6, 1.0),
+            morning_ambient: 0.08,
+            evening_ambient: 0.02,
+            sweep: 60.0,
+        }
+    }
+}
+
+impl Daylight {
+    fn color(&self, t: f32) -> Color {
+        let (from, to) = (Vec3::from(self.morning), Vec3::from(self.evening));
+        let rgb = from.lerp(to, t);
+        Color::rgb(rgb.x, rgb.y, rgb.z)
+    }
+
+    fn ambient(&self, t: f32) -> f32 {
+        self.morning_ambient + (self.evening_ambient - self.morning_ambient) * t
+    }
+
+    // Die Drehung um die Blickachse der Kamera, zu Beginn links geneigt und am Ende rechts.
+    fn rotation(&self, t: f32) -> Quat {
+        Quat::from_rotation_z((self.sweep * (0.5 - t)).to_radians())
+    }
+}
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(cycle_daylight.after(crate::frame_arena));
+    }
+}
+
+// Wie weit die Kampagne fortgeschritten ist, von 0 beim ersten bis 1 beim letzten Level.
+fn campaign_progress(campaign: &Campaign) -> f32 {
+    if campaign.levels.len() < 2 {
+        return 0.0;
+    }
+    campaign.current as f32 / (campaign.levels.len() - 1) as f32
+}
+
+// Die Lichtquelle kreist um die Mitte des Bodens, ihr Abstand bleibt dabei der von Arena::light_position.
+#[allow(clippy::too_many_arguments)]
+fn cycle_daylight(
+    time: Res<Time>,
+    arena: Res<Arena>,
+    campaign: Res<Campaign>,
+    theme_materials: Res<ThemeMaterials>,
+    themes: Res<Assets<Theme>>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut light_query: Query<(&mut Transform, &mut PointLight), With<MainLight>>,
+    mut progress: Local<f32>,
+) {
+    let daylight = theme_materials.theme(&themes).map(Theme::daylight).unwrap_or_default();
+    let target = campaign_progress(&campaign);
+    let step = DAYLIGHT_SPEED * time.delta_seconds();
+    *progress += (target - *progress).clamp(-step, step);
+
+    let color = daylight.color(*progress);
+    for (mut transform, mut light) in &mut light_query {
+        let center = Vec3::new((arena.left() + arena.right()) / 2.0, arena.bottom(), 0.0);
+        transform.translation = center + daylight.rotation(*progress) * (arena.light_position() - center);
+        light.color = color;
+    }
+    let brightness = daylight.ambient(*progress);
+    if ambient_light.brightness != brightness || ambient_light.color != color {
+        ambient_light.brightness = brightness;
+        ambient_light.color = color;
+    }
+}
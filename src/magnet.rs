@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::effect::{TimedEffect, TimedEffectPlugin};
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::{phase_set, Ball, Kinematic, Paddle, Phase, TimeScale};
+
+const MAGNET_DURATION: f32 = 8.0;
+// Wie stark der Ball pro Sekunde und Einheit Abstand zum Paddle hin gelenkt wird.
+const MAGNET_STRENGTH: f32 = 1.5;
+
+// Solange ein Ball magnetisch ist, wird er auf dem Weg nach unten sanft zum Paddle gezogen.
+#[derive(Component)]
+pub struct Magnet(pub Timer);
+
+impl TimedEffect for Magnet {
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+
+    fn kind(&self) -> PowerUpKind {
+        PowerUpKind::Magnet
+    }
+}
+
+pub struct MagnetPlugin;
+
+impl Plugin for MagnetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(TimedEffectPlugin::<Magnet>::default())
+            .add_system(apply_magnet_powerup)
+            .add_system_set(phase_set(Phase::Forces).with_system(steer_towards_paddle));
+    }
+}
+
+fn apply_magnet_powerup(
+    mut commands: Commands,
+    mut events: EventReader<PowerUpCollected>,
+    ball_query: Query<Entity, With<Ball>>,
+) {
+    for PowerUpCollected(kind) in events.iter() {
+        if *kind != PowerUpKind::Magnet {
+            continue;
+        }
+        for ball in &ball_query {
+            commands
+                .entity(ball)
+                .insert(Magnet(Timer::from_seconds(MAGNET_DURATION, TimerMode::Once)));
+        }
+    }
+}
+
+// Lenkt nur die Richtung des Balls, die Geschwindigkeit bleibt gleich. So lassen sich wilde Abpraller
+// noch retten, ohne dass der Ball schneller oder langsamer wird.
+fn steer_towards_paddle(
+    mut ball_query: Query<(&Transform, &mut Kinematic), With<Magnet>>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    time_scale: Res<TimeScale>,
+) {
+    let Ok(paddle_transform) = paddle_query.get_single() else { return };
+    for (transform, mut body) in &mut ball_query {
+        if body.velocity.y >= 0.0 {
+            continue;
+        }
+        let speed = body.velocity.length();
+        let offset = paddle_transform.translation.x - transform.translation.x;
+        body.velocity.x += offset * MAGNET_STRENGTH * time_scale.step();
+        body.velocity = body.velocity.normalize_or_zero() * speed;
+    }
+}
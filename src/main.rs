@@ -3,17 +3,122 @@
 //! This example demonstrates the built-in 3d shapes in Bevy.
 //! The scene includes a patterned texture and a rotation for visualizing the normals and UVs.
 
-use std::f32::consts::{FRAC_PI_4, PI};
-use bevy::{
-    prelude::*,
-    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
-};
-use bevy::pbr::extract_meshes;
+use std::f32::consts::PI;
+use bevy::prelude::*;
+use bevy::diagnostic::Diagnostics;
+use bevy::ecs::schedule::ShouldRun;
 use bevy::sprite::collide_aabb::{collide, Collision};
-use bevy::time::FixedTimestep;
+use serde::{Deserialize, Serialize};
+
+use level::{Campaign, Level};
+
+mod ability;
+mod accessibility;
+mod achievement;
+mod assist;
+mod attract;
+mod ball_physics;
+mod ball_size;
+mod brick_wave;
+mod bumper;
+mod captions;
+mod chain;
+mod challenge;
+mod cheats;
+mod cli;
+mod collision;
+mod config;
+mod controls;
+mod coop;
+mod cosmetics;
+mod crash;
+mod daily;
+mod danger;
+mod dash;
+mod debris;
+#[cfg(feature = "dev")]
+mod dev;
+mod diagnostics;
+mod difficulty;
+mod dissolve;
+mod easing;
+mod editor;
+mod effect;
+mod effect_hud;
+mod floor;
+mod framing;
+mod focus;
+mod format;
+mod energy;
+mod force_field;
+mod gamelog;
+mod ghost;
+mod grade;
+mod graphics;
+mod grid;
+mod haptics;
+mod hazard;
+mod hud;
+mod impact;
+mod input_buffer;
+mod intro;
+mod launch;
+mod level;
+mod lighting;
+#[cfg(feature = "online")]
+mod leaderboard;
+mod magnet;
+mod menu;
+mod mode;
+mod modding;
+mod near_miss;
+mod objective;
+mod obstacle;
+mod paddle_size;
+mod pool;
+mod portal;
+mod powerup;
+#[cfg(feature = "presence")]
+mod presence;
+mod prompt;
+mod progress;
+mod regenerate;
+mod rescue;
+mod results;
+mod resume;
+mod rng;
+mod rotation;
+mod run;
+mod save;
+mod sharing;
+mod share_card;
+mod shield;
+mod shop;
+mod snapshot;
+mod sound;
+mod spectator;
+mod speedrun;
+mod spin;
+#[cfg(feature = "dev")]
+mod stepping;
+mod stress;
+mod surface;
+mod survival;
+mod telemetry;
+mod texture;
+mod theme;
+mod tick_rate;
+mod tilt;
+mod time_attack;
+mod toast;
+mod tournament;
+mod trail;
+mod transition;
+mod trick_shot;
+mod turret;
+mod tween;
+mod tutorial;
 
-const TIME_STEP: f32 = 1.0 / 60.0;
-const PADDLE_SIZE: Vec3 = Vec3::new(2.0, 1.0, 1.0);
 const BRICK_SIZE: Vec3 = Vec3::new(1.0, 0.4, 1.0);
 const GAP_BETWEEN_BRICKS_AND_SIDES: f32 = 0.5;
 const GAP_BETWEEN_PADDLE_AND_FLOOR: f32 = 0.2;
@@ -21,22 +126,99 @@ const GAP_BETWEEN_BRICKS: f32 = 0.3;
 const GAP_BETWEEN_BRICKS_AND_CEILING: f32 = 0.3;
 const PADDLE_PADDING: f32 = 0.1;
 const GAP_BETWEEN_PADDLE_AND_BRICKS: f32 = 3.0;
-const BALL_SIZE: Vec3 = Vec3::new(0.2, 0.2, 0.2);
-const BALL_SPEED: f32 = 7.0;
-const PADDLE_SPEED: f32 = 8.0;
 const WALL_THICKNESS: f32 = 1.0;
-const BALL_STARTING_POSITION: Vec3 = Vec3::new(-4.0, 2.0, 0.0);
-const INITIAL_BALL_DIRECTION: Vec3 = Vec3::new(0.5, -0.5, 0.0);
-const LEFT_WALL: f32 = 0.0;
-const RIGHT_WALL: f32 = 10.0;
-const TOP_WALL: f32 = 10.0;
-const BOTTOM_WALL: f32 = 0.0;
-const SCOREBOARD_FONT_SIZE: f32 = 40.0;
-const SCOREBOARD_TEXT_PADDING: Val = Val::Px(5.0);
-
-const WALL_COLOR: Color = Color::rgb(0., 0., 0.);
-const BRICK_COLOR: Color = Color::rgb(0., 0., 0.);
-const SCORE_COLOR: Color = Color::rgb(0.0, 0.0, 0.0);
+// Die Standardmaße der Arena, siehe Arena.
+const ARENA_WIDTH: f32 = 10.0;
+const ARENA_HEIGHT: f32 = 10.0;
+const ARENA_DEPTH: f32 = 1.0;
+const PADDLE_Y: f32 = 2.0;
+const FLOOR_HEIGHT: f32 = -2.0;
+const BALL_COLOR: Color = Color::RED;
+// Ein durchschlagender Ball glüht orange, damit man den Zustand sofort erkennt.
+const PIERCING_BALL_COLOR: Color = Color::ORANGE;
+// Anteil der Paddle-Geschwindigkeit, den ein seitlich angestoßener Ball mitbekommt.
+const PADDLE_CAROM_TRANSFER: f32 = 0.8;
+// Jeder wievielte Brick ein Strafbrick ist. Sie sind dunkelrot, damit man sie vorher erkennt.
+const PENALTY_BRICK_INTERVAL: usize = 7;
+const PENALTY_BRICK_COLOR: Color = Color::rgb(0.5, 0.0, 0.0);
+
+// Die Zustände des Spiels. Beim Start werden zuerst die Level geladen, danach geht es ins Hauptmenü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GameState {
+    Loading,
+    Menu,
+    CustomGame,
+    Playing,
+    // Die Kamerafahrt zu Beginn eines Levels. Sie liegt über Playing auf dem Zustandsstapel.
+    LevelIntro,
+    // Die Abrechnung eines geschafften Levels mit Boni, danach geht es zur Belohnung oder ins Hauptmenü.
+    Results,
+    // Nach einem geschafften Level wird einer von drei Modifikatoren für den Rest des Durchlaufs gewählt.
+    Reward,
+    // Zwischen zwei Leveln der Kampagne werden hier Münzen gegen Upgrades getauscht.
+    Shop,
+    // Auswahl der Skins für Ball und Paddle.
+    Cosmetics,
+    // Einstellungen zur Barrierefreiheit, etwa Farbpaletten für Farbsehschwächen.
+    Accessibility,
+    // Auswahl des Spielmodus vor dem Start.
+    ModeSelect,
+    // Freigeschaltete Level der Kampagne einzeln wiederholen.
+    LevelSelect,
+    // Die Spitze der Online-Bestenliste, nur mit dem Feature "online" vorhanden.
+    #[cfg(feature = "online")]
+    Leaderboard,
+    // Bricks mit der Maus ins Raster malen, probespielen und als Level-Datei speichern.
+    Editor,
+    // Die Level aus dem Ordner der eigenen Level, etwa per Code oder Drag-and-drop importierte.
+    UserLevels,
+    // Die letzten Spielereignisse Schritt für Schritt ansehen. Liegt wie die Kamerafahrt über Playing.
+    LogInspector,
+    // Vor und zwischen den Zügen eines Turniers: Aufstellung, Zwischenstand und wer als Nächstes dran ist.
+    Standings,
+}
+
+// Label für den festen Spieltakt, damit auch Systeme aus anderen Modulen im selben Takt laufen.
+#[derive(RunCriteriaLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct FixedTick;
+
+// Die Phasen eines festen Spielschritts in ihrer Reihenfolge. Neue Spielsysteme kommen über phase_set in ihre Phase
+// und müssen sich nicht an einzelne Systeme aus anderen Modulen hängen.
+#[derive(SystemLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Phase {
+    // Tasten und alles, was direkt am Paddle hängt.
+    Input,
+    // Kräfte, die die Geschwindigkeit ändern, bevor bewegt wird.
+    Forces,
+    Movement,
+    Collision,
+    // Regeln, die auf das Ergebnis der Kollisionen schauen: verlorene Bälle, ablaufende Effekte.
+    Scoring,
+    // Reine Darstellung, die den fertigen Stand des Schritts zeigt.
+    Vfx,
+}
+
+impl Phase {
+    fn previous(self) -> Option<Phase> {
+        match self {
+            Phase::Input => None,
+            Phase::Forces => Some(Phase::Input),
+            Phase::Movement => Some(Phase::Forces),
+            Phase::Collision => Some(Phase::Movement),
+            Phase::Scoring => Some(Phase::Collision),
+            Phase::Vfx => Some(Phase::Scoring),
+        }
+    }
+}
+
+// Ein Set im festen Takt, das in seiner Phase und nach der vorherigen Phase läuft.
+fn phase_set(phase: Phase) -> SystemSet {
+    let set = SystemSet::new().with_run_criteria(FixedTick).label(phase);
+    match phase.previous() {
+        Some(previous) => set.after(previous),
+        None => set,
+    }
+}
 
 // Hier sind die Entities und Komponenten die in dem Projekt genutzt werden
 
@@ -46,394 +228,1209 @@ struct Paddle;
 #[derive(Component)]
 struct Ball;
 
-#[derive(Component, Deref, DerefMut)]
-struct Velocity(Vec3);
+// Alles, was sich von selbst bewegt: Ball, fallende Power-Ups und Trümmer. apply_velocity bewegt sie alle gemeinsam,
+// neue bewegliche Objekte brauchen also keinen eigenen Bewegungscode.
+#[derive(Component, Clone, Copy, Default)]
+struct Kinematic {
+    velocity: Vec3,
+    // Beschleunigung, die in jedem Schritt auf die Geschwindigkeit addiert wird.
+    gravity: Vec3,
+    // Anteil der Geschwindigkeit, der beim Aufprall auf den Boden erhalten bleibt. Bei 0 fällt das Objekt hindurch.
+    bounciness: f32,
+}
+
+impl Kinematic {
+    fn moving(velocity: Vec3) -> Self {
+        Kinematic { velocity, ..default() }
+    }
+}
 
 #[derive(Component)]
 struct Collider;
 
+// Die Bodenebene unter der Arena.
+#[derive(Component)]
+struct Floor;
+
+// Die Lichtquelle, die die ganze Arena beleuchtet.
+#[derive(Component)]
+struct MainLight;
+
+// Die Kamera, die das Spiel zeigt. Mit dem Feature "dev" kann eine zweite für das Debug-Fenster dazukommen.
+#[derive(Component)]
+struct MainCamera;
+
+// Wird bei jeder Berührung des Balls gesendet, mit der Stelle, an der er sich gerade befindet.
+struct CollisionEvent {
+    position: Vec3,
+    // Ob der Ball an einer Wand abgeprallt ist, siehe trick_shot.rs.
+    wall: bool,
+}
+
+// Wird gesendet, wenn der Ball das Paddle berührt.
 #[derive(Default)]
-struct CollisionEvent;
+struct PaddleHit;
 
+// Die Zelle im BrickGrid, zu der dieser Brick gehört.
 #[derive(Component)]
-struct Brick;
+struct Brick {
+    row: usize,
+    column: usize,
+}
 
-#[derive(Resource)]
-struct CollisionSound(Handle<AudioSource>);
+// Solange ein Ball diese Komponente hat, zerstört er Bricks, ohne von ihnen abzuprallen. Dazu steht, was sie
+// ausgelöst hat: ein Feuerball oder der Smash.
+#[derive(Component)]
+struct Piercing(Timer, powerup::PowerUpKind);
 
-#[derive(Bundle)]
-struct WallBundle {
-    pbr_bundle: PbrBundle,
-    collider: Collider,
+impl effect::TimedEffect for Piercing {
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+
+    fn kind(&self) -> powerup::PowerUpKind {
+        self.1
+    }
 }
 
-enum WallLocation {
-    Left,
-    Right,
-    Bottom,
-    Top,
+// Strafbricks lassen beim Zerstören immer eine Gefahr fallen, der der Spieler ausweichen muss.
+#[derive(Component)]
+struct PenaltyBrick;
+
+// Wird gesendet, wenn ein Brick zerstört wurde, damit sich Effekte wie Trümmer daran hängen können.
+struct BrickDestroyed {
+    position: Vec3,
+    material: Handle<StandardMaterial>,
+    kind: level::BrickKind,
 }
-// Ähnlich wie ich in dem Beispiel werden die Wände hier sehr generell implementiert, um das Wiederverwenden dieser einfacher zu machen. 
-impl WallLocation {
-    
-    fn position(&self) -> Vec3 {
+
+// Wird bei jedem Treffer eines Bricks gesendet, auch wenn er danach noch steht.
+struct BrickHit {
+    position: Vec3,
+    size: Vec3,
+    // Das Material des Bricks, falls er eines hat. Davon hängen Klang und Splitter des Treffers ab.
+    surface: Option<surface::Surface>,
+}
+
+// Alles, was zu einem laufenden Level gehört, wird beim Verlassen von GameState::Playing wieder entfernt.
+#[derive(Component)]
+struct LevelEntity;
+
+// Modifikatoren, die im Menü "Eigenes Spiel" vor dem Start gewählt werden.
+#[derive(Resource, Default)]
+struct Mutators {
+    gravity: bool,
+    arena_size: ArenaSize,
+    challenges: challenge::Challenges,
+}
+
+// Verkleinert oder vergrößert die Arena des Levels.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+enum ArenaSize {
+    #[default]
+    Level,
+    Tiny,
+    Huge,
+}
+
+impl ArenaSize {
+    const ALL: [ArenaSize; 3] = [ArenaSize::Level, ArenaSize::Tiny, ArenaSize::Huge];
+
+    fn name(self) -> &'static str {
         match self {
-            WallLocation::Left => Vec3::new(LEFT_WALL - (RIGHT_WALL / 2.0), TOP_WALL / 2.0,0.),
-            WallLocation::Right => Vec3::new(RIGHT_WALL / 2., TOP_WALL / 2.0, 0.),
-            WallLocation::Top => Vec3::new(0.0, TOP_WALL, 0.),
-            WallLocation::Bottom => Vec3::new(0.0,BOTTOM_WALL,0.),
+            ArenaSize::Level => "Wie im Level",
+            ArenaSize::Tiny => "Winzig",
+            ArenaSize::Huge => "Riesig",
         }
     }
 
-    fn size(&self) -> Vec3 {
-        let arena_height = TOP_WALL - BOTTOM_WALL;
-        let arena_width = RIGHT_WALL - LEFT_WALL;
-        assert!(arena_height > 0.0);
-        assert!(arena_width > 0.0);
-        println!("{}. {}", arena_height, arena_width);
+    fn next(self) -> ArenaSize {
+        let current = ArenaSize::ALL.iter().position(|size| *size == self).unwrap_or(0);
+        ArenaSize::ALL[(current + 1) % ArenaSize::ALL.len()]
+    }
+
+    fn factor(self) -> f32 {
         match self {
-            WallLocation::Left | WallLocation::Right => {
-                Vec3::new(WALL_THICKNESS, arena_height + WALL_THICKNESS , 1.)
-            }
-            WallLocation::Bottom | WallLocation::Top => {
-                Vec3::new(arena_height + WALL_THICKNESS, WALL_THICKNESS, 1.)
-            }
+            ArenaSize::Level => 1.0,
+            ArenaSize::Tiny => 0.7,
+            ArenaSize::Huge => 1.6,
         }
     }
 }
 
-impl WallBundle {
-    fn new(location: WallLocation, material: Handle<StandardMaterial>, mesh: Handle<Mesh>) -> WallBundle {
-        println!("{}", location.position().to_string());
-         return WallBundle {
-                    pbr_bundle: PbrBundle {
-                    transform: Transform::from_translation(location.position()).with_scale(location.size()),
-                    material,
-                    mesh,
-                        ..default()
-                    },
-                    collider: Collider,
+// Globaler Zeitfaktor für alle Bewegungssysteme. 1.0 ist normale Geschwindigkeit, 0.0 friert das Spiel ein.
+// Zeitlupe, Hit-Stop oder Pause setzen nur den Faktor, statt dass jedes System selbst mit der Dauer eines Schritts
+// rechnet. Die Dauer kommt aus der gewählten Taktrate, siehe tick_rate.rs.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+struct TimeScale {
+    factor: f32,
+    tick: f32,
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        TimeScale {
+            factor: 1.0,
+            tick: tick_rate::TickRate::default().seconds(),
+        }
+    }
+}
+
+// Startgeschwindigkeit des Balls im laufenden Level, aus dem Level und den Modifikatoren des Durchlaufs.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+struct BallSpeed(f32);
+
+// Die Maße des Spielfelds. Es liegt waagerecht mittig um x = 0 und beginnt unten bei y = 0.
+// Wände, die Grenzen des Paddles, das Raster der Bricks und die Kamera werden alle hieraus berechnet.
+// Ein Level kann eigene Maße vorgeben, fehlende Angaben bekommen den Standardwert.
+#[derive(Resource, Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct Arena {
+    width: f32,
+    height: f32,
+    // Die Tiefe der Wände.
+    depth: f32,
+    #[serde(skip)]
+    wall_thickness: f32,
+    // Um so viel sind die Seitenwände im Überlebensmodus schon nach innen gerückt, siehe survival.rs.
+    // Das Raster der Bricks bleibt dabei, wo es ist.
+    #[serde(skip)]
+    squeeze: f32,
+    // Die Kamera aus der Level-Datei, schon gegen die Maße geprüft. Ohne Angabe richtet sie sich nach der Größe.
+    #[serde(skip)]
+    camera: Option<framing::CameraFraming>,
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Arena {
+            width: ARENA_WIDTH,
+            height: ARENA_HEIGHT,
+            depth: ARENA_DEPTH,
+            wall_thickness: WALL_THICKNESS,
+            squeeze: 0.0,
+            camera: None,
+        }
+    }
+}
+
+impl Arena {
+    fn left(&self) -> f32 {
+        -self.width / 2.0 + self.squeeze
+    }
+
+    fn right(&self) -> f32 {
+        self.width / 2.0 - self.squeeze
+    }
+
+    fn bottom(&self) -> f32 {
+        0.0
+    }
+
+    fn top(&self) -> f32 {
+        self.height
+    }
+
+    // Die längere Seite der Arena. Kamera, Boden und Licht wachsen mit ihr.
+    fn extent(&self) -> f32 {
+        self.width.max(self.height)
+    }
+
+    // Eine eigene Kamera wächst mit, damit sie dieselbe Arena im Bild hat.
+    fn scaled(self, factor: f32) -> Arena {
+        Arena {
+            width: self.width * factor,
+            height: self.height * factor,
+            camera: self.camera.map(|camera| camera.scaled(factor)),
+            ..self
+        }
+    }
+
+    // Die Kamera schaut schräg von oben auf die Mitte der Arena, mit Abstand nach ihrer Größe. Ein Level kann
+    // Standort und Blickpunkt selbst vorgeben.
+    fn camera_transform(&self) -> Transform {
+        if let Some(camera) = self.camera {
+            return camera.transform();
         }
+        let center = Vec3::new((self.left() + self.right()) / 2.0, (self.bottom() + self.top()) / 2.0, 0.0);
+        Transform::from_xyz(center.x, self.top(), 2.0 * self.extent()).looking_at(center, Vec3::Y)
+    }
+
+    // Das Sichtfeld in Bogenmaß.
+    fn camera_fov(&self) -> f32 {
+        self.camera.and_then(|camera| camera.fov()).unwrap_or(framing::DEFAULT_FOV)
+    }
+
+    fn light_position(&self) -> Vec3 {
+        Vec3::new(self.extent(), 2.5 * self.extent(), self.extent())
+    }
+
+    // Der Boden ragt auf allen Seiten weit über die Arena hinaus.
+    fn floor_size(&self) -> f32 {
+        5.0 * self.extent()
     }
 
+    // Die Wände liegen mittig auf den Grenzen und überlappen sich in den Ecken.
+    fn wall_transform(&self, location: WallLocation) -> Transform {
+        let center_y = (self.bottom() + self.top()) / 2.0;
+        let (position, size) = match location {
+            WallLocation::Left => (Vec2::new(self.left(), center_y), Vec2::new(self.wall_thickness, self.height + self.wall_thickness)),
+            WallLocation::Right => (Vec2::new(self.right(), center_y), Vec2::new(self.wall_thickness, self.height + self.wall_thickness)),
+            WallLocation::Bottom => (Vec2::new(0.0, self.bottom()), Vec2::new(self.width + self.wall_thickness, self.wall_thickness)),
+            WallLocation::Top => (Vec2::new(0.0, self.top()), Vec2::new(self.width + self.wall_thickness, self.wall_thickness)),
+        };
+        Transform::from_translation(position.extend(0.0)).with_scale(size.extend(self.depth))
+    }
+
+    // Hält das Paddle zwischen den Wänden. Die Breite ist die aktuelle Skalierung, da Effekte das Paddle verkleinern oder vergrößern.
+    // Ist das Paddle breiter als die Arena, bleibt es in der Mitte.
+    fn clamp_paddle_x(&self, x: f32, width: f32) -> f32 {
+        let left_bound = self.left() + self.wall_thickness / 2.0 + width / 2.0 + PADDLE_PADDING;
+        let right_bound = self.right() - self.wall_thickness / 2.0 - width / 2.0 - PADDLE_PADDING;
+        if left_bound > right_bound {
+            return (self.left() + self.right()) / 2.0;
+        }
+        x.clamp(left_bound, right_bound)
+    }
+}
+
+// Das Raster, in dem die Bricks eines Levels liegen. Es ergibt sich aus der Arena und den Abständen, Reihe 0 ist die unterste.
+struct GridGeometry {
+    columns: usize,
+    rows: usize,
+    // Mittelpunkt des Bricks in Reihe 0 und Spalte 0.
+    origin: Vec2,
+}
+
+impl GridGeometry {
+    fn new(arena: &Arena) -> Self {
+        // Da die Bricks anhand der Konstanten generiert werden, kann es bei falschen Parametern passieren, dass sie eine Größe < 0 haben.
+        const _: () = assert!(BRICK_SIZE.x > 0.0 && BRICK_SIZE.y > 0.0 && BRICK_SIZE.z > 0.0);
+
+        let paddle_y = arena.bottom() + GAP_BETWEEN_PADDLE_AND_FLOOR;
+        // Die volle Breite, auch wenn die Seitenwände gerade nach innen gerückt sind. Sonst würden die Bricks mitwandern.
+        let total_width_of_bricks = arena.width - 2. * GAP_BETWEEN_BRICKS_AND_SIDES;
+        let bottom_edge_of_bricks = paddle_y + GAP_BETWEEN_PADDLE_AND_BRICKS;
+        let total_height_of_bricks = arena.top() - bottom_edge_of_bricks - GAP_BETWEEN_BRICKS_AND_CEILING;
+
+        assert!(total_width_of_bricks > 0.0);
+        assert!(total_height_of_bricks > 0.0);
+
+        // Ich berechne wie viele Reihen und Spalten an Bricks es geben kann
+        let columns = (total_width_of_bricks / (BRICK_SIZE.x + GAP_BETWEEN_BRICKS)).floor() as usize;
+        let rows = (total_height_of_bricks / (BRICK_SIZE.y + GAP_BETWEEN_BRICKS)).floor() as usize;
+        let n_vertical_gaps = columns - 1;
+
+        // Da es Spalten und Reihen nur als ganze Zahl geben, wird das hier sichergestellt.
+        // Die Arena liegt mittig um x = 0.
+        let center_of_bricks = 0.0;
+        let left_edge_of_bricks = center_of_bricks
+            - (columns as f32 / 2.0 * BRICK_SIZE.x)
+            - n_vertical_gaps as f32 / 2.0 * GAP_BETWEEN_BRICKS;
+
+        GridGeometry {
+            columns,
+            rows,
+            origin: Vec2::new(left_edge_of_bricks + BRICK_SIZE.x / 2., bottom_edge_of_bricks + BRICK_SIZE.y / 2.),
+        }
+    }
+
+    fn cell_size() -> Vec2 {
+        Vec2::new(BRICK_SIZE.x + GAP_BETWEEN_BRICKS, BRICK_SIZE.y + GAP_BETWEEN_BRICKS)
+    }
+
+    fn position(&self, row: usize, column: usize) -> Vec2 {
+        self.origin + Vec2::new(column as f32, row as f32) * Self::cell_size()
+    }
+
+    // Die Zelle (Reihe, Spalte), in der ein Punkt liegt. Die Lücke rechts und über einem Brick zählt zu seiner Zelle.
+    fn cell_at(&self, point: Vec2) -> Option<(usize, usize)> {
+        let relative = (point - self.origin + BRICK_SIZE.truncate() / 2.0) / Self::cell_size();
+        if relative.x < 0.0 || relative.y < 0.0 {
+            return None;
+        }
+        let (row, column) = (relative.y as usize, relative.x as usize);
+        (row < self.rows && column < self.columns).then_some((row, column))
+    }
+}
+
+impl TimeScale {
+    // Die Dauer eines festen Simulationsschritts nach Anwendung des Zeitfaktors.
+    fn step(&self) -> f32 {
+        self.tick * self.factor
+    }
+}
+
+#[derive(Bundle)]
+struct WallBundle {
+    pbr_bundle: PbrBundle,
+    collider: Collider,
+    layers: collision::CollisionLayers,
+    location: WallLocation,
+}
+
+// Bleibt an der Wand, damit sie sich neu ausrichten lässt, wenn sich die Arena ändert.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum WallLocation {
+    Left,
+    Right,
+    Bottom,
+    Top,
+}
+// Ähnlich wie ich in dem Beispiel werden die Wände hier sehr generell implementiert, um das Wiederverwenden dieser einfacher zu machen.
+// Wo eine Wand liegt und wie groß sie ist, bestimmt die Arena.
+impl WallBundle {
+    fn new(location: WallLocation, arena: &Arena, material: Handle<StandardMaterial>, mesh: Handle<Mesh>) -> WallBundle {
+        WallBundle {
+            pbr_bundle: PbrBundle {
+                transform: arena.wall_transform(location),
+                material,
+                mesh,
+                ..default()
+            },
+            collider: Collider,
+            layers: collision::Layer::Wall.into(),
+            location,
+        }
+    }
 }
 
 #[derive(Resource)]
 struct Scoreboard {
     score: usize,
+    // Bricks, die seit der letzten Berührung des Paddles zerstört wurden.
+    combo: u32,
+}
+
+// Verbleibende Bälle im aktuellen Level. Fällt der Ball durch den Boden, wird einer abgezogen.
+#[derive(Resource)]
+struct Lives(u32);
+
+// Wird gesendet, wenn alle Bricks eines Levels zerstört wurden. `index` ist die Position des Levels in der Kampagne,
+// `last` ist beim letzten Level der Kampagne gesetzt.
+struct LevelCleared {
+    index: usize,
+    last: bool,
+}
+
+// Wird gesendet, wenn der Ball unten aus dem Spielfeld gefallen ist, mit der Stelle, an der er verloren ging.
+struct BallLost {
+    position: Vec3,
 }
 
+// Wird gesetzt, sobald die Bricks des Levels in der Welt sind. Erst danach bedeutet "keine Bricks mehr", dass das Level geschafft ist.
+#[derive(Resource, Default)]
+struct LevelInProgress(bool);
+
 
 // Hier werden alle Ressourcen, events und systeme angegeben, welche die App nutzt.
 fn main() {
-    App::new()
-        .insert_resource(Scoreboard { score: 0})
+    // Die Kommandozeile wird vor allem anderen gelesen, bei --help oder falschen Argumenten endet das Programm hier.
+    let options = <cli::LaunchOptions as clap::Parser>::parse();
+    let mut app = App::new();
+    app.insert_resource(Scoreboard { score: 0, combo: 0 })
+        .insert_resource(Lives(config::GameConfig::default().starting_lives))
+        .init_resource::<LevelInProgress>()
         .insert_resource(ClearColor(Color::rgb(0.7, 1.0, 1.0)))
-        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .init_resource::<TimeScale>()
+        .init_resource::<Mutators>()
+        .init_resource::<Arena>()
+        .init_resource::<BallSpeed>()
+        .add_plugins(
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    window: WindowDescriptor { mode: options.window_mode(), ..default() },
+                    ..default()
+                })
+                // Level- und Theme-Dateien werden neu geladen, sobald sie sich auf der Platte ändern. Das braucht nur,
+                // wer an den Dateien arbeitet, also nur in Debug-Builds und mit dem Feature "dev".
+                .set(AssetPlugin { watch_for_changes: cfg!(any(debug_assertions, feature = "dev")), ..default() }),
+        )
+        .add_state(GameState::Loading)
+        .add_plugin(cli::CliPlugin(options))
+        .add_plugin(stress::StressPlugin)
+        .add_plugin(snapshot::SnapshotPlugin)
+        .add_plugin(crash::CrashReportPlugin)
+        .add_plugin(toast::ToastPlugin)
+        .add_plugin(captions::CaptionsPlugin)
+        .add_plugin(danger::DangerPlugin)
+        .add_plugin(diagnostics::DiagnosticsHudPlugin)
+        .add_plugin(controls::ControlsPlugin)
+        .add_plugin(input_buffer::InputBufferPlugin)
+        .add_plugin(gamelog::GameLogPlugin)
+        .add_plugin(save::SavePlugin)
+        .add_plugin(config::GameConfigPlugin)
+        .add_plugin(ball_physics::BallPhysicsPlugin)
+        .add_plugin(modding::ModPlugin)
+        .add_plugin(cosmetics::CosmeticsPlugin)
+        .add_plugin(accessibility::AccessibilityPlugin)
+        .add_plugin(assist::AssistPlugin)
+        .add_plugin(difficulty::DifficultyPlugin)
+        .add_plugin(hud::HudPlugin)
+        .add_plugin(effect::ActiveEffectsPlugin)
+        .add_plugin(effect_hud::EffectHudPlugin)
+        .add_plugin(prompt::ButtonPromptPlugin)
+        .add_plugin(haptics::HapticsPlugin)
+        .add_plugin(graphics::GraphicsPlugin)
+        .add_plugin(tick_rate::TickRatePlugin)
+        .add_plugin(transition::TransitionPlugin)
+        .add_plugin(tween::TweenPlugin)
+        .add_plugin(framing::FramingPlugin)
+        .add_plugin(achievement::AchievementPlugin)
+        .add_plugin(mode::ModePlugin)
+        .add_plugin(rng::RngPlugin)
+        .add_plugin(attract::AttractPlugin)
+        .add_plugin(rotation::ArenaRotationPlugin)
+        .add_plugin(results::ResultsPlugin)
+        .add_plugin(impact::ImpactPlugin)
+        .add_plugin(cheats::CheatPlugin)
+        .add_plugin(progress::ProgressPlugin)
+        .add_plugin(time_attack::TimeAttackPlugin)
+        .add_plugin(coop::CoopPlugin)
+        .add_plugin(survival::SurvivalPlugin)
+        .add_plugin(tournament::TournamentPlugin)
+        .add_plugin(speedrun::SpeedrunPlugin)
+        .add_plugin(telemetry::TelemetryPlugin)
+        .add_plugin(editor::EditorPlugin)
+        .add_plugin(sharing::SharingPlugin)
+        .add_plugin(tutorial::TutorialPlugin)
+        .add_plugin(resume::ResumePlugin)
+        .add_plugin(intro::IntroPlugin)
+        .add_plugin(level::LevelPlugin)
+        .add_plugin(grid::BrickGridPlugin)
+        .add_plugin(theme::ThemePlugin)
+        .add_plugin(texture::BackdropPlugin)
+        .add_plugin(lighting::LightingPlugin)
+        .add_plugin(focus::FocusPlugin)
+        .add_plugin(menu::MenuPlugin)
+        .add_plugin(force_field::ForceFieldPlugin)
+        .add_plugin(sound::SoundPlugin)
+        .add_plugin(bumper::BumperPlugin)
+        .add_plugin(portal::PortalPlugin)
+        .add_plugin(obstacle::ObstaclePlugin)
+        .add_plugin(debris::DebrisPlugin)
+        .add_plugin(dissolve::DissolvePlugin)
+        .add_plugin(dash::DashPlugin)
+        .add_plugin(energy::EnergyPlugin)
+        .add_plugin(trail::TrailPlugin)
+        .add_plugin(powerup::PowerUpPlugin)
+        .add_plugin(shield::ShieldPlugin)
+        .add_plugin(magnet::MagnetPlugin)
+        .add_plugin(paddle_size::PaddleSizePlugin)
+        .add_plugin(launch::LaunchPlugin)
+        .add_plugin(ball_size::BallSizePlugin)
+        .add_plugin(tilt::TiltPlugin)
+        .add_plugin(spin::SpinPlugin)
+        .add_plugin(hazard::HazardPlugin)
+        .add_plugin(ghost::GhostPlugin)
+        .add_plugin(regenerate::RegeneratePlugin)
+        .add_plugin(chain::ChainPlugin)
+        .add_plugin(turret::TurretPlugin)
+        .add_plugin(surface::SurfacePlugin)
+        .add_plugin(trick_shot::TrickShotPlugin)
+        .add_plugin(share_card::ShareCardPlugin)
+        .add_plugin(near_miss::NearMissPlugin)
+        .add_plugin(floor::FloorPlugin)
+        .add_plugin(spectator::SpectatorPlugin)
+        .add_plugin(brick_wave::BrickWavePlugin)
+        .add_plugin(objective::ObjectivePlugin)
+        .add_plugin(rescue::RescuePlugin)
+        .add_plugin(shop::ShopPlugin)
+        .add_plugin(run::RunPlugin)
+        .add_plugin(effect::TimedEffectPlugin::<Piercing>::default())
         .add_startup_system(setup)
         .add_event::<CollisionEvent>()
+        .add_event::<PaddleHit>()
+        .add_event::<BrickDestroyed>()
+        .add_event::<BrickHit>()
+        .add_event::<BallLost>()
+        .add_event::<LevelCleared>()
+        .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_level))
+        .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(despawn_level))
+        // Die Bedingung für den festen Takt wird hier einmal angelegt, alle anderen Sets verweisen über FixedTick darauf.
         .add_system_set(
             SystemSet::new()
-                .with_run_criteria(FixedTimestep::step(TIME_STEP as f64))
-                .with_system(rotate)
-                .with_system(check_for_collision)
-                .with_system(move_object.before(check_for_collision))
-                .with_system(apply_velocity.before(check_for_collision))
+                .with_run_criteria(fixed_gameplay_tick.label(FixedTick))
+                .label(Phase::Input)
+                .with_system(move_object),
         )
-        .add_system(update_scoreboard)
-        .add_system(bevy::window::close_on_esc)
-        .run();
+        .add_system_set(phase_set(Phase::Movement).with_system(apply_velocity))
+        .add_system_set(phase_set(Phase::Collision).with_system(check_for_collision))
+        .add_system_set(phase_set(Phase::Scoring).with_system(check_ball_lost))
+        .add_system_set(phase_set(Phase::Vfx).with_system(rotate))
+        .add_system(update_ball_material)
+        .add_system(frame_arena)
+        .add_system_set(
+            SystemSet::on_update(GameState::Playing)
+                .with_system(back_to_menu)
+                .with_system(check_level_cleared),
+        )
+        .add_system_set(SystemSet::on_update(GameState::Menu).with_system(bevy::window::close_on_esc));
+    #[cfg(feature = "online")]
+    app.add_plugin(leaderboard::LeaderboardPlugin);
+    #[cfg(feature = "presence")]
+    app.add_plugin(presence::PresencePlugin);
+    #[cfg(feature = "dev")]
+    app.add_plugin(dev::DevPlugin).add_plugin(stepping::SteppingPlugin);
+    app.run();
+}
+
+// Ersetzt FixedTimestep: Die Spielsysteme laufen in festen Schritten, aber nur während GameState::Playing.
+// Außerhalb des Spiels wird keine Zeit angesammelt, damit es beim Zurückkehren keinen Nachhol-Sprung gibt.
+#[allow(clippy::too_many_arguments)]
+fn fixed_gameplay_tick(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    transition: Res<transition::ScreenTransition>,
+    controls: Res<controls::ActiveControls>,
+    #[cfg(feature = "dev")] mut stepping: ResMut<stepping::Stepping>,
+    mut run_ticks: ResMut<mode::RunTicks>,
+    time_scale: Res<TimeScale>,
+    mut progress: ResMut<tick_rate::TickProgress>,
+    mut accumulator: Local<f64>,
+    mut looping: Local<bool>,
+) -> ShouldRun {
+    // Während eines Bildschirmübergangs und nach dem Trennen des Gamepads steht das Spiel still.
+    if *state.current() != GameState::Playing || transition.is_active() || controls.is_waiting() {
+        *accumulator = 0.0;
+        *looping = false;
+        return ShouldRun::No;
+    }
+    // Die Zeit des Frames wird nur beim ersten Aufruf addiert, danach werden nur noch Schritte abgebaut.
+    // Mit dem Feature "dev" lässt sich der Takt anhalten und verlangsamen, siehe stepping.rs.
+    // Für die Prüfung des Ergebnisses zählen echte Zeit und gelaufene Schritte getrennt mit.
+    // Was danach übrig bleibt, sagt der Interpolation, wie weit es bis zum nächsten Schritt ist.
+    let tick = time_scale.tick as f64;
+    if !*looping {
+        let elapsed = time.delta_seconds_f64();
+        run_ticks.real_seconds += elapsed;
+        #[cfg(feature = "dev")]
+        let elapsed = stepping.advance(elapsed, tick);
+        *accumulator += elapsed;
+    }
+    if *accumulator >= tick {
+        *accumulator -= tick;
+        run_ticks.ticks += 1;
+        run_ticks.simulated_seconds += tick;
+        *looping = true;
+        ShouldRun::YesAndCheckAgain
+    } else {
+        progress.0 = (*accumulator / tick) as f32;
+        *looping = false;
+        ShouldRun::No
+    }
+}
+
+// Mit Escape geht es aus dem laufenden Spiel zurück ins Hauptmenü.
+fn back_to_menu(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut transition: ResMut<transition::ScreenTransition>,
+    mode: Res<mode::GameMode>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        // Die Taste wird zurückgesetzt, sonst würde close_on_esc im Menü im selben Frame das Fenster schließen.
+        keyboard_input.reset(KeyCode::Escape);
+        transition.to(mode.exit_state());
+    }
 }
 
+// Die Szene, die in allen Zuständen sichtbar bleibt: Boden, Licht und Kamera. Ihre Maße kommen aus der Arena.
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut images: ResMut<Assets<Image>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut asset_server: Res<AssetServer>,
+    arena: Res<Arena>,
 ) {
-    let debug_material = materials.add(StandardMaterial {
-        base_color_texture: Some(images.add(uv_debug_texture())),
-        ..default()
-    });
-// Das hier ist die Bodenebene
+// Das hier ist die Bodenebene, eine Ebene der Größe 1, die auf die Arena skaliert wird.
     
-    commands.spawn(PbrBundle {
-        mesh: meshes.add(shape::Plane { size: 50. }.into()),
-        material: materials.add(Color::SILVER.into()),
-        transform: Transform::from_xyz(0.0, -2.0, 0.0),
-        ..default()
-    });
-
-    let paddle_y = BOTTOM_WALL + GAP_BETWEEN_PADDLE_AND_FLOOR;
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Plane { size: 1. }.into()),
+            material: materials.add(Color::SILVER.into()),
+            transform: Transform::from_xyz(0.0, FLOOR_HEIGHT, 0.0).with_scale(Vec3::splat(arena.floor_size())),
+            ..default()
+        },
+        Floor,
+    ));
 
     // Lichtquelle
-    commands.spawn(PointLightBundle {
-        point_light: PointLight {
-            intensity: 9000.0,
-            range: 100.,
-            shadows_enabled: true,
+    commands.spawn((
+        PointLightBundle {
+            point_light: PointLight {
+                intensity: 9000.0,
+                range: 10. * arena.extent(),
+                shadows_enabled: true,
+                ..default()
+            },
+            transform: Transform::from_translation(arena.light_position()),
             ..default()
         },
-        transform: Transform::from_xyz(10.0, 25.0, 10.0),
-        ..default()
-    });
+        MainLight,
+    ));
     
     // Kamera-Objekt
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 10., 20.0).looking_at(Vec3::new(0., 5., 0.), Vec3::Y),
-        ..default()
-    });
-    
-    
-    // Der Ball ist auch nur ein PBR, mit den Eigenschaften Velocity und Ball. Dies wird in unteren System (Funktionen) verwendet. 
     commands.spawn((
-        PbrBundle {
-            mesh: meshes.add(shape::UVSphere::default().into()).into(),
-            material: materials.add(StandardMaterial {
-                base_color: Color::RED,
-                ..default()
+        Camera3dBundle {
+            transform: arena.camera_transform(),
+            ..default()
+        },
+        MainCamera,
+    ));
+}
 
-            }),
-            transform: Transform::from_translation(BALL_STARTING_POSITION).with_scale(BALL_SIZE)
+// Ändert sich die Arena, etwa beim Start eines Levels mit eigenen Maßen, werden Boden und Licht neu ausgerichtet.
+// Die Kamera folgt in framing.rs.
+#[allow(clippy::type_complexity)]
+fn frame_arena(
+    arena: Res<Arena>,
+    mut floor_query: Query<&mut Transform, With<Floor>>,
+    mut light_query: Query<(&mut Transform, &mut PointLight), (With<MainLight>, Without<Floor>)>,
+) {
+    if !arena.is_changed() {
+        return;
+    }
+    for mut transform in &mut floor_query {
+        transform.scale = Vec3::splat(arena.floor_size());
+    }
+    for (mut transform, mut light) in &mut light_query {
+        transform.translation = arena.light_position();
+        light.range = 10. * arena.extent();
+    }
+}
+
+// Baut beim Betreten von GameState::Playing das aktuelle Level der Kampagne auf.
+#[allow(clippy::too_many_arguments)]
+fn spawn_level(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    theme_materials: Res<theme::ThemeMaterials>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    (mutators, mode): (Res<Mutators>, Res<mode::GameMode>),
+    mut lives: ResMut<Lives>,
+    mut level_in_progress: ResMut<LevelInProgress>,
+    upgrades: Res<shop::Upgrades>,
+    run_modifiers: Res<run::RunModifiers>,
+    cosmetics: Res<cosmetics::CosmeticsConfig>,
+    assists: Res<assist::Assists>,
+    mut arena: ResMut<Arena>,
+    mut brick_grid: ResMut<grid::BrickGrid>,
+    (config, asset_server): (Res<config::GameConfig>, Res<AssetServer>),
+) {
+    let level = levels.get(campaign.current_handle()).expect("Level wurde vor dem Start geladen");
+    // Die Arena kommt aus dem Level, der Modifikator kann sie verkleinern oder vergrößern.
+    // Sie wird direkt gesetzt, damit die folgenden Systeme beim Betreten schon die neuen Maße sehen.
+    let mut level_arena = level.arena.unwrap_or_default();
+    level_arena.camera = level.camera.and_then(|camera| camera.fit(&level_arena));
+    *arena = level_arena.scaled(mutators.arena_size.factor());
+    lives.0 = upgrades.starting_lives(&config);
+    level_in_progress.0 = false;
+
+    // Die Schwerkraft gilt, wenn das Level sie vorgibt oder sie im Menü als Modifikator gewählt wurde.
+    let gravity = if level.gravity || mutators.gravity { Vec3::new(0.0, -config.gravity, 0.0) } else { Vec3::ZERO };
+
+    let ball_speed = config.ball_speed * level.ball_speed * run_modifiers.ball_speed_factor() * assists.ball_speed_factor()
+        * mutators.challenges.ball_speed_factor();
+    commands.insert_resource(BallSpeed(ball_speed));
+    // Der Ball liegt zu Beginn auf dem Paddle und fliegt erst los, wenn der Spieler ihn abschießt.
+    let ball_size = config.ball_size * run_modifiers.ball_size_factor();
+    let paddle_start = Transform::from_xyz(0.0, PADDLE_Y, 0.0).with_scale(config.paddle_size);
+
+    // Der Ball ist auch nur ein PBR, mit den Eigenschaften Kinematic und Ball. Dies wird in unteren System (Funktionen) verwendet. 
+    let mut ball = commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(cosmetics.ball.mesh()),
+            material: materials.add(cosmetics.ball.material()),
+            transform: Transform::from_translation(launch::held_position(&paddle_start, ball_size)).with_scale(ball_size)
                 .with_rotation(Quat::from_rotation_x(-PI / 4.)),
             ..default()
         },
         Ball,
-        Velocity(INITIAL_BALL_DIRECTION.normalize()*BALL_SPEED),
+        collision::CollisionLayers::from(collision::Layer::Ball),
+        Kinematic {
+            velocity: Vec3::ZERO,
+            gravity,
+            bounciness: 0.0,
+        },
+        spin::AngularVelocity::default(),
+        launch::HeldBall::default(),
+        // Wie der Ball abprallt, kommt aus seiner Datei. Bis sie geladen ist, gelten die Standardwerte.
+        asset_server.load::<ball_physics::BallPhysics, _>(ball_physics::BallPhysics::path(
+            level.ball.as_deref().unwrap_or(ball_physics::DEFAULT_BALL),
+        )),
+        LevelEntity,
     ));
+    if run_modifiers.fire_start_duration() > 0.0 {
+        let duration = run_modifiers.fire_start_duration();
+        ball.insert(Piercing(Timer::from_seconds(duration, TimerMode::Once), powerup::PowerUpKind::Fireball));
+    }
     
-    // Wände nutzen ein einfaches Material, welches einfach eine lilane Farbe bekommen.
-    let wall_material = materials.add(StandardMaterial{
-        base_color: Color::PURPLE,
-        ..default()
-    });
+    // Wände nutzen ein einfaches Material, dessen Farbe aus dem Theme kommt.
+    let wall_material = theme_materials.walls.clone();
 
     // Als Grundobjekt (Mesh) der Wände nutze ich Würfel, welche so skaliert werden, dass sie die Form von länglichen Quadern annehmen
-    let wall_mesh: Handle<Mesh> = meshes.add(shape::Cube::default().into()).into();
+    let wall_mesh: Handle<Mesh> = meshes.add(shape::Cube::default().into());
 
     // Auf Grund von Rusts Borrow- / Ownershipsystem wird das mesh und Material immer wieder gecloned, da es sonst nicht mehr im Memory wäre.
-    commands.spawn(WallBundle::new(WallLocation::Left, wall_material.clone(), wall_mesh.clone()));
-    commands.spawn(WallBundle::new(WallLocation::Right, wall_material.clone(), wall_mesh.clone()));
-    commands.spawn(WallBundle::new(WallLocation::Bottom, wall_material.clone(), wall_mesh.clone()));
-    commands.spawn(WallBundle::new(WallLocation::Top, wall_material.clone(), wall_mesh.clone()));
+    commands.spawn((WallBundle::new(WallLocation::Left, &arena, wall_material.clone(), wall_mesh.clone()), LevelEntity));
+    commands.spawn((WallBundle::new(WallLocation::Right, &arena, wall_material.clone(), wall_mesh.clone()), LevelEntity));
+    // Die untere Wand ist nur noch zu sehen und hat keinen Collider mehr, der Ball fällt durch und geht verloren.
+    commands.spawn((WallBundle::new(WallLocation::Bottom, &arena, wall_material.clone(), wall_mesh.clone()).pbr_bundle, LevelEntity));
+    coop::spawn_ceiling(&mut commands, &arena, *mode, wall_material.clone(), wall_mesh.clone());
 
 
+    let paddle_width =
+        upgrades.paddle_width(&config) * assists.paddle_width_factor() * mutators.challenges.paddle_width_factor();
     // Das Paddle ist auch nur ein skalierter Würfel mit den Eigenschaften 'Collider' und 'Paddle', welche von den Systemen zum Querien verwendet werden.
     commands.spawn((
         PbrBundle {
-            mesh: meshes.add(shape::Cube::default().into()).into(),
-            material: materials.add(StandardMaterial {
-                base_color: Color::BLUE,
-                ..default()
-            }),
-            transform: Transform::from_translation(Vec3::new(0., 2.0, 0.)).with_scale(Vec3::new(1.0, 0.2, 1.0)),
+            mesh: meshes.add(shape::Cube::default().into()),
+            material: materials.add(cosmetics.paddle.material()),
+            transform: Transform::from_translation(Vec3::new(0., PADDLE_Y, 0.)).with_scale(Vec3::new(paddle_width, config.paddle_size.y, config.paddle_size.z)),
             ..default()
         },
         Paddle,
+        collision::CollisionLayers::from(collision::Layer::Paddle),
+        tilt::PaddleTilt::default(),
+        spin::PaddleMotion::default(),
         Collider,
+        ability::Ability::<dash::Dash>::default(),
+        LevelEntity,
         ));
 
-    // Scoreboard
-    commands.spawn(
-        TextBundle::from_sections([
-            TextSection::new(
-                "Score: ",
-                TextStyle {
-                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
-                    font_size: SCOREBOARD_FONT_SIZE,
-                    color: SCORE_COLOR,
-                },
-            ),
-            TextSection::from_style(TextStyle {
-                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
-                font_size: SCOREBOARD_FONT_SIZE,
-                color: SCORE_COLOR,
-            }),
-        ])
-            .with_style(Style {
-                position_type: PositionType::Absolute,
-                position: UiRect {
-                    top: SCOREBOARD_TEXT_PADDING,
-                    left: SCOREBOARD_TEXT_PADDING,
-                    ..default()
-                },
-                ..default()
-            }),
-    );
-    // Da die Bricks anhand der Konstanten generiert werden, kann es bei falschen Parametern passieren, dass sie eine Größe < 0 haben.
-    assert!(BRICK_SIZE.x > 0.0);
-    assert!(BRICK_SIZE.y > 0.0);
-    assert!(BRICK_SIZE.z > 0.0);
-
-    let total_width_of_bricks = (RIGHT_WALL - LEFT_WALL) - 2. * GAP_BETWEEN_BRICKS_AND_SIDES;
-    let bottom_edge_of_bricks = paddle_y + GAP_BETWEEN_PADDLE_AND_BRICKS;
-    let total_height_of_bricks = TOP_WALL - bottom_edge_of_bricks - GAP_BETWEEN_BRICKS_AND_CEILING;
-
-    assert!(total_width_of_bricks > 0.0);
-    assert!(total_height_of_bricks > 0.0);
-
-    // Ich berechne wie viele Reihen und Spalten an Bricks es geben kann
-    let n_columns = (total_width_of_bricks / (BRICK_SIZE.x + GAP_BETWEEN_BRICKS)).floor() as usize;
-    let n_rows = (total_height_of_bricks / (BRICK_SIZE.y + GAP_BETWEEN_BRICKS)).floor() as usize;
-    let n_vertical_gaps = n_columns - 1;
-
-    // Da es Spalten und Reihen nur als ganze Zahl geben, wird das hier sichergestellt.
-    let center_of_bricks = 0.0;
-    let left_edge_of_bricks = center_of_bricks
-        - (n_columns as f32 / 2.0 * BRICK_SIZE.x)
-        - n_vertical_gaps as f32 / 2.0 * GAP_BETWEEN_BRICKS;
-
-    let offset_x = left_edge_of_bricks + BRICK_SIZE.x / 2.;
-    let offset_y = bottom_edge_of_bricks + BRICK_SIZE.y / 2.;
-
-    // Hier werden die Bricks anhand der vorher berechneten Werte generiert.
-    for row in 0..n_rows {
-        for column in 0..n_columns {
-            let brick_position = Vec2::new(
-                offset_x + column as f32 * (BRICK_SIZE.x + GAP_BETWEEN_BRICKS),
-                offset_y + row as f32 * (BRICK_SIZE.y + GAP_BETWEEN_BRICKS),
-            );
+    // Die Bricks kommen aus dem Raster und dem Layout der Level-Datei. Gespawnt werden sie von grid::sync_bricks.
+    *brick_grid = grid::BrickGrid::from_level(level, &GridGeometry::new(&arena));
+}
 
-            // brick
-            commands.spawn((
-                PbrBundle {
-                    mesh: meshes.add(shape::Cube::default().into()).into(),
-                    material: debug_material.clone(),
-                    transform: Transform {
-                        translation: brick_position.extend(0.0),
-                        scale: Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Brick,
-                Collider,
-            ));
-        }
+fn despawn_level(mut commands: Commands, query: Query<Entity, With<LevelEntity>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
-// Alle Entities mit der Komponente 'Paddle' sollen sich um ihre eigene Y-Achse drehen.
-fn rotate(mut query: Query<&mut Transform, With<Paddle>>, time: Res<Time>) {
+// Alle Entities mit der Komponente 'Paddle' sollen sich um ihre eigene Y-Achse drehen. Lokal, damit ein gekipptes Paddle gekippt bleibt.
+fn rotate(
+    mut query: Query<&mut Transform, With<Paddle>>,
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    settings: Res<accessibility::AccessibilitySettings>,
+) {
+    if !settings.allows_motion() {
+        return;
+    }
     for mut transform in &mut query {
-        transform.rotate_y(time.delta_seconds() / 2.);
+        transform.rotate_local_y(time.delta_seconds() * time_scale.factor / 2.);
     }
 }
 
-fn update_scoreboard(scoreboard: Res<Scoreboard>, mut query: Query<&mut Text>) {
-    let mut text = query.single_mut();
-    text.sections[1].value = scoreboard.score.to_string();
-}
-
-// Alle Objekte mit der Komponente 'Paddle' können mit dem Keyboard bewegt werden.
-fn move_object(mut query: Query<&mut Transform, With<Paddle>>, keyboard_input: Res<Input<KeyCode>>){
-    let mut direction = 0.0;
-    let mut object_transform = query.single_mut();
-    if keyboard_input.pressed(KeyCode::Up) {
-        direction += 1.0;
-    }
-    if keyboard_input.pressed(KeyCode::Down){
-        direction -= 1.0;
+// Alle Objekte mit der Komponente 'Paddle' können mit Tastatur oder Gamepad bewegt werden.
+fn move_object(
+    mut query: Query<(&mut Transform, Option<&hazard::ReversedControls>), With<Paddle>>,
+    held_query: Query<(), With<launch::HeldBall>>,
+    controls: Res<controls::ActiveControls>,
+    time_scale: Res<TimeScale>,
+    arena: Res<Arena>,
+    config: Res<config::GameConfig>,
+    mutators: Res<Mutators>,
+) {
+    let Ok((mut object_transform, reversed)) = query.get_single_mut() else { return };
+    // Solange der Ball auf dem Paddle liegt, wird mit dieser Eingabe gezielt, siehe launch.
+    if !held_query.is_empty() {
+        return;
     }
+    let direction =
+        controls.direction() * hazard::control_sign(reversed) * mutators.challenges.control_sign();
 
-    let new_object_positiion = object_transform.translation.x + direction * PADDLE_SPEED * TIME_STEP;
+    let new_object_positiion = object_transform.translation.x + direction * config.paddle_speed * time_scale.step();
 
-    let left_bound = -5.0 + WALL_THICKNESS / 2.0 + PADDLE_SIZE.x / 2.0 + PADDLE_PADDING;
-    let right_bound = 5.0 - WALL_THICKNESS / 2.0 - PADDLE_SIZE.x / 2.0 - PADDLE_PADDING;
-
-    object_transform.translation.x = new_object_positiion.clamp(left_bound, right_bound);
+    object_transform.translation.x = arena.clamp_paddle_x(new_object_positiion, object_transform.scale.x);
 }
 
-// Diese Textur habe ich als Beispiel aus einem anderen Beispiel gefunden, welche das Verhalten von 3D Objekten gezeigt habe, ich fande es passend und habe es übernommen.
-fn uv_debug_texture() -> Image {
-    const TEXTURE_SIZE: usize = 8;
-
-    let mut palette: [u8; 32] = [
-        255, 102, 159, 255, 255, 159, 102, 255, 236, 255, 102, 255, 121, 255, 102, 255, 102, 255,
-        198, 255, 102, 198, 255, 255, 121, 102, 255, 255, 236, 102, 255, 255,
-    ];
+// Wir verschieben alle beweglichen Objekte einfach anhand ihrer Velocity und Framerate.
+// Ein Ball, der noch auf dem Paddle liegt, bleibt dort liegen.
+fn apply_velocity(
+    mut query: Query<(&mut Transform, &mut Kinematic), Without<launch::HeldBall>>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = time_scale.step();
+    for (mut transform, mut body) in &mut query {
+        let body = &mut *body;
+        // Mit Schwerkraft wird das Objekt vor der Bewegung nach unten beschleunigt.
+        body.velocity += body.gravity * step;
+        transform.translation += body.velocity * step;
 
-    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
-    for y in 0..TEXTURE_SIZE {
-        let offset = TEXTURE_SIZE * y * 4;
-        texture_data[offset..(offset + TEXTURE_SIZE * 4)].copy_from_slice(&palette);
-        palette.rotate_right(4);
+        // Springende Objekte prallen vom Boden ab und verlieren dabei an Schwung.
+        let floor = FLOOR_HEIGHT + transform.scale.y / 2.0;
+        if body.bounciness > 0.0 && transform.translation.y < floor && body.velocity.y < 0.0 {
+            transform.translation.y = floor;
+            body.velocity.y = -body.velocity.y;
+            body.velocity *= body.bounciness;
+        }
     }
-
-    Image::new_fill(
-        Extent3d {
-            width: TEXTURE_SIZE as u32,
-            height: TEXTURE_SIZE as u32,
-            depth_or_array_layers: 1,
-        },
-        TextureDimension::D2,
-        &texture_data,
-        TextureFormat::Rgba8UnormSrgb,
-    )
 }
-// Wir verschieben das Objekte einfach anhand der Velocity und Framerate.
-fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>) {
-    for (mut transform, velocity) in &mut query {
-        transform.translation.x += velocity.x * TIME_STEP;
-        transform.translation.y += velocity.y * TIME_STEP;
-        transform.translation.z += velocity.z * TIME_STEP;
-    }
+
+// Punkte für einen zerstörten Brick nach Balancing, Modifikatoren des Durchlaufs und Herausforderungen.
+fn brick_points(
+    kind: level::BrickKind,
+    config: &config::GameConfig,
+    run_modifiers: &run::RunModifiers,
+    mutators: &Mutators,
+) -> usize {
+    let points = config.brick_score * kind.score() * run_modifiers.score_per_brick();
+    (points as f32 * mutators.challenges.score_factor()).round() as usize
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn check_for_collision(
-    mut commands: Commands,
     mut scoreboard: ResMut<Scoreboard>,
-    mut ball_query: Query<(&mut Velocity, &Transform), With<Ball>>,
-    collider_query: Query<(Entity, &Transform, Option<&Brick>), With<Collider>>,
+    run_modifiers: Res<run::RunModifiers>,
+    mut ball_query: Query<
+        (
+            &mut Kinematic,
+            &mut Transform,
+            &collision::CollisionLayers,
+            Option<&Piercing>,
+            Option<&ball_size::BallResize>,
+            Option<&Handle<ball_physics::BallPhysics>>,
+            Option<&hazard::SpeedUp>,
+        ),
+        (With<Ball>, Without<launch::HeldBall>),
+    >,
+    collider_query: Query<
+        (
+            Entity,
+            &Transform,
+            &collision::CollisionLayers,
+            Option<&Brick>,
+            Option<&Paddle>,
+            Option<&tilt::PaddleTilt>,
+            Option<&spin::PaddleMotion>,
+            Option<&surface::Surface>,
+            Option<&WallLocation>,
+        ),
+        (With<Collider>, Without<Ball>),
+    >,
+    brick_materials: Query<&Handle<StandardMaterial>, With<Brick>>,
+    mut brick_grid: ResMut<grid::BrickGrid>,
     mut collision_events: EventWriter<CollisionEvent>,
+    mut paddle_hit_events: EventWriter<PaddleHit>,
+    mut brick_destroyed_events: EventWriter<BrickDestroyed>,
+    mut brick_hit_events: EventWriter<BrickHit>,
+    mut diagnostics: ResMut<Diagnostics>,
+    config: Res<config::GameConfig>,
+    mutators: Res<Mutators>,
+    ball_speed: Res<BallSpeed>,
+    ball_physics: Res<Assets<ball_physics::BallPhysics>>,
 ) {
-    // Da es nur einen Ball gibt, können wir der Engine sagen, dass wir nur das erste Objekt aus dem 'ball_query' haben wollen
-    let (mut ball_velocity, ball_transform)  = ball_query.single_mut();
-
-    for (collider_entity, transform, maybe_brick) in &collider_query {
-        let collision = collide(
-            ball_transform.translation,
-            ball_transform.scale.truncate(),
-            transform.translation,
-            transform.scale.truncate(),
-        );
-        // Some() lässt sich wie 'Any' in Python lesen,  ~ collision != null
-        if let Some(collision) = collision {
-
-            collision_events.send_default();
-
-            // Falls das Objekt mit dem kollidiert wird ein Brick ist, soll das Scoreboard geupdated werden und der Brick entfernt werden
-            if maybe_brick.is_some() {
-
-                scoreboard.score += 1;
-                commands.entity(collider_entity).despawn();
+    // Im Spiel gibt es nur einen Ball, im Lasttest aber viele. Jeder wird für sich gegen alle Collider geprüft.
+    // Für die Diagnose wird mitgezählt, wie viele Objekte in diesem Schritt geprüft werden.
+    let mut checks = 0;
+    for (mut ball, mut ball_transform, ball_layers, piercing, resize, physics, speed_up) in &mut ball_query {
+        let physics = ball_physics::resolve(physics, &ball_physics);
+        // Ein beschleunigter Ball misst sich an der beschleunigten Geschwindigkeit.
+        let speed = ball_speed.0 * speed_up.map_or(1.0, |_| hazard::SPEED_UP_FACTOR);
+        for (collider_entity, transform, layers, maybe_brick, paddle, tilt, motion, surface, wall) in &collider_query {
+            // Was nicht auf einer passenden Ebene liegt, wird gar nicht erst geprüft.
+            if !ball_layers.interacts_with(layers) {
+                continue;
             }
+            checks += 1;
+            let collision = collide(
+                ball_transform.translation,
+                ball_transform.scale.truncate(),
+                transform.translation,
+                transform.scale.truncate(),
+            );
+            // Some() lässt sich wie 'Any' in Python lesen,  ~ collision != null
+            if let Some(collision) = collision {
+                // Ein Brick, dessen Zelle schon leer ist, wurde gerade zerstört und steht nur bis zum nächsten Abgleich noch da.
+                if maybe_brick.is_some_and(|brick| brick_grid.get(brick.row, brick.column).is_none()) {
+                    continue;
+                }
 
-            // standardmäßig soll die Richtung nicht verändert werden.
-            let mut reflect_x = false;
-            let mut reflect_y = false;
+                collision_events.send(CollisionEvent {
+                    position: ball_transform.translation,
+                    wall: wall.is_some(),
+                });
+                // Berührt der Ball das Paddle, beginnt die Kombo von vorn.
+                if paddle.is_some() {
+                    scoreboard.combo = 0;
+                    paddle_hit_events.send_default();
+                }
 
+                // Falls das Objekt mit dem kollidiert wird ein Brick ist, soll das Scoreboard geupdated werden und der Brick entfernt werden
+                // Entfernt wird der Brick dabei nur im Raster, seine Entity verschwindet beim nächsten Abgleich.
+                if let Some(brick) = maybe_brick {
+                    brick_hit_events.send(BrickHit {
+                        position: transform.translation,
+                        size: transform.scale,
+                        surface: surface.copied(),
+                    });
+                    if let Some(cell) = brick_grid.hit(brick.row, brick.column) {
+                        // Der kleine Ball bringt mehr Punkte.
+                        let factor = resize.map_or(1, |resize| resize.score_factor());
+                        scoreboard.score += brick_points(cell.kind, &config, &run_modifiers, &mutators) * factor;
+                        scoreboard.combo += 1;
+                        if let Ok(material) = brick_materials.get(collider_entity) {
+                            brick_destroyed_events.send(BrickDestroyed {
+                                position: transform.translation,
+                                material: material.clone(),
+                                kind: cell.kind,
+                            });
+                        }
+                        // Ein durchschlagender Ball fliegt einfach weiter.
+                        if piercing.is_some() {
+                            continue;
+                        }
+                    }
+                }
 
-            // match ist eine Struktur in Rust, bei welcher man eine Funktion anhand des current value einer enum ausführen kann
-            // Wir stellen sicher, dass der Ball von innerhalb des Spiels kommt/
-            match collision {
-                Collision::Left => reflect_x = ball_velocity.x > 0.0,
-                Collision::Right => reflect_x = ball_velocity.x < 0.0,
-                Collision::Top => reflect_y = ball_velocity.y < 0.0,
-                Collision::Bottom => reflect_y = ball_velocity.y > 0.0,
-                Collision::Inside => {  }
-            }
-            // Falls true, wird die x-Velocity gespiegelt
-            if reflect_x {
-                ball_velocity.x = -ball_velocity.x;
-            }
+                // Der Ball wird an der Normalen der Kontaktstelle gespiegelt. An einer Kante ist das eine Achse,
+                // an einer Ecke zeigt die Normale schräg von der Ecke weg, sodass sich beide Richtungen ändern.
+                // Wir stellen sicher, dass der Ball von außen kommt, sonst würde er im Objekt hin und her springen.
+                let Some(normal) = contact_normal(&ball_transform, transform, &collision) else { continue };
+                // Schiebt sich das Paddle seitlich in einen langsamen Ball, wird er relativ zum Paddle gespiegelt und
+                // bekommt so dessen Bewegung mit. Von oben ändert das nichts, weil die Normale dort senkrecht steht.
+                let carried = motion.map_or(Vec3::ZERO, |motion| Vec3::X * motion.velocity() * PADDLE_CAROM_TRANSFER);
+                let approach = (ball.velocity - carried).dot(normal);
+                let bounced = if let (Some(tilt), Collision::Top) = (tilt, &collision) {
+                    // Von oben auf ein gekipptes Paddle prallt der Ball an dessen schräger Oberseite ab.
+                    let falling = ball.velocity.y < 0.0;
+                    if falling {
+                        ball.velocity = tilt.reflect(ball.velocity);
+                    }
+                    falling
+                } else if approach < 0.0 {
+                    ball.velocity -= 2.0 * approach * normal;
+                    true
+                } else {
+                    false
+                };
+                if bounced {
+                    // Der Ball selbst und das Material des Bricks bremsen ihn oder beschleunigen ihn.
+                    let surface_restitution = surface.map_or(1.0, |surface| surface.restitution());
+                    ball.velocity = physics.respond(ball.velocity, surface_restitution, speed);
+                    // Manche Bälle werden mit jedem Treffer auf dem Paddle schneller.
+                    if paddle.is_some() {
+                        ball.velocity = physics.ramp(ball.velocity, speed);
+                    }
+                }
 
-            if reflect_y {
-                ball_velocity.y = -ball_velocity.y;
+                // Danach wird der Ball aus dem Objekt geschoben. Sonst steckt er im nächsten Schritt noch darin,
+                // löst erneut eine Kollision aus und wird ein zweites Mal gespiegelt.
+                let depth = penetration_depth(&ball_transform, transform, normal);
+                ball_transform.translation += normal * depth;
             }
         }
+    }
+    diagnostics.add_measurement(diagnostics::COLLISION_CHECKS, || checks as f64);
+}
 
+// Die Normale der Stelle, an der der Ball das Objekt berührt. Liegt der Mittelpunkt des Balls neben einer Ecke,
+// zeigt sie von der Ecke zum Mittelpunkt. Steckt der Mittelpunkt schon im Objekt, entscheidet die Seite aus collide.
+fn contact_normal(ball: &Transform, collider: &Transform, collision: &Collision) -> Option<Vec3> {
+    let half_size = collider.scale.truncate() / 2.0;
+    let offset = (ball.translation - collider.translation).truncate();
+    let outside = offset - offset.clamp(-half_size, half_size);
+    if outside.length_squared() > f32::EPSILON {
+        return Some(outside.normalize().extend(0.0));
+    }
+    match collision {
+        Collision::Left => Some(Vec3::NEG_X),
+        Collision::Right => Some(Vec3::X),
+        Collision::Top => Some(Vec3::Y),
+        Collision::Bottom => Some(Vec3::NEG_Y),
+        Collision::Inside => None,
+    }
+}
 
+// Wie weit der Ball entlang der Normalen verschoben werden muss, damit er das Objekt nicht mehr überlappt.
+// Bei einer schrägen Normalen reicht es, auf einer der beiden Achsen frei zu sein.
+fn penetration_depth(ball: &Transform, collider: &Transform, normal: Vec3) -> f32 {
+    let offset = (ball.translation - collider.translation).truncate().abs();
+    let overlap = (ball.scale.truncate() + collider.scale.truncate()) / 2.0 - offset;
+    [(overlap.x, normal.x), (overlap.y, normal.y)]
+        .into_iter()
+        .filter(|(_, direction)| direction.abs() > f32::EPSILON)
+        .map(|(overlap, direction)| overlap.max(0.0) / direction.abs())
+        .fold(f32::INFINITY, f32::min)
+}
+
+// Sind alle Bricks zerstört, gibt es die Abrechnung und eine Belohnung, dann geht es in den Shop und danach ins nächste Level.
+// Nach dem letzten Level der Kampagne ist der Durchlauf geschafft und es geht nach der Abrechnung zurück ins Hauptmenü.
+#[allow(clippy::too_many_arguments)]
+fn check_level_cleared(
+    brick_grid: Res<grid::BrickGrid>,
+    mut level_in_progress: ResMut<LevelInProgress>,
+    mut campaign: ResMut<Campaign>,
+    mut state: ResMut<State<GameState>>,
+    mut transition: ResMut<transition::ScreenTransition>,
+    mut cleared_events: EventWriter<LevelCleared>,
+    mode: Res<mode::GameMode>,
+    mut results: ResMut<results::LevelResults>,
+    replay: Res<snapshot::SnapshotReplay>,
+) {
+    if !brick_grid.is_empty() {
+        level_in_progress.0 = true;
+        return;
+    }
+    if !level_in_progress.0 {
+        return;
+    }
+    level_in_progress.0 = false;
+    // Ein Probespiel aus dem Editor, ein eigenes Level und ein Bonuslevel zählen nicht als geschafftes Level,
+    // es geht einfach dorthin zurück, wo sie gestartet wurden.
+    if matches!(*mode, mode::GameMode::TestPlay | mode::GameMode::UserLevel | mode::GameMode::Bonus) {
+        transition.to(mode.exit_state());
+        return;
+    }
+    // Ein nachgespielter Schnappschuss zählt in keinem Modus, danach ist er vorbei.
+    if replay.0 {
+        transition.to(GameState::Menu);
+        return;
+    }
+    let last = campaign.current + 1 >= campaign.levels.len();
+    // Im Zeitangriff, im Koop- und im Überlebensmodus geht es ohne Pause mit dem nächsten Level weiter, nach dem
+    // letzten wieder von vorn.
+    if matches!(*mode, mode::GameMode::TimeAttack | mode::GameMode::Coop | mode::GameMode::Survival) {
+        // Steht schon ein anderer Wechsel an, etwa der Log-Inspektor oder das Ende im Überlebensmodus, geht der
+        // vor. Läuft das Level danach noch, wird es im nächsten Frame wieder versucht.
+        if state.restart().is_err() {
+            level_in_progress.0 = true;
+            return;
         }
-}
\ No newline at end of file
+        cleared_events.send(LevelCleared { index: campaign.current, last: false });
+        campaign.current = (campaign.current + 1) % campaign.levels.len();
+        return;
+    }
+    // Tutorial und Demo zählen nicht als geschafftes Level.
+    if matches!(*mode, mode::GameMode::Tutorial | mode::GameMode::Demo) {
+        transition.to(GameState::Menu);
+        return;
+    }
+    // Im Turnier endet der Zug mit dem Level, danach kommt der Zwischenstand und der nächste Spieler.
+    if *mode == mode::GameMode::Tournament {
+        cleared_events.send(LevelCleared { index: campaign.current, last: false });
+        transition.to(GameState::Standings);
+        return;
+    }
+    // Die tägliche Herausforderung besteht aus einem einzigen Level. Vor dem Weitergehen kommt die Abrechnung.
+    if *mode == mode::GameMode::Daily {
+        cleared_events.send(LevelCleared { index: campaign.current, last: false });
+        results.show(campaign.current, GameState::Menu);
+        transition.to(GameState::Results);
+        return;
+    }
+    cleared_events.send(LevelCleared { index: campaign.current, last });
+    results.show(campaign.current, if last { GameState::Menu } else { GameState::Reward });
+    if !last {
+        campaign.current += 1;
+    }
+    transition.to(GameState::Results);
+}
+
+// Fällt der Ball unter die untere Wand, kostet das ein Leben. Solange noch Leben übrig sind, startet der Ball neu,
+// sonst ist das Spiel vorbei und es geht zurück ins Hauptmenü. Knapp unter dem Paddle kann ihn vorher einmal
+// pro Level noch ein Stupser retten.
+#[allow(clippy::too_many_arguments)]
+fn check_ball_lost(
+    mut commands: Commands,
+    mut ball_query: Query<(Entity, &mut Transform, &mut Kinematic), With<Ball>>,
+    paddle_query: Query<&Transform, (With<Paddle>, Without<Ball>)>,
+    mut lives: ResMut<Lives>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mode: Res<mode::GameMode>,
+    assists: Res<assist::Assists>,
+    mut transition: ResMut<transition::ScreenTransition>,
+    mut ball_lost_events: EventWriter<BallLost>,
+    arena: Res<Arena>,
+    config: Res<config::GameConfig>,
+    mut rescue: ResMut<rescue::RescueCharge>,
+    mut rescued_events: EventWriter<rescue::BallRescued>,
+    mut floor: ResMut<floor::FloorMap>,
+    mut floor_bounces: EventWriter<floor::FloorBounce>,
+) {
+    let Ok((ball, mut transform, mut body)) = ball_query.get_single_mut() else { return };
+    let paddle_bottom = PADDLE_Y - config.paddle_size.y / 2.0;
+    let depth = paddle_bottom - transform.translation.y;
+    let falling = -body.velocity.y;
+    // Was unter dem Ball liegt, entscheidet, wo er verloren geht. Ohne Zonen im Level ist der Boden überall offen.
+    let surface = floor.surface_at(transform.translation.x);
+    let slow = falling > 0.0 && falling < config.rescue_max_fall_speed;
+    if rescue.available && slow && depth > 0.0 && depth < config.rescue_margin && surface != floor::FloorSurface::Lava {
+        rescue.available = false;
+        body.velocity.y = falling;
+        transform.translation.y = paddle_bottom;
+        rescued_events.send(rescue::BallRescued { position: transform.translation });
+        return;
+    }
+    let floor_height = arena.bottom() + arena.wall_thickness / 2.0;
+    if let floor::FloorSurface::Bumper(zone) = surface {
+        if transform.translation.y <= floor_height && falling > 0.0 {
+            floor.consume_bumper(zone);
+            body.velocity.y = falling;
+            transform.translation.y = floor_height;
+            floor_bounces.send(floor::FloorBounce);
+            return;
+        }
+    }
+    // Lava verschluckt den Ball schon, sobald er unter das Paddle gerät, dort ist er ohnehin nicht mehr zu halten.
+    // Im Koop-Modus geht der Ball auch verloren, wenn er oben durch die Lücke in der Decke entkommt.
+    let swallowed = surface == floor::FloorSurface::Lava && depth > 0.0;
+    let escaped = *mode == mode::GameMode::Coop && transform.translation.y > coop::escape_height(&arena);
+    if !escaped && !swallowed && transform.translation.y > floor_height {
+        return;
+    }
+    ball_lost_events.send(BallLost { position: transform.translation });
+    scoreboard.combo = 0;
+    // Im Zeitangriff kostet ein verlorener Ball Zeit statt eines Lebens, das zieht das TimeAttackPlugin ab.
+    // Im Tutorial darf man beliebig oft üben, ebenso mit der Hilfe für unendlich viele Leben.
+    let loses_life = !matches!(*mode, mode::GameMode::TimeAttack | mode::GameMode::Tutorial);
+    if loses_life && !assists.is_enabled(assist::Assist::InfiniteLives) {
+        lives.0 = lives.0.saturating_sub(1);
+    }
+    if lives.0 == 0 {
+        transition.to(mode.exit_state());
+        return;
+    }
+    // Der neue Ball liegt wieder auf dem Paddle.
+    if let Ok(paddle) = paddle_query.get_single() {
+        transform.translation = launch::held_position(paddle, transform.scale);
+    }
+    body.velocity = Vec3::ZERO;
+    commands.entity(ball).insert(launch::HeldBall::default());
+}
+
+fn update_ball_material(
+    query: Query<(&Handle<StandardMaterial>, Option<&Piercing>), With<Ball>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cosmetics: Res<cosmetics::CosmeticsConfig>,
+    palette: Res<accessibility::Palette>,
+) {
+    for (handle, piercing) in &query {
+        let color = if piercing.is_some() { palette.piercing_ball() } else { palette.ball(cosmetics.ball.color()) };
+        // get_mut meldet das Material als geändert, deshalb nur bei einem echten Farbwechsel.
+        if materials.get(handle).is_some_and(|material| material.base_color != color) {
+            let material = materials.get_mut(handle).unwrap();
+            if piercing.is_some() {
+                material.base_color = color;
+                material.emissive = color;
+            } else {
+                // Ohne Durchschlag bekommt der Ball wieder das Material seines Skins, in der Farbe der Palette.
+                // Die Textur, an der man den Drall sieht, bleibt erhalten.
+                let texture = material.base_color_texture.take();
+                *material = cosmetics.ball.material();
+                material.base_color = color;
+                material.base_color_texture = texture;
+            }
+        }
+    }
+}
@@ -0,0 +1,688 @@
+use bevy::prelude::*;
+
+use crate::accessibility::{AccessibilitySettings, Palette};
+use crate::assist::{Assist, Assists};
+use crate::challenge::Challenge;
+use crate::cosmetics::{cycle_ball_skin, cycle_paddle_skin, CosmeticsConfig};
+use crate::daily::DailyChallenge;
+use crate::focus::{Activated, Focused};
+use crate::format;
+use crate::graphics::GraphicsSettings;
+use crate::haptics::HapticsSettings;
+use crate::hud::UiSize;
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::progress::MAX_STARS;
+use crate::resume::PendingResume;
+use crate::run::RunModifiers;
+use crate::save::SaveData;
+use crate::tick_rate::TickRate;
+use crate::transition::{ScreenTransition, TransitionStyle};
+use crate::tutorial::TutorialLevel;
+use crate::shop::{Upgrade, Upgrades, Wallet};
+use crate::{GameState, Mutators, Scoreboard};
+
+const MENU_FONT_SIZE: f32 = 36.0;
+const TITLE_FONT_SIZE: f32 = 64.0;
+const MENU_TEXT_COLOR: Color = Color::rgb(0.95, 0.95, 0.95);
+const NORMAL_BUTTON: Color = Color::rgb(0.35, 0.1, 0.45);
+const HOVERED_BUTTON: Color = Color::rgb(0.5, 0.2, 0.6);
+const PRESSED_BUTTON: Color = Color::rgb(0.25, 0.05, 0.3);
+
+// Alle Entities eines Menübildschirms bekommen diese Komponente, damit sie beim Verlassen entfernt werden können.
+#[derive(Component)]
+struct MenuScreen;
+
+// Was beim Klick auf einen Button passieren soll.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum MenuButton {
+    Continue,
+    Play,
+    Campaign,
+    LevelSelect,
+    SelectLevel(usize),
+    SelectBonusLevel(usize),
+    TimeAttack,
+    Coop,
+    Survival,
+    Daily,
+    Tutorial,
+    CustomGame,
+    Cosmetics,
+    Accessibility,
+    CycleColorblindMode,
+    ToggleReducedMotion,
+    ToggleCaptions,
+    CycleDangerWarning,
+    ToggleTelemetry,
+    CycleUiSize,
+    ToggleRumble,
+    ToggleImpactLights,
+    ToggleAdaptiveHud,
+    CycleTickRate,
+    CycleTransition,
+    ToggleAssist(Assist),
+    Editor,
+    UserLevels,
+    // Die beiden behandelt sharing.rs, dort ist die Liste der Dateien bekannt.
+    PlayUserLevel(usize),
+    PasteLevelCode,
+    #[cfg(feature = "online")]
+    Leaderboard,
+    CycleBallSkin,
+    CyclePaddleSkin,
+    CycleLevel,
+    ToggleGravity,
+    ToggleChallenge(Challenge),
+    CycleArenaSize,
+    StartCustom,
+    Back,
+    Buy(Upgrade),
+    NextLevel,
+    ChooseModifier(usize),
+    // Behandelt results.rs, dort ist bekannt, wie weit das Hochzählen ist.
+    FinishResults,
+    // Die vier behandelt tournament.rs, dort liegen Aufstellung und Zwischenstand.
+    Tournament,
+    CycleTournamentPlayers,
+    CycleTournamentRounds,
+    NextTurn,
+}
+
+#[derive(Component)]
+struct CoinsText;
+
+#[derive(Component)]
+struct CurseText;
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "online")]
+        app.add_system_set(SystemSet::on_exit(GameState::Leaderboard).with_system(despawn_menu));
+        app.add_system_set(SystemSet::on_enter(GameState::Menu).with_system(spawn_main_menu))
+            .add_system_set(SystemSet::on_exit(GameState::Menu).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::CustomGame).with_system(spawn_custom_game_menu))
+            .add_system_set(
+                SystemSet::on_update(GameState::CustomGame).with_system(update_custom_game_labels),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::CustomGame).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::ModeSelect).with_system(spawn_mode_select))
+            .add_system_set(SystemSet::on_update(GameState::ModeSelect).with_system(update_mode_labels))
+            .add_system_set(SystemSet::on_exit(GameState::ModeSelect).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::LevelSelect).with_system(spawn_level_select))
+            .add_system_set(SystemSet::on_exit(GameState::LevelSelect).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::Cosmetics).with_system(spawn_cosmetics_menu))
+            .add_system_set(SystemSet::on_update(GameState::Cosmetics).with_system(update_cosmetics_labels))
+            .add_system_set(SystemSet::on_exit(GameState::Cosmetics).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::Accessibility).with_system(spawn_accessibility_menu))
+            .add_system_set(SystemSet::on_update(GameState::Accessibility).with_system(update_accessibility_labels))
+            .add_system_set(SystemSet::on_exit(GameState::Accessibility).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::Reward).with_system(spawn_reward))
+            .add_system_set(SystemSet::on_update(GameState::Reward).with_system(update_reward_labels))
+            .add_system_set(SystemSet::on_exit(GameState::Reward).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(GameState::Shop).with_system(spawn_shop))
+            .add_system_set(SystemSet::on_update(GameState::Shop).with_system(update_shop_labels))
+            .add_system_set(SystemSet::on_exit(GameState::Shop).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_exit(GameState::UserLevels).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_exit(GameState::Results).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_exit(GameState::Standings).with_system(despawn_menu))
+            .add_system(menu_action)
+            .add_system(update_button_colors);
+    }
+}
+
+pub fn button_text(text: &str, asset_server: &AssetServer) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+            font_size: MENU_FONT_SIZE,
+            color: MENU_TEXT_COLOR,
+        },
+    )
+}
+
+pub fn spawn_button(parent: &mut ChildBuilder, asset_server: &AssetServer, action: MenuButton, text: &str) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(420.0), Val::Px(60.0)),
+                    margin: UiRect::all(Val::Px(8.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            action,
+        ))
+        .with_children(|button| {
+            button.spawn(button_text(text, asset_server));
+        });
+}
+
+// Der Wurzelknoten eines Menüs füllt das ganze Fenster und zentriert seine Kinder untereinander.
+pub fn spawn_screen(commands: &mut Commands, asset_server: &AssetServer, title: &str, build: impl FnOnce(&mut ChildBuilder)) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            MenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                        font_size: TITLE_FONT_SIZE,
+                        color: NORMAL_BUTTON,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(24.0)),
+                    ..default()
+                }),
+            );
+            build(parent);
+        });
+}
+
+fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>, save: Res<SaveData>) {
+    spawn_screen(&mut commands, &asset_server, "KuerteilCG", |parent| {
+        if save.suspended_run.is_some() {
+            spawn_button(parent, &asset_server, MenuButton::Continue, "Fortsetzen");
+        }
+        spawn_button(parent, &asset_server, MenuButton::Play, "Spielen");
+        spawn_button(parent, &asset_server, MenuButton::CustomGame, "Eigenes Spiel");
+        spawn_button(parent, &asset_server, MenuButton::Cosmetics, "Aussehen");
+        spawn_button(parent, &asset_server, MenuButton::Accessibility, "Barrierefreiheit");
+        spawn_button(parent, &asset_server, MenuButton::Editor, "Editor");
+        spawn_button(parent, &asset_server, MenuButton::UserLevels, "Eigene Level");
+        #[cfg(feature = "online")]
+        spawn_button(parent, &asset_server, MenuButton::Leaderboard, "Bestenliste");
+    });
+}
+
+// Im Menü "Eigenes Spiel" werden Level und Modifikatoren frei gewählt. Die Beschriftungen setzt update_custom_game_labels.
+fn spawn_custom_game_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(&mut commands, &asset_server, "Eigenes Spiel", |parent| {
+        spawn_button(parent, &asset_server, MenuButton::CycleLevel, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleGravity, "");
+        spawn_button(parent, &asset_server, MenuButton::CycleArenaSize, "");
+        for challenge in Challenge::ALL {
+            spawn_button(parent, &asset_server, MenuButton::ToggleChallenge(challenge), "");
+        }
+        spawn_button(parent, &asset_server, MenuButton::StartCustom, "Starten");
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+// Die Modi zeigen ihren bisherigen Rekord, die Beschriftungen setzt update_mode_labels.
+fn spawn_mode_select(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(&mut commands, &asset_server, "Spielmodus", |parent| {
+        spawn_button(parent, &asset_server, MenuButton::Campaign, "");
+        spawn_button(parent, &asset_server, MenuButton::LevelSelect, "Levelauswahl");
+        spawn_button(parent, &asset_server, MenuButton::TimeAttack, "");
+        spawn_button(parent, &asset_server, MenuButton::Daily, "");
+        spawn_button(parent, &asset_server, MenuButton::Coop, "");
+        spawn_button(parent, &asset_server, MenuButton::Survival, "");
+        spawn_button(parent, &asset_server, MenuButton::Tournament, "Turnier (2-4 Spieler)");
+        spawn_button(parent, &asset_server, MenuButton::Tutorial, "Tutorial");
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+fn update_mode_labels(
+    save: Res<SaveData>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let daily = DailyChallenge::today();
+    for (button, children) in &button_query {
+        let (name, best) = match button {
+            MenuButton::Campaign => (GameMode::Campaign.name().to_string(), save.high_scores.best(GameMode::Campaign)),
+            MenuButton::TimeAttack => (GameMode::TimeAttack.name().to_string(), save.high_scores.best(GameMode::TimeAttack)),
+            MenuButton::Coop => (GameMode::Coop.name().to_string(), save.high_scores.best(GameMode::Coop)),
+            MenuButton::Survival => (GameMode::Survival.name().to_string(), save.high_scores.best(GameMode::Survival)),
+            MenuButton::Daily => (
+                format!("{} {}", GameMode::Daily.name(), daily.date_label()),
+                save.daily_scores.best(daily.day),
+            ),
+            _ => continue,
+        };
+        let label = match best {
+            Some(best) => format!("{} (Rekord: {})", name, format::number(best)),
+            None => name,
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+// Jedes Level der Kampagne mit Rekord und Sternen. Gesperrte Level zeigen ihren Namen nicht und lassen sich nicht starten.
+// Darunter die Bonuslevel mit den Sternen aus Nebenzielen, die sie brauchen. Punkte aus einem Bonuslevel, das hierher
+// zurückführt, zählen nicht und werden verworfen.
+fn spawn_level_select(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    save: Res<SaveData>,
+    mut scoreboard: ResMut<Scoreboard>,
+) {
+    scoreboard.score = 0;
+    let objective_stars = save.level_progress.objective_stars();
+    spawn_screen(&mut commands, &asset_server, "Levelauswahl", |parent| {
+        for (index, handle) in campaign.levels.iter().enumerate() {
+            let label = if !save.level_progress.is_unlocked(index) {
+                format!("{}. Gesperrt", index + 1)
+            } else {
+                let level = levels.get(handle);
+                let name = level.map_or("", |level| level.name.as_str());
+                let objectives = level.map_or(0, |level| level.objectives.len());
+                let goals = if objectives == 0 {
+                    String::new()
+                } else {
+                    let met = save.level_progress.record(index).map_or(0, |record| record.objective_stars());
+                    format!(" Ziele: {met}/{objectives}")
+                };
+                let grade = save
+                    .level_progress
+                    .record(index)
+                    .and_then(|record| record.grade)
+                    .map_or(String::new(), |grade| format!(" Note: {}", grade.label()));
+                match save.level_progress.record(index) {
+                    Some(record) => format!(
+                        "{}. {} {}{} (Rekord: {}){grade}{goals}",
+                        index + 1,
+                        name,
+                        "*".repeat(record.stars as usize),
+                        "-".repeat((MAX_STARS - record.stars) as usize),
+                        format::number(record.best_score),
+                    ),
+                    None => format!("{}. {}{goals}", index + 1, name),
+                }
+            };
+            spawn_button(parent, &asset_server, MenuButton::SelectLevel(index), &label);
+        }
+        for (index, (handle, required)) in campaign.bonus_levels.iter().enumerate() {
+            let label = if objective_stars >= *required {
+                format!("Bonus: {}", levels.get(handle).map_or("", |level| level.name.as_str()))
+            } else {
+                format!("Bonus: Gesperrt ({objective_stars}/{required} Sterne)")
+            };
+            spawn_button(parent, &asset_server, MenuButton::SelectBonusLevel(index), &label);
+        }
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+// Gesperrte Skins werden beim Durchschalten übersprungen, bis der passende Erfolg freigeschaltet ist.
+fn spawn_cosmetics_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(&mut commands, &asset_server, "Aussehen", |parent| {
+        spawn_button(parent, &asset_server, MenuButton::CycleBallSkin, "");
+        spawn_button(parent, &asset_server, MenuButton::CyclePaddleSkin, "");
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+fn update_cosmetics_labels(
+    cosmetics: Res<CosmeticsConfig>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children) in &button_query {
+        let label = match button {
+            MenuButton::CycleBallSkin => format!("Ball: {}", cosmetics.ball.name()),
+            MenuButton::CyclePaddleSkin => format!("Paddle: {}", cosmetics.paddle.name()),
+            _ => continue,
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+fn spawn_accessibility_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(&mut commands, &asset_server, "Barrierefreiheit", |parent| {
+        spawn_button(parent, &asset_server, MenuButton::CycleColorblindMode, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleReducedMotion, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleCaptions, "");
+        spawn_button(parent, &asset_server, MenuButton::CycleDangerWarning, "");
+        spawn_button(parent, &asset_server, MenuButton::CycleUiSize, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleRumble, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleImpactLights, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleAdaptiveHud, "");
+        spawn_button(parent, &asset_server, MenuButton::CycleTickRate, "");
+        spawn_button(parent, &asset_server, MenuButton::CycleTransition, "");
+        spawn_button(parent, &asset_server, MenuButton::ToggleTelemetry, "");
+        for assist in Assist::ALL {
+            spawn_button(parent, &asset_server, MenuButton::ToggleAssist(assist), "");
+        }
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_accessibility_labels(
+    palette: Res<Palette>,
+    settings: Res<AccessibilitySettings>,
+    assists: Res<Assists>,
+    ui_size: Res<UiSize>,
+    haptics: Res<HapticsSettings>,
+    graphics: Res<GraphicsSettings>,
+    tick_rate: Res<TickRate>,
+    transition_style: Res<TransitionStyle>,
+    save: Res<SaveData>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children) in &button_query {
+        let label = match button {
+            MenuButton::CycleColorblindMode => format!("Farbsehschwäche: {}", palette.mode.name()),
+            MenuButton::ToggleReducedMotion => {
+                format!("Weniger Bewegung: {}", if settings.reduced_motion { "An" } else { "Aus" })
+            }
+            MenuButton::ToggleCaptions => format!("Tonhinweise: {}", if settings.captions { "An" } else { "Aus" }),
+            MenuButton::CycleDangerWarning => format!("Warnung bei tiefem Ball: {}", settings.danger_warning.name()),
+            MenuButton::CycleUiSize => format!("UI-Größe: {}", ui_size.name()),
+            MenuButton::CycleTransition => format!("Übergang: {}", transition_style.name()),
+            MenuButton::ToggleRumble => format!("Vibration: {}", if haptics.rumble { "An" } else { "Aus" }),
+            MenuButton::ToggleImpactLights => {
+                format!("Lichtblitze: {}", if graphics.impact_lights { "An" } else { "Aus" })
+            }
+            MenuButton::ToggleAdaptiveHud => {
+                format!("Anzeige ausblenden: {}", if graphics.adaptive_hud { "An" } else { "Aus" })
+            }
+            MenuButton::CycleTickRate => format!("Spieltakt: {}", tick_rate.name()),
+            MenuButton::ToggleTelemetry => {
+                format!("Spieldaten sammeln: {}", if save.telemetry { "An" } else { "Aus" })
+            }
+            MenuButton::ToggleAssist(assist) => {
+                format!("Hilfe {}: {}", assist.name(), if assists.is_enabled(*assist) { "An" } else { "Aus" })
+            }
+            _ => continue,
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+// Nach einem geschafften Level wird einer von drei Segen gewählt. Die Angebote würfelt das RunPlugin aus.
+// Mit eingeschalteten Hilfen steht unter dem Titel, welche davon geholfen haben.
+fn spawn_reward(mut commands: Commands, asset_server: Res<AssetServer>, assists: Res<Assists>) {
+    spawn_screen(&mut commands, &asset_server, "Level geschafft!", |parent| {
+        let assist_label = assists.label();
+        if !assist_label.is_empty() {
+            parent.spawn(button_text(&format!("Mit Hilfen: {assist_label}"), &asset_server));
+        }
+        for index in 0..3 {
+            spawn_button(parent, &asset_server, MenuButton::ChooseModifier(index), "");
+        }
+        parent.spawn((button_text("", &asset_server), CurseText));
+    });
+}
+
+fn update_reward_labels(
+    modifiers: Res<RunModifiers>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut curse_query: Query<&mut Text, With<CurseText>>,
+    mut text_query: Query<&mut Text, Without<CurseText>>,
+) {
+    if let Ok(mut text) = curse_query.get_single_mut() {
+        text.sections[0].value = modifiers.last_curse.map_or(String::new(), |curse| curse.name().to_string());
+    }
+    for (button, children) in &button_query {
+        let MenuButton::ChooseModifier(index) = *button else { continue };
+        let label = modifiers.offers.get(index).map_or("", |modifier| modifier.name());
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label.to_string();
+        }
+    }
+}
+
+// Der Shop zwischen zwei Leveln. Preise und Stufen der Upgrades setzt update_shop_labels.
+fn spawn_shop(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(&mut commands, &asset_server, "Shop", |parent| {
+        parent.spawn((button_text("", &asset_server), CoinsText));
+        for upgrade in Upgrade::ALL {
+            spawn_button(parent, &asset_server, MenuButton::Buy(upgrade), "");
+        }
+        spawn_button(parent, &asset_server, MenuButton::NextLevel, "Weiter");
+    });
+}
+
+fn update_shop_labels(
+    wallet: Res<Wallet>,
+    upgrades: Res<Upgrades>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut coins_query: Query<&mut Text, With<CoinsText>>,
+    mut text_query: Query<&mut Text, Without<CoinsText>>,
+) {
+    if let Ok(mut text) = coins_query.get_single_mut() {
+        text.sections[0].value = format!("Münzen: {}", wallet.coins);
+    }
+    for (button, children) in &button_query {
+        let MenuButton::Buy(upgrade) = *button else { continue };
+        let label = match upgrades.price(upgrade) {
+            Some(price) => format!("{} {} ({} M)", upgrade.name(), upgrades.level(upgrade) + 1, price),
+            None => format!("{}: max.", upgrade.name()),
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+fn update_custom_game_labels(
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    mutators: Res<Mutators>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children) in &button_query {
+        let label = match button {
+            MenuButton::CycleLevel => {
+                let name = levels
+                    .get(campaign.current_handle())
+                    .map_or("?", |level| level.name.as_str());
+                format!("Level {}: {}", campaign.current + 1, name)
+            }
+            MenuButton::ToggleGravity => {
+                format!("Schwerkraft: {}", if mutators.gravity { "An" } else { "Aus" })
+            }
+            MenuButton::CycleArenaSize => format!("Arena: {}", mutators.arena_size.name()),
+            MenuButton::ToggleChallenge(challenge) => {
+                format!("{}: {}", challenge.name(), if mutators.challenges.is_active(*challenge) { "An" } else { "Aus" })
+            }
+            MenuButton::StartCustom => format!("Starten (Punkte x{:.2})", mutators.challenges.score_factor()),
+            _ => continue,
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+// Der ausgewählte Button leuchtet wie einer unter der Maus, egal ob er mit Maus, Tastatur oder Gamepad gewählt wurde.
+fn update_button_colors(mut query: Query<(&Interaction, Option<&Focused>, &mut BackgroundColor), With<MenuButton>>) {
+    for (interaction, focused, mut color) in &mut query {
+        let target = match (interaction, focused) {
+            (Interaction::Clicked, _) => PRESSED_BUTTON,
+            (_, Some(_)) => HOVERED_BUTTON,
+            _ => NORMAL_BUTTON,
+        };
+        if color.0 != target {
+            *color = target.into();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    button_query: Query<&MenuButton>,
+    mut activated: EventReader<Activated>,
+    mut transition: ResMut<ScreenTransition>,
+    mut campaign: ResMut<Campaign>,
+    mut mutators: ResMut<Mutators>,
+    mut wallet: ResMut<Wallet>,
+    mut upgrades: ResMut<Upgrades>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut cosmetics: ResMut<CosmeticsConfig>,
+    mut save: ResMut<SaveData>,
+    mut mode: ResMut<GameMode>,
+    mut daily: ResMut<DailyChallenge>,
+    mut pending: ResMut<PendingResume>,
+    tutorial: Res<TutorialLevel>,
+    mut settings: (
+        ResMut<Palette>,
+        ResMut<AccessibilitySettings>,
+        ResMut<Assists>,
+        ResMut<UiSize>,
+        ResMut<HapticsSettings>,
+        ResMut<TransitionStyle>,
+        ResMut<GraphicsSettings>,
+        ResMut<TickRate>,
+    ),
+) {
+    // Ein Mausklick und eine Bestätigung mit Tastatur oder Gamepad lösen dasselbe aus.
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Clicked)
+        .map(|(_, button)| *button);
+    let confirmed = activated.iter().filter_map(|Activated(entity)| button_query.get(*entity).ok().copied());
+    let pressed: Vec<MenuButton> = clicked.chain(confirmed).collect();
+    for button in &pressed {
+        match button {
+            MenuButton::Continue => {
+                pending.0 = save.suspended_run.take();
+                transition.to(GameState::Playing);
+            }
+            MenuButton::Play => transition.to(GameState::ModeSelect),
+            MenuButton::Campaign | MenuButton::TimeAttack | MenuButton::Coop | MenuButton::Survival => {
+                // Ein neuer Durchlauf verwirft den unterbrochenen.
+                save.suspended_run = None;
+                // Die Modi starten immer beim ersten Level und ohne Modifikatoren.
+                campaign.current = 0;
+                *mutators = Mutators::default();
+                *mode = match button {
+                    MenuButton::TimeAttack => GameMode::TimeAttack,
+                    MenuButton::Coop => GameMode::Coop,
+                    MenuButton::Survival => GameMode::Survival,
+                    _ => GameMode::Campaign,
+                };
+                transition.to(GameState::Playing);
+            }
+            MenuButton::LevelSelect => transition.to(GameState::LevelSelect),
+            MenuButton::SelectLevel(index) => {
+                if save.level_progress.is_unlocked(*index) {
+                    // Von hier aus geht es wie in der Kampagne mit den folgenden Leveln weiter.
+                    save.suspended_run = None;
+                    campaign.current = *index;
+                    campaign.custom_level = None;
+                    *mutators = Mutators::default();
+                    *mode = GameMode::Campaign;
+                    transition.to(GameState::Playing);
+                }
+            }
+            MenuButton::SelectBonusLevel(index) => {
+                let Some((handle, required)) = campaign.bonus_levels.get(*index).cloned() else { continue };
+                if save.level_progress.objective_stars() >= required {
+                    save.suspended_run = None;
+                    campaign.custom_level = Some(handle);
+                    *mutators = Mutators::default();
+                    *mode = GameMode::Bonus;
+                    transition.to(GameState::Playing);
+                }
+            }
+            MenuButton::Daily => {
+                // Level und Modifikatoren kommen fest aus dem Datum und lassen sich nicht ändern.
+                save.suspended_run = None;
+                *daily = DailyChallenge::today();
+                campaign.current = daily.level_index(campaign.levels.len());
+                *mutators = daily.mutators();
+                *mode = GameMode::Daily;
+                transition.to(GameState::Playing);
+            }
+            MenuButton::Tutorial => {
+                campaign.custom_level = Some(tutorial.0.clone());
+                *mutators = Mutators::default();
+                *mode = GameMode::Tutorial;
+                transition.to(GameState::Playing);
+            }
+            MenuButton::CustomGame => transition.to(GameState::CustomGame),
+            MenuButton::Cosmetics => transition.to(GameState::Cosmetics),
+            MenuButton::Editor => transition.to(GameState::Editor),
+            MenuButton::UserLevels => transition.to(GameState::UserLevels),
+            MenuButton::PlayUserLevel(_) | MenuButton::PasteLevelCode | MenuButton::FinishResults => {}
+            MenuButton::Tournament
+            | MenuButton::CycleTournamentPlayers
+            | MenuButton::CycleTournamentRounds
+            | MenuButton::NextTurn => {}
+            MenuButton::Accessibility => transition.to(GameState::Accessibility),
+            MenuButton::CycleColorblindMode => settings.0.mode = settings.0.mode.next(),
+            MenuButton::ToggleReducedMotion => {
+                settings.1.reduced_motion = !settings.1.reduced_motion;
+            }
+            MenuButton::ToggleCaptions => settings.1.captions = !settings.1.captions,
+            MenuButton::CycleDangerWarning => settings.1.danger_warning = settings.1.danger_warning.next(),
+            MenuButton::CycleUiSize => *settings.3 = settings.3.next(),
+            MenuButton::ToggleRumble => settings.4.rumble = !settings.4.rumble,
+            MenuButton::ToggleImpactLights => settings.6.impact_lights = !settings.6.impact_lights,
+            MenuButton::ToggleAdaptiveHud => settings.6.adaptive_hud = !settings.6.adaptive_hud,
+            MenuButton::CycleTickRate => *settings.7 = settings.7.next(),
+            MenuButton::CycleTransition => *settings.5 = settings.5.next(),
+            MenuButton::ToggleTelemetry => save.telemetry = !save.telemetry,
+            MenuButton::ToggleAssist(assist) => settings.2.toggle(*assist),
+            #[cfg(feature = "online")]
+            MenuButton::Leaderboard => transition.to(GameState::Leaderboard),
+            MenuButton::CycleBallSkin => cycle_ball_skin(&mut cosmetics, &save),
+            MenuButton::CyclePaddleSkin => cycle_paddle_skin(&mut cosmetics, &save),
+            MenuButton::CycleLevel => {
+                campaign.current = (campaign.current + 1) % campaign.levels.len();
+            }
+            MenuButton::ToggleGravity => mutators.gravity = !mutators.gravity,
+            MenuButton::CycleArenaSize => mutators.arena_size = mutators.arena_size.next(),
+            MenuButton::ToggleChallenge(challenge) => mutators.challenges.toggle(*challenge),
+            MenuButton::StartCustom => {
+                save.suspended_run = None;
+                *mode = GameMode::Campaign;
+                transition.to(GameState::Playing);
+            }
+            MenuButton::Back => transition.to(GameState::Menu),
+            MenuButton::Buy(upgrade) => {
+                upgrades.try_buy(*upgrade, &mut wallet);
+            }
+            MenuButton::NextLevel => transition.to(GameState::Playing),
+            MenuButton::ChooseModifier(index) => {
+                if let Some(&modifier) = modifiers.offers.get(*index) {
+                    modifiers.choose(modifier);
+                    transition.to(GameState::Shop);
+                }
+            }
+        }
+    }
+}
+
+fn despawn_menu(mut commands: Commands, query: Query<Entity, With<MenuScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
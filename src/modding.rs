@@ -0,0 +1,344 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use bevy::prelude::*;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+
+use crate::accessibility::Palette;
+use crate::level::BrickKind;
+use crate::powerup::{spawn_powerup, PowerUpCollected, PowerUpKind};
+use crate::toast::ShowToast;
+use crate::{Arena, BrickDestroyed, Scoreboard};
+
+// Alle *.rhai-Dateien aus diesem Ordner neben dem Spiel werden beim Start geladen, in alphabetischer Reihenfolge.
+const MODS_DIR: &str = "mods";
+// Mod-Power-Ups aus einem Power-Up-Callback fallen so weit unter der Decke in der Mitte der Arena.
+const CALLBACK_DROP_DEPTH: f32 = 1.0;
+// So viele Schritte darf ein Skript je Aufruf machen, so tief darf es Funktionen verschachteln. Eine Endlosschleife
+// oder Rekursion bricht dann mit einem Fehler ab, statt den Start oder das Spiel einzufrieren.
+const MAX_OPERATIONS: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 32;
+
+// Ein Brick, den ein Mod registriert hat. Im Layout der Level-Dateien steht er mit seinem Zeichen.
+pub struct ModBrick {
+    pub symbol: char,
+    pub name: String,
+    pub color: Color,
+    pub health: u32,
+    pub score: usize,
+}
+
+pub struct ModPowerUp {
+    pub name: String,
+    pub color: Color,
+}
+
+#[derive(Default)]
+pub struct ModRegistry {
+    pub bricks: Vec<ModBrick>,
+    pub powerups: Vec<ModPowerUp>,
+}
+
+impl ModRegistry {
+    pub fn brick(&self, index: u8) -> &ModBrick {
+        &self.bricks[index as usize]
+    }
+
+    pub fn powerup(&self, index: u8) -> &ModPowerUp {
+        &self.powerups[index as usize]
+    }
+
+    pub fn brick_kind(&self, symbol: char) -> Option<BrickKind> {
+        let index = self.bricks.iter().position(|brick| brick.symbol == symbol)?;
+        Some(BrickKind::Mod(index as u8))
+    }
+
+    fn powerup_kind(&self, name: &str) -> Option<PowerUpKind> {
+        let index = self.powerups.iter().position(|powerup| powerup.name == name)?;
+        Some(PowerUpKind::Mod(index as u8))
+    }
+}
+
+// Wird beim Start einmal gefüllt und ändert sich danach nicht mehr. Die Registry liegt außerhalb der Welt, weil schon
+// der Level-Loader die Zeichen der Mod-Bricks kennen muss und Namen wie bei den eingebauten Arten &'static sind.
+static REGISTRY: OnceLock<ModRegistry> = OnceLock::new();
+
+pub fn registry() -> &'static ModRegistry {
+    REGISTRY.get_or_init(ModRegistry::default)
+}
+
+// Was ein Skript in einem Callback auslöst. Die Aufrufe sammeln sich hier und werden danach im Spiel ausgeführt.
+enum ScriptAction {
+    AddScore(i64),
+    DropPowerUp(String),
+    Toast(String),
+}
+
+#[derive(Resource)]
+struct Mods {
+    engine: Engine,
+    scripts: Vec<(PathBuf, AST)>,
+    actions: Arc<Mutex<Vec<ScriptAction>>>,
+}
+
+impl Mods {
+    // Ruft den Callback in jedem Skript auf, das ihn definiert. Fehler landen im Log, das Spiel läuft weiter.
+    fn call(&self, callback: &str, argument: Dynamic) {
+        for (path, ast) in &self.scripts {
+            if !ast.iter_functions().any(|function| function.name == callback) {
+                continue;
+            }
+            let result = self.engine.call_fn::<Dynamic>(&mut Scope::new(), ast, callback, (argument.clone(),));
+            if let Err(error) = result {
+                warn!("{callback} in {} ist fehlgeschlagen: {error}", path.display());
+            }
+        }
+    }
+
+    fn take_actions(&self) -> Vec<ScriptAction> {
+        self.actions.lock().map(|mut actions| actions.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+// Skripte im Ordner mods/ können eigene Bricks und Power-Ups registrieren und auf zerstörte Bricks und
+// eingesammelte Power-Ups reagieren:
+//
+//     register_brick("H", #{ name: "Hart", color: [0.3, 0.3, 0.4], health: 3, score: 5 });
+//     register_powerup("Bonus", #{ color: [1.0, 1.0, 0.0] });
+//     fn on_brick_destroyed(brick) { if brick.symbol == "H" { drop_powerup("Bonus"); } }
+//     fn on_powerup_collected(name) { if name == "Bonus" { add_score(50); toast("Bonus!"); } }
+//
+// Registrieren geht nur beim Laden, denn Level-Loader und Theme brauchen die Bricks schon beim Start.
+pub struct ModPlugin;
+
+impl Plugin for ModPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_mods(Path::new(MODS_DIR)))
+            .add_system(run_brick_callbacks)
+            .add_system(run_powerup_callbacks);
+    }
+}
+
+fn load_mods(dir: &Path) -> Mods {
+    // Nach dem Laden ist die Registry hier None, spätere Aufrufe von register_* schlagen dann fehl.
+    let registry = Arc::new(Mutex::new(Some(ModRegistry::default())));
+    let actions = Arc::new(Mutex::new(Vec::new()));
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS).set_max_call_levels(MAX_CALL_LEVELS);
+    register_api(&mut engine, &registry, &actions);
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(Result::ok).map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "rhai"));
+    paths.sort();
+
+    let mut scripts = Vec::new();
+    for path in paths {
+        // Der Hauptteil eines Skripts läuft genau einmal, hier werden Bricks und Power-Ups registriert.
+        match engine.compile_file(path.clone()).and_then(|ast| engine.run_ast(&ast).map(|()| ast)) {
+            Ok(ast) => {
+                info!("Mod geladen: {}", path.display());
+                scripts.push((path, ast));
+            }
+            Err(error) => warn!("Mod {} konnte nicht geladen werden: {error}", path.display()),
+        }
+    }
+
+    let loaded = registry.lock().ok().and_then(|mut registry| registry.take()).unwrap_or_default();
+    if REGISTRY.set(loaded).is_err() {
+        warn!("Die Mods wurden schon geladen, die neuen Registrierungen werden ignoriert");
+    }
+    // Aktionen aus dem Hauptteil haben noch kein Spiel, auf das sie wirken könnten.
+    if let Ok(mut actions) = actions.lock() {
+        actions.clear();
+    }
+    Mods { engine, scripts, actions }
+}
+
+fn register_api(
+    engine: &mut Engine,
+    registry: &Arc<Mutex<Option<ModRegistry>>>,
+    actions: &Arc<Mutex<Vec<ScriptAction>>>,
+) {
+    let bricks = registry.clone();
+    engine.register_fn("register_brick", move |symbol: &str, options: Map| -> Result<(), Box<EvalAltResult>> {
+        let mut guard = bricks.lock().map_err(|_| "Registry nicht verfügbar")?;
+        let registry = guard.as_mut().ok_or("Bricks lassen sich nur beim Laden registrieren")?;
+        let mut chars = symbol.chars();
+        let (Some(character), None) = (chars.next(), chars.next()) else {
+            return Err(format!("\"{symbol}\" ist kein einzelnes Zeichen").into());
+        };
+        if BrickKind::is_reserved_symbol(character) || registry.brick_kind(character).is_some() {
+            return Err(format!("Das Zeichen \"{symbol}\" ist schon vergeben").into());
+        }
+        if registry.bricks.len() > u8::MAX as usize {
+            return Err("Zu viele Bricks registriert".into());
+        }
+        registry.bricks.push(ModBrick {
+            symbol: character,
+            name: string_option(&options, "name")?.unwrap_or_else(|| symbol.to_string()),
+            color: color_option(&options)?,
+            health: int_option(&options, "health")?.unwrap_or(1).clamp(1, u32::MAX as i64) as u32,
+            score: int_option(&options, "score")?.unwrap_or(1).max(0) as usize,
+        });
+        Ok(())
+    });
+
+    let powerups = registry.clone();
+    engine.register_fn("register_powerup", move |name: &str, options: Map| -> Result<(), Box<EvalAltResult>> {
+        let mut guard = powerups.lock().map_err(|_| "Registry nicht verfügbar")?;
+        let registry = guard.as_mut().ok_or("Power-Ups lassen sich nur beim Laden registrieren")?;
+        if registry.powerup_kind(name).is_some() {
+            return Err(format!("Das Power-Up \"{name}\" gibt es schon").into());
+        }
+        if registry.powerups.len() > u8::MAX as usize {
+            return Err("Zu viele Power-Ups registriert".into());
+        }
+        registry.powerups.push(ModPowerUp {
+            name: name.to_string(),
+            color: color_option(&options)?,
+        });
+        Ok(())
+    });
+
+    let queue = actions.clone();
+    engine.register_fn("add_score", move |points: i64| push_action(&queue, ScriptAction::AddScore(points)));
+    let queue = actions.clone();
+    engine.register_fn("drop_powerup", move |name: &str| {
+        push_action(&queue, ScriptAction::DropPowerUp(name.to_string()))
+    });
+    let queue = actions.clone();
+    engine.register_fn("toast", move |text: &str| push_action(&queue, ScriptAction::Toast(text.to_string())));
+}
+
+fn push_action(actions: &Mutex<Vec<ScriptAction>>, action: ScriptAction) {
+    if let Ok(mut actions) = actions.lock() {
+        actions.push(action);
+    }
+}
+
+// Zahlen dürfen im Skript mit oder ohne Nachkommastellen stehen.
+fn number(value: &Dynamic) -> Result<f64, Box<EvalAltResult>> {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|value| value as f64))
+        .map_err(|_| format!("{value} ist keine Zahl").into())
+}
+
+fn int_option(options: &Map, key: &str) -> Result<Option<i64>, Box<EvalAltResult>> {
+    let Some(value) = options.get(key) else { return Ok(None) };
+    value.as_int().map(Some).map_err(|_| format!("{key} muss eine ganze Zahl sein").into())
+}
+
+fn string_option(options: &Map, key: &str) -> Result<Option<String>, Box<EvalAltResult>> {
+    let Some(value) = options.get(key) else { return Ok(None) };
+    value.clone().into_string().map(Some).map_err(|_| format!("{key} muss ein Text sein").into())
+}
+
+// Die Farbe als [r, g, b] mit Werten von 0 bis 1. Ohne Angabe ist sie weiß.
+fn color_option(options: &Map) -> Result<Color, Box<EvalAltResult>> {
+    let Some(value) = options.get("color") else { return Ok(Color::WHITE) };
+    let channels = value.clone().into_array().map_err(|_| "color muss eine Liste [r, g, b] sein")?;
+    let [r, g, b] = channels.as_slice() else { return Err("color braucht genau drei Werte".into()) };
+    Ok(Color::rgb(number(r)? as f32, number(g)? as f32, number(b)? as f32))
+}
+
+// Die Aktionen eines Callbacks werden sofort ausgeführt. Power-Ups fallen dort, wo der Brick war.
+#[allow(clippy::too_many_arguments)]
+fn apply_actions(
+    actions: Vec<ScriptAction>,
+    position: Vec3,
+    commands: &mut Commands,
+    scoreboard: &mut Scoreboard,
+    toasts: &mut EventWriter<ShowToast>,
+    mesh: &mut Option<Handle<Mesh>>,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    palette: &Palette,
+) {
+    for action in actions {
+        match action {
+            ScriptAction::AddScore(points) => {
+                scoreboard.score = scoreboard.score.saturating_add_signed(points as isize);
+            }
+            ScriptAction::DropPowerUp(name) => {
+                let Some(kind) = registry().powerup_kind(&name) else {
+                    warn!("Ein Mod wollte das unbekannte Power-Up \"{name}\" fallen lassen");
+                    continue;
+                };
+                let mesh = mesh
+                    .get_or_insert_with(|| meshes.add(shape::Cube::default().into()))
+                    .clone();
+                spawn_powerup(commands, mesh, materials, palette, kind, position);
+            }
+            ScriptAction::Toast(text) => toasts.send(ShowToast(text)),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_brick_callbacks(
+    mut commands: Commands,
+    mods: Res<Mods>,
+    mut events: EventReader<BrickDestroyed>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut toasts: EventWriter<ShowToast>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    palette: Res<Palette>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    for event in events.iter() {
+        let mut brick = Map::new();
+        brick.insert("x".into(), Dynamic::from_float(event.position.x as f64));
+        brick.insert("y".into(), Dynamic::from_float(event.position.y as f64));
+        brick.insert("symbol".into(), event.kind.symbol().to_string().into());
+        brick.insert("penalty".into(), (event.kind == BrickKind::Penalty).into());
+        mods.call("on_brick_destroyed", brick.into());
+        let actions = mods.take_actions();
+        apply_actions(
+            actions,
+            event.position,
+            &mut commands,
+            &mut scoreboard,
+            &mut toasts,
+            &mut mesh,
+            &mut meshes,
+            &mut materials,
+            &palette,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_powerup_callbacks(
+    mut commands: Commands,
+    mods: Res<Mods>,
+    mut events: EventReader<PowerUpCollected>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut toasts: EventWriter<ShowToast>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    palette: Res<Palette>,
+    arena: Res<Arena>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    let drop_position = Vec3::new((arena.left() + arena.right()) / 2.0, arena.top() - CALLBACK_DROP_DEPTH, 0.0);
+    for PowerUpCollected(kind) in events.iter() {
+        mods.call("on_powerup_collected", kind.name().into());
+        let actions = mods.take_actions();
+        apply_actions(
+            actions,
+            drop_position,
+            &mut commands,
+            &mut scoreboard,
+            &mut toasts,
+            &mut mesh,
+            &mut meshes,
+            &mut materials,
+            &palette,
+        );
+    }
+}
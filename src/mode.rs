@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::cheats::Cheats;
+use crate::daily::DailyChallenge;
+use crate::format;
+use crate::save::SaveData;
+use crate::snapshot::SnapshotReplay;
+use crate::tick_rate::TickRate;
+use crate::toast::ShowToast;
+use crate::{GameState, Scoreboard};
+
+// So viele Einträge behält jede Bestenliste.
+const HIGH_SCORE_ENTRIES: usize = 5;
+// So weit dürfen gespielte Zeit und gezählte Schritte auseinanderliegen, bevor ein Ergebnis nicht mehr gewertet wird.
+// Der Rest im Akkumulator und Rundungsfehler bleiben weit darunter.
+const TICK_TOLERANCE_SECONDS: f64 = 1.0;
+const TICK_TOLERANCE_FACTOR: f64 = 0.01;
+
+// Der gewählte Spielmodus. Aufbau, Gewinn- und Verlustbedingungen richten sich danach.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum GameMode {
+    // Level der Reihe nach mit Leben, Belohnungen und Shop.
+    #[default]
+    Campaign,
+    // Möglichst viele Punkte, bevor die Zeit abläuft. Ein verlorener Ball kostet Zeit statt Leben.
+    TimeAttack,
+    // Ein Level mit Modifikatoren aus dem heutigen Datum, ohne Belohnungen und Shop.
+    Daily,
+    // Ein Level aus dem Editor ausprobieren. Es zählt für keine Bestenliste und endet wieder im Editor.
+    TestPlay,
+    // Ein Level aus dem Ordner der eigenen Level. Wie das Probespiel ohne Wertung, es endet wieder in der Liste.
+    UserLevel,
+    // Ein Bonuslevel, freigeschaltet mit Sternen aus Nebenzielen. Ohne Wertung, es endet wieder in der Levelauswahl.
+    Bonus,
+    // Ein einfaches Level mit Hinweisen, die Schritt für Schritt durch die Steuerung führen. Ohne Leben und ohne Wertung.
+    Tutorial,
+    // Spielt sich selbst, wenn das Hauptmenü lange unberührt bleibt. Ohne Wertung, jede Eingabe beendet sie.
+    Demo,
+    // Zwei Spieler an einer Tastatur, einer am Paddle unten, einer an der Lücke in der Decke. Wie im Zeitangriff
+    // geht es ohne Pause durch die Level, dazu läuft die gemeinsam durchgehaltene Zeit mit.
+    Coop,
+    // Die Seitenwände rücken langsam zusammen, bis das Paddle nicht mehr dazwischen passt. Punkte gibt es vor
+    // allem für jede überstandene Sekunde.
+    Survival,
+    // Zwei bis vier Spieler wechseln sich an einem Gerät ab. In jeder Runde spielt jeder dasselbe Level mit demselben
+    // Seed, wer die meisten Runden gewinnt, gewinnt das Turnier. Siehe tournament.rs.
+    Tournament,
+}
+
+impl GameMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Campaign => "Kampagne",
+            GameMode::TimeAttack => "Zeitangriff",
+            GameMode::Daily => "Täglich",
+            GameMode::TestPlay => "Probespiel",
+            GameMode::UserLevel => "Eigenes Level",
+            GameMode::Bonus => "Bonus",
+            GameMode::Tutorial => "Tutorial",
+            GameMode::Demo => "Demo",
+            GameMode::Coop => "Koop (2 Spieler)",
+            GameMode::Survival => "Überleben",
+            GameMode::Tournament => "Turnier",
+        }
+    }
+
+    // Wohin es geht, wenn das Spiel verlassen wird oder keine Leben mehr übrig sind.
+    pub fn exit_state(self) -> GameState {
+        match self {
+            GameMode::TestPlay => GameState::Editor,
+            GameMode::UserLevel => GameState::UserLevels,
+            GameMode::Bonus => GameState::LevelSelect,
+            GameMode::Tournament => GameState::Standings,
+            _ => GameState::Menu,
+        }
+    }
+}
+
+// Absteigend sortierte Bestenlisten je Modus.
+#[derive(Serialize, Deserialize, Default)]
+pub struct HighScores(HashMap<GameMode, Vec<usize>>);
+
+impl HighScores {
+    pub fn best(&self, mode: GameMode) -> Option<usize> {
+        self.0.get(&mode).and_then(|scores| scores.first().copied())
+    }
+
+    fn insert(&mut self, mode: GameMode, score: usize) {
+        let scores = self.0.entry(mode).or_default();
+        scores.push(score);
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        scores.truncate(HIGH_SCORE_ENTRIES);
+    }
+}
+
+// Das Ergebnis eines beendeten Durchlaufs, so wie es auch an die Online-Bestenliste geht.
+// Der Seed ist nur bei der täglichen Herausforderung gesetzt, da nur dort alle dasselbe Level spielen.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ScoreEntry {
+    pub mode: GameMode,
+    pub score: usize,
+    pub seed: Option<u64>,
+    // Gespielte Schritte des festen Takts, damit auch die Bestenliste die Dauer des Durchlaufs prüfen kann.
+    // Gezählt in Schritten zu 60 Hz, ganz gleich, mit welcher Taktrate gespielt wurde.
+    #[serde(default)]
+    pub ticks: u64,
+}
+
+// Punkte und Zeiten hängen nur vom festen Takt ab. Hier wird mitgezählt, wie viele Schritte ein Durchlauf hatte und
+// wie viel echte Zeit dabei vergangen ist. Passen beide nicht zusammen, wurde der Takt verlangsamt oder angehalten,
+// etwa mit den Debug-Tasten, und das Ergebnis ist nicht mit anderen Rechnern vergleichbar.
+#[derive(Resource, Default)]
+pub struct RunTicks {
+    pub ticks: u64,
+    // Die Summe der Schritte in Sekunden. Die Taktrate kann sich zwischen unterbrochenem und fortgesetztem
+    // Durchlauf ändern, deshalb reicht die Zahl der Schritte allein nicht.
+    pub simulated_seconds: f64,
+    pub real_seconds: f64,
+}
+
+impl RunTicks {
+    fn is_consistent(&self) -> bool {
+        let difference = (self.simulated_seconds - self.real_seconds).abs();
+        difference <= TICK_TOLERANCE_SECONDS + self.real_seconds * TICK_TOLERANCE_FACTOR
+    }
+
+    fn reference_ticks(&self) -> u64 {
+        (self.simulated_seconds / TickRate::Hz60.seconds() as f64).round() as u64
+    }
+}
+
+// Wird gesendet, wenn ein Durchlauf mit Punkten beendet wurde.
+pub struct RunFinished(pub ScoreEntry);
+
+pub struct ModePlugin;
+
+impl Plugin for ModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RunFinished>()
+            .init_resource::<GameMode>()
+            .init_resource::<DailyChallenge>()
+            .init_resource::<RunTicks>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(finish_run));
+    }
+}
+
+// Zurück im Hauptmenü ist der Durchlauf vorbei. Die erreichte Punktzahl landet in der Bestenliste des Modus.
+// Die tägliche Herausforderung hat eine eigene Bestenliste je Tag. Ein Turnier hat seinen eigenen Endstand.
+#[allow(clippy::too_many_arguments)]
+pub fn finish_run(
+    mut scoreboard: ResMut<Scoreboard>,
+    (mode, replay): (Res<GameMode>, Res<SnapshotReplay>),
+    daily: Res<DailyChallenge>,
+    mut save: ResMut<SaveData>,
+    mut finished_events: EventWriter<RunFinished>,
+    cheats: Res<Cheats>,
+    mut run_ticks: ResMut<RunTicks>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let ticks = std::mem::take(&mut *run_ticks);
+    // Ein unterbrochener Durchlauf ist noch nicht vorbei, seine Punkte werden erst beim Fortsetzen weitergezählt.
+    // Mit Cheats erreichte Punkte und nachgespielte Schnappschüsse zählen ebenfalls nicht.
+    if scoreboard.score == 0
+        || save.suspended_run.is_some()
+        || cheats.used_this_run
+        || replay.0
+        || matches!(
+            *mode,
+            GameMode::TestPlay
+                | GameMode::UserLevel
+                | GameMode::Bonus
+                | GameMode::Tutorial
+                | GameMode::Demo
+                | GameMode::Tournament
+        )
+    {
+        scoreboard.score = 0;
+        return;
+    }
+    if !ticks.is_consistent() {
+        warn!(
+            "Durchlauf nicht gewertet: {} Schritte passen nicht zu {:.1} s Spielzeit",
+            ticks.ticks, ticks.real_seconds
+        );
+        toasts.send(ShowToast("Ergebnis nicht gewertet: Spieltakt verändert".to_string()));
+        scoreboard.score = 0;
+        return;
+    }
+    if *mode == GameMode::Daily {
+        save.daily_scores.insert(daily.day, scoreboard.score);
+    } else {
+        if save.high_scores.best(*mode).is_none_or(|best| scoreboard.score > best) {
+            toasts.send(ShowToast(format!("Neuer Highscore: {}!", format::number(scoreboard.score))));
+        }
+        save.high_scores.insert(*mode, scoreboard.score);
+    }
+    finished_events.send(RunFinished(ScoreEntry {
+        mode: *mode,
+        score: scoreboard.score,
+        seed: (*mode == GameMode::Daily).then_some(daily.seed),
+        ticks: ticks.reference_ticks(),
+    }));
+    scoreboard.score = 0;
+}
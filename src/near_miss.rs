@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::config::GameConfig;
+use crate::{phase_set, Ball, Brick, BrickHit, GameState, Paddle, PaddleHit, Phase};
+
+// Wird gesendet, wenn der Ball gerade noch am Rand des Paddles gefangen wurde oder knapp an der Ecke eines Bricks
+// vorbeigeflogen ist. `distance` ist der Abstand in Metern, bei einer Rettung bis zur Kante des Paddles.
+pub struct NearMiss {
+    pub position: Vec3,
+    pub kind: NearMissKind,
+    pub distance: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NearMissKind {
+    CloseSave,
+    BrickCorner,
+}
+
+// Der knappste Moment im laufenden Level, für die Abrechnung.
+#[derive(Resource, Default)]
+pub struct ClosestCall(pub Option<f32>);
+
+pub struct NearMissPlugin;
+
+impl Plugin for NearMissPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClosestCall>()
+            .add_event::<NearMiss>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(reset_closest_call))
+            .add_system_set(
+                phase_set(Phase::Scoring)
+                    .with_system(detect_close_saves)
+                    .with_system(detect_corner_misses)
+                    .with_system(track_closest_call.after(detect_close_saves).after(detect_corner_misses)),
+            );
+    }
+}
+
+fn reset_closest_call(mut closest: ResMut<ClosestCall>) {
+    closest.0 = None;
+}
+
+// Nach einem Treffer am Paddle zählt der Ball, der ihm am nächsten ist. Liegt seine Mitte höchstens um den Rand
+// innerhalb der Kante oder schon darüber hinaus, war es knapp.
+fn detect_close_saves(
+    mut paddle_hits: EventReader<PaddleHit>,
+    ball_query: Query<&Transform, With<Ball>>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    config: Res<GameConfig>,
+    mut near_misses: EventWriter<NearMiss>,
+) {
+    if paddle_hits.iter().count() == 0 {
+        return;
+    }
+    let Ok(paddle) = paddle_query.get_single() else { return };
+    let closest = ball_query.iter().min_by(|a, b| {
+        let (a, b) = (a.translation.distance(paddle.translation), b.translation.distance(paddle.translation));
+        a.total_cmp(&b)
+    });
+    let Some(ball) = closest else { return };
+    let edge_gap = paddle.scale.x / 2.0 - (ball.translation.x - paddle.translation.x).abs();
+    if edge_gap < config.near_miss_margin {
+        near_misses.send(NearMiss {
+            position: ball.translation,
+            kind: NearMissKind::CloseSave,
+            distance: edge_gap.max(0.0),
+        });
+    }
+}
+
+// Ein Ball kommt einer Ecke nahe, wenn er schräg außerhalb des Bricks liegt und höchstens um den Rand von ihr
+// entfernt ist. Gemeldet wird erst, wenn er sich wieder entfernt hat, ohne den Brick zu treffen, mit dem kleinsten
+// Abstand unterwegs.
+fn detect_corner_misses(
+    mut brick_hits: EventReader<BrickHit>,
+    ball_query: Query<(Entity, &Transform), With<Ball>>,
+    brick_query: Query<(Entity, &Transform), With<Brick>>,
+    config: Res<GameConfig>,
+    mut near_misses: EventWriter<NearMiss>,
+    mut passing: Local<HashMap<(Entity, Entity), (f32, Vec3)>>,
+) {
+    let hit: Vec<Vec3> = brick_hits.iter().map(|event| event.position).collect();
+    for (ball, ball_transform) in &ball_query {
+        let radius = ball_transform.scale.x / 2.0;
+        for (brick, brick_transform) in &brick_query {
+            let key = (ball, brick);
+            if hit.contains(&brick_transform.translation) {
+                passing.remove(&key);
+                continue;
+            }
+            let outside = (ball_transform.translation - brick_transform.translation).truncate().abs()
+                - brick_transform.scale.truncate() / 2.0;
+            let gap = outside.max(Vec2::ZERO).length() - radius;
+            if outside.x > 0.0 && outside.y > 0.0 && gap > 0.0 && gap < config.near_miss_margin {
+                let entry = passing.entry(key).or_insert((gap, ball_transform.translation));
+                if gap < entry.0 {
+                    *entry = (gap, ball_transform.translation);
+                }
+            } else if gap > config.near_miss_margin {
+                if let Some((distance, position)) = passing.remove(&key) {
+                    near_misses.send(NearMiss { position, kind: NearMissKind::BrickCorner, distance });
+                }
+            }
+        }
+    }
+    // Verschwundene Bälle und Bricks zählen nicht mehr.
+    passing.retain(|(ball, brick), _| ball_query.contains(*ball) && brick_query.contains(*brick));
+}
+
+fn track_closest_call(mut events: EventReader<NearMiss>, mut closest: ResMut<ClosestCall>) {
+    for event in events.iter() {
+        if closest.0.is_none_or(|closest| event.distance < closest) {
+            closest.0 = Some(event.distance);
+        }
+    }
+}
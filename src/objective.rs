@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::save::SaveData;
+use crate::toast::ShowToast;
+use crate::{phase_set, GameState, LevelCleared, Phase, Scoreboard, TimeScale};
+
+// Ein freiwilliges Nebenziel eines Levels. Jedes erreichte Ziel bringt einen Stern, mit dem sich Bonuslevel öffnen.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum Objective {
+    // Das Level in höchstens so vielen Sekunden schaffen.
+    TimeLimit(f32),
+    // Kein Power-Up einsammeln. Münzen und Gefahren zählen nicht.
+    NoPowerUps,
+    // Mindestens einmal so viele Bricks hintereinander zerstören, ohne dass der Ball das Paddle berührt.
+    MinCombo(u32),
+}
+
+impl Objective {
+    pub fn description(self) -> String {
+        match self {
+            Objective::TimeLimit(seconds) => format!("Unter {seconds:.0} s"),
+            Objective::NoPowerUps => "Ohne Power-Ups".to_string(),
+            Objective::MinCombo(combo) => format!("Kombo {combo}"),
+        }
+    }
+
+    fn is_met(self, tracker: &ObjectiveTracker) -> bool {
+        match self {
+            Objective::TimeLimit(seconds) => tracker.elapsed <= seconds,
+            Objective::NoPowerUps => !tracker.used_powerup,
+            Objective::MinCombo(combo) => tracker.best_combo >= combo,
+        }
+    }
+}
+
+// Was im laufenden Level für die Nebenziele mitgezählt wird.
+#[derive(Resource, Default)]
+pub struct ObjectiveTracker {
+    elapsed: f32,
+    used_powerup: bool,
+    best_combo: u32,
+}
+
+impl ObjectiveTracker {
+    // Gespielte Zeit im Level in Sekunden.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn best_combo(&self) -> u32 {
+        self.best_combo
+    }
+
+    pub fn met_objectives(&self, objectives: &[Objective]) -> usize {
+        objectives.iter().filter(|objective| objective.is_met(self)).count()
+    }
+}
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ObjectiveTracker>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_tracking))
+            .add_system_set(phase_set(Phase::Scoring).with_system(track_objectives))
+            .add_system(track_powerups)
+            .add_system(complete_objectives);
+    }
+}
+
+// Zu Beginn jedes Levels wird von vorn gezählt und einmal angezeigt, welche Ziele es gibt.
+fn start_tracking(
+    mut tracker: ResMut<ObjectiveTracker>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    mode: Res<GameMode>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    *tracker = ObjectiveTracker::default();
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if *mode == GameMode::Campaign && !level.objectives.is_empty() {
+        let goals: Vec<String> = level.objectives.iter().map(|objective| objective.description()).collect();
+        toasts.send(ShowToast(format!("Ziele: {}", goals.join(", "))));
+    }
+}
+
+fn track_objectives(mut tracker: ResMut<ObjectiveTracker>, scoreboard: Res<Scoreboard>, time_scale: Res<TimeScale>) {
+    tracker.elapsed += time_scale.step();
+    tracker.best_combo = tracker.best_combo.max(scoreboard.combo);
+}
+
+fn track_powerups(mut events: EventReader<PowerUpCollected>, mut tracker: ResMut<ObjectiveTracker>) {
+    if events.iter().any(|PowerUpCollected(kind)| *kind != PowerUpKind::Coin && !kind.is_hazard()) {
+        tracker.used_powerup = true;
+    }
+}
+
+// Nur in der Kampagne werden Ziele gewertet. Ein einmal erreichtes Ziel bleibt erreicht, auch wenn es später misslingt.
+fn complete_objectives(
+    mut events: EventReader<LevelCleared>,
+    tracker: Res<ObjectiveTracker>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    mode: Res<GameMode>,
+    mut save: ResMut<SaveData>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for event in events.iter() {
+        if *mode != GameMode::Campaign {
+            continue;
+        }
+        let Some(level) = campaign.levels.get(event.index).and_then(|handle| levels.get(handle)) else { continue };
+        let met: Vec<(usize, Objective)> = level
+            .objectives
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, objective)| objective.is_met(&tracker))
+            .collect();
+        for (_, objective) in &met {
+            toasts.send(ShowToast(format!("Ziel erreicht: {}", objective.description())));
+        }
+        save.level_progress.complete_objectives(event.index, met.iter().map(|(index, _)| *index));
+    }
+}
@@ -0,0 +1,173 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{Campaign, Level};
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, Ball, GameState, Kinematic, LevelEntity, Phase, Scoreboard, TimeScale};
+
+const OBSTACLE_DEPTH: f32 = 0.6;
+// Fliegt der Ball so knapp an einem Hindernis vorbei, ohne es zu berühren, gibt es Bonuspunkte.
+const THREAD_MARGIN: f32 = 0.35;
+const THREAD_SCORE: usize = 10;
+
+// Wie sich ein Hindernis bewegt.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum Motion {
+    // Dreht sich um die eigene Mitte, in Grad pro Sekunde. Positiv ist gegen den Uhrzeigersinn.
+    Rotating(f32),
+    // Pendelt zwischen position - travel und position + travel, eine ganze Schwingung dauert period Sekunden.
+    Oscillating { travel: Vec2, period: f32 },
+}
+
+// Ein bewegliches Hindernis, wie es in der Level-Datei angegeben wird, etwa ein Drehbalken:
+// (position: (0.0, 4.0), size: (3.0, 0.2), motion: Rotating(90.0))
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ObstacleDefinition {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub motion: Motion,
+}
+
+#[derive(Component)]
+struct Obstacle {
+    origin: Vec2,
+    half_size: Vec2,
+    motion: Motion,
+    elapsed: f32,
+    // Ob der Ball gerade knapp vorbeifliegt und ob er das Hindernis dabei berührt hat.
+    near: bool,
+    touched: bool,
+}
+
+impl Obstacle {
+    // Geschwindigkeit der Oberfläche an einem Punkt relativ zur Mitte. Sie gibt dem Ball beim Abprallen einen Stoß.
+    fn surface_velocity(&self, offset: Vec2) -> Vec2 {
+        match self.motion {
+            Motion::Rotating(degrees) => degrees.to_radians() * offset.perp(),
+            Motion::Oscillating { travel, period } => travel * TAU / period * (TAU * self.elapsed / period).cos(),
+        }
+    }
+}
+
+pub struct ObstaclePlugin;
+
+impl Plugin for ObstaclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_obstacles))
+            .add_system_set(phase_set(Phase::Movement).with_system(move_obstacles))
+            .add_system_set(phase_set(Phase::Collision).with_system(obstacle_collision));
+    }
+}
+
+fn spawn_obstacles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if level.obstacles.is_empty() {
+        return;
+    }
+
+    let mesh = meshes.add(shape::Cube::default().into());
+    let material = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.3, 0.3, 0.35),
+        metallic: 0.6,
+        perceptual_roughness: 0.4,
+        ..default()
+    });
+    for definition in &level.obstacles {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(definition.position.extend(0.0))
+                    .with_scale(definition.size.extend(OBSTACLE_DEPTH)),
+                ..default()
+            },
+            Obstacle {
+                origin: definition.position,
+                half_size: definition.size / 2.0,
+                motion: definition.motion,
+                elapsed: 0.0,
+                near: false,
+                touched: false,
+            },
+            LevelEntity,
+        ));
+    }
+}
+
+fn move_obstacles(mut query: Query<(&mut Transform, &mut Obstacle)>, time_scale: Res<TimeScale>) {
+    for (mut transform, mut obstacle) in &mut query {
+        obstacle.elapsed += time_scale.step();
+        match obstacle.motion {
+            Motion::Rotating(degrees) => {
+                transform.rotation = Quat::from_rotation_z((degrees * obstacle.elapsed).to_radians());
+            }
+            Motion::Oscillating { travel, period } => {
+                let offset = travel * (TAU * obstacle.elapsed / period).sin();
+                transform.translation = (obstacle.origin + offset).extend(0.0);
+            }
+        }
+    }
+}
+
+// Die Hindernisse können schräg stehen, deshalb wird der Ball in ihr eigenes Koordinatensystem gedreht und dort
+// der nächste Punkt des Rechtecks gesucht. Beim Abprallen zählt die Bewegung der Oberfläche mit, der Ball behält
+// aber sein Tempo und fliegt nur in eine schwer vorhersehbare Richtung weiter.
+fn obstacle_collision(
+    mut ball_query: Query<(&mut Transform, &mut Kinematic), With<Ball>>,
+    mut obstacle_query: Query<(&Transform, &mut Obstacle), Without<Ball>>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for (mut ball_transform, mut body) in &mut ball_query {
+        let ball_radius = ball_transform.scale.x / 2.0;
+        for (obstacle_transform, mut obstacle) in &mut obstacle_query {
+            let center = obstacle_transform.translation.truncate();
+            let rotation = obstacle_transform.rotation;
+            let local = (rotation.inverse() * (ball_transform.translation.truncate() - center).extend(0.0)).truncate();
+            let closest = local.clamp(-obstacle.half_size, obstacle.half_size);
+            let gap = local.distance(closest) - ball_radius;
+
+            if gap < 0.0 {
+                obstacle.touched = true;
+                // Steckt die Mitte des Balls schon im Rechteck, geht es auf dem kürzesten Weg hinaus.
+                let local_normal = if local == closest {
+                    let depth = obstacle.half_size - local.abs();
+                    if depth.x < depth.y { Vec2::X * local.x.signum() } else { Vec2::Y * local.y.signum() }
+                } else {
+                    (local - closest).normalize()
+                };
+                let normal = (rotation * local_normal.extend(0.0)).truncate();
+                let contact = (rotation * closest.extend(0.0)).truncate();
+                let relative = body.velocity.truncate() - obstacle.surface_velocity(contact);
+                let approach = relative.dot(normal);
+                if approach < 0.0 {
+                    let speed = body.velocity.length();
+                    let deflected = body.velocity.truncate() - 2.0 * approach * normal;
+                    body.velocity = (deflected.normalize_or_zero() * speed).extend(body.velocity.z);
+                }
+                let position = center + contact + normal * ball_radius;
+                ball_transform.translation = position.extend(ball_transform.translation.z);
+                continue;
+            }
+
+            if gap < THREAD_MARGIN {
+                obstacle.near = true;
+            } else if obstacle.near {
+                if !obstacle.touched {
+                    scoreboard.score += THREAD_SCORE;
+                    sounds.send(PlaySound(Sfx::Thread));
+                }
+                obstacle.near = false;
+                obstacle.touched = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::assist::Assists;
+use crate::config::GameConfig;
+use crate::easing::Ease;
+use crate::effect::{Stacking, TimedEffect, TimedEffectPlugin};
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::shop::Upgrades;
+use crate::tween::{Lens, LensPlugin, Tween};
+use crate::{phase_set, Arena, Mutators, Paddle, PaddleHit, Phase, TimeScale};
+
+const EXPAND_FACTOR: f32 = 1.6;
+const EXPAND_DURATION: f32 = 10.0;
+// Breitenänderung pro Sekunde, damit das Paddle nicht schlagartig springt.
+const RESIZE_SPEED: f32 = 2.0;
+// Beim Treffer wird das Paddle kurz auf diesen Anteil seiner Höhe gestaucht und federt zurück.
+const SQUASH_HEIGHT: f32 = 0.7;
+const SQUASH_DURATION: f32 = 0.25;
+
+// Fordert eine zeitlich begrenzte Größenänderung des Paddles an. Wie sie mit einer laufenden umgeht, regelt Stacking.
+pub struct ResizePaddle {
+    pub factor: f32,
+    pub duration: f32,
+}
+
+#[derive(Component)]
+pub struct PaddleResize {
+    pub factor: f32,
+    pub duration: Timer,
+}
+
+impl TimedEffect for PaddleResize {
+    fn timer(&self) -> &Timer {
+        &self.duration
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.duration
+    }
+
+    fn kind(&self) -> PowerUpKind {
+        if self.factor > 1.0 { PowerUpKind::Expand } else { PowerUpKind::Shrink }
+    }
+}
+
+// Ändert nur die Höhe, die Breite gehört tween_paddle_width.
+struct HeightLens {
+    start: f32,
+    end: f32,
+}
+
+impl Lens for HeightLens {
+    type Target = Transform;
+
+    fn apply(&self, transform: &mut Transform, ratio: f32) {
+        transform.scale.y = self.start + (self.end - self.start) * ratio;
+    }
+}
+
+pub struct PaddleSizePlugin;
+
+impl Plugin for PaddleSizePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ResizePaddle>()
+            .add_plugin(TimedEffectPlugin::<PaddleResize>::default())
+            .add_plugin(LensPlugin::<HeightLens>::default())
+            .add_system(squash_paddle)
+            .add_system(apply_expand_powerup)
+            .add_system(start_resize.after(apply_expand_powerup))
+            .add_system_set(phase_set(Phase::Input).with_system(tween_paddle_width));
+    }
+}
+
+fn apply_expand_powerup(mut events: EventReader<PowerUpCollected>, mut resize_events: EventWriter<ResizePaddle>) {
+    for PowerUpCollected(kind) in events.iter() {
+        if *kind == PowerUpKind::Expand {
+            resize_events.send(ResizePaddle {
+                factor: EXPAND_FACTOR,
+                duration: EXPAND_DURATION,
+            });
+        }
+    }
+}
+
+fn start_resize(
+    mut commands: Commands,
+    mut events: EventReader<ResizePaddle>,
+    paddle_query: Query<(Entity, Option<&PaddleResize>), With<Paddle>>,
+) {
+    let Ok((paddle, resize)) = paddle_query.get_single() else { return };
+    // Mehrere Ereignisse im selben Frame sehen alle noch den alten Zustand, gezählt wird deshalb hier mit.
+    let mut active = resize.map(|resize| resize.factor);
+    for event in events.iter() {
+        if Stacking::resize(active, event.factor) == Stacking::Cancel {
+            commands.entity(paddle).remove::<PaddleResize>();
+            active = None;
+            continue;
+        }
+        commands.entity(paddle).insert(PaddleResize {
+            factor: event.factor,
+            duration: Timer::from_seconds(event.duration, TimerMode::Once),
+        });
+        active = Some(event.factor);
+    }
+}
+
+// Die Breite läuft gleichmäßig auf den Zielwert zu. Ohne Effekt ist das die Breite aus den Shop-Upgrades, Hilfen
+// und Herausforderungen.
+fn tween_paddle_width(
+    mut query: Query<(&mut Transform, Option<&PaddleResize>), With<Paddle>>,
+    time_scale: Res<TimeScale>,
+    upgrades: Res<Upgrades>,
+    assists: Res<Assists>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    mutators: Res<Mutators>,
+) {
+    let Ok((mut transform, resize)) = query.get_single_mut() else { return };
+    let target = upgrades.paddle_width(&config)
+        * assists.paddle_width_factor()
+        * mutators.challenges.paddle_width_factor()
+        * resize.map_or(1.0, |resize| resize.factor);
+    let max_change = RESIZE_SPEED * time_scale.step();
+    let width = transform.scale.x + (target - transform.scale.x).clamp(-max_change, max_change);
+    transform.scale.x = width;
+    // Wächst das Paddle direkt an der Wand, wird es wieder ins Spielfeld geschoben.
+    transform.translation.x = arena.clamp_paddle_x(transform.translation.x, width);
+}
+
+// Der Ball ist beim Stauchen schon wieder unterwegs nach oben, die kurz kleinere Höhe ändert am Treffen nichts.
+fn squash_paddle(
+    mut commands: Commands,
+    mut paddle_hits: EventReader<PaddleHit>,
+    query: Query<Entity, With<Paddle>>,
+    config: Res<GameConfig>,
+    settings: Res<AccessibilitySettings>,
+) {
+    if paddle_hits.iter().count() == 0 || !settings.allows_motion() {
+        return;
+    }
+    let Ok(paddle) = query.get_single() else { return };
+    let squash = HeightLens {
+        start: config.paddle_size.y * SQUASH_HEIGHT,
+        end: config.paddle_size.y,
+    };
+    commands.entity(paddle).insert(Tween::new(squash, SQUASH_DURATION).with_ease(Ease::OutBack));
+}
@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::GameState;
+
+// Ein Vorrat an kurzlebigen Entities einer Art, etwa Trümmer oder Punkte einer Spur. Statt sie zu despawnen,
+// werden sie nur versteckt und beim nächsten Mal mit neuen Werten wieder eingesetzt. So bleiben ihre Komponenten
+// und damit ihr Archetyp gleich, und eine Kettenreaktion spawnt nicht hunderte Entities in einem Frame.
+// Systeme, die über T laufen, überspringen unsichtbare Entities, denn die liegen gerade im Vorrat.
+#[derive(Resource)]
+pub struct Pool<T: Component> {
+    free: Vec<Entity>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Component> Default for Pool<T> {
+    fn default() -> Self {
+        Pool {
+            free: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> Pool<T> {
+    // Eine freie Entity, falls es eine gibt. Sie trägt noch alle alten Komponenten, der Aufrufer überschreibt
+    // die, die sich ändern, und macht sie wieder sichtbar.
+    pub fn take(&mut self) -> Option<Entity> {
+        self.free.pop()
+    }
+
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity).insert(Visibility::INVISIBLE);
+        self.free.push(entity);
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+}
+
+pub struct PoolPlugin<T: Component>(PhantomData<T>);
+
+impl<T: Component> Default for PoolPlugin<T> {
+    fn default() -> Self {
+        PoolPlugin(PhantomData)
+    }
+}
+
+impl<T: Component> Plugin for PoolPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Pool<T>>()
+            .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(clear_pool::<T>));
+    }
+}
+
+// Gepoolte Entities gehören zum Level und werden mit ihm despawnt. Danach darf nichts mehr auf sie verweisen.
+fn clear_pool<T: Component>(mut pool: ResMut<Pool<T>>) {
+    pool.free.clear();
+}
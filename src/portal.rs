@@ -0,0 +1,157 @@
+use std::f32::consts::FRAC_PI_2;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{Campaign, Level};
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, Ball, GameState, Kinematic, LevelEntity, Phase, TimeScale};
+
+// So lange kann ein Ball nach einem Teleport kein Portal mehr betreten, sonst würde er sofort zurückgeschickt.
+const PORTAL_COOLDOWN: f32 = 0.5;
+
+fn default_radius() -> f32 {
+    0.4
+}
+
+// Ein Portalpaar aus der Level-Datei. `rotation` (in Grad) dreht die Flugrichtung beim Durchqueren.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PortalPairDefinition {
+    pub a: Vec2,
+    pub b: Vec2,
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+#[derive(Component)]
+struct Portal {
+    partner: Entity,
+    radius: f32,
+    rotation: f32,
+}
+
+#[derive(Component, Deref, DerefMut)]
+struct PortalCooldown(Timer);
+
+pub struct PortalPlugin;
+
+impl Plugin for PortalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_portals))
+            // Ein Portal ist eine Kollision wie jede andere, aber der Ball soll schon am Ziel abprallen.
+            .add_system_set(
+                phase_set(Phase::Collision)
+                    .with_system(tick_portal_cooldowns.before(teleport_balls))
+                    .with_system(teleport_balls.before(crate::check_for_collision)),
+            );
+    }
+}
+
+fn spawn_portals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = levels.get(campaign.current_handle()) else { return };
+    if level.portals.is_empty() {
+        return;
+    }
+
+    let mesh = meshes.add(
+        shape::Torus {
+            radius: 1.0,
+            ring_radius: 0.15,
+            ..default()
+        }
+        .into(),
+    );
+    let entry_material = materials.add(Color::ORANGE.into());
+    let exit_material = materials.add(Color::CYAN.into());
+
+    for definition in &level.portals {
+        // Der Torus liegt standardmäßig in der XZ-Ebene und wird zur Kamera hin aufgestellt.
+        let transform = |position: Vec2| {
+            Transform::from_translation(position.extend(0.0))
+                .with_rotation(Quat::from_rotation_x(FRAC_PI_2))
+                .with_scale(Vec3::splat(definition.radius))
+        };
+        let a = commands
+            .spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: entry_material.clone(),
+                    transform: transform(definition.a),
+                    ..default()
+                },
+                LevelEntity,
+            ))
+            .id();
+        // Durch das zweite Portal geht es mit der umgekehrten Drehung zurück.
+        let b = commands
+            .spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: exit_material.clone(),
+                    transform: transform(definition.b),
+                    ..default()
+                },
+                Portal {
+                    partner: a,
+                    radius: definition.radius,
+                    rotation: -definition.rotation.to_radians(),
+                },
+                LevelEntity,
+            ))
+            .id();
+        commands.entity(a).insert(Portal {
+            partner: b,
+            radius: definition.radius,
+            rotation: definition.rotation.to_radians(),
+        });
+    }
+}
+
+fn tick_portal_cooldowns(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PortalCooldown)>,
+    time_scale: Res<TimeScale>,
+) {
+    for (entity, mut cooldown) in &mut query {
+        if cooldown.tick(Duration::from_secs_f32(time_scale.step())).finished() {
+            commands.entity(entity).remove::<PortalCooldown>();
+        }
+    }
+}
+
+// Ein Ball, dessen Mittelpunkt in einem Portal liegt, kommt am Partnerportal wieder heraus.
+#[allow(clippy::type_complexity)]
+fn teleport_balls(
+    mut commands: Commands,
+    mut ball_query: Query<(Entity, &mut Transform, &mut Kinematic), (With<Ball>, Without<PortalCooldown>)>,
+    portal_query: Query<(&Transform, &Portal), Without<Ball>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for (ball, mut ball_transform, mut body) in &mut ball_query {
+        for (portal_transform, portal) in &portal_query {
+            let distance = ball_transform.translation.truncate().distance(portal_transform.translation.truncate());
+            if distance > portal.radius {
+                continue;
+            }
+            let Ok((partner_transform, _)) = portal_query.get(portal.partner) else { continue };
+
+            ball_transform.translation.x = partner_transform.translation.x;
+            ball_transform.translation.y = partner_transform.translation.y;
+            body.velocity = Quat::from_rotation_z(portal.rotation) * body.velocity;
+            commands
+                .entity(ball)
+                .insert(PortalCooldown(Timer::from_seconds(PORTAL_COOLDOWN, TimerMode::Once)));
+            sounds.send(PlaySound(Sfx::Portal));
+            break;
+        }
+    }
+}
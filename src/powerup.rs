@@ -0,0 +1,299 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::accessibility::{AccessibilitySettings, Palette};
+use crate::assist::{Assist, Assists};
+use crate::collision::{CollisionLayers, Layer};
+use crate::config::GameConfig;
+use crate::difficulty::Difficulty;
+use crate::easing::Ease;
+use crate::level::BrickKind;
+use crate::modding;
+use crate::rng::GameRng;
+use crate::shop::Upgrades;
+use crate::sound::{PlaySound, Sfx};
+use crate::toast::ShowToast;
+use crate::tween::{ScaleLens, Tween};
+use crate::{phase_set, Arena, Ball, BrickDestroyed, Kinematic, LevelEntity, Paddle, Phase, Piercing, TimeScale};
+
+const POWERUP_FALL_SPEED: f32 = 2.5;
+const POWERUP_SIZE: Vec3 = Vec3::new(0.4, 0.4, 0.4);
+// So lange wächst ein Power-Up aus dem zerstörten Brick auf seine Größe.
+const POP_IN_DURATION: f32 = 0.3;
+const FIREBALL_DURATION: f32 = 6.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerUpKind {
+    Fireball,
+    Shield,
+    Magnet,
+    Expand,
+    // Ein großer Ball trifft leichter, ein kleiner ist schwerer zu halten und bringt dafür mehr Punkte.
+    GiantBall,
+    MiniBall,
+    // Gefahren fallen aus Strafbricks und sollten besser nicht eingesammelt werden.
+    Shrink,
+    SpeedUp,
+    ReverseControls,
+    // Münzen werden im Shop zwischen den Leveln ausgegeben.
+    Coin,
+    // Ein Power-Up aus einem Mod. Es fällt nur, wenn ein Skript es fallen lässt, und wirkt nur über dessen Callback.
+    Mod(u8),
+    // Kein Power-Up, sondern der Smash aus der Energieleiste. Er fällt nie, steht aber als eigener Effekt in der
+    // Anzeige, obwohl er den Ball wie ein Feuerball durchschlagend macht.
+    Smash,
+}
+
+impl PowerUpKind {
+    const BENEFITS: [PowerUpKind; 6] = [
+        PowerUpKind::Fireball,
+        PowerUpKind::Shield,
+        PowerUpKind::Magnet,
+        PowerUpKind::Expand,
+        PowerUpKind::GiantBall,
+        PowerUpKind::MiniBall,
+    ];
+    const HAZARDS: [PowerUpKind; 3] = [PowerUpKind::Shrink, PowerUpKind::SpeedUp, PowerUpKind::ReverseControls];
+
+    pub fn is_hazard(self) -> bool {
+        PowerUpKind::HAZARDS.contains(&self)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUpKind::Fireball => "Feuerball",
+            PowerUpKind::Shield => "Schild",
+            PowerUpKind::Magnet => "Magnet",
+            PowerUpKind::Expand => "Breites Paddle",
+            PowerUpKind::GiantBall => "Riesenball",
+            PowerUpKind::MiniBall => "Miniball (doppelte Punkte)",
+            PowerUpKind::Shrink => "Schmales Paddle",
+            PowerUpKind::SpeedUp => "Schneller Ball",
+            PowerUpKind::ReverseControls => "Vertauschte Steuerung",
+            PowerUpKind::Coin => "Münze",
+            PowerUpKind::Mod(index) => &modding::registry().powerup(index).name,
+            PowerUpKind::Smash => "Smash",
+        }
+    }
+
+    // Die Farben ohne Farbsehschwäche-Modus, alle anderen Paletten stehen in accessibility.rs.
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::Fireball => Color::ORANGE_RED,
+            PowerUpKind::Shield => Color::CYAN,
+            PowerUpKind::Magnet => Color::FUCHSIA,
+            PowerUpKind::Expand => Color::LIME_GREEN,
+            PowerUpKind::GiantBall => Color::BLUE,
+            PowerUpKind::MiniBall => Color::PINK,
+            // Warnfarben: Rot, Gelb und Schwarz stechen zwischen den positiven Power-Ups heraus.
+            PowerUpKind::Shrink => Color::RED,
+            PowerUpKind::SpeedUp => Color::YELLOW,
+            PowerUpKind::ReverseControls => Color::BLACK,
+            PowerUpKind::Coin => Color::GOLD,
+            PowerUpKind::Mod(index) => modding::registry().powerup(index).color,
+            // Die Farbe der vollen Energieleiste.
+            PowerUpKind::Smash => Color::rgb(1.0, 0.35, 0.0),
+        }
+    }
+
+    // Ein kurzes Zeichen für das Symbol in der Anzeige der laufenden Effekte.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            PowerUpKind::Fireball => "F",
+            PowerUpKind::Shield => "S",
+            PowerUpKind::Magnet => "M",
+            PowerUpKind::Expand => "<>",
+            PowerUpKind::GiantBall => "O",
+            PowerUpKind::MiniBall => "o",
+            PowerUpKind::Shrink => "><",
+            PowerUpKind::SpeedUp => ">>",
+            PowerUpKind::ReverseControls => "R",
+            PowerUpKind::Coin => "$",
+            PowerUpKind::Mod(_) => "?",
+            PowerUpKind::Smash => "X",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct PowerUp(pub PowerUpKind);
+
+// Wird gesendet, wenn das Paddle ein Power-Up eingesammelt hat. Die einzelnen Effekte reagieren darauf.
+pub struct PowerUpCollected(pub PowerUpKind);
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PowerUpCollected>()
+            .add_system(drop_powerups)
+            .add_system(apply_fireball)
+            .add_system(announce_powerups)
+            // Power-Ups von Mods und aus dem Tutorial kommen erst mit dem Ende des Updates in die Welt.
+            .add_system_to_stage(CoreStage::PostUpdate, pop_in_powerups)
+            .add_system_set(phase_set(Phase::Movement).with_system(fall_powerups.after(crate::apply_velocity)))
+            .add_system_set(phase_set(Phase::Collision).with_system(collect_powerups));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn drop_powerups(
+    mut commands: Commands,
+    mut events: EventReader<BrickDestroyed>,
+    upgrades: Res<Upgrades>,
+    config: Res<GameConfig>,
+    palette: Res<Palette>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+    mut game_rng: ResMut<GameRng>,
+    (assists, difficulty): (Res<Assists>, Res<Difficulty>),
+) {
+    let rng = &mut *game_rng;
+    let adaptive = if assists.is_enabled(Assist::Adaptive) { difficulty.drop_chance_factor(&config) } else { 1.0 };
+    let powerup_chance = (upgrades.drop_chance(&config) * adaptive).clamp(0.0, 1.0);
+    for event in events.iter() {
+        // Strafbricks lassen immer eine Gefahr fallen, normale Bricks nur manchmal ein Power-Up.
+        let kind = if event.kind == BrickKind::Penalty {
+            *PowerUpKind::HAZARDS.choose(rng).unwrap()
+        } else if rng.gen_bool(powerup_chance) {
+            *PowerUpKind::BENEFITS.choose(rng).unwrap()
+        } else if rng.gen_bool(config.coin_drop_chance.clamp(0.0, 1.0)) {
+            PowerUpKind::Coin
+        } else {
+            continue;
+        };
+        let mesh = mesh
+            .get_or_insert_with(|| meshes.add(shape::Cube::default().into()))
+            .clone();
+        spawn_powerup(&mut commands, mesh, &mut materials, &palette, kind, event.position);
+    }
+}
+
+// Lässt ein Power-Up an der Position nach unten fallen, auch außerhalb von zerstörten Bricks.
+pub fn spawn_powerup(
+    commands: &mut Commands,
+    mesh: Handle<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    palette: &Palette,
+    kind: PowerUpKind,
+    position: Vec3,
+) {
+    let color = palette.powerup(kind);
+    commands.spawn((
+        PbrBundle {
+            mesh,
+            material: materials.add(StandardMaterial {
+                base_color: color,
+                emissive: color * 0.5,
+                ..default()
+            }),
+            transform: Transform::from_translation(position)
+                .with_scale(POWERUP_SIZE)
+                .with_rotation(Quat::from_rotation_z(PI / 4.0)),
+            ..default()
+        },
+        PowerUp(kind),
+        Kinematic::moving(Vec3::NEG_Y * POWERUP_FALL_SPEED),
+        CollisionLayers::from(Layer::Pickup),
+        LevelEntity,
+    ));
+}
+
+// Das Einsammeln rechnet immer mit der vollen Größe, das Aufploppen ist nur zu sehen.
+fn pop_in_powerups(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform), Added<PowerUp>>,
+    settings: Res<AccessibilitySettings>,
+) {
+    if !settings.allows_motion() {
+        return;
+    }
+    for (entity, mut transform) in &mut query {
+        let grow = ScaleLens {
+            start: Vec3::ZERO,
+            end: transform.scale,
+        };
+        commands.entity(entity).insert(Tween::new(grow, POP_IN_DURATION).with_ease(Ease::OutBack));
+        transform.scale = Vec3::ZERO;
+    }
+}
+
+// Power-Ups fallen gleichmäßig nach unten, das übernimmt apply_velocity. Hier drehen sie sich und verschwinden,
+// wenn das Paddle sie verpasst.
+fn fall_powerups(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform), With<PowerUp>>,
+    time_scale: Res<TimeScale>,
+    settings: Res<AccessibilitySettings>,
+    arena: Res<Arena>,
+) {
+    for (entity, mut transform) in &mut query {
+        if settings.allows_motion() {
+            transform.rotate_y(time_scale.step() * 2.0);
+        }
+        if transform.translation.y < arena.bottom() - 1.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn collect_powerups(
+    mut commands: Commands,
+    powerup_query: Query<(Entity, &Transform, &PowerUp, &CollisionLayers)>,
+    paddle_query: Query<(&Transform, &CollisionLayers), With<Paddle>>,
+    mut collected_events: EventWriter<PowerUpCollected>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let Ok((paddle_transform, paddle_layers)) = paddle_query.get_single() else { return };
+    for (entity, transform, powerup, layers) in &powerup_query {
+        if !layers.interacts_with(paddle_layers) {
+            continue;
+        }
+        let caught = collide(
+            transform.translation,
+            POWERUP_SIZE.truncate(),
+            paddle_transform.translation,
+            paddle_transform.scale.truncate(),
+        );
+        if caught.is_some() {
+            commands.entity(entity).despawn();
+            collected_events.send(PowerUpCollected(powerup.0));
+            let sound = match powerup.0 {
+                PowerUpKind::Coin => Sfx::Coin,
+                kind if kind.is_hazard() => Sfx::Hazard,
+                _ => Sfx::PowerUp,
+            };
+            sounds.send(PlaySound(sound));
+        }
+    }
+}
+
+// Jedes eingesammelte Power-Up wird mit Namen eingeblendet, damit man weiß, was gerade wirkt.
+fn announce_powerups(mut events: EventReader<PowerUpCollected>, mut toasts: EventWriter<ShowToast>) {
+    for PowerUpCollected(kind) in events.iter() {
+        toasts.send(ShowToast(kind.name().to_string()));
+    }
+}
+
+// Der Feuerball lässt alle Bälle eine Zeit lang durch die Bricks hindurch brennen.
+fn apply_fireball(
+    mut commands: Commands,
+    mut events: EventReader<PowerUpCollected>,
+    ball_query: Query<Entity, With<Ball>>,
+) {
+    for PowerUpCollected(kind) in events.iter() {
+        if *kind != PowerUpKind::Fireball {
+            continue;
+        }
+        for ball in &ball_query {
+            commands
+                .entity(ball)
+                .insert(Piercing(Timer::from_seconds(FIREBALL_DURATION, TimerMode::Once), PowerUpKind::Fireball));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::env;
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use bevy::prelude::*;
+use serde_json::{json, Value};
+
+use crate::format;
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::{GameState, Scoreboard};
+
+// Die ID der Discord-Anwendung, unter der das Spiel angezeigt wird. Ohne sie bleibt die Anzeige aus.
+const CLIENT_ID_VARIABLE: &str = "KUERTEIL_DISCORD_CLIENT_ID";
+// Discord öffnet je laufender Instanz einen eigenen Kanal mit einer Nummer von 0 bis 9.
+const IPC_SLOTS: u32 = 10;
+const OPCODE_HANDSHAKE: u32 = 0;
+const OPCODE_FRAME: u32 = 1;
+const OPCODE_CLOSE: u32 = 2;
+
+// Was Freunde in Discord unter dem Spiel sehen, zwei Zeilen.
+struct Activity {
+    details: String,
+    state: String,
+}
+
+// Die Verbindung zu Discord lebt in einem eigenen Thread, damit ein hängender Client das Spiel nicht aufhält.
+#[derive(Resource)]
+struct Presence(Sender<Activity>);
+
+pub struct PresencePlugin;
+
+impl Plugin for PresencePlugin {
+    fn build(&self, app: &mut App) {
+        let Ok(client_id) = env::var(CLIENT_ID_VARIABLE) else {
+            info!("{CLIENT_ID_VARIABLE} ist nicht gesetzt, die Discord-Anzeige bleibt aus");
+            return;
+        };
+        let (sender, receiver) = mpsc::channel();
+        if let Err(error) = thread::Builder::new().name("presence".into()).spawn(move || publish(client_id, receiver)) {
+            warn!("Die Discord-Anzeige konnte nicht gestartet werden: {error}");
+            return;
+        }
+        app.insert_resource(Presence(sender)).add_system(update_presence);
+    }
+}
+
+// Bei jedem Zustandswechsel geht der aktuelle Stand an Discord, auch beim Betreten und Verlassen eines Levels.
+fn update_presence(
+    presence: Res<Presence>,
+    state: Res<State<GameState>>,
+    mode: Res<GameMode>,
+    campaign: Option<Res<Campaign>>,
+    levels: Res<Assets<Level>>,
+    scoreboard: Res<Scoreboard>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let level = campaign.and_then(|campaign| levels.get(campaign.current_handle()).map(|level| level.name.clone()));
+    let score = format!("{} Punkte", format::number(scoreboard.score));
+    let activity = match (state.current(), level) {
+        (GameState::Playing | GameState::LevelIntro | GameState::LogInspector, Some(level)) => Activity {
+            details: format!("{}: {level}", mode.name()),
+            state: score,
+        },
+        (GameState::Results | GameState::Reward | GameState::Shop | GameState::Standings, _) => Activity {
+            details: format!("{}: zwischen zwei Leveln", mode.name()),
+            state: score,
+        },
+        (GameState::Editor, _) => Activity {
+            details: "Baut ein Level".to_string(),
+            state: String::new(),
+        },
+        _ => Activity {
+            details: "Im Menü".to_string(),
+            state: String::new(),
+        },
+    };
+    // Ist der Thread beendet, gibt es nichts mehr zu tun.
+    let _ = presence.0.send(activity);
+}
+
+// Kommen mehrere Stände schnell hintereinander, wird nur der letzte gesendet. Ist Discord nicht erreichbar, wird es
+// beim nächsten Stand wieder versucht.
+fn publish(client_id: String, updates: Receiver<Activity>) {
+    let mut connection: Option<Connection> = None;
+    let mut reported = false;
+    while let Ok(activity) = updates.recv() {
+        let activity = updates.try_iter().last().unwrap_or(activity);
+        if connection.is_none() {
+            match Connection::open(&client_id) {
+                Ok(opened) => connection = Some(opened),
+                Err(error) if !reported => {
+                    info!("Discord ist nicht erreichbar: {error}");
+                    reported = true;
+                }
+                Err(_) => {}
+            }
+        }
+        let Some(open) = &mut connection else { continue };
+        if let Err(error) = open.set_activity(&activity) {
+            info!("Die Verbindung zu Discord ist abgebrochen: {error}");
+            connection = None;
+        }
+    }
+}
+
+#[cfg(unix)]
+type Stream = std::os::unix::net::UnixStream;
+#[cfg(windows)]
+type Stream = std::fs::File;
+
+// Der Kanal liegt unter Unix als Socket im temporären Verzeichnis, unter Windows als Named Pipe.
+#[cfg(unix)]
+fn connect() -> io::Result<Stream> {
+    let directories = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .filter_map(|variable| env::var(variable).ok())
+        .chain(["/tmp".to_string()]);
+    for directory in directories {
+        for slot in 0..IPC_SLOTS {
+            if let Ok(stream) = Stream::connect(format!("{directory}/discord-ipc-{slot}")) {
+                return Ok(stream);
+            }
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "kein Discord-Kanal gefunden"))
+}
+
+#[cfg(windows)]
+fn connect() -> io::Result<Stream> {
+    for slot in 0..IPC_SLOTS {
+        let pipe = std::fs::OpenOptions::new().read(true).write(true).open(format!(r"\\.\pipe\discord-ipc-{slot}"));
+        if let Ok(pipe) = pipe {
+            return Ok(pipe);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "kein Discord-Kanal gefunden"))
+}
+
+// Jede Nachricht ist ein Rahmen aus Opcode und Länge, beide als u32 in Little Endian, gefolgt von JSON.
+struct Connection {
+    stream: Stream,
+    nonce: u64,
+}
+
+impl Connection {
+    fn open(client_id: &str) -> io::Result<Connection> {
+        let mut connection = Connection { stream: connect()?, nonce: 0 };
+        connection.send(OPCODE_HANDSHAKE, json!({ "v": 1, "client_id": client_id }))?;
+        connection.receive()?;
+        Ok(connection)
+    }
+
+    fn set_activity(&mut self, activity: &Activity) -> io::Result<()> {
+        self.nonce += 1;
+        // Discord lehnt leere Zeilen ab, sie fallen weg.
+        let mut fields = json!({ "details": activity.details });
+        if !activity.state.is_empty() {
+            fields["state"] = Value::from(activity.state.as_str());
+        }
+        let command = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": { "pid": std::process::id(), "activity": fields },
+            "nonce": self.nonce.to_string(),
+        });
+        self.send(OPCODE_FRAME, command)?;
+        self.receive()
+    }
+
+    fn send(&mut self, opcode: u32, payload: Value) -> io::Result<()> {
+        let body = payload.to_string();
+        let mut frame = Vec::with_capacity(8 + body.len());
+        frame.extend_from_slice(&opcode.to_le_bytes());
+        frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        frame.extend_from_slice(body.as_bytes());
+        self.stream.write_all(&frame)
+    }
+
+    // Die Antwort wird nur gelesen, damit der Kanal nicht vollläuft. Schließt Discord die Verbindung, ist das ein
+    // Fehler.
+    fn receive(&mut self) -> io::Result<()> {
+        let mut header = [0; 8];
+        self.stream.read_exact(&mut header)?;
+        let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let mut body = vec![0; length as usize];
+        self.stream.read_exact(&mut body)?;
+        if opcode == OPCODE_CLOSE {
+            return Err(io::Error::new(io::ErrorKind::ConnectionAborted, String::from_utf8_lossy(&body).into_owned()));
+        }
+        Ok(())
+    }
+}
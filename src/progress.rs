@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::GameConfig;
+use crate::grade::Grade;
+use crate::mode::GameMode;
+use crate::save::SaveData;
+use crate::shop::Upgrades;
+use crate::{GameState, LevelCleared, Lives, Scoreboard};
+
+pub const MAX_STARS: u32 = 3;
+
+// Das beste Ergebnis in einem Level der Kampagne.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct LevelRecord {
+    pub best_score: usize,
+    pub stars: u32,
+    // Die jemals erreichten Nebenziele des Levels, ein Bit je Ziel in der Reihenfolge der Level-Datei.
+    #[serde(default)]
+    pub objectives: u32,
+    // Die beste Note aus der Abrechnung, nur in Leveln mit Sollwerten.
+    #[serde(default)]
+    pub grade: Option<Grade>,
+}
+
+impl LevelRecord {
+    pub fn objective_stars(&self) -> u32 {
+        self.objectives.count_ones()
+    }
+}
+
+// Ergebnisse je Level der Kampagne. Ein Level ist freigeschaltet, wenn es das erste ist oder das vorherige geschafft wurde.
+#[derive(Serialize, Deserialize, Default)]
+pub struct LevelProgress(HashMap<usize, LevelRecord>);
+
+impl LevelProgress {
+    pub fn record(&self, index: usize) -> Option<&LevelRecord> {
+        self.0.get(&index)
+    }
+
+    pub fn is_unlocked(&self, index: usize) -> bool {
+        index == 0 || self.0.contains_key(&(index - 1))
+    }
+
+    // Alle Sterne aus Nebenzielen über die ganze Kampagne. Sie schalten die Bonuslevel frei.
+    pub fn objective_stars(&self) -> u32 {
+        self.0.values().map(LevelRecord::objective_stars).sum()
+    }
+
+    pub fn complete_objectives(&mut self, index: usize, objectives: impl Iterator<Item = usize>) {
+        let record = self.0.entry(index).or_default();
+        record.objectives |= objectives.fold(0, |bits, objective| bits | 1 << objective);
+    }
+
+    pub fn improve_grade(&mut self, index: usize, grade: Grade) {
+        let record = self.0.entry(index).or_default();
+        record.grade = record.grade.max(Some(grade));
+    }
+
+    fn insert(&mut self, index: usize, score: usize, stars: u32) {
+        let record = self.0.entry(index).or_default();
+        record.best_score = record.best_score.max(score);
+        record.stars = record.stars.max(stars);
+    }
+}
+
+// Punktestand zu Beginn des laufenden Levels, damit nur die Punkte dieses Levels in seinen Rekord eingehen.
+#[derive(Resource, Default)]
+pub struct LevelStartScore(pub usize);
+
+pub struct ProgressPlugin;
+
+impl Plugin for ProgressPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelStartScore>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Playing).with_system(remember_start_score.after(crate::spawn_level)),
+            )
+            .add_system(record_level);
+    }
+}
+
+fn remember_start_score(scoreboard: Res<Scoreboard>, mut start: ResMut<LevelStartScore>) {
+    start.0 = scoreboard.score;
+}
+
+// Drei Sterne ohne verlorenes Leben, für jedes verlorene einer weniger, mindestens aber einer.
+fn stars(lives: u32, starting_lives: u32) -> u32 {
+    MAX_STARS.saturating_sub(starting_lives.saturating_sub(lives)).max(1)
+}
+
+// Nur die Kampagne zählt für den Fortschritt, im Zeitangriff und in der täglichen Herausforderung gibt es keine Leben zu halten.
+#[allow(clippy::too_many_arguments)]
+fn record_level(
+    mut events: EventReader<LevelCleared>,
+    mode: Res<GameMode>,
+    scoreboard: Res<Scoreboard>,
+    start: Res<LevelStartScore>,
+    lives: Res<Lives>,
+    upgrades: Res<Upgrades>,
+    config: Res<GameConfig>,
+    mut save: ResMut<SaveData>,
+) {
+    for event in events.iter() {
+        if *mode != GameMode::Campaign {
+            continue;
+        }
+        let score = scoreboard.score.saturating_sub(start.0);
+        save.level_progress.insert(event.index, score, stars(lives.0, upgrades.starting_lives(&config)));
+    }
+}
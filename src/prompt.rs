@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::controls::ActiveControls;
+use crate::input_buffer::Action;
+use crate::launch::HeldBall;
+use crate::mode::GameMode;
+use crate::{GameState, LevelEntity};
+
+const GLYPH_FONT_SIZE: f32 = 24.0;
+const LABEL_FONT_SIZE: f32 = 28.0;
+const LABEL_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+// Tasten sehen aus wie eine helle Tastenkappe mit dunkler Schrift, Gamepad-Knöpfe wie der farbige Knopf selbst.
+const KEY_CAP_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+const KEY_TEXT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const BUTTON_TEXT_COLOR: Color = Color::WHITE;
+// Unter diesem Abstand zum unteren Rand steht der Hinweis, über dem Tutorialtext und den Cooldowns.
+const PROMPT_BOTTOM: f32 = 90.0;
+
+// Die Zeile mit Tastensymbol und Aktion, mittig über dem Paddle.
+#[derive(Component)]
+struct ButtonPrompt;
+
+#[derive(Component)]
+struct Glyph;
+
+#[derive(Component)]
+struct GlyphText;
+
+#[derive(Component)]
+struct PromptLabel;
+
+pub struct ButtonPromptPlugin;
+
+impl Plugin for ButtonPromptPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_prompt))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_prompt));
+    }
+}
+
+fn spawn_prompt(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(PROMPT_BOTTOM),
+                        left: Val::Px(0.0),
+                        right: Val::Px(0.0),
+                        ..default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: Visibility::INVISIBLE,
+                ..default()
+            },
+            ButtonPrompt,
+            LevelEntity,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            padding: UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(2.0), Val::Px(2.0)),
+                            margin: UiRect::right(Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    Glyph,
+                ))
+                .with_children(|glyph| {
+                    glyph.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: GLYPH_FONT_SIZE,
+                                color: KEY_TEXT_COLOR,
+                            },
+                        ),
+                        GlyphText,
+                    ));
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: LABEL_FONT_SIZE,
+                        color: LABEL_COLOR,
+                    },
+                ),
+                PromptLabel,
+            ));
+        });
+}
+
+// Welcher Hinweis gerade passt. Bisher ist das nur der Abschuss, solange der Ball auf dem Paddle liegt,
+// auch im ersten Schritt des Tutorials. In der Demo spielt niemand, dort gibt es keinen Hinweis.
+fn current_action(mode: GameMode, ball_held: bool) -> Option<Action> {
+    (ball_held && mode != GameMode::Demo).then_some(Action::Launch)
+}
+
+// Symbol und Beschriftung folgen dem Gerät, mit dem gerade gespielt wird.
+#[allow(clippy::type_complexity)]
+fn update_prompt(
+    mode: Res<GameMode>,
+    controls: Res<ActiveControls>,
+    held_query: Query<(), With<HeldBall>>,
+    mut prompt_query: Query<&mut Visibility, With<ButtonPrompt>>,
+    mut glyph_query: Query<&mut BackgroundColor, With<Glyph>>,
+    mut text_query: Query<&mut Text, (With<GlyphText>, Without<PromptLabel>)>,
+    mut label_query: Query<&mut Text, (With<PromptLabel>, Without<GlyphText>)>,
+) {
+    let Ok(mut visibility) = prompt_query.get_single_mut() else { return };
+    let Some(action) = current_action(*mode, !held_query.is_empty()) else {
+        visibility.is_visible = false;
+        return;
+    };
+    visibility.is_visible = true;
+    let gamepad = controls.is_gamepad();
+    if let (Ok(mut background), Ok(mut glyph_text)) = (glyph_query.get_single_mut(), text_query.get_single_mut()) {
+        let section = &mut glyph_text.sections[0];
+        section.value = action.binding_label(gamepad).to_string();
+        if gamepad {
+            background.0 = action.button_color();
+            section.style.color = BUTTON_TEXT_COLOR;
+        } else {
+            background.0 = KEY_CAP_COLOR;
+            section.style.color = KEY_TEXT_COLOR;
+        }
+    }
+    if let Ok(mut label) = label_query.get_single_mut() {
+        label.sections[0].value = action.name().to_string();
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::grid::BrickGrid;
+use crate::level::BrickKind;
+use crate::theme::ThemeMaterials;
+use crate::{phase_set, Arena, Ball, BrickDestroyed, GridGeometry, LevelEntity, Phase, TimeScale, BRICK_SIZE};
+
+const REGROW_DELAY: f32 = 10.0;
+// So lange vor dem Nachwachsen ist an der Stelle schon ein durchscheinender Keim zu sehen, der bis zur vollen Größe wächst.
+const TELEGRAPH_DURATION: f32 = 2.0;
+const TELEGRAPH_ALPHA: f32 = 0.35;
+// Der Keim pulsiert leicht, damit er zwischen den festen Bricks auffällt.
+const PULSE_SPEED: f32 = 12.0;
+const PULSE_AMOUNT: f32 = 0.08;
+
+// Eine geleerte Zelle, in der ein nachwachsender Brick stand. Die Entity ist zugleich der sichtbare Keim.
+#[derive(Component)]
+struct Regrowing {
+    row: usize,
+    column: usize,
+    timer: Timer,
+}
+
+pub struct RegeneratePlugin;
+
+impl Plugin for RegeneratePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            phase_set(Phase::Scoring)
+                .with_system(plant_seeds)
+                .with_system(regrow_bricks.after(plant_seeds)),
+        );
+    }
+}
+
+fn plant_seeds(
+    mut commands: Commands,
+    mut events: EventReader<BrickDestroyed>,
+    arena: Res<Arena>,
+    theme_materials: Res<ThemeMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut seed_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    let geometry = GridGeometry::new(&arena);
+    for event in events.iter().filter(|event| event.kind == BrickKind::Regenerating) {
+        let Some((row, column)) = geometry.cell_at(event.position.truncate()) else { continue };
+        let (mesh, material) = seed_assets.get_or_insert_with(|| {
+            let brick_material = theme_materials.brick(BrickKind::Regenerating);
+            let mut seed_material = materials.get(&brick_material).cloned().unwrap_or_default();
+            seed_material.base_color.set_a(TELEGRAPH_ALPHA);
+            seed_material.alpha_mode = AlphaMode::Blend;
+            (meshes.add(shape::Cube::default().into()), materials.add(seed_material))
+        });
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(geometry.position(row, column).extend(0.0)).with_scale(Vec3::ZERO),
+                ..default()
+            },
+            Regrowing {
+                row,
+                column,
+                timer: Timer::from_seconds(REGROW_DELAY, TimerMode::Once),
+            },
+            LevelEntity,
+        ));
+    }
+}
+
+// Ist die Zeit um, kommt der Brick ins Raster zurück und sync_bricks spawnt ihn. Steckt gerade ein Ball in der Zelle,
+// wartet der Keim, bis sie frei ist, sonst säße der Ball im Brick fest.
+fn regrow_bricks(
+    mut commands: Commands,
+    mut brick_grid: ResMut<BrickGrid>,
+    mut query: Query<(Entity, &mut Regrowing, &mut Transform), Without<Ball>>,
+    ball_query: Query<&Transform, With<Ball>>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = Duration::from_secs_f32(time_scale.step());
+    for (entity, mut regrowing, mut transform) in &mut query {
+        regrowing.timer.tick(step);
+        let remaining = regrowing.timer.remaining_secs();
+        if remaining > TELEGRAPH_DURATION {
+            continue;
+        }
+        let growth = 1.0 - remaining / TELEGRAPH_DURATION;
+        let pulse = 1.0 + PULSE_AMOUNT * (regrowing.timer.elapsed_secs() * PULSE_SPEED).sin();
+        transform.scale = Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0) * growth * pulse;
+        if !regrowing.timer.finished() {
+            continue;
+        }
+        let blocked = ball_query.iter().any(|ball| {
+            let offset = (ball.translation - transform.translation).truncate().abs();
+            let reach = (BRICK_SIZE.truncate() + ball.scale.truncate()) / 2.0;
+            offset.x < reach.x && offset.y < reach.y
+        });
+        if blocked {
+            continue;
+        }
+        brick_grid.restore(regrowing.row, regrowing.column, BrickKind::Regenerating);
+        commands.entity(entity).despawn();
+    }
+}
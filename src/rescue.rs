@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::sound::{PlaySound, Sfx};
+use crate::{Arena, GameState, LevelEntity};
+
+const FLASH_DURATION: f32 = 0.5;
+const FLASH_COLOR: Color = Color::rgb(0.3, 0.9, 1.0);
+const FLASH_THICKNESS: f32 = 0.08;
+
+// Wird gesendet, wenn check_ball_lost den Ball knapp unter dem Paddle noch gerettet hat.
+pub struct BallRescued {
+    pub position: Vec3,
+}
+
+// Ob die Rettung in diesem Level noch zur Verfügung steht. Sie gibt es nur einmal pro Level.
+#[derive(Resource, Default)]
+pub struct RescueCharge {
+    pub available: bool,
+}
+
+// Eine leuchtende Linie auf Höhe der Rettung, die schnell verblasst.
+#[derive(Component)]
+struct RescueFlash(Timer);
+
+pub struct RescuePlugin;
+
+impl Plugin for RescuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BallRescued>()
+            .init_resource::<RescueCharge>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(recharge))
+            .add_system(flash_rescue)
+            .add_system(fade_flash);
+    }
+}
+
+fn recharge(mut charge: ResMut<RescueCharge>) {
+    charge.available = true;
+}
+
+fn flash_rescue(
+    mut commands: Commands,
+    mut events: EventReader<BallRescued>,
+    arena: Res<Arena>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for event in events.iter() {
+        sounds.send(PlaySound(Sfx::Rescue));
+        let center = Vec3::new((arena.left() + arena.right()) / 2.0, event.position.y, event.position.z);
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(shape::Cube::default().into()),
+                material: materials.add(StandardMaterial {
+                    base_color: FLASH_COLOR,
+                    emissive: FLASH_COLOR,
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation(center).with_scale(Vec3::new(
+                    arena.width,
+                    FLASH_THICKNESS,
+                    FLASH_THICKNESS,
+                )),
+                ..default()
+            },
+            RescueFlash(Timer::from_seconds(FLASH_DURATION, TimerMode::Once)),
+            LevelEntity,
+        ));
+    }
+}
+
+fn fade_flash(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut RescueFlash, &Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, handle) in &mut query {
+        if flash.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            materials.remove(handle);
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color.set_a(flash.0.percent_left());
+        }
+    }
+}
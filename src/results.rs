@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::focus::Activated;
+use crate::format;
+use crate::grade::Grade;
+use crate::level::{Campaign, Level};
+use crate::menu::{button_text, spawn_button, spawn_screen, MenuButton};
+use crate::mode::GameMode;
+use crate::near_miss::ClosestCall;
+use crate::objective::ObjectiveTracker;
+use crate::progress::LevelStartScore;
+use crate::save::SaveData;
+use crate::sound::{PlaySound, Sfx};
+use crate::transition::ScreenTransition;
+use crate::{GameState, Lives, Scoreboard};
+
+const COMBO_BONUS: usize = 5;
+// Für jede Sekunde unter dieser Zeit gibt es Punkte.
+const TIME_BONUS_LIMIT: f32 = 120.0;
+const TIME_BONUS_PER_SECOND: f32 = 2.0;
+const LIFE_BONUS: usize = 100;
+const STAR_BONUS: usize = 250;
+// So lange zählt eine Zeile hoch, bevor die nächste beginnt. Dabei tickt es in festen Abständen.
+const COUNT_DURATION: f32 = 0.6;
+const TICK_INTERVAL: f32 = 0.06;
+
+// Die Abrechnung des zuletzt geschafften Levels. check_level_cleared legt fest, für welches Level
+// und wohin es danach weitergeht, die Zeilen werden beim Betreten des Bildschirms berechnet.
+#[derive(Resource)]
+pub struct LevelResults {
+    level: usize,
+    next: GameState,
+    rows: Vec<(&'static str, usize)>,
+    // Die Note nach den Sollwerten des Levels, falls es welche hat.
+    grade: Option<Grade>,
+    // Der knappste Moment im Level in Metern, falls es einen gab. Bringt keine Punkte.
+    closest_call: Option<f32>,
+    elapsed: f32,
+}
+
+impl Default for LevelResults {
+    fn default() -> Self {
+        LevelResults { level: 0, next: GameState::Menu, rows: Vec::new(), grade: None, closest_call: None, elapsed: 0.0 }
+    }
+}
+
+impl LevelResults {
+    pub fn show(&mut self, level: usize, next: GameState) {
+        self.level = level;
+        self.next = next;
+    }
+
+    fn total(&self) -> usize {
+        self.rows.iter().map(|(_, points)| points).sum()
+    }
+
+    fn finished(&self) -> bool {
+        self.elapsed >= self.rows.len() as f32 * COUNT_DURATION
+    }
+}
+
+#[derive(Component)]
+struct ResultsText;
+
+pub struct ResultsPlugin;
+
+impl Plugin for ResultsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelResults>()
+            .add_system_set(SystemSet::on_enter(GameState::Results).with_system(tally_results))
+            .add_system_set(
+                SystemSet::on_update(GameState::Results)
+                    .with_system(count_up)
+                    .with_system(results_action),
+            );
+    }
+}
+
+// Die Punkte aus dem Level stehen schon im Punktestand, die Boni kommen hier dazu. Hat das Level Sollwerte, gibt es
+// eine Note, in der Kampagne wird die beste je Level im Spielstand gemerkt.
+#[allow(clippy::too_many_arguments)]
+fn tally_results(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut results: ResMut<LevelResults>,
+    mut scoreboard: ResMut<Scoreboard>,
+    start: Res<LevelStartScore>,
+    tracker: Res<ObjectiveTracker>,
+    lives: Res<Lives>,
+    (campaign, levels): (Res<Campaign>, Res<Assets<Level>>),
+    (mode, closest_call): (Res<GameMode>, Res<ClosestCall>),
+    mut save: ResMut<SaveData>,
+) {
+    let level = campaign.levels.get(results.level).and_then(|handle| levels.get(handle));
+    let stars = level.map_or(0, |level| tracker.met_objectives(&level.objectives));
+    let time_bonus = ((TIME_BONUS_LIMIT - tracker.elapsed()).max(0.0) * TIME_BONUS_PER_SECOND).round() as usize;
+    let base = scoreboard.score.saturating_sub(start.0);
+    results.rows = vec![
+        ("Punkte", base),
+        ("Kombo-Bonus", tracker.best_combo() as usize * COMBO_BONUS),
+        ("Zeit-Bonus", time_bonus),
+        ("Leben-Bonus", lives.0 as usize * LIFE_BONUS),
+        ("Sterne-Bonus", stars * STAR_BONUS),
+    ];
+    results.closest_call = closest_call.0;
+    results.elapsed = 0.0;
+    scoreboard.score += results.total() - base;
+    let total = results.total();
+    results.grade = level.and_then(|level| level.par).map(|par| Grade::from_result(total, tracker.elapsed(), par));
+    if let (Some(grade), GameMode::Campaign) = (results.grade, *mode) {
+        save.level_progress.improve_grade(results.level, grade);
+    }
+
+    spawn_screen(&mut commands, &asset_server, "Level geschafft!", |parent| {
+        parent.spawn((button_text("", &asset_server), ResultsText));
+        spawn_button(parent, &asset_server, MenuButton::FinishResults, "Weiter");
+    });
+}
+
+// Die Zeilen zählen nacheinander von null hoch. Jede fertige Zeile klingelt, die Summe erscheint zum Schluss.
+fn count_up(
+    mut results: ResMut<LevelResults>,
+    time: Res<Time>,
+    mut text_query: Query<&mut Text, With<ResultsText>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let before = results.elapsed;
+    results.elapsed += time.delta_seconds();
+    let now = results.elapsed;
+    if !results.finished() && (now / TICK_INTERVAL).floor() > (before / TICK_INTERVAL).floor() {
+        sounds.send(PlaySound(Sfx::Tally));
+    }
+    let mut lines = Vec::new();
+    for (row, (label, points)) in results.rows.iter().enumerate() {
+        let start = row as f32 * COUNT_DURATION;
+        if before < start + COUNT_DURATION && now >= start + COUNT_DURATION {
+            sounds.send(PlaySound(Sfx::Coin));
+        }
+        let progress = ((now - start) / COUNT_DURATION).clamp(0.0, 1.0);
+        let shown = (*points as f32 * progress).round() as usize;
+        lines.push(format!("{label:<14}{:>8}", format::number(shown)));
+    }
+    if results.finished() {
+        lines.push(format!("{:<14}{:>8}", "Gesamt", format::number(results.total())));
+        if let Some(grade) = results.grade {
+            lines.push(format!("{:<14}{:>8}", "Note", grade.label()));
+        }
+        if let Some(distance) = results.closest_call {
+            lines.push(format!("{:<14}{:>8}", "Knappste Stelle", format::centimeters(distance)));
+        }
+    }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+// Der erste Druck überspringt das Hochzählen, der zweite geht weiter zur Belohnung oder ins Hauptmenü.
+fn results_action(
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    button_query: Query<&MenuButton>,
+    mut activated: EventReader<Activated>,
+    mut results: ResMut<LevelResults>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .any(|(interaction, button)| *interaction == Interaction::Clicked && *button == MenuButton::FinishResults);
+    let confirmed = activated
+        .iter()
+        .filter(|Activated(entity)| matches!(button_query.get(*entity), Ok(MenuButton::FinishResults)))
+        .count()
+        > 0;
+    if !clicked && !confirmed {
+        return;
+    }
+    if results.finished() {
+        transition.to(results.next);
+    } else {
+        results.elapsed = results.rows.len() as f32 * COUNT_DURATION;
+    }
+}
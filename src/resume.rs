@@ -0,0 +1,283 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::Challenges;
+use crate::daily::DailyChallenge;
+use crate::energy::Energy;
+use crate::grid::{BrickGrid, Cell};
+use crate::hazard::{ReversedControls, SpeedUp};
+use crate::launch::HeldBall;
+use crate::level::{BrickKind, Campaign};
+use crate::magnet::Magnet;
+use crate::mode::GameMode;
+use crate::paddle_size::PaddleResize;
+use crate::powerup::PowerUpKind;
+use crate::run::RunModifiers;
+use crate::save::SaveData;
+use crate::shield::{spawn_shield_entity, Shield};
+use crate::shop::{Upgrades, Wallet};
+use crate::snapshot::SnapshotReplay;
+use crate::time_attack::Countdown;
+use crate::{Arena, ArenaSize, Ball, GameState, Kinematic, Lives, Mutators, Paddle, Piercing, Scoreboard};
+
+// Ein verbliebener Brick mit seiner Zelle, seiner Art als Zeichen aus dem Layout und den übrigen Leben.
+#[derive(Serialize, Deserialize, Clone)]
+struct BrickSnapshot {
+    row: usize,
+    column: usize,
+    symbol: char,
+    health: u32,
+}
+
+// Laufende Effekte mit ihrer Restdauer in Sekunden.
+#[derive(Serialize, Deserialize, Clone)]
+enum EffectSnapshot {
+    Piercing(f32),
+    Smash(f32),
+    Magnet(f32),
+    SpeedUp(f32),
+    ReversedControls(f32),
+    PaddleResize { factor: f32, remaining: f32 },
+    Shield(f32),
+}
+
+// Alles, was nötig ist, um einen unterbrochenen Durchlauf genau so wieder aufzubauen.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunSnapshot {
+    level: usize,
+    mode: GameMode,
+    gravity: bool,
+    #[serde(default)]
+    arena_size: ArenaSize,
+    #[serde(default)]
+    challenges: Challenges,
+    daily_day: u64,
+    daily_seed: u64,
+    score: usize,
+    lives: u32,
+    energy: f32,
+    countdown: f32,
+    wallet: Wallet,
+    upgrades: Upgrades,
+    modifiers: RunModifiers,
+    bricks: Vec<BrickSnapshot>,
+    ball_position: Vec3,
+    ball_velocity: Vec3,
+    paddle_x: f32,
+    effects: Vec<EffectSnapshot>,
+}
+
+// Der Durchlauf, der gerade fortgesetzt wird. Er wird verbraucht, sobald das Level wiederhergestellt ist.
+#[derive(Resource, Default)]
+pub struct PendingResume(pub Option<RunSnapshot>);
+
+pub struct ResumePlugin;
+
+impl Plugin for ResumePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingResume>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Playing).with_system(restore_run_state.before(crate::spawn_level)),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(suspend_run.before(crate::back_to_menu))
+                    .with_system(restore_level),
+            );
+    }
+}
+
+fn timer(remaining: f32) -> Timer {
+    Timer::from_seconds(remaining, TimerMode::Once)
+}
+
+// Wer das Spiel mit Escape verlässt, unterbricht den Durchlauf nur. Der Stand landet im Spielstand
+// und kann im Hauptmenü mit "Fortsetzen" wieder aufgenommen werden.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn suspend_run(
+    (keyboard_input, replay): (Res<Input<KeyCode>>, Res<SnapshotReplay>),
+    mut save: ResMut<SaveData>,
+    (campaign, mode, mutators, daily): (Res<Campaign>, Res<GameMode>, Res<Mutators>, Res<DailyChallenge>),
+    (scoreboard, lives, energy, countdown): (Res<Scoreboard>, Res<Lives>, Res<Energy>, Res<Countdown>),
+    (wallet, upgrades, modifiers): (Res<Wallet>, Res<Upgrades>, Res<RunModifiers>),
+    brick_grid: Res<BrickGrid>,
+    ball_query: Query<
+        (&Transform, &Kinematic, Option<&Piercing>, Option<&Magnet>, Option<&SpeedUp>),
+        With<Ball>,
+    >,
+    paddle_query: Query<(&Transform, Option<&PaddleResize>, Option<&ReversedControls>), With<Paddle>>,
+    shield_query: Query<&Shield>,
+) {
+    // Probespiel, eigene Level, Tutorial, Demo, Koop, Überleben, Turnier und nachgespielte Schnappschüsse werden
+    // nicht aufgehoben.
+    if !keyboard_input.just_pressed(KeyCode::Escape)
+        || replay.0
+        || matches!(
+            *mode,
+            GameMode::TestPlay
+                | GameMode::UserLevel
+                | GameMode::Bonus
+                | GameMode::Tutorial
+                | GameMode::Demo
+                | GameMode::Coop
+                | GameMode::Survival
+                | GameMode::Tournament
+        )
+    {
+        return;
+    }
+    let (Ok(ball), Ok(paddle)) = (ball_query.get_single(), paddle_query.get_single()) else { return };
+    let (ball_transform, body, piercing, magnet, speed_up) = ball;
+    let (paddle_transform, resize, reversed) = paddle;
+
+    let mut effects = Vec::new();
+    if let Some(piercing) = piercing {
+        let remaining = piercing.0.remaining_secs();
+        effects.push(match piercing.1 {
+            PowerUpKind::Smash => EffectSnapshot::Smash(remaining),
+            _ => EffectSnapshot::Piercing(remaining),
+        });
+    }
+    if let Some(magnet) = magnet {
+        effects.push(EffectSnapshot::Magnet(magnet.0.remaining_secs()));
+    }
+    if let Some(speed_up) = speed_up {
+        effects.push(EffectSnapshot::SpeedUp(speed_up.0.remaining_secs()));
+    }
+    if let Some(reversed) = reversed {
+        effects.push(EffectSnapshot::ReversedControls(reversed.0.remaining_secs()));
+    }
+    if let Some(resize) = resize {
+        effects.push(EffectSnapshot::PaddleResize {
+            factor: resize.factor,
+            remaining: resize.duration.remaining_secs(),
+        });
+    }
+    if let Ok(shield) = shield_query.get_single() {
+        effects.push(EffectSnapshot::Shield(shield.lifetime.remaining_secs()));
+    }
+
+    save.suspended_run = Some(RunSnapshot {
+        level: campaign.current,
+        mode: *mode,
+        gravity: mutators.gravity,
+        arena_size: mutators.arena_size,
+        challenges: mutators.challenges.clone(),
+        daily_day: daily.day,
+        daily_seed: daily.seed,
+        score: scoreboard.score,
+        lives: lives.0,
+        energy: energy.0,
+        countdown: countdown.0.remaining_secs(),
+        wallet: wallet.clone(),
+        upgrades: upgrades.clone(),
+        modifiers: modifiers.clone(),
+        bricks: brick_grid
+            .bricks()
+            .map(|(row, column, cell)| BrickSnapshot { row, column, symbol: cell.kind.symbol(), health: cell.health })
+            .collect(),
+        ball_position: ball_transform.translation,
+        ball_velocity: body.velocity,
+        paddle_x: paddle_transform.translation.x,
+        effects,
+    });
+}
+
+// Vor dem Aufbau des Levels werden die Ressourcen des Durchlaufs gesetzt, damit spawn_level das richtige Level
+// mit den richtigen Upgrades baut.
+#[allow(clippy::too_many_arguments)]
+fn restore_run_state(
+    pending: Res<PendingResume>,
+    mut campaign: ResMut<Campaign>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut daily: ResMut<DailyChallenge>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut countdown: ResMut<Countdown>,
+    mut run: (ResMut<Wallet>, ResMut<Upgrades>, ResMut<RunModifiers>),
+) {
+    let Some(snapshot) = &pending.0 else { return };
+    campaign.current = snapshot.level;
+    *mode = snapshot.mode;
+    mutators.gravity = snapshot.gravity;
+    mutators.arena_size = snapshot.arena_size;
+    mutators.challenges = snapshot.challenges.clone();
+    *daily = DailyChallenge {
+        day: snapshot.daily_day,
+        seed: snapshot.daily_seed,
+    };
+    scoreboard.score = snapshot.score;
+    countdown.0 = timer(snapshot.countdown);
+    *run.0 = snapshot.wallet.clone();
+    *run.1 = snapshot.upgrades.clone();
+    *run.2 = snapshot.modifiers.clone();
+}
+
+// Sobald das frisch gebaute Level in der Welt ist, bekommt das Raster die verbliebenen Bricks mit ihren Leben, und
+// Ball, Paddle und Effekte werden auf den gespeicherten Stand gesetzt.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn restore_level(
+    mut commands: Commands,
+    mut pending: ResMut<PendingResume>,
+    mut lives: ResMut<Lives>,
+    mut energy: ResMut<Energy>,
+    mut brick_grid: ResMut<BrickGrid>,
+    mut ball_query: Query<(Entity, &mut Transform, &mut Kinematic), With<Ball>>,
+    mut paddle_query: Query<(Entity, &mut Transform), (With<Paddle>, Without<Ball>)>,
+    mut assets: (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
+    arena: Res<Arena>,
+) {
+    if pending.0.is_none() || brick_grid.is_empty() {
+        return;
+    }
+    let (Ok(ball), Ok(paddle)) = (ball_query.get_single_mut(), paddle_query.get_single_mut()) else { return };
+    let snapshot = pending.0.take().unwrap();
+
+    lives.0 = snapshot.lives;
+    energy.0 = snapshot.energy;
+    let cells = snapshot.bricks.iter().filter_map(|brick| {
+        let kind = BrickKind::from_symbol(brick.symbol)?;
+        Some((brick.row, brick.column, Cell { kind, health: brick.health }))
+    });
+    brick_grid.replace(cells);
+
+    let (ball, mut ball_transform, mut body) = ball;
+    ball_transform.translation = snapshot.ball_position;
+    body.velocity = snapshot.ball_velocity;
+    // Ein gehaltener Ball steht still, alles andere war schon abgeschossen.
+    if body.velocity != Vec3::ZERO {
+        commands.entity(ball).remove::<HeldBall>();
+    }
+    let (paddle, mut paddle_transform) = paddle;
+    paddle_transform.translation.x = snapshot.paddle_x;
+
+    for effect in snapshot.effects {
+        match effect {
+            EffectSnapshot::Piercing(remaining) => {
+                commands.entity(ball).insert(Piercing(timer(remaining), PowerUpKind::Fireball));
+            }
+            EffectSnapshot::Smash(remaining) => {
+                commands.entity(ball).insert(Piercing(timer(remaining), PowerUpKind::Smash));
+            }
+            EffectSnapshot::Magnet(remaining) => {
+                commands.entity(ball).insert(Magnet(timer(remaining)));
+            }
+            // Die gespeicherte Geschwindigkeit ist schon erhöht, deshalb kommt nur der Timer zurück.
+            EffectSnapshot::SpeedUp(remaining) => {
+                commands.entity(ball).insert(SpeedUp(timer(remaining)));
+            }
+            EffectSnapshot::ReversedControls(remaining) => {
+                commands.entity(paddle).insert(ReversedControls(timer(remaining)));
+            }
+            EffectSnapshot::PaddleResize { factor, remaining } => {
+                commands.entity(paddle).insert(PaddleResize {
+                    factor,
+                    duration: timer(remaining),
+                });
+            }
+            EffectSnapshot::Shield(remaining) => {
+                spawn_shield_entity(&mut commands, &mut assets.0, &mut assets.1, &arena, remaining);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::daily::DailyChallenge;
+use crate::level::Campaign;
+use crate::mode::GameMode;
+use crate::tournament::Tournament;
+use crate::GameState;
+
+// Der gemeinsame Zufall des Spiels. Power-Ups, Belohnungen, Geschütze und Tonhöhen ziehen alle hieraus, damit sich
+// ein Durchlauf mit demselben Seed genau so wiederholen lässt, etwa für Wiederholungen und Fehlerberichte.
+// Jedes Level beginnt mit einem eigenen Seed aus dem Seed des Durchlaufs und seiner Nummer. In der täglichen
+// Herausforderung ist das der Seed des Tages, im Turnier der des Turniers, so bekommen alle Spieler dieselben
+// Power-Ups.
+#[derive(Resource)]
+pub struct GameRng {
+    run_seed: u64,
+    level_seed: u64,
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(rand::random())
+    }
+}
+
+impl GameRng {
+    fn new(run_seed: u64) -> Self {
+        GameRng {
+            run_seed,
+            level_seed: run_seed,
+            rng: StdRng::seed_from_u64(run_seed),
+        }
+    }
+
+    // Startet einen neuen Durchlauf mit festem Seed, etwa von der Kommandozeile.
+    pub fn start_run(&mut self, seed: u64) {
+        *self = GameRng::new(seed);
+    }
+
+    pub fn level_seed(&self) -> u64 {
+        self.level_seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            // Vor dem Start von der Kommandozeile, damit ein dort angegebener Seed nicht gleich überschrieben wird.
+            .add_system_set(
+                SystemSet::on_enter(GameState::Menu).with_system(roll_run_seed.before(crate::cli::launch_from_cli)),
+            )
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(seed_level));
+    }
+}
+
+// Zurück im Hauptmenü beginnt der nächste Durchlauf mit einem neuen Seed.
+fn roll_run_seed(mut rng: ResMut<GameRng>) {
+    rng.start_run(rand::random());
+}
+
+fn seed_level(
+    mut rng: ResMut<GameRng>,
+    campaign: Res<Campaign>,
+    mode: Res<GameMode>,
+    daily: Res<DailyChallenge>,
+    tournament: Res<Tournament>,
+) {
+    let run_seed = match *mode {
+        GameMode::Daily => daily.seed,
+        GameMode::Tournament => tournament.seed(),
+        _ => rng.run_seed,
+    };
+    let level_seed = run_seed.wrapping_add(campaign.current as u64);
+    rng.level_seed = level_seed;
+    rng.rng = StdRng::seed_from_u64(level_seed);
+    info!("Seed des Levels: {level_seed}");
+}
@@ -0,0 +1,60 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::challenge::Challenge;
+use crate::tween::TweenSystem;
+use crate::{phase_set, Arena, GameState, MainCamera, Mutators, Phase, TimeScale};
+
+// So viele Sekunden dauert eine volle Umdrehung der Arena.
+const ROTATION_PERIOD: f32 = 90.0;
+
+// Wie weit sich die Arena im laufenden Level schon gedreht hat.
+#[derive(Resource, Default)]
+struct ArenaRotation(f32);
+
+pub struct ArenaRotationPlugin;
+
+impl Plugin for ArenaRotationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArenaRotation>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(reset_rotation))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(roll_camera.after(TweenSystem)))
+            .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(straighten_camera))
+            .add_system_set(phase_set(Phase::Vfx).with_system(advance_rotation));
+    }
+}
+
+fn reset_rotation(mut rotation: ResMut<ArenaRotation>) {
+    rotation.0 = 0.0;
+}
+
+// Die Drehung läuft im festen Takt mit, steht also in der Pause und beim Stillstand nach einem Treffer.
+fn advance_rotation(mut rotation: ResMut<ArenaRotation>, time_scale: Res<TimeScale>, mutators: Res<Mutators>) {
+    if mutators.challenges.is_active(Challenge::RotatingArena) {
+        rotation.0 = (rotation.0 + TAU / ROTATION_PERIOD * time_scale.step()) % TAU;
+    }
+}
+
+// Gedreht wird nur die Kamera um ihre Blickachse. Bewegung und Kollisionen rechnen weiter in den Koordinaten
+// der Arena, auf dem Bildschirm drehen sich Wände, Bricks, Paddle und Ball aber alle gemeinsam.
+fn roll_camera(
+    rotation: Res<ArenaRotation>,
+    arena: Res<Arena>,
+    mutators: Res<Mutators>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    if !mutators.challenges.is_active(Challenge::RotatingArena) {
+        return;
+    }
+    for mut transform in &mut camera_query {
+        *transform = arena.camera_transform();
+        transform.rotate_local_z(rotation.0);
+    }
+}
+
+fn straighten_camera(arena: Res<Arena>, mut camera_query: Query<&mut Transform, With<MainCamera>>) {
+    for mut transform in &mut camera_query {
+        *transform = arena.camera_transform();
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::rng::GameRng;
+use crate::GameState;
+
+const OFFER_COUNT: usize = 3;
+// Wahrscheinlichkeit, dass nach einem Level zusätzlich ein Fluch auf den Durchlauf fällt.
+const CURSE_CHANCE: f64 = 0.25;
+
+// Dauerhafte Modifikatoren eines Durchlaufs. Segen werden nach jedem geschafften Level gewählt, Flüche fallen zufällig an.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RunModifier {
+    SlowBall,
+    BigBall,
+    DoubleScore,
+    FireStart,
+    FastBall,
+    SmallBall,
+}
+
+impl RunModifier {
+    const BLESSINGS: [RunModifier; 4] =
+        [RunModifier::SlowBall, RunModifier::BigBall, RunModifier::DoubleScore, RunModifier::FireStart];
+    const CURSES: [RunModifier; 2] = [RunModifier::FastBall, RunModifier::SmallBall];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RunModifier::SlowBall => "Langsamer Ball",
+            RunModifier::BigBall => "Großer Ball",
+            RunModifier::DoubleScore => "Doppelte Punkte",
+            RunModifier::FireStart => "Feuriger Start",
+            RunModifier::FastBall => "Fluch: Schneller Ball",
+            RunModifier::SmallBall => "Fluch: Kleiner Ball",
+        }
+    }
+}
+
+// Alle Modifikatoren des laufenden Durchlaufs. Spawn- und Kollisionssysteme fragen nur die Faktoren ab,
+// mehrfach gewählte Modifikatoren wirken entsprechend mehrfach.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct RunModifiers {
+    active: Vec<RunModifier>,
+    #[serde(skip)]
+    pub offers: Vec<RunModifier>,
+    #[serde(skip)]
+    pub last_curse: Option<RunModifier>,
+}
+
+impl RunModifiers {
+    fn count(&self, modifier: RunModifier) -> i32 {
+        self.active.iter().filter(|active| **active == modifier).count() as i32
+    }
+
+    pub fn ball_speed_factor(&self) -> f32 {
+        0.85_f32.powi(self.count(RunModifier::SlowBall)) * 1.2_f32.powi(self.count(RunModifier::FastBall))
+    }
+
+    pub fn ball_size_factor(&self) -> f32 {
+        1.4_f32.powi(self.count(RunModifier::BigBall)) * 0.75_f32.powi(self.count(RunModifier::SmallBall))
+    }
+
+    pub fn score_per_brick(&self) -> usize {
+        1 << self.count(RunModifier::DoubleScore)
+    }
+
+    // Sekunden, die der Ball zu Beginn eines Levels durchschlagend ist.
+    pub fn fire_start_duration(&self) -> f32 {
+        self.count(RunModifier::FireStart) as f32 * 3.0
+    }
+
+    pub fn choose(&mut self, modifier: RunModifier) {
+        self.active.push(modifier);
+        self.offers.clear();
+    }
+}
+
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunModifiers>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_modifiers))
+            .add_system_set(SystemSet::on_enter(GameState::Reward).with_system(roll_offers));
+    }
+}
+
+fn reset_modifiers(mut modifiers: ResMut<RunModifiers>) {
+    *modifiers = RunModifiers::default();
+}
+
+// Beim Betreten der Belohnung werden drei verschiedene Segen angeboten. Ab und zu kommt ungefragt ein Fluch dazu.
+fn roll_offers(mut modifiers: ResMut<RunModifiers>, mut game_rng: ResMut<GameRng>) {
+    let rng = &mut *game_rng;
+    modifiers.offers = RunModifier::BLESSINGS.choose_multiple(rng, OFFER_COUNT).copied().collect();
+    modifiers.last_curse = None;
+    if rng.gen_bool(CURSE_CHANCE) {
+        let curse = *RunModifier::CURSES.choose(rng).unwrap();
+        modifiers.active.push(curse);
+        modifiers.last_curse = Some(curse);
+    }
+}
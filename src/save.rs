@@ -0,0 +1,190 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::{AccessibilitySettings, ColorblindMode};
+use crate::achievement::Achievement;
+use crate::assist::Assists;
+use crate::cosmetics::CosmeticsConfig;
+use crate::daily::DailyScores;
+use crate::graphics::GraphicsSettings;
+use crate::haptics::HapticsSettings;
+use crate::hud::UiSize;
+use crate::mode::{HighScores, ScoreEntry};
+use crate::progress::LevelProgress;
+use crate::resume::RunSnapshot;
+use crate::speedrun::SpeedrunRecords;
+use crate::tick_rate::TickRate;
+use crate::transition::TransitionStyle;
+
+// Der Spielstand liegt als RON-Datei neben dem Spiel. Fehlt sie oder ist sie kaputt, wird mit einem leeren Stand begonnen.
+// Ein kaputter Stand wird vorher beiseitegelegt, damit er nicht beim nächsten Speichern überschrieben wird.
+const SAVE_PATH: &str = "save.ron";
+const BROKEN_SAVE_PATH: &str = "save.broken.ron";
+
+// Die Version, in der dieses Spiel den Spielstand schreibt. Wer das Format so ändert, dass ein alter Stand nicht mehr
+// passt, erhöht sie und hängt unten einen Schritt an MIGRATIONS an.
+const SAVE_VERSION: u32 = 2;
+
+// Ein Schritt hebt den Text eines Spielstands um eine Version an, der Index ist die Version, von der er ausgeht.
+// Er arbeitet auf dem Text, weil der alte Stand sich oft gar nicht mehr in SaveData lesen lässt, etwa nach dem
+// Umbenennen eines Spielmodus.
+type Migration = fn(String) -> String;
+
+const MIGRATIONS: [Migration; SAVE_VERSION as usize] = [from_unversioned, drop_suspended_run];
+
+// Stände von vor der Versionsnummer haben schon dasselbe Format, ihnen fehlt nur das Feld `version`.
+fn from_unversioned(contents: String) -> String {
+    contents
+}
+
+// In Version 1 merkte sich ein unterbrochener Durchlauf nur die Positionen der Bricks, nicht ihre Art und Leben. Daraus
+// lässt sich das Raster nicht wieder aufbauen, der Durchlauf fällt weg. Alles andere bleibt.
+fn drop_suspended_run(contents: String) -> String {
+    const FIELD: &str = "suspended_run:";
+    let Some(start) = contents.find(FIELD) else { return contents };
+    let value = start + FIELD.len();
+    if !contents[value..].trim_start().starts_with("Some") {
+        return contents;
+    }
+    let Some(open) = contents[value..].find('(').map(|offset| value + offset) else { return contents };
+    // Das Ende ist die schließende Klammer zu Some(. Klammern in Zeichenketten zählen nicht.
+    let (mut depth, mut in_string, mut escaped) = (0, false, false);
+    for (offset, character) in contents[open..].char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    let end = open + offset + 1;
+                    return format!("{}{FIELD} None{}", &contents[..start], &contents[end..]);
+                }
+            }
+            _ => {}
+        }
+    }
+    contents
+}
+
+// Nur die Versionsnummer, alle anderen Felder werden beim Lesen übersprungen.
+#[derive(Deserialize)]
+struct SaveHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+// Alles, was über einen Durchlauf hinaus erhalten bleibt. Neue Felder brauchen #[serde(default)], damit alte Stände lesbar bleiben.
+// Für alles andere, wie umbenannte oder umgebaute Felder, gibt es die Schritte in MIGRATIONS.
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct SaveData {
+    // Fehlt in Ständen von vor der Versionsnummer, die gelten als Version 0.
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub achievements: Vec<Achievement>,
+    #[serde(default)]
+    pub cosmetics: CosmeticsConfig,
+    #[serde(default)]
+    pub colorblind_mode: ColorblindMode,
+    #[serde(default)]
+    pub accessibility: AccessibilitySettings,
+    #[serde(default)]
+    pub assists: Assists,
+    #[serde(default)]
+    pub ui_size: UiSize,
+    #[serde(default)]
+    pub haptics: HapticsSettings,
+    #[serde(default)]
+    pub transition_style: TransitionStyle,
+    #[serde(default)]
+    pub graphics: GraphicsSettings,
+    #[serde(default)]
+    pub tick_rate: TickRate,
+    #[serde(default)]
+    pub bricks_destroyed: u32,
+    #[serde(default)]
+    pub high_scores: HighScores,
+    #[serde(default)]
+    pub speedrun_timer: bool,
+    // Nur wer zustimmt, dessen Spiele werden für die Abstimmung der Level mitgeschrieben, siehe telemetry.rs.
+    #[serde(default)]
+    pub telemetry: bool,
+    #[serde(default)]
+    pub speedrun: SpeedrunRecords,
+    #[serde(default)]
+    pub daily_scores: DailyScores,
+    #[serde(default)]
+    pub level_progress: LevelProgress,
+    // Ergebnisse, die noch nicht an die Online-Bestenliste übertragen werden konnten.
+    #[serde(default)]
+    pub pending_submissions: Vec<ScoreEntry>,
+    // Ein mit Escape unterbrochener Durchlauf, den das Hauptmenü fortsetzen kann.
+    #[serde(default)]
+    pub suspended_run: Option<RunSnapshot>,
+}
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_save())
+            .add_system_to_stage(CoreStage::Last, write_save);
+    }
+}
+
+fn load_save() -> SaveData {
+    let fresh = SaveData { version: SAVE_VERSION, ..default() };
+    let Ok(contents) = fs::read_to_string(SAVE_PATH) else { return fresh };
+    let version = match ron::from_str::<SaveHeader>(&contents) {
+        Ok(header) => header.version,
+        Err(error) => {
+            warn!("Spielstand konnte nicht gelesen werden: {error}");
+            back_up(BROKEN_SAVE_PATH);
+            return fresh;
+        }
+    };
+    // Vor jeder Umwandlung bleibt der alte Stand als Kopie erhalten. Ein Stand aus einer neueren Version des Spiels
+    // wird so gut wie möglich gelesen, seine Kopie behält die Felder, die diese Version nicht kennt.
+    if version != SAVE_VERSION {
+        back_up(&format!("save.v{version}.ron"));
+    }
+    if version > SAVE_VERSION {
+        warn!("Der Spielstand stammt aus einer neueren Version ({version}), diese kennt nur Version {SAVE_VERSION}");
+    }
+    let contents = MIGRATIONS
+        .iter()
+        .skip(version as usize)
+        .fold(contents, |contents, migration| migration(contents));
+    match ron::from_str::<SaveData>(&contents) {
+        Ok(save) => SaveData { version: SAVE_VERSION, ..save },
+        Err(error) => {
+            warn!("Spielstand der Version {version} konnte nicht gelesen werden: {error}");
+            back_up(BROKEN_SAVE_PATH);
+            fresh
+        }
+    }
+}
+
+fn back_up(path: &str) {
+    match fs::copy(SAVE_PATH, path) {
+        Ok(_) => info!("Bisheriger Spielstand gesichert unter {path}"),
+        Err(error) => warn!("Spielstand konnte nicht nach {path} gesichert werden: {error}"),
+    }
+}
+
+// Geschrieben wird nur, wenn sich am Spielstand etwas geändert hat.
+fn write_save(save: Res<SaveData>) {
+    if !save.is_changed() || save.is_added() {
+        return;
+    }
+    let result = ron::ser::to_string_pretty(&*save, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| fs::write(SAVE_PATH, contents).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        warn!("Spielstand konnte nicht gespeichert werden: {error}");
+    }
+}
@@ -0,0 +1,253 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ab_glyph::{point, Font as _, FontArc, PxScale, ScaleFont};
+use bevy::prelude::*;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+
+use crate::format;
+use crate::level::{Campaign, Level};
+use crate::mode::RunFinished;
+use crate::rng::GameRng;
+use crate::toast::ShowToast;
+use crate::{phase_set, GameState, Phase, Scoreboard};
+
+// Karten landen in diesem Ordner neben dem Spiel, eine Datei pro beendetem Durchlauf.
+const CARD_DIR: &str = "share-cards";
+const CARD_FONT: &str = "fonts/FiraMono-Medium.ttf";
+const CARD_WIDTH: usize = 640;
+const CARD_HEIGHT: usize = 360;
+const CARD_MARGIN: usize = 40;
+const TOP_COLOR: [u8; 3] = [40, 12, 60];
+const BOTTOM_COLOR: [u8; 3] = [12, 30, 70];
+const TITLE_COLOR: [u8; 3] = [255, 215, 90];
+const TEXT_COLOR: [u8; 3] = [240, 240, 240];
+const DIM_COLOR: [u8; 3] = [150, 150, 180];
+// Die Reihe Bricks am oberen Rand, in den Farben der eingebauten Bricks.
+const BRICK_COLORS: [[u8; 3]; 4] = [[200, 200, 210], [230, 60, 60], [50, 165, 75], [205, 165, 65]];
+const BRICK_COUNT: usize = 8;
+const BRICK_HEIGHT: usize = 14;
+const BRICK_GAP: usize = 4;
+const TITLE_SIZE: f32 = 40.0;
+const LINE_SIZE: f32 = 26.0;
+const FOOTER_SIZE: f32 = 18.0;
+
+// Was auf der Karte steht und sich nicht aus dem Endergebnis ablesen lässt. Wird während des Spiels mitgeschrieben,
+// weil das Hauptmenü Level und Seed schon für den nächsten Durchlauf zurücksetzt.
+#[derive(Resource, Default)]
+struct RunHighlights {
+    best_combo: u32,
+    level: usize,
+    level_name: String,
+    seed: u64,
+}
+
+pub struct ShareCardPlugin;
+
+impl Plugin for ShareCardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunHighlights>()
+            .add_startup_system(load_card_font)
+            .add_system_set(SystemSet::on_exit(GameState::Menu).with_system(reset_highlights))
+            .add_system_set(phase_set(Phase::Scoring).with_system(track_highlights))
+            .add_system(write_share_card);
+    }
+}
+
+#[derive(Resource)]
+struct CardFont(Handle<Font>);
+
+fn load_card_font(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CardFont(asset_server.load(CARD_FONT)));
+}
+
+fn reset_highlights(mut highlights: ResMut<RunHighlights>) {
+    *highlights = RunHighlights::default();
+}
+
+fn track_highlights(
+    mut highlights: ResMut<RunHighlights>,
+    scoreboard: Res<Scoreboard>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    rng: Res<GameRng>,
+) {
+    highlights.best_combo = highlights.best_combo.max(scoreboard.combo);
+    if highlights.level != campaign.current + 1 || highlights.level_name.is_empty() {
+        highlights.level = campaign.current + 1;
+        let level = levels.get(campaign.current_handle());
+        highlights.level_name = level.map_or_else(String::new, |level| level.name.clone());
+    }
+    highlights.seed = rng.level_seed();
+}
+
+// Jeder gewertete Durchlauf bekommt eine Karte zum Teilen. Sie wird ohne Grafikkarte in ein Bild gezeichnet,
+// denn ein Auslesen gerenderter Bilder gibt es im Spiel nicht.
+fn write_share_card(
+    mut events: EventReader<RunFinished>,
+    highlights: Res<RunHighlights>,
+    card_font: Res<CardFont>,
+    fonts: Res<Assets<Font>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for RunFinished(entry) in events.iter() {
+        let Some(font) = fonts.get(&card_font.0) else {
+            warn!("Karte zum Teilen übersprungen, die Schrift ist nicht geladen");
+            continue;
+        };
+        let mut card = Canvas::new(CARD_WIDTH, CARD_HEIGHT);
+        card.gradient(TOP_COLOR, BOTTOM_COLOR);
+        let brick_width = (CARD_WIDTH - 2 * CARD_MARGIN + BRICK_GAP) / BRICK_COUNT - BRICK_GAP;
+        for index in 0..BRICK_COUNT {
+            let x = CARD_MARGIN + index * (brick_width + BRICK_GAP);
+            card.rect(x, CARD_MARGIN / 2, brick_width, BRICK_HEIGHT, BRICK_COLORS[index % BRICK_COLORS.len()]);
+        }
+
+        let title_y = (CARD_MARGIN + BRICK_HEIGHT + 10) as f32;
+        card.text(&font.font, "KuerteilCG", CARD_MARGIN as f32, title_y, TITLE_SIZE, TITLE_COLOR);
+        let level = if highlights.level_name.is_empty() {
+            format!("Level {}", highlights.level)
+        } else {
+            format!("Level {}: {}", highlights.level, highlights.level_name)
+        };
+        let lines = [
+            format!("Punkte: {}", format::number(entry.score)),
+            level,
+            format!("Längste Kombo: {}", highlights.best_combo),
+            format!("Seed: {}", highlights.seed),
+        ];
+        let mut y = title_y + TITLE_SIZE + 16.0;
+        for line in &lines {
+            card.text(&font.font, line, CARD_MARGIN as f32, y, LINE_SIZE, TEXT_COLOR);
+            y += LINE_SIZE + 10.0;
+        }
+        let footer_y = (CARD_HEIGHT - CARD_MARGIN) as f32 - FOOTER_SIZE;
+        card.text(&font.font, entry.mode.name(), CARD_MARGIN as f32, footer_y, FOOTER_SIZE, DIM_COLOR);
+
+        let message = match save(&card) {
+            Ok(path) => format!("Karte gespeichert unter {}", path.display()),
+            Err(error) => format!("Karte konnte nicht gespeichert werden: {error}"),
+        };
+        toasts.send(ShowToast(message));
+    }
+}
+
+fn save(card: &Canvas) -> Result<PathBuf, String> {
+    fs::create_dir_all(CARD_DIR).map_err(|error| error.to_string())?;
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let path = Path::new(CARD_DIR).join(format!("{seconds}.png"));
+    fs::write(&path, card.encode_png()).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+// Ein Bild im Speicher, Zeile für Zeile mit je vier Bytes pro Pixel.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas { width, height, pixels: vec![0; width * height * 4] }
+    }
+
+    // Mischt die Farbe mit dem Anteil `alpha` über das, was schon da ist. Außerhalb des Bildes passiert nichts.
+    fn blend(&mut self, x: usize, y: usize, color: [u8; 3], alpha: f32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let index = (y * self.width + x) * 4;
+        for (channel, value) in color.into_iter().enumerate() {
+            let old = self.pixels[index + channel] as f32;
+            self.pixels[index + channel] = (old + (value as f32 - old) * alpha.clamp(0.0, 1.0)).round() as u8;
+        }
+        self.pixels[index + 3] = 255;
+    }
+
+    fn gradient(&mut self, top: [u8; 3], bottom: [u8; 3]) {
+        for y in 0..self.height {
+            let t = y as f32 / (self.height - 1) as f32;
+            let color = [0, 1, 2].map(|channel| {
+                let (top, bottom) = (top[channel] as f32, bottom[channel] as f32);
+                (top + (bottom - top) * t) as u8
+            });
+            for x in 0..self.width {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    fn rect(&mut self, left: usize, top: usize, width: usize, height: usize, color: [u8; 3]) {
+        for y in top..top + height {
+            for x in left..left + width {
+                self.blend(x, y, color, 1.0);
+            }
+        }
+    }
+
+    // Schreibt eine Zeile Text, `top` ist die Oberkante der Zeile.
+    fn text(&mut self, font: &FontArc, text: &str, left: f32, top: f32, size: f32, color: [u8; 3]) {
+        let font = font.as_scaled(PxScale::from(size));
+        let baseline = top + font.ascent();
+        let mut x = left;
+        let mut previous = None;
+        for character in text.chars() {
+            let id = font.glyph_id(character);
+            if let Some(previous) = previous {
+                x += font.kern(previous, id);
+            }
+            previous = Some(id);
+            let glyph = id.with_scale_and_position(font.scale(), point(x, baseline));
+            x += font.h_advance(id);
+            let Some(outline) = font.outline_glyph(glyph) else { continue };
+            let bounds = outline.px_bounds();
+            outline.draw(|glyph_x, glyph_y, coverage| {
+                let (px, py) = (bounds.min.x + glyph_x as f32, bounds.min.y + glyph_y as f32);
+                if px >= 0.0 && py >= 0.0 {
+                    self.blend(px as usize, py as usize, color, coverage);
+                }
+            });
+        }
+    }
+
+    // PNG mit 8 Bit je Kanal und Alpha. Jede Zeile beginnt mit dem Filter 0, danach wird alles per zlib gepackt.
+    fn encode_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.height * (self.width * 4 + 1));
+        for row in self.pixels.chunks(self.width * 4) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &compress_to_vec_zlib(&raw, 6));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data));
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// Die Prüfsumme, die PNG für jeden Abschnitt verlangt.
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
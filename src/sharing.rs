@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+use crate::focus::Activated;
+use crate::level::{Campaign, Level};
+use crate::menu::{button_text, spawn_button, spawn_screen, MenuButton};
+use crate::mode::GameMode;
+use crate::toast::ShowToast;
+use crate::transition::ScreenTransition;
+use crate::{GameState, Mutators, Scoreboard};
+
+// Eigene und importierte Level liegen in diesem Ordner neben dem Spiel, eine Datei pro Level.
+pub const USER_LEVELS_DIR: &str = "my-levels";
+const LEVEL_EXTENSION: &str = ".level.ron";
+// Größer wird keine Level-Datei. Schützt davor, dass ein präparierter Code beim Entpacken den Speicher füllt.
+const MAX_LEVEL_BYTES: usize = 64 * 1024;
+const COMPRESSION_LEVEL: u8 = 9;
+
+// Ein Level als kurzer Text zum Weitergeben: die Level-Datei ohne Einrückung, gepackt und base64-kodiert.
+pub fn level_code(level: &Level) -> Result<String, String> {
+    let ron = ron::to_string(level).map_err(|error| error.to_string())?;
+    let packed = compress_to_vec(ron.as_bytes(), COMPRESSION_LEVEL);
+    Ok(base64::encode_config(packed, base64::URL_SAFE_NO_PAD))
+}
+
+pub fn level_from_code(code: &str) -> Result<Level, String> {
+    let packed = base64::decode_config(code.trim(), base64::URL_SAFE_NO_PAD).map_err(|_| "Kein gültiger Level-Code")?;
+    let ron = decompress_to_vec_with_limit(&packed, MAX_LEVEL_BYTES).map_err(|_| "Kein gültiger Level-Code")?;
+    ron::de::from_bytes(&ron).map_err(|error| format!("Der Code enthält kein gültiges Level: {error}"))
+}
+
+pub fn copy_to_clipboard(text: String) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|error| error.to_string())
+}
+
+pub fn paste_from_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|error| error.to_string())
+}
+
+// Speichert das Level unter seinem Namen im Ordner der eigenen Level. Gibt es den Namen schon, wird hochgezählt.
+pub fn save_user_level(level: &Level) -> Result<PathBuf, String> {
+    fs::create_dir_all(USER_LEVELS_DIR).map_err(|error| error.to_string())?;
+    let stem: String = level
+        .name
+        .chars()
+        .map(|character| if character.is_alphanumeric() { character.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let stem = if stem.trim_matches('-').is_empty() { "level".to_string() } else { stem };
+    let path = (1..)
+        .map(|number| match number {
+            1 => Path::new(USER_LEVELS_DIR).join(format!("{stem}{LEVEL_EXTENSION}")),
+            _ => Path::new(USER_LEVELS_DIR).join(format!("{stem}-{number}{LEVEL_EXTENSION}")),
+        })
+        .find(|path| !path.exists())
+        .expect("Irgendein Dateiname ist noch frei");
+    let contents =
+        ron::ser::to_string_pretty(level, ron::ser::PrettyConfig::default()).map_err(|error| error.to_string())?;
+    fs::write(&path, contents).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+fn read_level(path: &Path) -> Result<Level, String> {
+    let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    ron::from_str(&contents).map_err(|error| error.to_string())
+}
+
+// Die Level aus dem Ordner, wie sie beim Öffnen der Liste gefunden wurden. Der Index eines Buttons zeigt hier hinein.
+#[derive(Resource, Default)]
+struct UserLevels(Vec<(PathBuf, String)>);
+
+pub struct SharingPlugin;
+
+impl Plugin for SharingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UserLevels>()
+            .add_system(import_dropped_files)
+            .add_system_set(SystemSet::on_enter(GameState::UserLevels).with_system(spawn_user_levels))
+            .add_system_set(SystemSet::on_update(GameState::UserLevels).with_system(user_level_action));
+    }
+}
+
+fn scan_user_levels() -> Vec<(PathBuf, String)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(USER_LEVELS_DIR)
+        .map(|entries| entries.filter_map(Result::ok).map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|path| path.to_string_lossy().ends_with(LEVEL_EXTENSION));
+    paths.sort();
+    // Dateien, die sich nicht lesen lassen, tauchen gar nicht erst in der Liste auf.
+    paths
+        .into_iter()
+        .filter_map(|path| read_level(&path).ok().map(|level| (path, level.name)))
+        .collect()
+}
+
+// Die Liste der eigenen Level. Punkte aus einem eigenen Level zählen nicht, sie werden hier wie im Editor verworfen.
+fn spawn_user_levels(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut user_levels: ResMut<UserLevels>,
+    mut scoreboard: ResMut<Scoreboard>,
+) {
+    scoreboard.score = 0;
+    user_levels.0 = scan_user_levels();
+    spawn_screen(&mut commands, &asset_server, "Eigene Level", |parent| {
+        if user_levels.0.is_empty() {
+            parent.spawn(button_text(&format!("Noch keine Level in {USER_LEVELS_DIR}/"), &asset_server));
+        }
+        for (index, (_, name)) in user_levels.0.iter().enumerate() {
+            spawn_button(parent, &asset_server, MenuButton::PlayUserLevel(index), name);
+        }
+        spawn_button(parent, &asset_server, MenuButton::PasteLevelCode, "Code einfügen");
+        spawn_button(parent, &asset_server, MenuButton::Back, "Zurück");
+    });
+}
+
+// Die Buttons dieser Liste werden hier statt in menu_action behandelt, da nur hier die Dateien bekannt sind.
+#[allow(clippy::too_many_arguments)]
+fn user_level_action(
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    button_query: Query<&MenuButton>,
+    mut activated: EventReader<Activated>,
+    user_levels: Res<UserLevels>,
+    mut campaign: ResMut<Campaign>,
+    mut levels: ResMut<Assets<Level>>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut state: ResMut<State<GameState>>,
+    mut transition: ResMut<ScreenTransition>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Clicked)
+        .map(|(_, button)| *button);
+    let confirmed = activated.iter().filter_map(|Activated(entity)| button_query.get(*entity).ok().copied());
+    let pressed: Vec<MenuButton> = clicked.chain(confirmed).collect();
+    for button in pressed {
+        match button {
+            MenuButton::PlayUserLevel(index) => {
+                let Some((path, _)) = user_levels.0.get(index) else { continue };
+                match read_level(path) {
+                    Ok(level) => {
+                        campaign.custom_level = Some(levels.add(level));
+                        *mode = GameMode::UserLevel;
+                        *mutators = Mutators::default();
+                        transition.to(GameState::Playing);
+                    }
+                    Err(error) => toasts.send(ShowToast(format!("Level konnte nicht geladen werden: {error}"))),
+                }
+            }
+            MenuButton::PasteLevelCode => {
+                let result = paste_from_clipboard().and_then(|code| level_from_code(&code));
+                match result.and_then(|level| save_user_level(&level).map(|_| level)) {
+                    Ok(level) => {
+                        toasts.send(ShowToast(format!("Level importiert: {}", level.name)));
+                        // Neu aufbauen, damit das Level in der Liste erscheint.
+                        let _ = state.restart();
+                    }
+                    Err(error) => toasts.send(ShowToast(error)),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Eine Level-Datei, die auf das Fenster gezogen wird, landet bei den eigenen Levels, egal wo im Spiel man gerade ist.
+fn import_dropped_files(
+    mut events: EventReader<FileDragAndDrop>,
+    mut state: ResMut<State<GameState>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for event in events.iter() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = event else { continue };
+        if !path_buf.to_string_lossy().ends_with(LEVEL_EXTENSION) {
+            toasts.send(ShowToast(format!("Nur {LEVEL_EXTENSION}-Dateien lassen sich importieren")));
+            continue;
+        }
+        match read_level(path_buf).and_then(|level| save_user_level(&level).map(|_| level)) {
+            Ok(level) => {
+                toasts.send(ShowToast(format!("Level importiert: {}", level.name)));
+                if *state.current() == GameState::UserLevels {
+                    let _ = state.restart();
+                }
+            }
+            Err(error) => toasts.send(ShowToast(format!("Level konnte nicht importiert werden: {error}"))),
+        }
+    }
+}
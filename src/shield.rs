@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::effect::ActiveEffects;
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, Arena, Ball, Kinematic, LevelEntity, PADDLE_Y, Phase, TimeScale};
+
+const SHIELD_DURATION: f32 = 15.0;
+// In den letzten Sekunden blinkt der Schild, damit man sieht, dass er bald verschwindet.
+const SHIELD_BLINK_TIME: f32 = 3.0;
+const SHIELD_HEIGHT: f32 = 0.15;
+const SHIELD_OFFSET_BELOW_PADDLE: f32 = 0.8;
+const SHIELD_COLOR: Color = Color::rgba(0.0, 0.8, 1.0, 0.35);
+
+// Eine durchscheinende Wand knapp unter dem Paddle. Sie fängt den Ball genau einmal auf und zerbricht dann.
+#[derive(Component)]
+pub struct Shield {
+    pub lifetime: Timer,
+}
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_shield)
+            .add_system(report_shield)
+            .add_system_set(phase_set(Phase::Collision).with_system(block_ball))
+            .add_system_set(phase_set(Phase::Scoring).with_system(expire_shield));
+    }
+}
+
+// Der Schild ist kein TimedEffect, meldet seine Restdauer aber genauso.
+fn report_shield(shield_query: Query<&Shield>, mut active: ResMut<ActiveEffects>) {
+    for shield in &shield_query {
+        active.report(PowerUpKind::Shield, &shield.lifetime);
+    }
+}
+
+// Der Schild reicht von Wand zu Wand.
+fn shield_size(arena: &Arena) -> Vec3 {
+    Vec3::new(arena.width - arena.wall_thickness, SHIELD_HEIGHT, 1.0)
+}
+
+fn spawn_shield(
+    mut commands: Commands,
+    mut events: EventReader<PowerUpCollected>,
+    mut shield_query: Query<&mut Shield>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    arena: Res<Arena>,
+) {
+    for PowerUpCollected(kind) in events.iter() {
+        if *kind != PowerUpKind::Shield {
+            continue;
+        }
+        // Ein zweiter Schild verlängert nur den bestehenden, statt einen weiteren zu stapeln.
+        if let Ok(mut shield) = shield_query.get_single_mut() {
+            shield.lifetime.reset();
+            continue;
+        }
+        spawn_shield_entity(&mut commands, &mut meshes, &mut materials, &arena, SHIELD_DURATION);
+    }
+}
+
+// Baut die Schildwand mit der angegebenen Restdauer, auch für fortgesetzte Durchläufe.
+pub fn spawn_shield_entity(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    arena: &Arena,
+    duration: f32,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Cube::default().into()),
+            material: materials.add(StandardMaterial {
+                base_color: SHIELD_COLOR,
+                emissive: Color::rgb(0.0, 0.3, 0.4),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, PADDLE_Y - SHIELD_OFFSET_BELOW_PADDLE, 0.0).with_scale(shield_size(arena)),
+            ..default()
+        },
+        Shield {
+            lifetime: Timer::from_seconds(duration, TimerMode::Once),
+        },
+        LevelEntity,
+    ));
+}
+
+// Fällt der Ball auf den Schild, prallt er nach oben ab und der Schild ist verbraucht.
+// Das passiert vor check_ball_lost, damit der gerettete Ball kein Leben kostet.
+fn block_ball(
+    mut commands: Commands,
+    shield_query: Query<(Entity, &Transform), With<Shield>>,
+    mut ball_query: Query<(&Transform, &mut Kinematic), With<Ball>>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let Ok((shield, shield_transform)) = shield_query.get_single() else { return };
+    for (ball_transform, mut body) in &mut ball_query {
+        let reached_shield = ball_transform.translation.y - ball_transform.scale.y / 2.0
+            <= shield_transform.translation.y + SHIELD_HEIGHT / 2.0;
+        if body.velocity.y < 0.0 && reached_shield {
+            body.velocity.y = -body.velocity.y;
+            commands.entity(shield).despawn();
+            sounds.send(PlaySound(Sfx::ShieldBreak));
+            return;
+        }
+    }
+}
+
+fn expire_shield(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Shield, &mut Visibility)>,
+    time_scale: Res<TimeScale>,
+    settings: Res<AccessibilitySettings>,
+) {
+    for (entity, mut shield, mut visibility) in &mut query {
+        if shield.lifetime.tick(Duration::from_secs_f32(time_scale.step())).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let remaining = shield.lifetime.remaining_secs();
+        // Ohne Blinken bleibt der Schild bis zum Schluss sichtbar.
+        visibility.is_visible =
+            remaining > SHIELD_BLINK_TIME || !settings.allows_motion() || (remaining * 8.0).fract() < 0.5;
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::powerup::{PowerUpCollected, PowerUpKind};
+use crate::config::GameConfig;
+use crate::GameState;
+
+const PADDLE_WIDTH_PER_LEVEL: f32 = 0.2;
+const MAX_UPGRADE_LEVEL: u32 = 3;
+
+// Die Münzen des aktuellen Durchlaufs. Sie werden im Shop zwischen den Leveln ausgegeben.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct Wallet {
+    pub coins: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Upgrade {
+    ExtraLife,
+    WidePaddle,
+    DropRate,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 3] = [Upgrade::ExtraLife, Upgrade::WidePaddle, Upgrade::DropRate];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Upgrade::ExtraLife => "Extra-Leben",
+            Upgrade::WidePaddle => "Breites Paddle",
+            Upgrade::DropRate => "Mehr Power-Ups",
+        }
+    }
+
+    fn base_price(self) -> u32 {
+        match self {
+            Upgrade::ExtraLife => 5,
+            Upgrade::WidePaddle => 4,
+            Upgrade::DropRate => 3,
+        }
+    }
+}
+
+// Gekaufte Upgrades gelten bis zum Ende des Durchlaufs, also bis es zurück ins Hauptmenü geht.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct Upgrades {
+    extra_lives: u32,
+    wide_paddle: u32,
+    drop_rate: u32,
+}
+
+impl Upgrades {
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        match upgrade {
+            Upgrade::ExtraLife => self.extra_lives,
+            Upgrade::WidePaddle => self.wide_paddle,
+            Upgrade::DropRate => self.drop_rate,
+        }
+    }
+
+    // Jede weitere Stufe kostet mehr. Ist die höchste Stufe erreicht, gibt es keinen Preis mehr.
+    pub fn price(&self, upgrade: Upgrade) -> Option<u32> {
+        let level = self.level(upgrade);
+        (level < MAX_UPGRADE_LEVEL).then(|| upgrade.base_price() * (level + 1))
+    }
+
+    pub fn try_buy(&mut self, upgrade: Upgrade, wallet: &mut Wallet) -> bool {
+        let Some(price) = self.price(upgrade) else { return false };
+        if wallet.coins < price {
+            return false;
+        }
+        wallet.coins -= price;
+        match upgrade {
+            Upgrade::ExtraLife => self.extra_lives += 1,
+            Upgrade::WidePaddle => self.wide_paddle += 1,
+            Upgrade::DropRate => self.drop_rate += 1,
+        }
+        true
+    }
+
+    pub fn starting_lives(&self, config: &GameConfig) -> u32 {
+        config.starting_lives + self.extra_lives
+    }
+
+    pub fn paddle_width(&self, config: &GameConfig) -> f32 {
+        config.paddle_size.x * (1.0 + self.wide_paddle as f32 * PADDLE_WIDTH_PER_LEVEL)
+    }
+
+    pub fn drop_chance(&self, config: &GameConfig) -> f64 {
+        config.drop_chance + self.drop_rate as f64 * config.drop_chance_per_level
+    }
+}
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Wallet>()
+            .init_resource::<Upgrades>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_run))
+            .add_system(collect_coins);
+    }
+}
+
+// Zurück im Hauptmenü ist der Durchlauf vorbei, Münzen und Upgrades verfallen.
+fn reset_run(mut wallet: ResMut<Wallet>, mut upgrades: ResMut<Upgrades>) {
+    *wallet = Wallet::default();
+    *upgrades = Upgrades::default();
+}
+
+fn collect_coins(mut events: EventReader<PowerUpCollected>, mut wallet: ResMut<Wallet>) {
+    for PowerUpCollected(kind) in events.iter() {
+        if *kind == PowerUpKind::Coin {
+            wallet.coins += 1;
+        }
+    }
+}
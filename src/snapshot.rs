@@ -0,0 +1,281 @@
+use std::any::type_name;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::Challenges;
+use crate::grid::{BrickGrid, Cell};
+use crate::launch::HeldBall;
+use crate::level::{BrickKind, Campaign, Level};
+use crate::mode::GameMode;
+use crate::rng::GameRng;
+use crate::toast::ShowToast;
+use crate::transition::ScreenTransition;
+use crate::{collision, Arena, Ball, BallSpeed, GameState, Kinematic, LevelEntity, Mutators, Paddle, TimeScale};
+
+const SNAPSHOT_KEY: KeyCode = KeyCode::F11;
+// Schnappschüsse landen in diesem Ordner neben dem Spiel, eine Datei pro Druck auf F11.
+const SNAPSHOT_DIR: &str = "snapshots";
+const SNAPSHOT_EXTENSION: &str = ".world.ron";
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct KinematicSnapshot {
+    velocity: Vec3,
+    gravity: Vec3,
+    bounciness: f32,
+}
+
+impl KinematicSnapshot {
+    fn into_kinematic(self) -> Kinematic {
+        Kinematic {
+            velocity: self.velocity,
+            gravity: self.gravity,
+            bounciness: self.bounciness,
+        }
+    }
+}
+
+// Ein Objekt aus dem laufenden Level. Die Namen der Komponenten stehen zum Lesen im Bericht, wiederhergestellt
+// werden daraus nur Bälle und Paddle.
+#[derive(Serialize, Deserialize)]
+struct EntitySnapshot {
+    components: Vec<String>,
+    translation: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+    kinematic: Option<KinematicSnapshot>,
+}
+
+impl EntitySnapshot {
+    fn has<T>(&self) -> bool {
+        self.components.iter().any(|name| name == type_name::<T>())
+    }
+
+    fn transform(&self) -> Transform {
+        Transform { translation: self.translation, rotation: self.rotation, scale: self.scale }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BrickSnapshot {
+    row: usize,
+    column: usize,
+    symbol: char,
+    health: u32,
+}
+
+// Der Stand eines laufenden Levels für Fehlerberichte. Das Level selbst reist mit, damit der Stand auch mit
+// eigenen Leveln und ohne die Kampagne des Meldenden nachgestellt werden kann.
+#[derive(Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    mode: GameMode,
+    level: Level,
+    arena: Arena,
+    gravity: bool,
+    challenges: Challenges,
+    // Nur zur Auskunft: Der Zufall läuft nach dem Laden nicht genau gleich weiter.
+    level_seed: u64,
+    time_scale: f32,
+    ball_speed: f32,
+    bricks: Vec<BrickSnapshot>,
+    entities: Vec<EntitySnapshot>,
+}
+
+// Ein Schnappschuss von der Kommandozeile. Er wird nach dem Laden einmal gespielt.
+#[derive(Resource, Default)]
+pub struct PendingSnapshot(pub Option<WorldSnapshot>);
+
+// Ob gerade ein Schnappschuss nachgespielt wird. Er läuft im Modus, in dem er aufgenommen wurde, zählt aber wie ein
+// Probespiel nicht: keine Bestenliste, kein Fortschritt, kein Aufheben, und nach dem Level geht es ins Hauptmenü.
+// Die Datei kann schließlich von jemand anderem stammen und bearbeitet sein.
+#[derive(Resource, Default)]
+pub struct SnapshotReplay(pub bool);
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingSnapshot>()
+            .init_resource::<SnapshotReplay>()
+            .add_system(write_snapshot)
+            // Erst wenn der vorige Durchlauf abgerechnet und das eigene Level der Kampagne zurückgesetzt ist.
+            .add_system_set(
+                SystemSet::on_enter(GameState::Menu)
+                    .with_system(start_snapshot.after(crate::level::clear_custom_level).after(crate::mode::finish_run)),
+            )
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(restore_snapshot));
+    }
+}
+
+pub fn load(path: &Path) -> Result<WorldSnapshot, String> {
+    let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    ron::from_str(&contents).map_err(|error| error.to_string())
+}
+
+// F11 schreibt alle Objekte des Levels mit Position, Geschwindigkeit und Komponenten in eine Datei.
+// Das System braucht die ganze Welt, weil nur sie die Komponenten einer Entity aufzählen kann.
+fn write_snapshot(world: &mut World) {
+    if !world.resource::<Input<KeyCode>>().just_pressed(SNAPSHOT_KEY)
+        || *world.resource::<State<GameState>>().current() != GameState::Playing
+    {
+        return;
+    }
+    let message = match capture(world).and_then(|snapshot| save(&snapshot)) {
+        Ok(path) => format!("Schnappschuss gespeichert unter {}", path.display()),
+        Err(error) => format!("Schnappschuss fehlgeschlagen: {error}"),
+    };
+    world.send_event(ShowToast(message));
+}
+
+fn capture(world: &mut World) -> Result<WorldSnapshot, String> {
+    let campaign = world.resource::<Campaign>();
+    let level = world
+        .resource::<Assets<Level>>()
+        .get(campaign.current_handle())
+        .ok_or("Das Level ist nicht geladen")?
+        .clone();
+    let mutators = world.resource::<Mutators>();
+    let (gravity, challenges) = (mutators.gravity, mutators.challenges.clone());
+    let bricks = world
+        .resource::<BrickGrid>()
+        .bricks()
+        .map(|(row, column, cell)| BrickSnapshot { row, column, symbol: cell.kind.symbol(), health: cell.health })
+        .collect();
+
+    // Die Oberfläche gehört auch zum Level, sie hat aber mit Kollisionen nichts zu tun.
+    let mut query =
+        world.query_filtered::<(Entity, &Transform, Option<&Kinematic>), (With<LevelEntity>, Without<Node>)>();
+    let entities = query
+        .iter(world)
+        .map(|(entity, transform, kinematic)| EntitySnapshot {
+            components: world.inspect_entity(entity).iter().map(|info| info.name().to_string()).collect(),
+            translation: transform.translation,
+            rotation: transform.rotation,
+            scale: transform.scale,
+            kinematic: kinematic.map(|body| KinematicSnapshot {
+                velocity: body.velocity,
+                gravity: body.gravity,
+                bounciness: body.bounciness,
+            }),
+        })
+        .collect();
+
+    Ok(WorldSnapshot {
+        mode: *world.resource::<GameMode>(),
+        level,
+        arena: *world.resource::<Arena>(),
+        gravity,
+        challenges,
+        level_seed: world.resource::<GameRng>().level_seed(),
+        time_scale: world.resource::<TimeScale>().factor,
+        ball_speed: world.resource::<BallSpeed>().0,
+        bricks,
+        entities,
+    })
+}
+
+fn save(snapshot: &WorldSnapshot) -> Result<PathBuf, String> {
+    fs::create_dir_all(SNAPSHOT_DIR).map_err(|error| error.to_string())?;
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let path = Path::new(SNAPSHOT_DIR).join(format!("{seconds}{SNAPSHOT_EXTENSION}"));
+    let contents = ron::ser::to_string_pretty(snapshot, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())?;
+    fs::write(&path, contents).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+// Beim ersten Erreichen des Hauptmenüs startet das Level aus dem Schnappschuss, in seinen Maßen und seinem Modus.
+// Bei jedem späteren ist das Nachspielen vorbei.
+fn start_snapshot(
+    pending: Res<PendingSnapshot>,
+    mut replay: ResMut<SnapshotReplay>,
+    mut campaign: ResMut<Campaign>,
+    mut levels: ResMut<Assets<Level>>,
+    mut mode: ResMut<GameMode>,
+    mut mutators: ResMut<Mutators>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    replay.0 = pending.0.is_some();
+    let Some(snapshot) = &pending.0 else { return };
+    let mut level = snapshot.level.clone();
+    level.arena = Some(snapshot.arena);
+    campaign.custom_level = Some(levels.add(level));
+    *mode = snapshot.mode;
+    *mutators = Mutators { gravity: snapshot.gravity, challenges: snapshot.challenges.clone(), ..default() };
+    transition.to(GameState::Playing);
+}
+
+// Sobald das Level gebaut ist, bekommen Raster, Bälle und Paddle den Stand aus dem Schnappschuss.
+// Fehlen Bälle, werden sie mit Mesh und Material des ersten dazu gebaut, überzählige verschwinden.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn restore_snapshot(
+    mut commands: Commands,
+    mut pending: ResMut<PendingSnapshot>,
+    mut brick_grid: ResMut<BrickGrid>,
+    mut ball_query: Query<
+        (Entity, &mut Transform, &mut Kinematic, &Handle<Mesh>, &Handle<StandardMaterial>),
+        With<Ball>,
+    >,
+    mut paddle_query: Query<&mut Transform, (With<Paddle>, Without<Ball>)>,
+    mut time_scale: ResMut<TimeScale>,
+    mut ball_speed: ResMut<BallSpeed>,
+) {
+    if pending.0.is_none() || brick_grid.is_empty() || ball_query.is_empty() {
+        return;
+    }
+    let snapshot = pending.0.take().unwrap();
+    time_scale.factor = snapshot.time_scale;
+    ball_speed.0 = snapshot.ball_speed;
+
+    let cells = snapshot.bricks.iter().filter_map(|brick| {
+        let kind = BrickKind::from_symbol(brick.symbol)?;
+        Some((brick.row, brick.column, Cell { kind, health: brick.health }))
+    });
+    brick_grid.replace(cells);
+
+    let paddles = snapshot.entities.iter().filter(|entity| entity.has::<Paddle>());
+    for (saved, mut transform) in paddles.zip(&mut paddle_query) {
+        *transform = saved.transform();
+    }
+
+    let mut balls = snapshot.entities.iter().filter(|entity| entity.has::<Ball>());
+    let mut existing = ball_query.iter_mut();
+    let Some((ball, mut transform, mut body, mesh, material)) = existing.next() else { return };
+    let (mesh, material) = (mesh.clone(), material.clone());
+    for (ball, ..) in existing {
+        commands.entity(ball).despawn_recursive();
+    }
+    let Some(saved) = balls.next() else { return };
+    *transform = saved.transform();
+    *body = saved.kinematic.map_or(*body, KinematicSnapshot::into_kinematic);
+    set_held(&mut commands.entity(ball), saved);
+
+    for saved in balls {
+        let mut ball = commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: saved.transform(),
+                ..default()
+            },
+            Ball,
+            collision::CollisionLayers::from(collision::Layer::Ball),
+            saved.kinematic.map_or_else(Kinematic::default, KinematicSnapshot::into_kinematic),
+            LevelEntity,
+        ));
+        set_held(&mut ball, saved);
+    }
+}
+
+// Ein Ball, der beim Schnappschuss auf dem Paddle lag, liegt dort wieder. Alle anderen fliegen sofort.
+fn set_held(ball: &mut EntityCommands, saved: &EntitySnapshot) {
+    if saved.has::<HeldBall>() {
+        ball.insert(HeldBall::default());
+    } else {
+        ball.remove::<HeldBall>();
+    }
+}
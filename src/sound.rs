@@ -0,0 +1,343 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::{
+    audio::{play_queued_audio_system, AudioOutput, Decodable, Source},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::{HashMap, HashSet},
+};
+use rand::Rng;
+
+use crate::cli::LaunchOptions;
+use crate::focus::Activated;
+use crate::config::GameConfig;
+use crate::rng::GameRng;
+use crate::{Ball, CollisionEvent, Kinematic, PADDLE_Y};
+
+const SAMPLE_RATE: u32 = 44_100;
+// Gleichzeitig klingende Töne, insgesamt und je Soundeffekt. Was darüber hinausgeht, wird verworfen.
+const MAX_VOICES: usize = 12;
+const MAX_VOICES_PER_SFX: usize = 4;
+// Jeder weitere gleichzeitige Ton desselben Effekts wird um diesen Faktor leiser, damit eine Kettenexplosion
+// nicht zwanzigmal mit voller Lautstärke knallt.
+const VOICE_FALLOFF: f32 = 0.7;
+// Die Tonhöhe schwankt leicht, damit sich schnell wiederholte Effekte nicht mechanisch anhören.
+const PITCH_VARIATION: f32 = 0.05;
+
+// Da das Projekt keine Audiodateien hat, werden die Soundeffekte als kurze Töne synthetisiert.
+// Ein Ton gleitet über seine Dauer von `frequency` nach `end_frequency` und klingt linear aus.
+#[derive(TypeUuid, Clone, Copy)]
+#[uuid = "9b0f5d1e-2c47-4a8e-b6d3-71e4c2a5f830"]
+pub struct Tone {
+    frequency: f32,
+    end_frequency: f32,
+    duration: f32,
+    waveform: Waveform,
+}
+
+#[derive(Clone, Copy)]
+enum Waveform {
+    Sine,
+    Square,
+}
+
+pub struct ToneDecoder {
+    tone: Tone,
+    sample: u32,
+    total_samples: u32,
+    phase: f32,
+}
+
+impl Iterator for ToneDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.total_samples {
+            return None;
+        }
+        let progress = self.sample as f32 / self.total_samples as f32;
+        let frequency = self.tone.frequency + (self.tone.end_frequency - self.tone.frequency) * progress;
+        self.phase = (self.phase + frequency / SAMPLE_RATE as f32).fract();
+        self.sample += 1;
+
+        let value = match self.tone.waveform {
+            Waveform::Sine => (self.phase * TAU).sin(),
+            Waveform::Square => if self.phase < 0.5 { 0.5 } else { -0.5 },
+        };
+        Some(value * (1.0 - progress))
+    }
+}
+
+impl Source for ToneDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.total_samples - self.sample) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(self.tone.duration))
+    }
+}
+
+impl Decodable for Tone {
+    type Decoder = ToneDecoder;
+    type DecoderItem = f32;
+
+    fn decoder(&self) -> ToneDecoder {
+        ToneDecoder {
+            tone: *self,
+            sample: 0,
+            total_samples: (self.duration * SAMPLE_RATE as f32) as u32,
+            phase: 0.0,
+        }
+    }
+}
+
+// Alle Soundeffekte des Spiels. Systeme senden ein PlaySound-Event, statt selbst Audio abzuspielen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Sfx {
+    Bounce,
+    Bumper,
+    Portal,
+    Smash,
+    PowerUp,
+    ShieldBreak,
+    Hazard,
+    Coin,
+    Thread,
+    TurretCharge,
+    Rescue,
+    Click,
+    Tally,
+    Glass,
+    Metal,
+    Stone,
+    TrickShot,
+    // Ein Effekt eines Power-Ups läuft gleich aus.
+    EffectEnding,
+    // Ein Ball ist am Paddle vorbei und fällt.
+    BallFalling,
+    // Ein Ball fällt tief über dem Paddle, siehe danger.rs.
+    Danger,
+}
+
+impl Sfx {
+    const ALL: [Sfx; 20] = [
+        Sfx::Bounce,
+        Sfx::Bumper,
+        Sfx::Portal,
+        Sfx::Smash,
+        Sfx::PowerUp,
+        Sfx::ShieldBreak,
+        Sfx::Hazard,
+        Sfx::Coin,
+        Sfx::Thread,
+        Sfx::TurretCharge,
+        Sfx::Rescue,
+        Sfx::Click,
+        Sfx::Tally,
+        Sfx::Glass,
+        Sfx::Metal,
+        Sfx::Stone,
+        Sfx::TrickShot,
+        Sfx::EffectEnding,
+        Sfx::BallFalling,
+        Sfx::Danger,
+    ];
+
+    fn bus(self) -> Bus {
+        match self {
+            Sfx::Click | Sfx::Tally => Bus::Ui,
+            _ => Bus::Effects,
+        }
+    }
+
+    fn tone(self) -> Tone {
+        match self {
+            Sfx::Bounce => Tone { frequency: 440.0, end_frequency: 330.0, duration: 0.06, waveform: Waveform::Sine },
+            Sfx::Bumper => Tone { frequency: 520.0, end_frequency: 1040.0, duration: 0.15, waveform: Waveform::Square },
+            Sfx::Portal => Tone { frequency: 900.0, end_frequency: 300.0, duration: 0.25, waveform: Waveform::Sine },
+            Sfx::Smash => Tone { frequency: 110.0, end_frequency: 55.0, duration: 0.5, waveform: Waveform::Square },
+            Sfx::PowerUp => Tone { frequency: 660.0, end_frequency: 1320.0, duration: 0.2, waveform: Waveform::Sine },
+            Sfx::ShieldBreak => Tone { frequency: 1200.0, end_frequency: 200.0, duration: 0.3, waveform: Waveform::Square },
+            Sfx::Hazard => Tone { frequency: 220.0, end_frequency: 150.0, duration: 0.4, waveform: Waveform::Square },
+            Sfx::Coin => Tone { frequency: 1320.0, end_frequency: 1760.0, duration: 0.1, waveform: Waveform::Sine },
+            Sfx::Thread => Tone { frequency: 880.0, end_frequency: 1760.0, duration: 0.12, waveform: Waveform::Sine },
+            Sfx::TurretCharge => Tone { frequency: 150.0, end_frequency: 600.0, duration: 0.7, waveform: Waveform::Square },
+            Sfx::Rescue => Tone { frequency: 330.0, end_frequency: 990.0, duration: 0.3, waveform: Waveform::Sine },
+            Sfx::Click => Tone { frequency: 1000.0, end_frequency: 800.0, duration: 0.03, waveform: Waveform::Sine },
+            Sfx::Tally => Tone { frequency: 1500.0, end_frequency: 1500.0, duration: 0.02, waveform: Waveform::Square },
+            Sfx::Glass => Tone { frequency: 2400.0, end_frequency: 2200.0, duration: 0.15, waveform: Waveform::Sine },
+            Sfx::Metal => Tone { frequency: 1100.0, end_frequency: 1050.0, duration: 0.35, waveform: Waveform::Square },
+            Sfx::Stone => Tone { frequency: 90.0, end_frequency: 60.0, duration: 0.12, waveform: Waveform::Square },
+            Sfx::TrickShot => Tone { frequency: 780.0, end_frequency: 1560.0, duration: 0.25, waveform: Waveform::Square },
+            Sfx::EffectEnding => Tone { frequency: 990.0, end_frequency: 660.0, duration: 0.3, waveform: Waveform::Sine },
+            Sfx::BallFalling => Tone { frequency: 600.0, end_frequency: 200.0, duration: 0.4, waveform: Waveform::Sine },
+            Sfx::Danger => Tone { frequency: 1040.0, end_frequency: 1040.0, duration: 0.15, waveform: Waveform::Square },
+        }
+    }
+
+    // Die Bildunterschrift für Töne, die man ohne Ton nicht verpassen sollte. Alle anderen bekommen keine.
+    pub fn caption(self) -> Option<&'static str> {
+        match self {
+            Sfx::TurretCharge => Some("Geschütz lädt"),
+            Sfx::EffectEnding => Some("Effekt läuft aus"),
+            Sfx::BallFalling => Some("Ball fällt"),
+            Sfx::Danger => Some("Ball tief"),
+            _ => None,
+        }
+    }
+}
+
+// Die Kanäle des Mischpults, jeder mit eigener Lautstärke.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Bus {
+    Effects,
+    Ui,
+    // Musik gibt es noch nicht, der Kanal ist schon für sie vorgesehen.
+    #[allow(dead_code)]
+    Music,
+}
+
+impl Bus {
+    fn volume(self) -> f32 {
+        match self {
+            Bus::Effects => 0.8,
+            Bus::Ui => 0.5,
+            Bus::Music => 0.6,
+        }
+    }
+}
+
+pub struct PlaySound(pub Sfx);
+
+// Wie PlaySound, nur mit der Stelle in der Welt, von der der Ton kommt. Die Bildunterschriften zeigen in ihre Richtung.
+pub struct PlaySoundAt(pub Sfx, pub Vec3);
+
+#[derive(Resource)]
+struct SoundEffects(HashMap<Sfx, Handle<Tone>>);
+
+// Die gerade klingenden Töne mit ihrer restlichen Dauer.
+#[derive(Resource, Default)]
+struct Mixer {
+    voices: Vec<(Sfx, f32)>,
+}
+
+impl Mixer {
+    // Die Lautstärke für einen neuen Ton oder None, wenn schon zu viele klingen.
+    fn volume_for(&self, sfx: Sfx) -> Option<f32> {
+        let same = self.voices.iter().filter(|(playing, _)| *playing == sfx).count();
+        if self.voices.len() >= MAX_VOICES || same >= MAX_VOICES_PER_SFX {
+            return None;
+        }
+        Some(sfx.bus().volume() * VOICE_FALLOFF.powi(same as i32))
+    }
+}
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        // Entspricht dem, was das AudioPlugin für AudioSource registriert, nur für die synthetisierten Töne.
+        app.add_asset::<Tone>()
+            .init_resource::<Audio<Tone>>()
+            .init_non_send_resource::<AudioOutput<Tone>>()
+            .add_system_to_stage(CoreStage::PostUpdate, play_queued_audio_system::<Tone>)
+            .add_event::<PlaySound>()
+            .add_event::<PlaySoundAt>()
+            .init_resource::<Mixer>()
+            .add_startup_system(create_sound_effects)
+            .add_system(play_collision_sound.before(play_sounds))
+            .add_system(play_click_sound.before(play_sounds))
+            .add_system(play_falling_sound.before(play_sounds))
+            .add_system(play_sounds);
+    }
+}
+
+fn create_sound_effects(mut commands: Commands, mut tones: ResMut<Assets<Tone>>) {
+    let effects = Sfx::ALL
+        .into_iter()
+        .map(|sfx| (sfx, tones.add(sfx.tone())))
+        .collect();
+    commands.insert_resource(SoundEffects(effects));
+}
+
+// Jede Kollision des Balls erzeugt einen kurzen Aufprall-Ton.
+fn play_collision_sound(collision_events: EventReader<CollisionEvent>, mut sounds: EventWriter<PlaySound>) {
+    if !collision_events.is_empty() {
+        collision_events.clear();
+        sounds.send(PlaySound(Sfx::Bounce));
+    }
+}
+
+// Jeder gedrückte Button klickt, egal ob mit Maus, Tastatur oder Gamepad.
+fn play_click_sound(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    activated: EventReader<Activated>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let clicked = interaction_query.iter().any(|interaction| *interaction == Interaction::Clicked);
+    if clicked || !activated.is_empty() {
+        activated.clear();
+        sounds.send(PlaySound(Sfx::Click));
+    }
+}
+
+// Ein Ball, der unter die Unterkante des Paddles fällt, ist so gut wie verloren. Der Ton kommt einmal, bis er wieder
+// über dem Paddle ist.
+fn play_falling_sound(
+    ball_query: Query<(Entity, &Transform, &Kinematic), With<Ball>>,
+    config: Res<GameConfig>,
+    mut sounds: EventWriter<PlaySoundAt>,
+    mut falling: Local<HashSet<Entity>>,
+) {
+    let paddle_bottom = PADDLE_Y - config.paddle_size.y / 2.0;
+    for (ball, transform, body) in &ball_query {
+        if transform.translation.y >= paddle_bottom {
+            falling.remove(&ball);
+        } else if body.velocity.y < 0.0 && falling.insert(ball) {
+            sounds.send(PlaySoundAt(Sfx::BallFalling, transform.translation));
+        }
+    }
+    falling.retain(|ball| ball_query.contains(*ball));
+}
+
+// Mit --mute werden die Events nur noch verworfen. Sonst geht jeder Ton durch das Mischpult, das die Zahl
+// gleichzeitiger Töne begrenzt und Wiederholungen leiser macht. Die Tonhöhe wird auch für verworfene Töne gezogen,
+// damit der gemeinsame Zufall mit und ohne Ton gleich verläuft.
+#[allow(clippy::too_many_arguments)]
+fn play_sounds(
+    mut events: EventReader<PlaySound>,
+    mut positioned: EventReader<PlaySoundAt>,
+    effects: Res<SoundEffects>,
+    audio: Res<Audio<Tone>>,
+    options: Res<LaunchOptions>,
+    mut mixer: ResMut<Mixer>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    mixer.voices.retain_mut(|(_, remaining)| {
+        *remaining -= delta;
+        *remaining > 0.0
+    });
+    let sounds = events.iter().map(|PlaySound(sfx)| sfx).chain(positioned.iter().map(|PlaySoundAt(sfx, _)| sfx));
+    for sfx in sounds {
+        let pitch = rng.gen_range(1.0 - PITCH_VARIATION..=1.0 + PITCH_VARIATION);
+        if options.mute {
+            continue;
+        }
+        let Some(volume) = mixer.volume_for(*sfx) else { continue };
+        mixer.voices.push((*sfx, sfx.tone().duration / pitch));
+        let settings = PlaybackSettings::ONCE.with_volume(volume).with_speed(pitch);
+        audio.play_with_settings(effects.0[sfx].clone(), settings);
+    }
+}
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::coop::CeilingPaddle;
+use crate::mode::GameMode;
+use crate::toast::ShowToast;
+use crate::tween::TweenSystem;
+use crate::{Arena, Ball, GameState, MainCamera, Paddle};
+
+const SPECTATOR_KEY: KeyCode = KeyCode::V;
+// So viel Platz bleibt um Paddles und Ball herum frei, in Metern.
+const FRAME_MARGIN: f32 = 1.5;
+// Kleiner wird der Ausschnitt nie, sonst zoomt die Kamera bei dicht beieinander liegenden Objekten zu nah heran.
+const MIN_FRAME_HEIGHT: f32 = 6.0;
+// Die Kamera schaut wie die normale Kamera etwas von oben auf das Feld, nur flacher.
+const TILT: f32 = 0.2;
+// Wie schnell die Kamera dem Ausschnitt folgt. Größer heißt direkter.
+const FOLLOW_RATE: f32 = 4.0;
+
+// Die Zuschauerkamera für gemeinsame Spiele vor dem Bildschirm. Statt der festen Sicht auf die Arena hält sie
+// beide Paddles und den Ball im Bild und folgt ihnen weich, das sieht auch im Stream ruhig aus.
+#[derive(Resource, Default)]
+struct Spectator {
+    active: bool,
+}
+
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Spectator>()
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(toggle_spectator)
+                    .with_system(frame_players.after(toggle_spectator).after(TweenSystem)),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(reset_camera));
+    }
+}
+
+// Nur im Koop-Modus, dem einzigen mit zwei Spielern. V schaltet zwischen der Sicht auf die Arena und der
+// Zuschauerkamera um.
+fn toggle_spectator(
+    keyboard_input: Res<Input<KeyCode>>,
+    mode: Res<GameMode>,
+    arena: Res<Arena>,
+    mut spectator: ResMut<Spectator>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    if *mode != GameMode::Coop || !keyboard_input.just_pressed(SPECTATOR_KEY) {
+        return;
+    }
+    spectator.active = !spectator.active;
+    if !spectator.active {
+        for mut transform in &mut camera_query {
+            *transform = arena.camera_transform();
+        }
+    }
+    let name = if spectator.active { "Zuschauer" } else { "Arena" };
+    toasts.send(ShowToast(format!("Kamera: {name}")));
+}
+
+// Der Ausschnitt ist das Rechteck um beide Paddles und alle Bälle, mit Rand. Die Entfernung ergibt sich aus dem
+// Sichtfeld, sodass es in Höhe und Breite ganz hineinpasst.
+#[allow(clippy::type_complexity)]
+fn frame_players(
+    spectator: Res<Spectator>,
+    mode: Res<GameMode>,
+    time: Res<Time>,
+    player_query: Query<&Transform, Or<(With<Paddle>, With<CeilingPaddle>, With<Ball>)>>,
+    mut camera_query: Query<
+        (&mut Transform, &Projection),
+        (With<MainCamera>, Without<Paddle>, Without<CeilingPaddle>, Without<Ball>),
+    >,
+) {
+    if !spectator.active || *mode != GameMode::Coop {
+        return;
+    }
+    let mut points = player_query.iter().map(|transform| transform.translation.truncate());
+    let Some(first) = points.next() else { return };
+    let (min, max) = points.fold((first, first), |(min, max), point| (min.min(point), max.max(point)));
+    let center = (min + max) / 2.0;
+    let size = (max - min + Vec2::splat(2.0 * FRAME_MARGIN)).max(Vec2::splat(MIN_FRAME_HEIGHT));
+    for (mut transform, projection) in &mut camera_query {
+        let Projection::Perspective(perspective) = projection else { continue };
+        let height = size.y.max(size.x / perspective.aspect_ratio);
+        let distance = height / 2.0 / (perspective.fov / 2.0).tan();
+        let target = Transform::from_xyz(center.x, center.y + TILT * distance, distance)
+            .looking_at(center.extend(0.0), Vec3::Y);
+        let blend = (FOLLOW_RATE * time.delta_seconds()).min(1.0);
+        transform.translation = transform.translation.lerp(target.translation, blend);
+        transform.rotation = transform.rotation.slerp(target.rotation, blend);
+    }
+}
+
+// Menüs und das nächste Level beginnen wieder mit der normalen Sicht, die Einstellung selbst bleibt.
+fn reset_camera(
+    spectator: Res<Spectator>,
+    arena: Res<Arena>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    if !spectator.active {
+        return;
+    }
+    for mut transform in &mut camera_query {
+        *transform = arena.camera_transform();
+    }
+}
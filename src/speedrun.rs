@@ -0,0 +1,188 @@
+use std::fmt::Write as _;
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::format;
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::save::SaveData;
+use crate::{GameState, LevelCleared, LevelEntity};
+
+const TOGGLE_KEY: KeyCode = KeyCode::T;
+// Nach einer vollständigen Kampagne werden die Zeiten hierhin exportiert, damit externe Timer sie einlesen können.
+const SPLITS_EXPORT_PATH: &str = "splits.csv";
+const TIMER_FONT_SIZE: f32 = 28.0;
+const TIMER_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const AHEAD_COLOR: Color = Color::rgb(0.0, 0.55, 0.0);
+const BEHIND_COLOR: Color = Color::rgb(0.8, 0.0, 0.0);
+const GOLD_COLOR: Color = Color::rgb(0.85, 0.65, 0.0);
+
+// Die Zeiten des laufenden Durchlaufs. Gemessen wird nur im Spiel, Belohnung und Shop zählen nicht mit.
+#[derive(Resource, Default)]
+struct SpeedrunClock {
+    level_time: f32,
+    // Zeit pro geschafftem Level, nach Position in der Kampagne.
+    splits: Vec<(usize, f32)>,
+}
+
+impl SpeedrunClock {
+    fn total(&self) -> f32 {
+        self.splits.iter().map(|(_, time)| time).sum::<f32>() + self.level_time
+    }
+}
+
+// Bestzeiten für die Vergleiche. `best_run` ist der schnellste vollständige Durchlauf, `gold` die beste Zeit je Level.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SpeedrunRecords {
+    best_run: Vec<f32>,
+    gold: Vec<Option<f32>>,
+}
+
+#[derive(Component)]
+struct SpeedrunText;
+
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpeedrunClock>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_clock))
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_speedrun_text))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(toggle_timer)
+                    .with_system(tick_clock)
+                    .with_system(record_split.after(tick_clock))
+                    .with_system(update_speedrun_text.after(record_split)),
+            );
+    }
+}
+
+fn reset_clock(mut clock: ResMut<SpeedrunClock>) {
+    *clock = SpeedrunClock::default();
+}
+
+fn spawn_speedrun_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextStyle {
+        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+        font_size: TIMER_FONT_SIZE,
+        color: TIMER_COLOR,
+    };
+    commands.spawn((
+        TextBundle::from_sections([TextSection::from_style(style.clone()), TextSection::from_style(style)]).with_style(
+            Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(50.0),
+                    left: Val::Px(5.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ),
+        SpeedrunText,
+        LevelEntity,
+    ));
+}
+
+// Der Timer ist optional und wird mit T ein- und ausgeblendet. Die Wahl bleibt im Spielstand erhalten.
+fn toggle_timer(keyboard_input: Res<Input<KeyCode>>, mut save: ResMut<SaveData>) {
+    if keyboard_input.just_pressed(TOGGLE_KEY) {
+        save.speedrun_timer = !save.speedrun_timer;
+    }
+}
+
+// Speedruns messen echte Zeit, Zeitlupe und Pause durch den Zeitfaktor ändern daran nichts.
+fn tick_clock(mut clock: ResMut<SpeedrunClock>, time: Res<Time>) {
+    clock.level_time += time.delta_seconds();
+}
+
+fn record_split(
+    mut events: EventReader<LevelCleared>,
+    mut clock: ResMut<SpeedrunClock>,
+    mode: Res<GameMode>,
+    mut save: ResMut<SaveData>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+) {
+    for event in events.iter() {
+        let time = clock.level_time;
+        clock.level_time = 0.0;
+        clock.splits.push((event.index, time));
+        if *mode != GameMode::Campaign {
+            continue;
+        }
+
+        let records = &mut save.speedrun;
+        if records.gold.len() <= event.index {
+            records.gold.resize(event.index + 1, None);
+        }
+        if records.gold[event.index].is_none_or(|gold| time < gold) {
+            records.gold[event.index] = Some(time);
+        }
+
+        // Nur eine Kampagne vom ersten bis zum letzten Level zählt als vollständiger Durchlauf.
+        let complete = event.last && clock.splits.iter().map(|(index, _)| *index).eq(0..campaign.levels.len());
+        if !complete {
+            continue;
+        }
+        let run: Vec<f32> = clock.splits.iter().map(|(_, time)| *time).collect();
+        if records.best_run.is_empty() || run.iter().sum::<f32>() < records.best_run.iter().sum::<f32>() {
+            records.best_run = run;
+        }
+        export_splits(&clock.splits, &campaign, &levels);
+    }
+}
+
+// Schreibt die Zeiten als CSV: Level, Name, Levelzeit und Gesamtzeit in Sekunden.
+fn export_splits(splits: &[(usize, f32)], campaign: &Campaign, levels: &Assets<Level>) {
+    let mut contents = String::from("level,name,split,total\n");
+    let mut total = 0.0;
+    for (index, time) in splits {
+        total += time;
+        let name = levels.get(&campaign.levels[*index]).map_or("?", |level| level.name.as_str());
+        // Namen kommen aus Level-Dateien und Mods und dürfen Kommas enthalten. Deshalb stehen sie in Anführungszeichen,
+        // Anführungszeichen darin werden verdoppelt.
+        let name = name.replace('"', "\"\"");
+        let _ = writeln!(contents, "{},\"{name}\",{:.3},{:.3}", index + 1, time, total);
+    }
+    if let Err(error) = fs::write(SPLITS_EXPORT_PATH, contents) {
+        warn!("Splits konnten nicht exportiert werden: {error}");
+    }
+}
+
+// Oben steht die Gesamtzeit, dahinter der letzte Split mit dem Abstand zum besten Durchlauf.
+// Gold heißt: bester Split aller Zeiten für dieses Level.
+fn update_speedrun_text(
+    clock: Res<SpeedrunClock>,
+    save: Res<SaveData>,
+    mut query: Query<(&mut Text, &mut Visibility), With<SpeedrunText>>,
+) {
+    let Ok((mut text, mut visibility)) = query.get_single_mut() else { return };
+    visibility.is_visible = save.speedrun_timer;
+    if !save.speedrun_timer {
+        return;
+    }
+    text.sections[0].value = format::timer(clock.total());
+
+    let Some((index, time)) = clock.splits.last().copied() else {
+        text.sections[1].value.clear();
+        return;
+    };
+    let records = &save.speedrun;
+    let is_gold = records.gold.get(index).copied().flatten().is_some_and(|gold| time <= gold);
+    let delta = records.best_run.get(index).map(|best| time - best);
+    text.sections[1].value = match delta {
+        Some(delta) => format!("  L{}: {} ({})", index + 1, format::timer(time), format::signed_seconds(delta)),
+        None => format!("  L{}: {}", index + 1, format::timer(time)),
+    };
+    text.sections[1].style.color = if is_gold {
+        GOLD_COLOR
+    } else if delta.is_some_and(|delta| delta > 0.0) {
+        BEHIND_COLOR
+    } else {
+        AHEAD_COLOR
+    };
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::{collide, Collision};
+
+use crate::accessibility::AccessibilitySettings;
+use crate::ball_physics::{self, BallPhysics};
+use crate::{phase_set, Ball, BallLost, Kinematic, Paddle, Phase, TimeScale};
+
+// Wie viel Drall der Ball vom Paddle mitnimmt, steht in seiner BallPhysics.
+const MAX_SPIN: f32 = 8.0;
+// Anteil des Dralls, der pro Sekunde verloren geht.
+const SPIN_DECAY: f32 = 0.5;
+// Wie stark der Drall die Flugbahn krümmt. Klein genug, dass der Ball nur leicht abgelenkt wird.
+const MAGNUS_STRENGTH: f32 = 0.05;
+
+// Der Drall des Balls um die Z-Achse in Radiant pro Sekunde. Positiv dreht gegen den Uhrzeigersinn.
+#[derive(Component, Default)]
+pub struct AngularVelocity(pub f32);
+
+// Die Bewegung des Paddles im letzten Schritt, daraus entsteht der Drall beim Abprall.
+#[derive(Component, Default)]
+pub struct PaddleMotion {
+    last_x: Option<f32>,
+    velocity: f32,
+}
+
+impl PaddleMotion {
+    // Seitliche Geschwindigkeit des Paddles im letzten Schritt, positiv nach rechts.
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+}
+
+pub struct SpinPlugin;
+
+impl Plugin for SpinPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(show_spin_texture)
+            .add_system_set(phase_set(Phase::Forces).with_system(track_paddle_motion).with_system(curve_ball))
+            // Vor check_for_collision, denn danach ist der Ball schon aus dem Paddle geschoben und berührt es nicht mehr.
+            .add_system_set(
+                phase_set(Phase::Collision).with_system(spin_from_paddle.before(crate::check_for_collision)),
+            )
+            .add_system_set(phase_set(Phase::Scoring).with_system(reset_spin))
+            .add_system_set(phase_set(Phase::Vfx).with_system(rotate_ball));
+    }
+}
+
+fn track_paddle_motion(mut query: Query<(&Transform, &mut PaddleMotion)>, time_scale: Res<TimeScale>) {
+    let step = time_scale.step();
+    for (transform, mut motion) in &mut query {
+        let x = transform.translation.x;
+        motion.velocity = match motion.last_x {
+            Some(last_x) if step > 0.0 => (x - last_x) / step,
+            _ => 0.0,
+        };
+        motion.last_x = Some(x);
+    }
+}
+
+// Trifft der Ball das Paddle von oben, nimmt er einen Teil von dessen Bewegung als Drall mit.
+// Gezählt wird nur der erste Schritt einer Berührung.
+#[allow(clippy::type_complexity)]
+fn spin_from_paddle(
+    mut ball_query: Query<(&Transform, &mut AngularVelocity, Option<&Handle<BallPhysics>>), With<Ball>>,
+    paddle_query: Query<(&Transform, &PaddleMotion), With<Paddle>>,
+    ball_physics: Res<Assets<BallPhysics>>,
+    mut touching: Local<bool>,
+) {
+    let Ok((paddle_transform, motion)) = paddle_query.get_single() else { return };
+    for (ball_transform, mut spin, physics) in &mut ball_query {
+        let collision = collide(
+            ball_transform.translation,
+            ball_transform.scale.truncate(),
+            paddle_transform.translation,
+            paddle_transform.scale.truncate(),
+        );
+        let now_touching = matches!(collision, Some(Collision::Top));
+        if now_touching && !*touching {
+            let response = ball_physics::resolve(physics, &ball_physics).spin_response;
+            spin.0 = (spin.0 - motion.velocity * response).clamp(-MAX_SPIN, MAX_SPIN);
+        }
+        *touching = now_touching;
+    }
+}
+
+// Der Drall lenkt den Ball quer zu seiner Flugrichtung ab, wie beim Magnus-Effekt. Die Geschwindigkeit bleibt gleich.
+fn curve_ball(mut query: Query<(&mut Kinematic, &mut AngularVelocity), With<Ball>>, time_scale: Res<TimeScale>) {
+    let step = time_scale.step();
+    for (mut body, mut spin) in &mut query {
+        let speed = body.velocity.length();
+        if spin.0 != 0.0 && speed > 0.0 {
+            let curve = Vec3::Z.cross(body.velocity) * spin.0 * MAGNUS_STRENGTH * step;
+            body.velocity = (body.velocity + curve).normalize() * speed;
+        }
+        spin.0 *= (1.0 - SPIN_DECAY * step).max(0.0);
+    }
+}
+
+// Ein neu eingesetzter Ball startet ohne Drall.
+fn reset_spin(mut events: EventReader<BallLost>, mut query: Query<&mut AngularVelocity, With<Ball>>) {
+    if events.iter().count() == 0 {
+        return;
+    }
+    for mut spin in &mut query {
+        spin.0 = 0.0;
+    }
+}
+
+// Die Textur auf dem Ball dreht sich mit dem Drall, daran sieht man ihn. Bei weniger Bewegung bleibt sie stehen.
+fn rotate_ball(
+    mut query: Query<(&mut Transform, &AngularVelocity), With<Ball>>,
+    time_scale: Res<TimeScale>,
+    settings: Res<AccessibilitySettings>,
+) {
+    if !settings.allows_motion() {
+        return;
+    }
+    for (mut transform, spin) in &mut query {
+        transform.rotate_z(spin.0 * time_scale.step());
+    }
+}
+
+// Ein einfarbiger Ball würde seinen Drall nicht zeigen, deshalb bekommt er die Textur der Bricks.
+fn show_spin_texture(
+    query: Query<&Handle<StandardMaterial>, Added<Ball>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for handle in &query {
+        if let Some(material) = materials.get_mut(handle) {
+            material.base_color_texture = Some(images.add(crate::texture::uv_debug_texture()));
+        }
+    }
+}
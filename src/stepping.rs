@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::toast::ShowToast;
+
+// So schnell läuft die Zeit für den festen Takt in der Zeitlupe.
+const SLOW_RATE: f64 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+enum StepMode {
+    #[default]
+    Running,
+    Slow,
+    Paused,
+}
+
+// Zum Debuggen, nur mit dem Feature "dev": F5 hält den festen Takt an oder lässt ihn weiterlaufen, F6 macht im
+// Stillstand genau einen Schritt, F7 schaltet auf ein Zehntel der Geschwindigkeit. Der Takt selbst wird angehalten,
+// nicht nur die Bewegung, deshalb stehen alle Systeme in den Phasen gemeinsam still. Ein so gespielter Durchlauf
+// besteht die Prüfung in RunTicks nicht und wird nicht gewertet.
+#[derive(Resource, Default)]
+pub struct Stepping {
+    mode: StepMode,
+    pending_step: bool,
+}
+
+impl Stepping {
+    // Wie viel von `seconds` echter Zeit im festen Takt ankommt. Angehalten oder in Zeitlupe nur ein Teil, ein
+    // angeforderter Einzelschritt bringt genau einmal `tick` dazu.
+    pub fn advance(&mut self, seconds: f64, tick: f64) -> f64 {
+        let rate = match self.mode {
+            StepMode::Running => 1.0,
+            StepMode::Slow => SLOW_RATE,
+            StepMode::Paused => 0.0,
+        };
+        let step = if std::mem::take(&mut self.pending_step) { tick } else { 0.0 };
+        seconds * rate + step
+    }
+}
+
+pub struct SteppingPlugin;
+
+impl Plugin for SteppingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stepping>().add_system(stepping_keys);
+    }
+}
+
+fn stepping_keys(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut stepping: ResMut<Stepping>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let mode = if keyboard_input.just_pressed(KeyCode::F5) {
+        if stepping.mode == StepMode::Paused { StepMode::Running } else { StepMode::Paused }
+    } else if keyboard_input.just_pressed(KeyCode::F7) {
+        if stepping.mode == StepMode::Slow { StepMode::Running } else { StepMode::Slow }
+    } else {
+        if keyboard_input.just_pressed(KeyCode::F6) && stepping.mode == StepMode::Paused {
+            stepping.pending_step = true;
+        }
+        return;
+    };
+    stepping.mode = mode;
+    let message = match mode {
+        StepMode::Running => "Simulation läuft",
+        StepMode::Slow => "Simulation in Zeitlupe (10 %)",
+        StepMode::Paused => "Simulation angehalten, F6 für einen Schritt",
+    };
+    toasts.send(ShowToast(message.to_string()));
+}
@@ -0,0 +1,159 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::launch::HeldBall;
+use crate::level::Level;
+use crate::rng::GameRng;
+use crate::{collision, Arena, Ball, BallSpeed, Brick, GameState, GridGeometry, Kinematic, LevelEntity};
+
+// So lange wird gemessen, bevor die Frame-Zeiten ausgegeben werden und das Spiel endet.
+const MEASURE_SECONDS: f64 = 30.0;
+// Um diesen Faktor wächst die Arena, bis genug Bricks hineinpassen.
+const ARENA_GROWTH: f32 = 1.25;
+// Die Perzentile der Frame-Zeiten in der Ausgabe.
+const PERCENTILES: [f64; 4] = [0.5, 0.9, 0.99, 1.0];
+
+// Ein Lasttest mit `--stress <bricks> <balls>`. Die Arena wird so groß, dass die Bricks hineinpassen, und alle Bälle
+// fliegen sofort los. Sie prallen am Boden ab, damit die Zahl der Objekte während der ganzen Messung gleich bleibt.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct StressTest {
+    pub bricks: usize,
+    pub balls: usize,
+}
+
+impl StressTest {
+    pub fn level(&self) -> Level {
+        let mut arena = Arena::default();
+        let mut geometry = GridGeometry::new(&arena);
+        while geometry.rows * geometry.columns < self.bricks {
+            arena = arena.scaled(ARENA_GROWTH);
+            geometry = GridGeometry::new(&arena);
+        }
+        let layout = (0..self.bricks.div_ceil(geometry.columns.max(1)))
+            .map(|line| "#".repeat((self.bricks - line * geometry.columns).min(geometry.columns)))
+            .collect();
+        Level {
+            name: format!("Lasttest {} Bricks, {} Bälle", self.bricks, self.balls),
+            gravity: false,
+            force_fields: Vec::new(),
+            bumpers: Vec::new(),
+            portals: Vec::new(),
+            obstacles: Vec::new(),
+            floor: Vec::new(),
+            objectives: Vec::new(),
+            par: None,
+            ball_speed: 1.0,
+            layout: Some(layout),
+            arena: Some(arena),
+            camera: None,
+            ball: None,
+        }
+    }
+}
+
+// Die Frame-Zeiten seit dem Abschuss der Bälle in Sekunden.
+#[derive(Resource, Default)]
+struct FrameTimes {
+    started: bool,
+    elapsed: f64,
+    samples: Vec<f64>,
+}
+
+pub struct StressPlugin;
+
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FrameTimes>()
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(release_balls))
+            .add_system(measure_frames);
+    }
+}
+
+// Der Ball aus spawn_level fliegt sofort los, die übrigen kommen mit demselben Mesh und Material dazu.
+// Geht ein Ball doch einmal verloren, liegt er wieder auf dem Paddle und wird hier erneut abgeschossen.
+#[allow(clippy::type_complexity)]
+fn release_balls(
+    mut commands: Commands,
+    stress: Option<Res<StressTest>>,
+    mut ball_query: Query<
+        (Entity, &Transform, &mut Kinematic, &Handle<Mesh>, &Handle<StandardMaterial>, Option<&HeldBall>),
+        With<Ball>,
+    >,
+    ball_speed: Res<BallSpeed>,
+    mut game_rng: ResMut<GameRng>,
+    mut frame_times: ResMut<FrameTimes>,
+) {
+    let Some(stress) = stress else { return };
+    let rng = &mut *game_rng;
+    let mut direction = || Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(0.3..1.0), 0.0).normalize();
+    let mut template = None;
+    for (entity, transform, mut body, mesh, material, held) in &mut ball_query {
+        if held.is_some() {
+            body.velocity = direction() * ball_speed.0;
+            body.bounciness = 1.0;
+            commands.entity(entity).remove::<HeldBall>();
+        }
+        template = Some((*transform, body.gravity, mesh.clone(), material.clone()));
+    }
+    let Some((transform, gravity, mesh, material)) = template else { return };
+    for _ in ball_query.iter().count()..stress.balls {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform,
+                ..default()
+            },
+            Ball,
+            collision::CollisionLayers::from(collision::Layer::Ball),
+            Kinematic {
+                velocity: direction() * ball_speed.0,
+                gravity,
+                bounciness: 1.0,
+            },
+            LevelEntity,
+        ));
+    }
+    frame_times.started = true;
+}
+
+// Nach der Messzeit stehen die Perzentile im Log, danach beendet sich das Spiel.
+fn measure_frames(
+    time: Res<Time>,
+    stress: Option<Res<StressTest>>,
+    mut frame_times: ResMut<FrameTimes>,
+    brick_query: Query<(), With<Brick>>,
+    ball_query: Query<(), With<Ball>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(stress) = stress else { return };
+    if !frame_times.started {
+        return;
+    }
+    let delta = time.delta_seconds_f64();
+    frame_times.elapsed += delta;
+    frame_times.samples.push(delta);
+    if frame_times.elapsed < MEASURE_SECONDS {
+        return;
+    }
+    let mut samples = std::mem::take(&mut frame_times.samples);
+    samples.sort_by(f64::total_cmp);
+    let percentiles: Vec<_> = PERCENTILES
+        .iter()
+        .map(|&percentile| {
+            let index = ((samples.len() - 1) as f64 * percentile).round() as usize;
+            format!("p{}: {:.2} ms", (percentile * 100.0) as u32, samples[index] * 1000.0)
+        })
+        .collect();
+    info!(
+        "Lasttest mit {} Bricks und {} Bällen, am Ende {} Bricks und {} Bälle, {} Frames: {}",
+        stress.bricks,
+        stress.balls,
+        brick_query.iter().count(),
+        ball_query.iter().count(),
+        samples.len(),
+        percentiles.join(", ")
+    );
+    exit.send(AppExit);
+}
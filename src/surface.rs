@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::level::BrickKind;
+use crate::sound::{PlaySound, Sfx};
+use crate::{phase_set, BrickHit, Kinematic, LevelEntity, Phase, TimeScale};
+
+const PARTICLES_PER_HIT: usize = 5;
+const PARTICLE_SIZE: f32 = 0.08;
+const PARTICLE_SPEED: f32 = 3.0;
+const PARTICLE_GRAVITY: f32 = 9.81;
+const PARTICLE_LIFETIME: f32 = 0.4;
+
+// Das Material eines Bricks. Die Kollision liest es, um den Ball nach dem Spiegeln zu bremsen oder zu beschleunigen,
+// zusammen mit dessen BallPhysics. Bricks ohne Surface prallen ohne Verlust ab.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Surface {
+    Glass,
+    Metal,
+    Stone,
+}
+
+impl Surface {
+    const ALL: [Surface; 3] = [Surface::Glass, Surface::Metal, Surface::Stone];
+
+    pub fn of(kind: BrickKind) -> Option<Surface> {
+        match kind {
+            BrickKind::Glass => Some(Surface::Glass),
+            BrickKind::Metal => Some(Surface::Metal),
+            BrickKind::Stone => Some(Surface::Stone),
+            _ => None,
+        }
+    }
+
+    // Anteil der Geschwindigkeit, den der Ball beim Abprallen behält. Glas bremst kaum, Stein dämpft, Metall
+    // federt und gibt etwas dazu.
+    pub fn restitution(self) -> f32 {
+        match self {
+            Surface::Glass => 0.98,
+            Surface::Metal => 1.1,
+            Surface::Stone => 0.85,
+        }
+    }
+
+    fn sfx(self) -> Sfx {
+        match self {
+            Surface::Glass => Sfx::Glass,
+            Surface::Metal => Sfx::Metal,
+            Surface::Stone => Sfx::Stone,
+        }
+    }
+
+    // Glas splittert hell, Metall sprüht Funken, Stein bröckelt.
+    fn particle_color(self) -> Color {
+        match self {
+            Surface::Glass => Color::rgb(0.8, 0.95, 1.0),
+            Surface::Metal => Color::rgb(1.0, 0.8, 0.3),
+            Surface::Stone => Color::rgb(0.5, 0.47, 0.42),
+        }
+    }
+}
+
+// Ein kleines Teilchen, das beim Treffer vom Material abspringt. Bewegt wird es von apply_velocity.
+#[derive(Component)]
+struct ImpactParticle {
+    lifetime: Timer,
+}
+
+pub struct SurfacePlugin;
+
+impl Plugin for SurfacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(play_surface_sounds)
+            .add_system(spawn_impact_particles)
+            .add_system_set(phase_set(Phase::Vfx).with_system(update_impact_particles));
+    }
+}
+
+fn play_surface_sounds(mut events: EventReader<BrickHit>, mut sounds: EventWriter<PlaySound>) {
+    for surface in events.iter().filter_map(|event| event.surface) {
+        sounds.send(PlaySound(surface.sfx()));
+    }
+}
+
+// Die Teilchen teilen sich ein Mesh und je Material eine Farbe, in der Reihenfolge von Surface::ALL.
+// Sie werden kleiner statt durchsichtig.
+#[allow(clippy::type_complexity)]
+fn spawn_impact_particles(
+    mut commands: Commands,
+    mut events: EventReader<BrickHit>,
+    settings: Res<AccessibilitySettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut assets: Local<Option<(Handle<Mesh>, Vec<Handle<StandardMaterial>>)>>,
+) {
+    if !settings.allows_motion() {
+        events.clear();
+        return;
+    }
+    let (mesh, surface_materials) = assets.get_or_insert_with(|| {
+        let materials = Surface::ALL
+            .into_iter()
+            .map(|surface| {
+                materials.add(StandardMaterial {
+                    base_color: surface.particle_color(),
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .collect();
+        (meshes.add(shape::Cube::default().into()), materials)
+    });
+    let mut rng = rand::thread_rng();
+    for event in events.iter() {
+        let Some(surface) = event.surface else { continue };
+        let material = &surface_materials[surface as usize];
+        for _ in 0..PARTICLES_PER_HIT {
+            let direction = Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(0.0..0.5));
+            commands.spawn((
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    material: material.clone(),
+                    transform: Transform::from_translation(event.position).with_scale(Vec3::splat(PARTICLE_SIZE)),
+                    ..default()
+                },
+                ImpactParticle {
+                    lifetime: Timer::from_seconds(PARTICLE_LIFETIME, TimerMode::Once),
+                },
+                Kinematic {
+                    velocity: direction.normalize_or_zero() * PARTICLE_SPEED * rng.gen_range(0.5..1.0),
+                    gravity: Vec3::NEG_Y * PARTICLE_GRAVITY,
+                    bounciness: 0.0,
+                },
+                LevelEntity,
+            ));
+        }
+    }
+}
+
+fn update_impact_particles(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut ImpactParticle)>,
+    time_scale: Res<TimeScale>,
+) {
+    let step = Duration::from_secs_f32(time_scale.step());
+    for (entity, mut transform, mut particle) in &mut query {
+        if particle.lifetime.tick(step).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.scale = Vec3::splat(PARTICLE_SIZE * particle.lifetime.percent_left());
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::grid::BrickGrid;
+use crate::mode::GameMode;
+use crate::transition::ScreenTransition;
+use crate::{
+    phase_set, Arena, Ball, GameState, GridGeometry, Kinematic, Paddle, Phase, Scoreboard, TimeScale, WallLocation,
+    BRICK_SIZE, PADDLE_PADDING,
+};
+
+// So weit rückt jede Seitenwand pro Sekunde nach innen.
+const SQUEEZE_SPEED: f32 = 0.02;
+// Jede überstandene Sekunde bringt so viele Punkte.
+const POINTS_PER_SECOND: usize = 10;
+
+// Wie lange der Durchlauf schon läuft. Die Wände rücken über alle Level hinweg weiter zusammen.
+#[derive(Resource, Default)]
+struct SurvivalClock(f32);
+
+pub struct SurvivalPlugin;
+
+impl Plugin for SurvivalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurvivalClock>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_clock))
+            .add_system_set(phase_set(Phase::Movement).with_system(squeeze_arena.after(crate::apply_velocity)));
+    }
+}
+
+fn reset_clock(mut clock: ResMut<SurvivalClock>) {
+    clock.0 = 0.0;
+}
+
+// Die Seitenwände rücken im Überlebensmodus langsam zusammen. Paddle und Ball werden mit nach innen geschoben,
+// Bricks, die in einer Wand verschwinden, sind ohne Punkte verloren. Passt das Paddle nicht mehr zwischen die
+// Wände, ist der Durchlauf vorbei. Gewertet wird vor allem die Zeit bis dahin.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn squeeze_arena(
+    mode: Res<GameMode>,
+    mut clock: ResMut<SurvivalClock>,
+    time_scale: Res<TimeScale>,
+    mut arena: ResMut<Arena>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut brick_grid: ResMut<BrickGrid>,
+    mut wall_query: Query<(&WallLocation, &mut Transform)>,
+    mut paddle_query: Query<&mut Transform, (With<Paddle>, Without<WallLocation>)>,
+    mut ball_query: Query<(&mut Transform, &mut Kinematic), (With<Ball>, Without<Paddle>, Without<WallLocation>)>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    if *mode != GameMode::Survival {
+        return;
+    }
+    let before = clock.0;
+    clock.0 += time_scale.step();
+    scoreboard.score += (clock.0.floor() - before.floor()) as usize * POINTS_PER_SECOND;
+    arena.squeeze = clock.0 * SQUEEZE_SPEED;
+
+    for (location, mut transform) in &mut wall_query {
+        if matches!(location, WallLocation::Left | WallLocation::Right) {
+            *transform = arena.wall_transform(*location);
+        }
+    }
+
+    let inner_left = arena.left() + arena.wall_thickness / 2.0;
+    let inner_right = arena.right() - arena.wall_thickness / 2.0;
+    let geometry = GridGeometry::new(&arena);
+    let buried: Vec<_> = brick_grid
+        .bricks()
+        .map(|(row, column, _)| (row, column))
+        .filter(|&(row, column)| {
+            let x = geometry.position(row, column).x;
+            x - BRICK_SIZE.x / 2.0 < inner_left || x + BRICK_SIZE.x / 2.0 > inner_right
+        })
+        .collect();
+    for (row, column) in buried {
+        brick_grid.remove(row, column);
+    }
+
+    for (mut transform, mut body) in &mut ball_query {
+        let radius = transform.scale.x / 2.0;
+        if transform.translation.x < inner_left + radius {
+            transform.translation.x = inner_left + radius;
+            body.velocity.x = body.velocity.x.abs();
+        } else if transform.translation.x > inner_right - radius {
+            transform.translation.x = inner_right - radius;
+            body.velocity.x = -body.velocity.x.abs();
+        }
+    }
+
+    let Ok(mut paddle) = paddle_query.get_single_mut() else { return };
+    if inner_right - inner_left < paddle.scale.x + 2.0 * PADDLE_PADDING {
+        if !transition.is_active() {
+            transition.to(mode.exit_state());
+        }
+        return;
+    }
+    paddle.translation.x = arena.clamp_paddle_x(paddle.translation.x, paddle.scale.x);
+}
@@ -0,0 +1,216 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::level::{Campaign, Level};
+use crate::mode::GameMode;
+use crate::powerup::PowerUpCollected;
+use crate::save::SaveData;
+use crate::{BallLost, GameState, LevelCleared};
+
+// Jede Sitzung schreibt eine eigene Datei, eine Zeile JSON je Ereignis.
+const TELEMETRY_DIR: &str = "telemetry";
+
+// Was für die Abstimmung der Level zählt: wie oft ein Level geschafft wird, wo die Bälle verloren gehen und welche
+// Power-Ups eingesammelt werden. Namen, Spielstände oder Geräte kommen nicht hinein.
+#[derive(Serialize)]
+#[serde(tag = "event")]
+enum Record {
+    LevelEnded { cleared: bool, seconds: f32, balls_lost: u32 },
+    BallLost { x: f32, y: f32 },
+    PowerUp { kind: String },
+}
+
+// Eine Zeile der Datei. Die Sitzung ist eine Zufallszahl, die nur Ereignisse desselben Spielstarts verbindet.
+#[derive(Serialize)]
+struct Line<'a> {
+    session: u64,
+    time: u64,
+    level: &'a str,
+    mode: GameMode,
+    #[serde(flatten)]
+    record: Record,
+}
+
+// Der laufende Versuch eines Levels. Er endet mit dem Verlassen von GameState::Playing, geschafft oder nicht.
+struct Attempt {
+    started: f32,
+    cleared: bool,
+    balls_lost: u32,
+}
+
+#[derive(Resource)]
+struct TelemetryLog {
+    session: u64,
+    path: PathBuf,
+    attempt: Option<Attempt>,
+}
+
+impl TelemetryLog {
+    // Schlägt das Schreiben fehl, fehlt die Zeile eben. Das Spiel soll daran nicht hängen.
+    fn append(&self, level: &str, mode: GameMode, record: Record) {
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+        let line = Line { session: self.session, time, level, mode, record };
+        let result = serde_json::to_string(&line).map_err(|error| error.to_string()).and_then(|json| {
+            fs::create_dir_all(TELEMETRY_DIR).map_err(|error| error.to_string())?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|error| error.to_string())?;
+            writeln!(file, "{json}").map_err(|error| error.to_string())
+        });
+        if let Err(error) = result {
+            warn!("Spieldaten konnten nicht geschrieben werden: {error}");
+        }
+    }
+}
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        let session = rand::random();
+        app.insert_resource(TelemetryLog {
+            session,
+            path: Path::new(TELEMETRY_DIR).join(format!("{session:016x}.ndjson")),
+            attempt: None,
+        })
+        .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_attempt))
+        .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(end_attempt))
+        .add_system(record_events);
+        #[cfg(feature = "online")]
+        upload::build(app);
+    }
+}
+
+// Der Name des laufenden Levels, so wie er in der Datei steht.
+fn level_name(campaign: &Campaign, levels: &Assets<Level>) -> String {
+    levels.get(campaign.current_handle()).map_or_else(String::new, |level| level.name.clone())
+}
+
+// Ohne Zustimmung wird nichts mitgeschrieben, auch kein angefangener Versuch.
+fn start_attempt(mut log: ResMut<TelemetryLog>, save: Res<SaveData>, time: Res<Time>) {
+    log.attempt = save.telemetry.then(|| Attempt {
+        started: time.elapsed_seconds(),
+        cleared: false,
+        balls_lost: 0,
+    });
+}
+
+fn end_attempt(
+    mut log: ResMut<TelemetryLog>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    mode: Res<GameMode>,
+    time: Res<Time>,
+) {
+    let Some(attempt) = log.attempt.take() else { return };
+    let record = Record::LevelEnded {
+        cleared: attempt.cleared,
+        seconds: time.elapsed_seconds() - attempt.started,
+        balls_lost: attempt.balls_lost,
+    };
+    log.append(&level_name(&campaign, &levels), *mode, record);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_events(
+    mut log: ResMut<TelemetryLog>,
+    mut balls_lost: EventReader<BallLost>,
+    mut powerups: EventReader<PowerUpCollected>,
+    cleared: EventReader<LevelCleared>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<Level>>,
+    mode: Res<GameMode>,
+) {
+    let Some(attempt) = &mut log.attempt else {
+        balls_lost.clear();
+        powerups.clear();
+        cleared.clear();
+        return;
+    };
+    attempt.balls_lost += balls_lost.len() as u32;
+    attempt.cleared |= !cleared.is_empty();
+    cleared.clear();
+
+    let level = level_name(&campaign, &levels);
+    for BallLost { position } in balls_lost.iter() {
+        log.append(&level, *mode, Record::BallLost { x: position.x, y: position.y });
+    }
+    for PowerUpCollected(kind) in powerups.iter() {
+        log.append(&level, *mode, Record::PowerUp { kind: format!("{kind:?}") });
+    }
+}
+
+// Mit dem Feature "online" gehen die Dateien früherer Sitzungen an einen Server, wenn einer angegeben ist.
+// Übertragene Dateien werden gelöscht, die der laufenden Sitzung bleibt liegen, bis sie abgeschlossen ist.
+#[cfg(feature = "online")]
+mod upload {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use bevy::prelude::*;
+    use bevy::tasks::{IoTaskPool, Task};
+    use futures_lite::future;
+
+    use super::{TelemetryLog, TELEMETRY_DIR};
+    use crate::save::SaveData;
+
+    // Ohne diese Umgebungsvariable bleiben die Daten auf dem Rechner.
+    const ENDPOINT_VARIABLE: &str = "KUERTEIL_TELEMETRY_URL";
+
+    #[derive(Resource, Default)]
+    struct Upload {
+        task: Option<Task<Result<usize, String>>>,
+        started: bool,
+    }
+
+    pub fn build(app: &mut App) {
+        app.init_resource::<Upload>().add_system(upload_sessions);
+    }
+
+    // Einmal pro Spielstart, sobald die Zustimmung vorliegt.
+    fn upload_sessions(mut upload: ResMut<Upload>, log: Res<TelemetryLog>, save: Res<SaveData>) {
+        if let Some(task) = &mut upload.task {
+            let Some(result) = future::block_on(future::poll_once(task)) else { return };
+            upload.task = None;
+            match result {
+                Ok(0) => {}
+                Ok(sent) => info!("{sent} Dateien mit Spieldaten übertragen"),
+                Err(error) => warn!("Spieldaten konnten nicht übertragen werden: {error}"),
+            }
+            return;
+        }
+        if upload.started || !save.telemetry {
+            return;
+        }
+        upload.started = true;
+        let Ok(endpoint) = env::var(ENDPOINT_VARIABLE) else { return };
+        let current = log.path.clone();
+        upload.task = Some(IoTaskPool::get().spawn(async move {
+            let Ok(entries) = fs::read_dir(TELEMETRY_DIR) else { return Ok(0) };
+            let files: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| *path != current)
+                .collect();
+            let mut sent = 0;
+            for path in files {
+                let body = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+                let request = surf::post(&endpoint).body_string(body).content_type("application/x-ndjson");
+                let response = request.await.map_err(|error| error.to_string())?;
+                if !response.status().is_success() {
+                    return Err(format!("HTTP {}", response.status()));
+                }
+                fs::remove_file(&path).map_err(|error| error.to_string())?;
+                sent += 1;
+            }
+            Ok(sent)
+        }));
+    }
+}
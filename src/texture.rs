@@ -0,0 +1,233 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::VertexAttributeValues,
+        render_resource::{AddressMode, Extent3d, SamplerDescriptor, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::theme::{Theme, ThemeMaterials};
+use crate::{Arena, GameState, LevelEntity};
+
+const GRADIENT_HEIGHT: usize = 64;
+// Am dunkelsten Punkt des Verlaufs bleibt so viel der Hintergrundfarbe übrig.
+const GRADIENT_DARKEST: f32 = 0.45;
+const STARFIELD_SIZE: usize = 128;
+const STAR_COUNT: usize = 70;
+const STARFIELD_TILES: f32 = 4.0;
+// Immer derselbe Sternenhimmel, damit er sich zwischen zwei Starts nicht ändert.
+const STARFIELD_SEED: u64 = 0x5747_4152;
+// Um so viele Texturlängen pro Sekunde zieht der Hintergrund nach unten.
+const GRADIENT_SCROLL_SPEED: f32 = 0.03;
+const STARFIELD_SCROLL_SPEED: f32 = 0.06;
+
+// Diese Textur habe ich als Beispiel aus einem anderen Beispiel gefunden, welche das Verhalten von 3D Objekten gezeigt habe, ich fande es passend und habe es übernommen.
+pub fn uv_debug_texture() -> Image {
+    const TEXTURE_SIZE: usize = 8;
+
+    let mut palette: [u8; 32] = [
+        255, 102, 159, 255, 255, 159, 102, 255, 236, 255, 102, 255, 121, 255, 102, 255, 102, 255,
+        198, 255, 102, 198, 255, 255, 121, 102, 255, 255, 236, 102, 255, 255,
+    ];
+
+    let mut texture_data = [0; TEXTURE_SIZE * TEXTURE_SIZE * 4];
+    for y in 0..TEXTURE_SIZE {
+        let offset = TEXTURE_SIZE * y * 4;
+        texture_data[offset..(offset + TEXTURE_SIZE * 4)].copy_from_slice(&palette);
+        palette.rotate_right(4);
+    }
+
+    Image::new_fill(
+        Extent3d {
+            width: TEXTURE_SIZE as u32,
+            height: TEXTURE_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &texture_data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+// Eine Textur, die sich in beide Richtungen nahtlos wiederholt, damit sie endlos verschoben werden kann.
+fn repeating_texture(width: usize, height: usize, data: Vec<u8>) -> Image {
+    let mut image = Image::new(
+        Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::Descriptor(SamplerDescriptor {
+        address_mode_u: AddressMode::Repeat,
+        address_mode_v: AddressMode::Repeat,
+        ..ImageSampler::linear_descriptor()
+    });
+    image
+}
+
+// Ein senkrechter Verlauf in Grau, eingefärbt wird er erst über das Material. Er wird zur Mitte hin dunkler
+// und oben wieder hell, damit beim Verschieben keine Kante zu sehen ist.
+pub fn gradient_texture() -> Image {
+    let data = (0..GRADIENT_HEIGHT)
+        .flat_map(|y| {
+            let t = (y as f32 / GRADIENT_HEIGHT as f32 * std::f32::consts::TAU).cos() * 0.5 + 0.5;
+            let value = ((GRADIENT_DARKEST + (1.0 - GRADIENT_DARKEST) * t) * 255.0) as u8;
+            [value, value, value, 255]
+        })
+        .collect();
+    repeating_texture(1, GRADIENT_HEIGHT, data)
+}
+
+// Schwarzer Himmel mit verstreuten Sternen unterschiedlicher Helligkeit.
+pub fn starfield_texture() -> Image {
+    let mut data = vec![0; STARFIELD_SIZE * STARFIELD_SIZE * 4];
+    for pixel in data.chunks_exact_mut(4) {
+        pixel[3] = 255;
+    }
+    let mut rng = StdRng::seed_from_u64(STARFIELD_SEED);
+    for _ in 0..STAR_COUNT {
+        let index = rng.gen_range(0..STARFIELD_SIZE * STARFIELD_SIZE) * 4;
+        let brightness = rng.gen_range(120..=255);
+        data[index..index + 3].fill(brightness);
+    }
+    repeating_texture(STARFIELD_SIZE, STARFIELD_SIZE, data)
+}
+
+// Was hinter der Arena zu sehen ist. Ein Theme ohne Angabe zeigt nur die Hintergrundfarbe.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backdrop {
+    #[default]
+    Plain,
+    Gradient,
+    Starfield,
+}
+
+impl Backdrop {
+    fn scroll_speed(self) -> f32 {
+        match self {
+            Backdrop::Plain => 0.0,
+            Backdrop::Gradient => GRADIENT_SCROLL_SPEED,
+            Backdrop::Starfield => STARFIELD_SCROLL_SPEED,
+        }
+    }
+
+    // Wie oft die Textur auf der Fläche nebeneinander liegt. Die Sterne wären sonst viel zu groß.
+    fn tiles(self) -> f32 {
+        match self {
+            Backdrop::Starfield => STARFIELD_TILES,
+            Backdrop::Plain | Backdrop::Gradient => 1.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct BackdropTextures {
+    gradient: Handle<Image>,
+    starfield: Handle<Image>,
+}
+
+// Die Fläche hinter der Arena. Wie weit ihre Textur schon verschoben ist, steht hier.
+#[derive(Component, Default)]
+struct BackdropPlane {
+    scroll: f32,
+}
+
+pub struct BackdropPlugin;
+
+impl Plugin for BackdropPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(create_backdrop_textures)
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_backdrop.after(crate::spawn_level)))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(animate_backdrop));
+    }
+}
+
+fn create_backdrop_textures(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(BackdropTextures {
+        gradient: images.add(gradient_texture()),
+        starfield: images.add(starfield_texture()),
+    });
+}
+
+// Die Fläche steht senkrecht hinter der Arena und ist so groß, dass sie das ganze Bild der Kamera ausfüllt.
+// Bis animate_backdrop das Theme angewendet hat, bleibt sie unsichtbar.
+fn spawn_backdrop(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let size = 6.0 * arena.extent();
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(shape::Quad::new(Vec2::splat(size)).into()),
+            material: materials.add(StandardMaterial {
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(
+                (arena.left() + arena.right()) / 2.0,
+                (arena.bottom() + arena.top()) / 2.0,
+                -arena.extent(),
+            ),
+            visibility: Visibility::INVISIBLE,
+            ..default()
+        },
+        BackdropPlane::default(),
+        LevelEntity,
+    ));
+}
+
+// Das Theme kann sich jederzeit ändern, deshalb wird der Hintergrund jeden Frame daran angeglichen.
+// Verschoben wird über die UV-Koordinaten der Fläche, mit weniger Bewegung steht er still.
+#[allow(clippy::too_many_arguments)]
+fn animate_backdrop(
+    time: Res<Time>,
+    settings: Res<AccessibilitySettings>,
+    theme_materials: Res<ThemeMaterials>,
+    themes: Res<Assets<Theme>>,
+    textures: Res<BackdropTextures>,
+    mut query: Query<(&mut BackdropPlane, &mut Visibility, &Handle<StandardMaterial>, &Handle<Mesh>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Some(theme) = theme_materials.theme(&themes) else { return };
+    let backdrop = theme.backdrop();
+    let (texture, color) = match backdrop {
+        Backdrop::Plain => (None, Color::NONE),
+        Backdrop::Gradient => (Some(&textures.gradient), theme.background()),
+        Backdrop::Starfield => (Some(&textures.starfield), Color::WHITE),
+    };
+
+    for (mut plane, mut visibility, material, mesh) in &mut query {
+        visibility.is_visible = texture.is_some();
+        let outdated = materials
+            .get(material)
+            .is_some_and(|material| material.base_color_texture.as_ref() != texture || material.base_color != color);
+        if outdated {
+            if let Some(material) = materials.get_mut(material) {
+                material.base_color_texture = texture.cloned();
+                material.base_color = color;
+            }
+        }
+        if !settings.allows_motion() || backdrop.scroll_speed() == 0.0 {
+            continue;
+        }
+        plane.scroll = (plane.scroll + backdrop.scroll_speed() * time.delta_seconds()).fract();
+        let Some(mesh) = meshes.get_mut(mesh) else { continue };
+        // Die Ecken in der Reihenfolge von shape::Quad, oben hat v den Wert 0. Wird v kleiner, wandert das Bild nach unten.
+        let (tiles, v) = (backdrop.tiles(), -plane.scroll);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            VertexAttributeValues::Float32x2(vec![[0.0, tiles + v], [0.0, v], [tiles, v], [tiles, tiles + v]]),
+        );
+    }
+}
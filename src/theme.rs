@@ -0,0 +1,232 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::accessibility::Palette;
+use crate::level::BrickKind;
+use crate::lighting::Daylight;
+use crate::modding;
+use crate::texture::Backdrop;
+use crate::PENALTY_BRICK_COLOR;
+
+const THEME_PATH: &str = "themes/default.theme.ron";
+const GHOST_BRICK_COLOR: Color = Color::rgb(0.75, 0.8, 0.95);
+const REGENERATING_BRICK_COLOR: Color = Color::rgb(0.2, 0.65, 0.3);
+pub const CHAIN_BRICK_COLOR: Color = Color::rgb(0.8, 0.65, 0.25);
+const TURRET_BRICK_COLOR: Color = Color::rgb(0.25, 0.25, 0.3);
+const GLASS_BRICK_COLOR: Color = Color::rgba(0.7, 0.9, 1.0, 0.5);
+const METAL_BRICK_COLOR: Color = Color::rgb(0.6, 0.62, 0.68);
+const STONE_BRICK_COLOR: Color = Color::rgb(0.45, 0.42, 0.38);
+
+// Die Farben der Arena, so wie sie in assets/themes/*.theme.ron abgelegt sind. Jede Farbe ist ein RGB-Tripel.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "8f1d6c2e-4b7a-4e0f-9c3d-5a2b7e9f1c64"]
+pub struct Theme {
+    background: (f32, f32, f32),
+    walls: (f32, f32, f32),
+    // Die Bricks behalten ihre Textur, die Farbe färbt sie nur ein.
+    bricks: (f32, f32, f32),
+    penalty_bricks: (f32, f32, f32),
+    // Die Farbe des glühenden Randes, wenn ein Brick der jeweiligen Stufe wegbrennt.
+    bricks_edge: (f32, f32, f32),
+    penalty_bricks_edge: (f32, f32, f32),
+    #[serde(default)]
+    backdrop: Backdrop,
+    #[serde(default)]
+    daylight: Daylight,
+}
+
+impl Theme {
+    pub fn background(&self) -> Color {
+        rgb(self.background)
+    }
+
+    pub fn backdrop(&self) -> Backdrop {
+        self.backdrop
+    }
+
+    pub fn daylight(&self) -> Daylight {
+        self.daylight
+    }
+
+    pub fn dissolve_edge(&self, penalty: bool) -> Color {
+        rgb(if penalty { self.penalty_bricks_edge } else { self.bricks_edge })
+    }
+}
+
+fn rgb((r, g, b): (f32, f32, f32)) -> Color {
+    Color::rgb(r, g, b)
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let theme = ron::de::from_bytes::<Theme>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(theme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+// Alle Levels teilen sich diese Materialien. Ändert sich das Theme, werden nur die Materialien angepasst
+// und jede Wand und jeder Brick sieht sofort anders aus.
+#[derive(Resource)]
+pub struct ThemeMaterials {
+    theme: Handle<Theme>,
+    pub walls: Handle<StandardMaterial>,
+    pub bricks: Handle<StandardMaterial>,
+    pub penalty_bricks: Handle<StandardMaterial>,
+    // Die besonderen Bricks wie Geister, Kettenglieder oder Geschütze sehen überall gleich aus, das Theme färbt sie nicht.
+    ghost_bricks: Handle<StandardMaterial>,
+    regenerating_bricks: Handle<StandardMaterial>,
+    chain_bricks: Handle<StandardMaterial>,
+    turret_bricks: Handle<StandardMaterial>,
+    glass_bricks: Handle<StandardMaterial>,
+    metal_bricks: Handle<StandardMaterial>,
+    stone_bricks: Handle<StandardMaterial>,
+    // Bricks aus Mods behalten die Farbe, die ihr Skript vorgibt.
+    mod_bricks: Vec<Handle<StandardMaterial>>,
+}
+
+impl ThemeMaterials {
+    pub fn brick(&self, kind: BrickKind) -> Handle<StandardMaterial> {
+        match kind {
+            BrickKind::Normal => self.bricks.clone(),
+            BrickKind::Penalty => self.penalty_bricks.clone(),
+            BrickKind::Ghost => self.ghost_bricks.clone(),
+            BrickKind::Regenerating => self.regenerating_bricks.clone(),
+            BrickKind::Chain => self.chain_bricks.clone(),
+            BrickKind::Turret => self.turret_bricks.clone(),
+            BrickKind::Glass => self.glass_bricks.clone(),
+            BrickKind::Metal => self.metal_bricks.clone(),
+            BrickKind::Stone => self.stone_bricks.clone(),
+            BrickKind::Mod(index) => self.mod_bricks[index as usize].clone(),
+        }
+    }
+
+    // Solange die Theme-Datei noch lädt, gibt es hier nichts.
+    pub fn theme<'a>(&self, themes: &'a Assets<Theme>) -> Option<&'a Theme> {
+        themes.get(&self.theme)
+    }
+}
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Theme>()
+            .init_asset_loader::<ThemeLoader>()
+            .add_startup_system(load_theme)
+            .add_system(apply_theme);
+    }
+}
+
+fn load_theme(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Bis die Datei geladen ist, gelten die bisherigen Farben.
+    commands.insert_resource(ThemeMaterials {
+        theme: asset_server.load(THEME_PATH),
+        walls: materials.add(Color::PURPLE.into()),
+        bricks: materials.add(StandardMaterial {
+            base_color_texture: Some(images.add(crate::texture::uv_debug_texture())),
+            ..default()
+        }),
+        penalty_bricks: materials.add(StandardMaterial {
+            base_color: PENALTY_BRICK_COLOR,
+            emissive: Color::rgb(0.3, 0.0, 0.0),
+            ..default()
+        }),
+        ghost_bricks: materials.add(StandardMaterial {
+            base_color: GHOST_BRICK_COLOR,
+            emissive: Color::rgb(0.1, 0.15, 0.3),
+            ..default()
+        }),
+        regenerating_bricks: materials.add(StandardMaterial {
+            base_color: REGENERATING_BRICK_COLOR,
+            emissive: Color::rgb(0.0, 0.2, 0.05),
+            ..default()
+        }),
+        chain_bricks: materials.add(StandardMaterial {
+            base_color: CHAIN_BRICK_COLOR,
+            metallic: 0.8,
+            perceptual_roughness: 0.3,
+            ..default()
+        }),
+        turret_bricks: materials.add(StandardMaterial {
+            base_color: TURRET_BRICK_COLOR,
+            metallic: 0.5,
+            ..default()
+        }),
+        glass_bricks: materials.add(StandardMaterial {
+            base_color: GLASS_BRICK_COLOR,
+            alpha_mode: AlphaMode::Blend,
+            perceptual_roughness: 0.05,
+            reflectance: 0.9,
+            ..default()
+        }),
+        metal_bricks: materials.add(StandardMaterial {
+            base_color: METAL_BRICK_COLOR,
+            metallic: 1.0,
+            perceptual_roughness: 0.25,
+            ..default()
+        }),
+        stone_bricks: materials.add(StandardMaterial {
+            base_color: STONE_BRICK_COLOR,
+            perceptual_roughness: 1.0,
+            ..default()
+        }),
+        mod_bricks: modding::registry()
+            .bricks
+            .iter()
+            .map(|brick| materials.add(brick.color.into()))
+            .collect(),
+    });
+}
+
+// Reagiert auf das erste Laden und auf jede Änderung der Theme-Datei, während das Spiel läuft.
+// Auch ein Wechsel der Farbpalette wird hier übernommen, da sie Farben des Themes überschreibt.
+fn apply_theme(
+    mut events: EventReader<AssetEvent<Theme>>,
+    themes: Res<Assets<Theme>>,
+    theme_materials: Res<ThemeMaterials>,
+    palette: Res<Palette>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    let theme_changed = events.iter().any(|event| {
+        matches!(event, AssetEvent::Created { handle } | AssetEvent::Modified { handle } if *handle == theme_materials.theme)
+    });
+    if !theme_changed && !palette.is_changed() {
+        return;
+    }
+    let Some(theme) = themes.get(&theme_materials.theme) else { return };
+    clear_color.0 = rgb(theme.background);
+    if let Some(material) = materials.get_mut(&theme_materials.walls) {
+        material.base_color = rgb(theme.walls);
+    }
+    if let Some(material) = materials.get_mut(&theme_materials.bricks) {
+        material.base_color = rgb(theme.bricks);
+    }
+    if let Some(material) = materials.get_mut(&theme_materials.penalty_bricks) {
+        material.base_color = palette.penalty_bricks().unwrap_or(rgb(theme.penalty_bricks));
+    }
+}
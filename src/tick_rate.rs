@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use serde::{Deserialize, Serialize};
+
+use crate::save::SaveData;
+use crate::{phase_set, Kinematic, Paddle, Phase, TimeScale};
+
+// Springt ein Objekt zwischen zwei Schritten weiter als das, etwa durch ein Portal oder beim Zurücksetzen des Balls,
+// wird nicht dazwischen gezeichnet.
+const SNAP_DISTANCE: f32 = 1.0;
+
+// Wie oft pro Sekunde die Spielsysteme laufen. Mehr Schritte machen schnelle Bälle genauer, kosten aber Rechenzeit.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TickRate {
+    #[default]
+    Hz60,
+    Hz120,
+    Hz240,
+}
+
+impl TickRate {
+    const ALL: [TickRate; 3] = [TickRate::Hz60, TickRate::Hz120, TickRate::Hz240];
+
+    pub fn hertz(self) -> u32 {
+        match self {
+            TickRate::Hz60 => 60,
+            TickRate::Hz120 => 120,
+            TickRate::Hz240 => 240,
+        }
+    }
+
+    // Die Dauer eines Schritts in Sekunden.
+    pub fn seconds(self) -> f32 {
+        1.0 / self.hertz() as f32
+    }
+
+    pub fn name(self) -> String {
+        format!("{} Hz", self.hertz())
+    }
+
+    pub fn next(self) -> TickRate {
+        let current = TickRate::ALL.iter().position(|rate| *rate == self).unwrap_or(0);
+        TickRate::ALL[(current + 1) % TickRate::ALL.len()]
+    }
+}
+
+// Wie weit die Zeit seit dem letzten Schritt schon auf den nächsten zugelaufen ist, von 0 bis 1.
+// Setzt fixed_gameplay_tick, sobald im Frame kein Schritt mehr ansteht.
+#[derive(Resource, Default)]
+pub struct TickProgress(pub f32);
+
+// Die Stellen eines Objekts nach den letzten beiden Schritten. Gezeichnet wird dazwischen, damit die Bewegung auch
+// dann gleichmäßig aussieht, wenn Takt und Bildrate nicht zusammenpassen. `rendered` ist die gezeichnete Stelle,
+// daran erkennt der nächste Frame, ob jemand außerhalb des Takts das Objekt versetzt hat.
+#[derive(Component)]
+struct Interpolated {
+    previous: Vec3,
+    current: Vec3,
+    rendered: Vec3,
+}
+
+impl Interpolated {
+    fn at(position: Vec3) -> Self {
+        Interpolated { previous: position, current: position, rendered: position }
+    }
+}
+
+pub struct TickRatePlugin;
+
+impl Plugin for TickRatePlugin {
+    fn build(&self, app: &mut App) {
+        // Die Einstellung kommt aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let rate = app.world.resource::<SaveData>().tick_rate;
+        app.insert_resource(rate)
+            .init_resource::<TickProgress>()
+            .add_system(store_tick_rate)
+            .add_system(apply_tick_rate)
+            .add_system(attach_interpolation)
+            .add_system_to_stage(CoreStage::PreUpdate, restore_positions)
+            .add_system_set(phase_set(Phase::Vfx).with_system(record_positions))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                interpolate_positions.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+fn store_tick_rate(rate: Res<TickRate>, mut save: ResMut<SaveData>) {
+    if rate.is_changed() && save.tick_rate != *rate {
+        save.tick_rate = *rate;
+    }
+}
+
+// Alle Spielsysteme lesen die Dauer eines Schritts über TimeScale::step, sie wechselt also für alle gemeinsam.
+fn apply_tick_rate(rate: Res<TickRate>, mut time_scale: ResMut<TimeScale>) {
+    if rate.is_changed() {
+        time_scale.tick = rate.seconds();
+    }
+}
+
+// Alles, was sich im Takt bewegt, dazu das Paddle.
+#[allow(clippy::type_complexity)]
+fn attach_interpolation(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), (Or<(Added<Kinematic>, Added<Paddle>)>, Without<Interpolated>)>,
+) {
+    for (entity, transform) in &query {
+        commands.entity(entity).insert(Interpolated::at(transform.translation));
+    }
+}
+
+// Vor den Schritten eines Frames steht wieder die Stelle aus dem Takt im Transform. Wurde das Objekt seit dem
+// Zeichnen versetzt, gilt die neue Stelle ohne Übergang.
+fn restore_positions(mut query: Query<(&mut Transform, &mut Interpolated)>) {
+    for (mut transform, mut interpolated) in &mut query {
+        if transform.translation == interpolated.rendered {
+            transform.translation = interpolated.current;
+        } else {
+            *interpolated = Interpolated::at(transform.translation);
+        }
+    }
+}
+
+fn record_positions(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.previous = interpolated.current;
+        interpolated.current = transform.translation;
+        if interpolated.previous.distance(interpolated.current) > SNAP_DISTANCE {
+            interpolated.previous = interpolated.current;
+        }
+    }
+}
+
+// Gezeichnet wird zwischen den letzten beiden Schritten, dadurch liegt das Bild höchstens einen Schritt zurück.
+// Hat ein System nach dem letzten Schritt das Objekt versetzt, bleibt es dort.
+fn interpolate_positions(progress: Res<TickProgress>, mut query: Query<(&mut Transform, &mut Interpolated)>) {
+    for (mut transform, mut interpolated) in &mut query {
+        if transform.translation != interpolated.current {
+            *interpolated = Interpolated::at(transform.translation);
+            continue;
+        }
+        transform.translation = interpolated.previous.lerp(interpolated.current, progress.0);
+        interpolated.rendered = transform.translation;
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::{phase_set, Paddle, Phase, TimeScale};
+
+// Weiter als so lässt sich das Paddle nicht kippen, sonst würde der Ball flach zur Seite wegspringen.
+const MAX_TILT: f32 = 12.0 * std::f32::consts::PI / 180.0;
+// Radiant pro Sekunde, beim Kippen wie beim Zurückschwingen.
+const TILT_SPEED: f32 = 1.5;
+// So steil muss der Ball nach dem Abprall mindestens nach oben fliegen.
+const MIN_BOUNCE_Y: f32 = 0.3;
+
+// Der Winkel, um den das Paddle gerade um die Z-Achse gekippt ist. Positiv hebt die rechte Seite an.
+#[derive(Component, Default)]
+pub struct PaddleTilt(pub f32);
+
+impl PaddleTilt {
+    // Die Normale der gekippten Oberseite.
+    fn normal(&self) -> Vec3 {
+        Vec3::new(-self.0.sin(), self.0.cos(), 0.0)
+    }
+
+    // Spiegelt die Geschwindigkeit an der gekippten Oberseite statt an der Waagerechten.
+    // Würde der Ball danach zu flach fliegen, wird wie bei einem geraden Paddle gespiegelt.
+    pub fn reflect(&self, velocity: Vec3) -> Vec3 {
+        let normal = self.normal();
+        let reflected = velocity - 2.0 * velocity.dot(normal) * normal;
+        if reflected.y < MIN_BOUNCE_Y * reflected.length() {
+            return Vec3::new(velocity.x, -velocity.y, velocity.z);
+        }
+        reflected
+    }
+}
+
+pub struct TiltPlugin;
+
+impl Plugin for TiltPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(phase_set(Phase::Input).with_system(tilt_paddle));
+    }
+}
+
+// Q kippt das Paddle nach links, E nach rechts. Ohne Taste schwingt es in die Waagerechte zurück.
+fn tilt_paddle(
+    mut query: Query<(&mut Transform, &mut PaddleTilt), With<Paddle>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    time_scale: Res<TimeScale>,
+) {
+    let mut target = 0.0;
+    if keyboard_input.pressed(KeyCode::Q) {
+        target -= MAX_TILT;
+    }
+    if keyboard_input.pressed(KeyCode::E) {
+        target += MAX_TILT;
+    }
+    let max_change = TILT_SPEED * time_scale.step();
+    for (mut transform, mut tilt) in &mut query {
+        let angle = tilt.0 + (target - tilt.0).clamp(-max_change, max_change);
+        // Die Drehung um die eigene Y-Achse aus rotate bleibt dabei erhalten.
+        transform.rotation = Quat::from_rotation_z(angle - tilt.0) * transform.rotation;
+        tilt.0 = angle;
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::format;
+use crate::mode::GameMode;
+use crate::transition::ScreenTransition;
+use crate::{phase_set, BallLost, GameState, LevelEntity, Phase, TimeScale};
+
+const TIME_ATTACK_DURATION: f32 = 120.0;
+// Ein verlorener Ball kostet im Zeitangriff ein paar Sekunden.
+const BALL_LOST_PENALTY: f32 = 10.0;
+const CLOCK_FONT_SIZE: f32 = 48.0;
+const CLOCK_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+// In den letzten Sekunden wird die Uhr rot.
+const CLOCK_WARNING_TIME: f32 = 10.0;
+const CLOCK_WARNING_COLOR: Color = Color::rgb(0.8, 0.0, 0.0);
+
+// Die verbleibende Zeit des Zeitangriffs. Sie läuft über alle Level des Durchlaufs weiter.
+#[derive(Resource)]
+pub struct Countdown(pub Timer);
+
+impl Default for Countdown {
+    fn default() -> Self {
+        Countdown(Timer::from_seconds(TIME_ATTACK_DURATION, TimerMode::Once))
+    }
+}
+
+#[derive(Component)]
+struct CountdownText;
+
+pub struct TimeAttackPlugin;
+
+impl Plugin for TimeAttackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Countdown>()
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(reset_countdown))
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(spawn_countdown_text))
+            .add_system_set(phase_set(Phase::Scoring).with_system(tick_countdown))
+            .add_system_set(SystemSet::on_update(GameState::Playing).with_system(update_countdown_text));
+    }
+}
+
+fn reset_countdown(mut countdown: ResMut<Countdown>) {
+    *countdown = Countdown::default();
+}
+
+fn spawn_countdown_text(mut commands: Commands, asset_server: Res<AssetServer>, mode: Res<GameMode>) {
+    if *mode != GameMode::TimeAttack {
+        return;
+    }
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: CLOCK_FONT_SIZE,
+                color: CLOCK_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(85.0),
+                right: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        CountdownText,
+        LevelEntity,
+    ));
+}
+
+// Die Uhr läuft im festen Takt mit dem globalen Zeitfaktor, so bekommt jeder Rechner gleich viel Zeit.
+// Ist sie abgelaufen, endet der Durchlauf.
+fn tick_countdown(
+    mode: Res<GameMode>,
+    mut countdown: ResMut<Countdown>,
+    mut ball_lost_events: EventReader<BallLost>,
+    time_scale: Res<TimeScale>,
+    mut transition: ResMut<ScreenTransition>,
+) {
+    if *mode != GameMode::TimeAttack {
+        ball_lost_events.clear();
+        return;
+    }
+    let penalty = ball_lost_events.iter().count() as f32 * BALL_LOST_PENALTY;
+    let delta = time_scale.step() + penalty;
+    if countdown.0.tick(Duration::from_secs_f32(delta)).just_finished() {
+        transition.to(GameState::Menu);
+    }
+}
+
+fn update_countdown_text(countdown: Res<Countdown>, mut query: Query<&mut Text, With<CountdownText>>) {
+    let Ok(mut text) = query.get_single_mut() else { return };
+    let remaining = countdown.0.remaining_secs();
+    let section = &mut text.sections[0];
+    section.value = format::timer(remaining);
+    section.style.color = if remaining <= CLOCK_WARNING_TIME { CLOCK_WARNING_COLOR } else { CLOCK_COLOR };
+}
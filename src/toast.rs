@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::easing::Ease;
+use crate::tween::{Tween, UiPositionLens};
+
+const TOAST_DURATION: f32 = 3.0;
+// Die ersten Sekundenbruchteile gleitet eine Meldung von rechts herein, am Ende blendet sie aus.
+const SLIDE_DURATION: f32 = 0.25;
+const SLIDE_DISTANCE: f32 = 300.0;
+const FADE_DURATION: f32 = 0.6;
+// Mehr Meldungen stehen nie gleichzeitig untereinander, der Rest wartet in der Schlange.
+const MAX_VISIBLE_TOASTS: usize = 3;
+const TOAST_FONT_SIZE: f32 = 28.0;
+const TOAST_TEXT_COLOR: Color = Color::rgb(0.95, 0.95, 0.95);
+const TOAST_BACKGROUND: Color = Color::rgba(0.2, 0.05, 0.3, 0.85);
+
+// Zeigt eine kurze Meldung am rechten Rand. Jede Funktion, die etwas mitzuteilen hat, sendet nur dieses Event.
+pub struct ShowToast(pub String);
+
+#[derive(Resource, Default)]
+struct ToastQueue(VecDeque<String>);
+
+// Die Spalte, in der die Meldungen untereinander stehen. Sie bleibt über alle Bildschirme hinweg bestehen,
+// damit auch ein Erfolg beim Verlassen eines Levels noch im Menü zu sehen ist.
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component)]
+struct Toast(Timer);
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShowToast>()
+            .init_resource::<ToastQueue>()
+            .add_startup_system(spawn_toast_stack)
+            .add_system(queue_toasts)
+            .add_system(show_toasts.after(queue_toasts))
+            .add_system(animate_toasts);
+    }
+}
+
+fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                // Unter der Lebensanzeige und der Energieleiste.
+                position: UiRect {
+                    top: Val::Px(120.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            ..default()
+        },
+        ToastStack,
+    ));
+}
+
+fn queue_toasts(mut events: EventReader<ShowToast>, mut queue: ResMut<ToastQueue>) {
+    queue.0.extend(events.iter().map(|ShowToast(text)| text.clone()));
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    stack_query: Query<Entity, With<ToastStack>>,
+    toast_query: Query<(), With<Toast>>,
+    asset_server: Res<AssetServer>,
+    settings: Res<AccessibilitySettings>,
+) {
+    let Ok(stack) = stack_query.get_single() else { return };
+    let mut visible = toast_query.iter().count();
+    while visible < MAX_VISIBLE_TOASTS {
+        let Some(text) = queue.0.pop_front() else { break };
+        // Mit weniger Bewegung erscheinen Meldungen ohne hereinzugleiten.
+        let slide = if settings.allows_motion() { SLIDE_DISTANCE } else { 0.0 };
+        let toast = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position: UiRect::left(Val::Px(slide)),
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: TOAST_BACKGROUND.into(),
+                    ..default()
+                },
+                Toast(Timer::from_seconds(TOAST_DURATION, TimerMode::Once)),
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                        font_size: TOAST_FONT_SIZE,
+                        color: TOAST_TEXT_COLOR,
+                    },
+                ));
+            })
+            .id();
+        if slide > 0.0 {
+            let lens = UiPositionLens {
+                start: UiRect::left(Val::Px(slide)),
+                end: UiRect::left(Val::Px(0.0)),
+            };
+            commands.entity(toast).insert(Tween::new(lens, SLIDE_DURATION).with_ease(Ease::OutCubic));
+        }
+        commands.entity(stack).add_child(toast);
+        visible += 1;
+    }
+}
+
+// Meldungen laufen in echter Zeit, damit sie auch in Zeitlupe oder im Menü gleich lange stehen bleiben.
+// Das Hereingleiten übernimmt ihr Tween, hier wird nur ausgeblendet.
+fn animate_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut Toast, &mut BackgroundColor, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, mut toast, mut background, children) in &mut toast_query {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let alpha = (toast.0.remaining_secs() / FADE_DURATION).min(1.0);
+        background.0.set_a(TOAST_BACKGROUND.a() * alpha);
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].style.color.set_a(alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+
+use crate::focus::Activated;
+use crate::format;
+use crate::level::Campaign;
+use crate::menu::{button_text, spawn_button, spawn_screen, MenuButton};
+use crate::mode::GameMode;
+use crate::save::SaveData;
+use crate::toast::ShowToast;
+use crate::transition::ScreenTransition;
+use crate::{GameState, Mutators, Scoreboard};
+
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 4;
+// Wie viele Runden ein Turnier höchstens hat. Wer mehr als die Hälfte gewonnen hat, steht schon vorher als Sieger
+// fest.
+const SERIES_LENGTHS: [usize; 3] = [1, 3, 5];
+
+// Ein Turnier für mehrere Spieler an einem Gerät. In jeder Runde spielt jeder Spieler der Reihe nach einen Zug:
+// dasselbe Level mit demselben Seed, bis es geschafft ist oder die Leben aufgebraucht sind. Die höchste Punktzahl
+// gewinnt die Runde, bei Gleichstand alle mit der höchsten.
+#[derive(Resource)]
+pub struct Tournament {
+    players: usize,
+    rounds: usize,
+    seed: u64,
+    // Die Punkte je Runde und Spieler. Die letzte Runde kann noch unvollständig sein.
+    scores: Vec<Vec<usize>>,
+    // Ob gerade ein Zug läuft. Seine Punkte werden beim Betreten des Zwischenstands eingetragen.
+    in_turn: bool,
+}
+
+impl Default for Tournament {
+    fn default() -> Self {
+        Tournament {
+            players: MIN_PLAYERS,
+            rounds: SERIES_LENGTHS[1],
+            seed: rand::random(),
+            scores: Vec::new(),
+            in_turn: false,
+        }
+    }
+}
+
+impl Tournament {
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn started(&self) -> bool {
+        !self.scores.is_empty()
+    }
+
+    // Die Runde und der Spieler des nächsten Zugs, beide ab 0 gezählt.
+    fn next_turn(&self) -> (usize, usize) {
+        match self.scores.last() {
+            Some(round) if round.len() < self.players => (self.scores.len() - 1, round.len()),
+            _ => (self.scores.len(), 0),
+        }
+    }
+
+    fn record(&mut self, score: usize) {
+        let (round, _) = self.next_turn();
+        if round == self.scores.len() {
+            self.scores.push(Vec::new());
+        }
+        self.scores[round].push(score);
+    }
+
+    // Gewonnene Runden je Spieler. Nur vollständige Runden zählen.
+    fn round_wins(&self) -> Vec<usize> {
+        let mut wins = vec![0; self.players];
+        for round in self.scores.iter().filter(|round| round.len() == self.players) {
+            let best = round.iter().copied().max().unwrap_or(0);
+            for (player, score) in round.iter().enumerate() {
+                if *score == best {
+                    wins[player] += 1;
+                }
+            }
+        }
+        wins
+    }
+
+    fn totals(&self) -> Vec<usize> {
+        (0..self.players).map(|player| self.scores.iter().filter_map(|round| round.get(player)).sum()).collect()
+    }
+
+    // Vorbei, wenn alle Runden gespielt sind oder jemand mehr als die Hälfte davon gewonnen hat.
+    fn finished(&self) -> bool {
+        let (round, player) = self.next_turn();
+        player == 0 && (round >= self.rounds || self.round_wins().iter().any(|wins| *wins > self.rounds / 2))
+    }
+
+    // Wer vorn liegt: die meisten gewonnenen Runden, bei Gleichstand die meisten Punkte. Teilen sich mehrere
+    // Spieler den ersten Platz, gibt es keinen.
+    fn leader(&self) -> Option<usize> {
+        let (wins, totals) = (self.round_wins(), self.totals());
+        let best = (0..self.players).map(|player| (wins[player], totals[player])).max()?;
+        let mut leaders = (0..self.players).filter(|player| (wins[*player], totals[*player]) == best);
+        let leader = leaders.next();
+        leaders.next().is_none().then_some(leader).flatten()
+    }
+
+    fn players_label(&self) -> String {
+        format!("Spieler: {}", self.players)
+    }
+
+    fn rounds_label(&self) -> String {
+        format!("Runden: höchstens {}", self.rounds)
+    }
+
+    fn next_label(&self) -> String {
+        let (round, player) = self.next_turn();
+        format!("Weiter: Spieler {}, Runde {}", player + 1, round + 1)
+    }
+
+    // Eine Zeile je Spieler mit gewonnenen Runden, Punkten insgesamt und Punkten der letzten Runde.
+    fn standings(&self) -> String {
+        let (wins, totals) = (self.round_wins(), self.totals());
+        let mut lines = vec![format!("{:<11}{:>7}{:>10}{:>10}", "", "Siege", "Gesamt", "Zuletzt")];
+        for player in 0..self.players {
+            let last = self.scores.iter().rev().find_map(|round| round.get(player));
+            lines.push(format!(
+                "{:<11}{:>7}{:>10}{:>10}",
+                format!("Spieler {}", player + 1),
+                wins[player],
+                format::number(totals[player]),
+                last.map_or("-".to_string(), |score| format::number(*score)),
+            ));
+        }
+        if self.finished() {
+            lines.push(match self.leader() {
+                Some(winner) => format!("Sieger: Spieler {}", winner + 1),
+                None => "Unentschieden".to_string(),
+            });
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Component)]
+struct StandingsText;
+
+pub struct TournamentPlugin;
+
+impl Plugin for TournamentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tournament>()
+            .add_system_set(
+                SystemSet::on_enter(GameState::Standings)
+                    .with_system(record_turn.before(spawn_standings))
+                    .with_system(spawn_standings),
+            )
+            .add_system_set(SystemSet::on_update(GameState::Standings).with_system(update_standings_labels))
+            .add_system(tournament_action);
+    }
+}
+
+// Der Zug ist vorbei, ob geschafft, verloren oder mit Escape verlassen. Seine Punkte gehören dem Spieler, der dran
+// war, der nächste beginnt wieder bei null.
+fn record_turn(mut tournament: ResMut<Tournament>, mut scoreboard: ResMut<Scoreboard>) {
+    if !tournament.in_turn {
+        return;
+    }
+    tournament.in_turn = false;
+    tournament.record(scoreboard.score);
+    scoreboard.score = 0;
+}
+
+// Vor dem ersten Zug werden hier Spieler und Runden gewählt, danach zeigt der Bildschirm den Zwischenstand.
+fn spawn_standings(mut commands: Commands, asset_server: Res<AssetServer>, tournament: Res<Tournament>) {
+    let title = match (tournament.started(), tournament.finished()) {
+        (false, _) => "Turnier",
+        (true, false) => "Zwischenstand",
+        (true, true) => "Endstand",
+    };
+    spawn_screen(&mut commands, &asset_server, title, |parent| {
+        parent.spawn((button_text(&tournament.standings(), &asset_server), StandingsText));
+        if !tournament.started() {
+            spawn_button(parent, &asset_server, MenuButton::CycleTournamentPlayers, &tournament.players_label());
+            spawn_button(parent, &asset_server, MenuButton::CycleTournamentRounds, &tournament.rounds_label());
+        }
+        if tournament.finished() {
+            spawn_button(parent, &asset_server, MenuButton::Back, "Zurück zum Menü");
+        } else {
+            spawn_button(parent, &asset_server, MenuButton::NextTurn, &tournament.next_label());
+            spawn_button(parent, &asset_server, MenuButton::Back, "Turnier abbrechen");
+        }
+    });
+}
+
+fn update_standings_labels(
+    tournament: Res<Tournament>,
+    mut standings_query: Query<&mut Text, With<StandingsText>>,
+    button_query: Query<(&MenuButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<StandingsText>>,
+) {
+    if !tournament.is_changed() {
+        return;
+    }
+    if let Ok(mut text) = standings_query.get_single_mut() {
+        text.sections[0].value = tournament.standings();
+    }
+    for (button, children) in &button_query {
+        let label = match button {
+            MenuButton::CycleTournamentPlayers => tournament.players_label(),
+            MenuButton::CycleTournamentRounds => tournament.rounds_label(),
+            MenuButton::NextTurn => tournament.next_label(),
+            _ => continue,
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = label;
+        }
+    }
+}
+
+// "Turnier" in der Modusauswahl beginnt ein neues Turnier mit neuem Seed. Spieler und Runden bleiben vom letzten.
+#[allow(clippy::too_many_arguments)]
+fn tournament_action(
+    interaction_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    button_query: Query<&MenuButton>,
+    mut activated: EventReader<Activated>,
+    mut tournament: ResMut<Tournament>,
+    (mut mode, mut mutators, mut campaign): (ResMut<GameMode>, ResMut<Mutators>, ResMut<Campaign>),
+    mut save: ResMut<SaveData>,
+    mut transition: ResMut<ScreenTransition>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Clicked)
+        .map(|(_, button)| *button);
+    let confirmed = activated.iter().filter_map(|Activated(entity)| button_query.get(*entity).ok().copied());
+    let pressed: Vec<MenuButton> = clicked.chain(confirmed).collect();
+    for button in pressed {
+        match button {
+            MenuButton::Tournament => {
+                // Ein neuer Durchlauf verwirft den unterbrochenen.
+                save.suspended_run = None;
+                *tournament = Tournament { players: tournament.players, rounds: tournament.rounds, ..default() };
+                *mutators = Mutators::default();
+                *mode = GameMode::Tournament;
+                transition.to(GameState::Standings);
+            }
+            MenuButton::CycleTournamentPlayers => {
+                tournament.players = if tournament.players >= MAX_PLAYERS {
+                    MIN_PLAYERS
+                } else {
+                    tournament.players + 1
+                };
+            }
+            MenuButton::CycleTournamentRounds => {
+                let current = SERIES_LENGTHS.iter().position(|rounds| *rounds == tournament.rounds).unwrap_or(0);
+                tournament.rounds = SERIES_LENGTHS[(current + 1) % SERIES_LENGTHS.len()];
+            }
+            MenuButton::NextTurn if !tournament.finished() => {
+                // Jede Runde hat ihr eigenes Level, der Reihe nach aus der Kampagne.
+                let (round, player) = tournament.next_turn();
+                campaign.current = round % campaign.levels.len();
+                tournament.in_turn = true;
+                toasts.send(ShowToast(format!("Spieler {} ist dran", player + 1)));
+                transition.to(GameState::Playing);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::pool::{Pool, PoolPlugin};
+use crate::{phase_set, Ball, LevelEntity, Phase, Piercing, TimeScale};
+
+// Alle wie viele Sekunden ein neuer Punkt der Spur gesetzt wird und wie lange er sichtbar bleibt.
+// Gemessen in Schritten des Takts, damit die Spur bei jeder Taktrate gleich dicht ist.
+const TRAIL_INTERVAL: f32 = 2.0 / 60.0;
+const TRAIL_LIFETIME: f32 = 0.3;
+const TRAIL_SCALE: f32 = 0.6;
+const TRAIL_COLOR: Color = Color::rgb(1.0, 0.5, 0.5);
+const PIERCING_TRAIL_COLOR: Color = Color::rgb(1.0, 0.35, 0.0);
+
+// Die Spur eines Balls. Alle Punkte teilen sich ein Material, dessen Farbe sich mit dem Zustand des Balls ändert.
+#[derive(Component)]
+struct Trail {
+    material: Handle<StandardMaterial>,
+}
+
+#[derive(Component)]
+struct TrailDot {
+    size: Vec3,
+    lifetime: Timer,
+}
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(PoolPlugin::<TrailDot>::default())
+            .add_system(attach_trails)
+            .add_system(update_trail_color)
+            .add_system_set(
+                phase_set(Phase::Vfx)
+                    .with_system(spawn_trail_dots)
+                    .with_system(fade_trail_dots),
+            );
+    }
+}
+
+// Jeder neu gespawnte Ball bekommt automatisch eine Spur.
+fn attach_trails(
+    mut commands: Commands,
+    query: Query<Entity, Added<Ball>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for entity in &query {
+        let material = materials.add(StandardMaterial {
+            base_color: TRAIL_COLOR,
+            unlit: true,
+            ..default()
+        });
+        commands.entity(entity).insert(Trail { material });
+    }
+}
+
+fn update_trail_color(
+    query: Query<(&Trail, Option<&Piercing>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (trail, piercing) in &query {
+        let color = if piercing.is_some() { PIERCING_TRAIL_COLOR } else { TRAIL_COLOR };
+        if materials.get(&trail.material).is_some_and(|material| material.base_color != color) {
+            materials.get_mut(&trail.material).unwrap().base_color = color;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_trail_dots(
+    mut commands: Commands,
+    query: Query<(&Transform, &Trail)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut pool: ResMut<Pool<TrailDot>>,
+    settings: Res<AccessibilitySettings>,
+    time_scale: Res<TimeScale>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+    mut elapsed: Local<f32>,
+) {
+    *elapsed += time_scale.tick;
+    if *elapsed < TRAIL_INTERVAL || !settings.allows_motion() {
+        return;
+    }
+    *elapsed -= TRAIL_INTERVAL;
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(shape::UVSphere::default().into()))
+        .clone();
+    for (transform, trail) in &query {
+        let size = transform.scale * TRAIL_SCALE;
+        let dot = TrailDot {
+            size,
+            lifetime: Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once),
+        };
+        let dot_transform = Transform::from_translation(transform.translation).with_scale(size);
+        if let Some(entity) = pool.take() {
+            commands
+                .entity(entity)
+                .insert((dot_transform, trail.material.clone(), dot, Visibility::VISIBLE));
+            continue;
+        }
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: trail.material.clone(),
+                transform: dot_transform,
+                ..default()
+            },
+            dot,
+            LevelEntity,
+        ));
+    }
+}
+
+// Die Punkte schrumpfen über ihre Lebenszeit und kommen dann zurück in den Vorrat.
+fn fade_trail_dots(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut TrailDot, &Visibility)>,
+    mut pool: ResMut<Pool<TrailDot>>,
+    time_scale: Res<TimeScale>,
+) {
+    for (entity, mut transform, mut dot, visibility) in &mut query {
+        if !visibility.is_visible {
+            continue;
+        }
+        if dot.lifetime.tick(Duration::from_secs_f32(time_scale.step())).finished() {
+            pool.release(&mut commands, entity);
+        } else {
+            transform.scale = dot.size * dot.lifetime.percent_left();
+        }
+    }
+}
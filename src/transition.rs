@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::AccessibilitySettings;
+use crate::save::SaveData;
+use crate::GameState;
+
+// So lange dauert jede Hälfte eines Übergangs, das Zudecken und das Aufdecken.
+const HALF_DURATION: f32 = 0.25;
+const COVER_COLOR: Color = Color::BLACK;
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TransitionStyle {
+    #[default]
+    Fade,
+    // Ein schwarzer Balken wischt von links über das Bild und gibt es nach rechts wieder frei.
+    Wipe,
+    Off,
+}
+
+impl TransitionStyle {
+    const ALL: [TransitionStyle; 3] = [TransitionStyle::Fade, TransitionStyle::Wipe, TransitionStyle::Off];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TransitionStyle::Fade => "Blende",
+            TransitionStyle::Wipe => "Wischen",
+            TransitionStyle::Off => "Aus",
+        }
+    }
+
+    pub fn next(self) -> TransitionStyle {
+        let current = TransitionStyle::ALL.iter().position(|style| *style == self).unwrap_or(0);
+        TransitionStyle::ALL[(current + 1) % TransitionStyle::ALL.len()]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Phase {
+    #[default]
+    Idle,
+    Covering(GameState),
+    Revealing,
+}
+
+// Zustandswechsel laufen über diese Ressource statt direkt über State::set. Erst wenn das Bild ganz zugedeckt ist,
+// wird der Zustand gewechselt, sodass das Abbauen des alten und der Aufbau des neuen Bildschirms nicht zu sehen sind.
+#[derive(Resource, Default)]
+pub struct ScreenTransition {
+    phase: Phase,
+    timer: Timer,
+}
+
+impl ScreenTransition {
+    // Solange schon zugedeckt wird, werden weitere Wünsche ignoriert. So darf ein System, das jeden Frame
+    // dieselbe Bedingung prüft, den Wechsel auch mehrfach anfordern. Kommt ein Wunsch während des Aufdeckens,
+    // wird von der aktuellen Deckung aus gleich wieder zugedeckt.
+    pub fn to(&mut self, target: GameState) {
+        if let Phase::Covering(_) = self.phase {
+            return;
+        }
+        let covered = self.coverage();
+        self.phase = Phase::Covering(target);
+        self.timer = Timer::from_seconds(HALF_DURATION, TimerMode::Once);
+        self.timer.set_elapsed(Duration::from_secs_f32(covered * HALF_DURATION));
+    }
+
+    // Während eines Übergangs steht das Spiel still.
+    pub fn is_active(&self) -> bool {
+        self.phase != Phase::Idle
+    }
+
+    // Wie viel des Bildes gerade zugedeckt ist, von 0 bis 1.
+    fn coverage(&self) -> f32 {
+        match self.phase {
+            Phase::Idle => 0.0,
+            Phase::Covering(_) => self.timer.percent(),
+            Phase::Revealing => self.timer.percent_left(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct TransitionCover;
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        // Der Stil kommt aus dem Spielstand, das SavePlugin muss also vorher hinzugefügt werden.
+        let style = app.world.resource::<SaveData>().transition_style;
+        app.insert_resource(style)
+            .init_resource::<ScreenTransition>()
+            .add_startup_system(spawn_cover)
+            .add_system(store_transition_style)
+            .add_system(drive_transition)
+            .add_system(update_cover.after(drive_transition));
+    }
+}
+
+fn store_transition_style(style: Res<TransitionStyle>, mut save: ResMut<SaveData>) {
+    if style.is_changed() && save.transition_style != *style {
+        save.transition_style = *style;
+    }
+}
+
+// Die Abdeckung liegt über allem anderen, auch über Menüs und Meldungen.
+fn spawn_cover(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        TransitionCover,
+    ));
+}
+
+// Übergänge laufen in echter Zeit, damit sie auch bei angehaltenem Spiel weitergehen.
+fn drive_transition(
+    time: Res<Time>,
+    style: Res<TransitionStyle>,
+    mut transition: ResMut<ScreenTransition>,
+    mut state: ResMut<State<GameState>>,
+) {
+    let Phase::Covering(target) = transition.phase else {
+        if transition.phase == Phase::Revealing && transition.timer.tick(time.delta()).finished() {
+            transition.phase = Phase::Idle;
+        }
+        return;
+    };
+    if *style != TransitionStyle::Off && !transition.timer.tick(time.delta()).finished() {
+        return;
+    }
+    // Der Wechsel kann scheitern, wenn das Ziel schon der aktuelle Zustand ist. Dann wird nur wieder aufgedeckt.
+    let _ = state.set(target);
+    transition.phase = if *style == TransitionStyle::Off { Phase::Idle } else { Phase::Revealing };
+    transition.timer = Timer::from_seconds(HALF_DURATION, TimerMode::Once);
+}
+
+// Mit weniger Bewegung wird statt zu wischen immer nur abgeblendet.
+fn update_cover(
+    transition: Res<ScreenTransition>,
+    style: Res<TransitionStyle>,
+    settings: Res<AccessibilitySettings>,
+    mut query: Query<(&mut Style, &mut BackgroundColor), With<TransitionCover>>,
+) {
+    if !transition.is_changed() && !style.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let Ok((mut node_style, mut color)) = query.get_single_mut() else { return };
+    let coverage = transition.coverage();
+    if *style == TransitionStyle::Wipe && settings.allows_motion() {
+        node_style.size.width = Val::Percent(coverage * 100.0);
+        // Beim Zudecken wächst der Balken von links, beim Aufdecken schrumpft er nach rechts.
+        node_style.position = if transition.phase == Phase::Revealing {
+            UiRect {
+                right: Val::Px(0.0),
+                ..default()
+            }
+        } else {
+            UiRect {
+                left: Val::Px(0.0),
+                ..default()
+            }
+        };
+        *color = COVER_COLOR.into();
+    } else {
+        node_style.size.width = Val::Percent(100.0);
+        let mut cover = COVER_COLOR;
+        cover.set_a(coverage);
+        *color = cover.into();
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::accessibility::AccessibilitySettings;
+use crate::format;
+use crate::sound::{PlaySound, Sfx};
+use crate::tween::{TextAlphaLens, Tween, TweenCompleted, UiPositionLens};
+use crate::{
+    phase_set, BrickDestroyed, BrickHit, CollisionEvent, GameState, LevelEntity, MainCamera, PaddleHit, Phase,
+    Scoreboard,
+};
+
+// Ab so vielen Abprallern an den Wänden zwischen Paddle und Brick gilt ein Treffer als Trickschuss.
+const MIN_RICOCHETS: u32 = 3;
+// Der Bonus verdoppelt sich mit jedem weiteren Abpraller, höchstens bis zu dieser Zahl.
+const MAX_RICOCHETS: u32 = 6;
+const TRICK_SHOT_POINTS: usize = 25;
+const POPUP_DURATION: f32 = 1.2;
+// So viele Pixel steigt der Text über seine Lebenszeit auf.
+const POPUP_RISE: f32 = 40.0;
+const POPUP_FONT_SIZE: f32 = 26.0;
+const POPUP_COLOR: Color = Color::rgb(1.0, 0.85, 0.2);
+
+// Abpraller an den Wänden seit der letzten Berührung des Paddles oder dem letzten Treffer eines Bricks.
+#[derive(Resource, Default)]
+struct Ricochets(u32);
+
+// Wird gesendet, wenn ein Brick über mehrere Banden zerstört wurde.
+struct TrickShot {
+    position: Vec3,
+    ricochets: u32,
+    bonus: usize,
+}
+
+// Ein Text über der Stelle des Trickschusses. Er steigt auf und blendet aus, danach verschwindet er.
+#[derive(Component)]
+struct TrickShotPopup;
+
+pub struct TrickShotPlugin;
+
+impl Plugin for TrickShotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ricochets>()
+            .add_event::<TrickShot>()
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(reset_ricochets))
+            .add_system_set(phase_set(Phase::Scoring).with_system(count_ricochets))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(spawn_popups)
+                    .with_system(despawn_popups),
+            );
+    }
+}
+
+fn reset_ricochets(mut ricochets: ResMut<Ricochets>) {
+    ricochets.0 = 0;
+}
+
+fn bonus(ricochets: u32) -> usize {
+    TRICK_SHOT_POINTS << (ricochets.min(MAX_RICOCHETS) - MIN_RICOCHETS)
+}
+
+// Erst zählen die Wände dieses Schritts, dann wird ein zerstörter Brick gewertet, danach beginnt die Zählung
+// bei jedem Treffer und am Paddle von vorn.
+fn count_ricochets(
+    mut ricochets: ResMut<Ricochets>,
+    mut collisions: EventReader<CollisionEvent>,
+    mut paddle_hits: EventReader<PaddleHit>,
+    mut brick_hits: EventReader<BrickHit>,
+    mut destroyed: EventReader<BrickDestroyed>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut trick_shots: EventWriter<TrickShot>,
+) {
+    ricochets.0 += collisions.iter().filter(|event| event.wall).count() as u32;
+    if ricochets.0 >= MIN_RICOCHETS {
+        for event in destroyed.iter() {
+            let bonus = bonus(ricochets.0);
+            scoreboard.score += bonus;
+            trick_shots.send(TrickShot {
+                position: event.position,
+                ricochets: ricochets.0,
+                bonus,
+            });
+        }
+    } else {
+        destroyed.clear();
+    }
+    if paddle_hits.iter().count() > 0 || brick_hits.iter().count() > 0 {
+        ricochets.0 = 0;
+    }
+}
+
+// Der Text wird an der Bildschirmstelle des Bricks verankert, die Kamera bewegt sich während eines Levels kaum.
+fn spawn_popups(
+    mut commands: Commands,
+    mut events: EventReader<TrickShot>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    asset_server: Res<AssetServer>,
+    settings: Res<AccessibilitySettings>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.get_single() else { return };
+    for event in events.iter() {
+        sounds.send(PlaySound(Sfx::TrickShot));
+        let Some(origin) = camera.world_to_viewport(camera_transform, event.position) else { continue };
+        let mut popup = commands.spawn((
+            TextBundle::from_section(
+                format!("Trickschuss x{}  +{}", event.ricochets, format::number(event.bonus)),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                    font_size: POPUP_FONT_SIZE,
+                    color: POPUP_COLOR,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Px(origin.x),
+                    bottom: Val::Px(origin.y),
+                    ..default()
+                },
+                ..default()
+            }),
+            Tween::new(TextAlphaLens { start: 1.0, end: 0.0 }, POPUP_DURATION),
+            TrickShotPopup,
+            LevelEntity,
+        ));
+        // Mit weniger Bewegung bleibt der Text stehen und blendet nur aus.
+        if settings.allows_motion() {
+            let rise = UiPositionLens {
+                start: UiRect { left: Val::Px(origin.x), bottom: Val::Px(origin.y), ..default() },
+                end: UiRect { left: Val::Px(origin.x), bottom: Val::Px(origin.y + POPUP_RISE), ..default() },
+            };
+            popup.insert(Tween::new(rise, POPUP_DURATION));
+        }
+    }
+}
+
+// Aufsteigen und Ausblenden enden gleichzeitig, jeder Text wird trotzdem nur einmal entfernt.
+fn despawn_popups(
+    mut commands: Commands,
+    mut completed: EventReader<TweenCompleted>,
+    query: Query<(), With<TrickShotPopup>>,
+) {
+    let mut finished: Vec<Entity> =
+        completed.iter().map(|event| event.entity).filter(|entity| query.contains(*entity)).collect();
+    finished.sort();
+    finished.dedup();
+    for entity in finished {
+        commands.entity(entity).despawn_recursive();
+    }
+}
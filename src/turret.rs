@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use rand::Rng;
+
+use crate::collision::{CollisionLayers, Layer};
+use crate::grid::BrickGrid;
+use crate::level::BrickKind;
+use crate::paddle_size::ResizePaddle;
+use crate::rng::GameRng;
+use crate::sound::{PlaySound, PlaySoundAt, Sfx};
+use crate::{phase_set, Arena, Brick, Kinematic, LevelEntity, Paddle, Phase, TimeScale};
+
+const FIRE_INTERVAL: f32 = 4.0;
+// So lange vor dem Schuss glüht das Geschütz auf, damit man ausweichen kann.
+const TELEGRAPH_DURATION: f32 = 0.8;
+const TELEGRAPH_GLOW: Color = Color::rgb(1.0, 0.25, 0.0);
+const SHOT_SPEED: f32 = 2.5;
+const SHOT_SIZE: f32 = 0.2;
+const SHOT_COLOR: Color = Color::rgb(1.0, 0.3, 0.1);
+// Ein Treffer macht das Paddle kurz schmaler.
+const HIT_SHRINK_FACTOR: f32 = 0.6;
+const HIT_SHRINK_DURATION: f32 = 3.0;
+
+#[derive(Component)]
+struct Turret {
+    reload: Timer,
+    charging: bool,
+}
+
+#[derive(Component)]
+struct TurretShot;
+
+pub struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(arm_turrets)
+            .add_system_set(phase_set(Phase::Scoring).with_system(fire_turrets))
+            .add_system_set(phase_set(Phase::Collision).with_system(shot_hits));
+    }
+}
+
+// Jedes Geschütz bekommt ein eigenes Material zum Aufglühen. Die Nachladezeit beginnt zufällig versetzt,
+// damit nicht alle Geschütze gleichzeitig schießen.
+fn arm_turrets(
+    mut commands: Commands,
+    brick_grid: Res<BrickGrid>,
+    mut query: Query<(Entity, &Brick, &mut Handle<StandardMaterial>), Added<Brick>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = &mut *game_rng;
+    for (entity, brick, mut material) in &mut query {
+        if brick_grid.get(brick.row, brick.column).map(|cell| cell.kind) != Some(BrickKind::Turret) {
+            continue;
+        }
+        if let Some(own_material) = materials.get(&material).cloned() {
+            *material = materials.add(own_material);
+        }
+        let mut reload = Timer::from_seconds(FIRE_INTERVAL, TimerMode::Repeating);
+        reload.tick(Duration::from_secs_f32(rng.gen_range(0.0..FIRE_INTERVAL - TELEGRAPH_DURATION)));
+        commands.entity(entity).insert(Turret { reload, charging: false });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fire_turrets(
+    mut commands: Commands,
+    mut query: Query<(&Transform, &mut Turret, &Handle<StandardMaterial>)>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut sounds: EventWriter<PlaySoundAt>,
+    time_scale: Res<TimeScale>,
+    mut shot_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    let Ok(paddle_transform) = paddle_query.get_single() else { return };
+    let step = Duration::from_secs_f32(time_scale.step());
+    for (transform, mut turret, material) in &mut query {
+        let fired = turret.reload.tick(step).just_finished();
+        let remaining = turret.reload.remaining_secs();
+        let charge = if fired { 0.0 } else { (1.0 - remaining / TELEGRAPH_DURATION).max(0.0) };
+        if charge > 0.0 && !turret.charging {
+            sounds.send(PlaySoundAt(Sfx::TurretCharge, transform.translation));
+        }
+        turret.charging = charge > 0.0;
+        if let Some(material) = materials.get_mut(material) {
+            material.emissive = TELEGRAPH_GLOW * charge;
+        }
+        if !fired {
+            continue;
+        }
+
+        let (mesh, shot_material) = shot_assets.get_or_insert_with(|| {
+            (
+                meshes.add(shape::UVSphere::default().into()),
+                materials.add(StandardMaterial {
+                    base_color: SHOT_COLOR,
+                    emissive: SHOT_COLOR,
+                    ..default()
+                }),
+            )
+        });
+        let direction = (paddle_transform.translation - transform.translation).truncate().normalize_or_zero();
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh.clone(),
+                material: shot_material.clone(),
+                transform: Transform::from_translation(transform.translation).with_scale(Vec3::splat(SHOT_SIZE)),
+                ..default()
+            },
+            TurretShot,
+            Kinematic::moving((direction * SHOT_SPEED).extend(0.0)),
+            CollisionLayers::from(Layer::EnemyShot),
+            LevelEntity,
+        ));
+    }
+}
+
+// Trifft ein Schuss das Paddle, schrumpft es kurz. Verfehlte Schüsse verschwinden unter der Arena.
+fn shot_hits(
+    mut commands: Commands,
+    shot_query: Query<(Entity, &Transform, &CollisionLayers), With<TurretShot>>,
+    paddle_query: Query<(&Transform, &CollisionLayers), With<Paddle>>,
+    arena: Res<Arena>,
+    mut resize_events: EventWriter<ResizePaddle>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    let Ok((paddle_transform, paddle_layers)) = paddle_query.get_single() else { return };
+    for (entity, transform, layers) in &shot_query {
+        let hit = layers.interacts_with(paddle_layers)
+            && collide(
+                transform.translation,
+                Vec2::splat(SHOT_SIZE),
+                paddle_transform.translation,
+                paddle_transform.scale.truncate(),
+            )
+            .is_some();
+        if hit {
+            resize_events.send(ResizePaddle {
+                factor: HIT_SHRINK_FACTOR,
+                duration: HIT_SHRINK_DURATION,
+            });
+            sounds.send(PlaySound(Sfx::Hazard));
+        }
+        if hit || transform.translation.y < arena.bottom() - 1.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+
+use crate::accessibility::Palette;
+use crate::controls::ActiveControls;
+use crate::launch::HeldBall;
+use crate::level::Level;
+use crate::mode::GameMode;
+use crate::powerup::{spawn_powerup, PowerUp, PowerUpCollected, PowerUpKind};
+use crate::{Arena, BrickDestroyed, GameState, GridGeometry, LevelEntity, Paddle};
+
+const TUTORIAL_LEVEL_NAME: &str = "Tutorial";
+// So weit muss das Paddle bewegt werden, bevor es weitergeht.
+const MOVE_DISTANCE: f32 = 1.5;
+// So weit unter der Decke taucht das Übungs-Power-Up über dem Paddle auf.
+const POWERUP_DROP_DEPTH: f32 = 1.0;
+const PROMPT_FONT_SIZE: f32 = 32.0;
+const PROMPT_COLOR: Color = Color::rgb(0.1, 0.1, 0.4);
+
+// Die Schritte des Tutorials. Jeder endet erst, wenn das passende Ereignis im Spiel eingetreten ist.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum TutorialStep {
+    #[default]
+    Launch,
+    Move,
+    BreakBrick,
+    CatchPowerUp,
+    ClearLevel,
+}
+
+impl TutorialStep {
+    fn prompt(self, gamepad: bool) -> &'static str {
+        match self {
+            TutorialStep::Launch if gamepad => "Ziele mit dem linken Stick und schieß den Ball mit A ab",
+            TutorialStep::Move if gamepad => "Drück den linken Stick zur Seite, um das Paddle zu bewegen",
+            TutorialStep::Launch => "Ziele mit Links/Rechts und schieß den Ball mit der Leertaste ab",
+            TutorialStep::Move => "Halte Links/Rechts gedrückt, um das Paddle zu bewegen",
+            TutorialStep::BreakBrick => "Lenke den Ball mit dem Paddle gegen einen Brick",
+            TutorialStep::CatchPowerUp => "Fang das fallende Power-Up mit dem Paddle",
+            TutorialStep::ClearLevel => "Zerstöre alle Bricks",
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct Tutorial {
+    step: TutorialStep,
+    // Wo das Paddle zu Beginn des ersten Schritts stand.
+    paddle_start: Option<f32>,
+}
+
+// Das Level des Tutorials. Es wird beim Start in die Assets gelegt und aus dem Menü wie ein eigenes Level gestartet.
+#[derive(Resource)]
+pub struct TutorialLevel(pub Handle<Level>);
+
+#[derive(Component)]
+struct PromptText;
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tutorial>()
+            .add_startup_system(add_tutorial_level)
+            .add_system_set(SystemSet::on_enter(GameState::Playing).with_system(start_tutorial))
+            .add_system_set(
+                SystemSet::on_update(GameState::Playing)
+                    .with_system(advance_tutorial)
+                    .with_system(offer_powerup.after(advance_tutorial))
+                    .with_system(update_prompt.after(advance_tutorial)),
+            );
+    }
+}
+
+// Zwei volle Reihen oben im Raster, ohne Strafbricks und ohne Hindernisse.
+fn add_tutorial_level(mut commands: Commands, mut levels: ResMut<Assets<Level>>, arena: Res<Arena>) {
+    let row = "#".repeat(GridGeometry::new(&arena).columns);
+    let level = Level {
+        name: TUTORIAL_LEVEL_NAME.to_string(),
+        gravity: false,
+        force_fields: Vec::new(),
+        bumpers: Vec::new(),
+        portals: Vec::new(),
+        obstacles: Vec::new(),
+        floor: Vec::new(),
+        objectives: Vec::new(),
+        par: None,
+        ball_speed: 0.8,
+        layout: Some(vec![String::new(), row.clone(), row]),
+        arena: None,
+        camera: None,
+        ball: None,
+    };
+    commands.insert_resource(TutorialLevel(levels.add(level)));
+}
+
+fn start_tutorial(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut tutorial: ResMut<Tutorial>,
+    asset_server: Res<AssetServer>,
+) {
+    if *mode != GameMode::Tutorial {
+        return;
+    }
+    *tutorial = Tutorial::default();
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                font_size: PROMPT_FONT_SIZE,
+                color: PROMPT_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(20.0),
+                left: Val::Px(20.0),
+                ..default()
+            },
+            ..default()
+        }),
+        PromptText,
+        LevelEntity,
+    ));
+}
+
+fn advance_tutorial(
+    mode: Res<GameMode>,
+    mut tutorial: ResMut<Tutorial>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    held_query: Query<(), With<HeldBall>>,
+    mut brick_events: EventReader<BrickDestroyed>,
+    mut powerup_events: EventReader<PowerUpCollected>,
+) {
+    // Die Ereignisse werden in jedem Fall gelesen, damit keine alten in den nächsten Schritt rutschen.
+    let brick_destroyed = brick_events.iter().count() > 0;
+    let powerup_collected = powerup_events.iter().count() > 0;
+    if *mode != GameMode::Tutorial {
+        return;
+    }
+    match tutorial.step {
+        TutorialStep::Launch if held_query.is_empty() => tutorial.step = TutorialStep::Move,
+        TutorialStep::Move => {
+            let Ok(paddle) = paddle_query.get_single() else { return };
+            let start = *tutorial.paddle_start.get_or_insert(paddle.translation.x);
+            if (paddle.translation.x - start).abs() >= MOVE_DISTANCE {
+                tutorial.step = TutorialStep::BreakBrick;
+            }
+        }
+        TutorialStep::BreakBrick if brick_destroyed => tutorial.step = TutorialStep::CatchPowerUp,
+        TutorialStep::CatchPowerUp if powerup_collected => tutorial.step = TutorialStep::ClearLevel,
+        // Geschafft ist das Tutorial, wenn check_level_cleared keine Bricks mehr findet.
+        _ => {}
+    }
+}
+
+// Solange das Power-Up gefangen werden soll, fällt immer eines. Wer es verpasst, bekommt gleich das nächste.
+#[allow(clippy::too_many_arguments)]
+fn offer_powerup(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    tutorial: Res<Tutorial>,
+    powerup_query: Query<(), With<PowerUp>>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    palette: Res<Palette>,
+    arena: Res<Arena>,
+) {
+    if *mode != GameMode::Tutorial || tutorial.step != TutorialStep::CatchPowerUp || !powerup_query.is_empty() {
+        return;
+    }
+    let Ok(paddle) = paddle_query.get_single() else { return };
+    let position = Vec3::new(paddle.translation.x, arena.top() - POWERUP_DROP_DEPTH, 0.0);
+    let mesh = meshes.add(shape::Cube::default().into());
+    spawn_powerup(&mut commands, mesh, &mut materials, &palette, PowerUpKind::Expand, position);
+}
+
+// Der Hinweis nennt die Tasten des Geräts, mit dem gerade gespielt wird.
+fn update_prompt(
+    tutorial: Res<Tutorial>,
+    controls: Res<ActiveControls>,
+    mut query: Query<&mut Text, With<PromptText>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else { return };
+    text.sections[0].value = tutorial.step.prompt(controls.is_gamepad()).to_string();
+}
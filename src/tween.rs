@@ -0,0 +1,183 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::easing::Ease;
+
+// Was ein Tween verändert. Die Linse bekommt den Fortschritt schon durch die Kurve gelaufen, 0 am Anfang und 1 am
+// Ziel, bei überschwingenden Kurven zwischendurch auch etwas mehr.
+pub trait Lens: Send + Sync + 'static {
+    type Target: Component;
+
+    fn apply(&self, target: &mut Self::Target, ratio: f32);
+}
+
+// Eine Animation von einem Wert zu einem anderen. Sie läuft in echter Zeit, wie Meldungen und Übergänge, und wird
+// nach dem letzten Schritt entfernt. Während der Verzögerung steht das Ziel schon auf dem Anfangswert.
+#[derive(Component)]
+pub struct Tween<L: Lens> {
+    lens: L,
+    ease: Ease,
+    duration: f32,
+    delay: f32,
+    elapsed: f32,
+}
+
+impl<L: Lens> Tween<L> {
+    pub fn new(lens: L, duration: f32) -> Self {
+        Tween { lens, ease: Ease::default(), duration, delay: 0.0, elapsed: 0.0 }
+    }
+
+    pub fn with_ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        ((self.elapsed - self.delay) / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+// Alle Tweens laufen unter diesem Label. Systeme, die dasselbe setzen und dabei Vorrang haben sollen, laufen danach.
+#[derive(SystemLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TweenSystem;
+
+// Wird gesendet, wenn ein Tween sein Ziel erreicht hat und entfernt wird.
+pub struct TweenCompleted {
+    pub entity: Entity,
+}
+
+pub struct TranslationLens {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Lens for TranslationLens {
+    type Target = Transform;
+
+    fn apply(&self, transform: &mut Transform, ratio: f32) {
+        transform.translation = self.start + (self.end - self.start) * ratio;
+    }
+}
+
+// Bewegt und dreht zugleich, etwa eine Kamera. Die Skalierung bleibt, wie sie ist.
+pub struct TransformLens {
+    pub start: Transform,
+    pub end: Transform,
+}
+
+impl Lens for TransformLens {
+    type Target = Transform;
+
+    fn apply(&self, transform: &mut Transform, ratio: f32) {
+        transform.translation = self.start.translation + (self.end.translation - self.start.translation) * ratio;
+        transform.rotation = self.start.rotation.slerp(self.end.rotation, ratio);
+    }
+}
+
+pub struct ScaleLens {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Lens for ScaleLens {
+    type Target = Transform;
+
+    fn apply(&self, transform: &mut Transform, ratio: f32) {
+        transform.scale = self.start + (self.end - self.start) * ratio;
+    }
+}
+
+// Verschiebt einen UI-Knoten. Nur Seiten, die am Anfang und am Ende in Pixeln angegeben sind, werden überblendet,
+// alle anderen stehen gleich auf ihrem Endwert.
+pub struct UiPositionLens {
+    pub start: UiRect,
+    pub end: UiRect,
+}
+
+impl Lens for UiPositionLens {
+    type Target = Style;
+
+    fn apply(&self, style: &mut Style, ratio: f32) {
+        let lerp = |start: Val, end: Val| match (start, end) {
+            (Val::Px(start), Val::Px(end)) => Val::Px(start + (end - start) * ratio),
+            _ => end,
+        };
+        style.position = UiRect {
+            left: lerp(self.start.left, self.end.left),
+            right: lerp(self.start.right, self.end.right),
+            top: lerp(self.start.top, self.end.top),
+            bottom: lerp(self.start.bottom, self.end.bottom),
+        };
+    }
+}
+
+// Blendet alle Abschnitte eines Textes gemeinsam ein oder aus.
+pub struct TextAlphaLens {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Lens for TextAlphaLens {
+    type Target = Text;
+
+    fn apply(&self, text: &mut Text, ratio: f32) {
+        let alpha = (self.start + (self.end - self.start) * ratio).clamp(0.0, 1.0);
+        for section in &mut text.sections {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+// Das Event und die Linsen, die mehrere Funktionen brauchen. Eigene Linsen bringen ihr LensPlugin selbst mit.
+pub struct TweenPlugin;
+
+impl Plugin for TweenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TweenCompleted>()
+            .add_plugin(LensPlugin::<TranslationLens>::default())
+            .add_plugin(LensPlugin::<TransformLens>::default())
+            .add_plugin(LensPlugin::<ScaleLens>::default())
+            .add_plugin(LensPlugin::<UiPositionLens>::default())
+            .add_plugin(LensPlugin::<TextAlphaLens>::default());
+    }
+}
+
+pub struct LensPlugin<L: Lens>(PhantomData<L>);
+
+impl<L: Lens> Default for LensPlugin<L> {
+    fn default() -> Self {
+        LensPlugin(PhantomData)
+    }
+}
+
+impl<L: Lens> Plugin for LensPlugin<L> {
+    fn build(&self, app: &mut App) {
+        app.add_system(animate_tweens::<L>.label(TweenSystem));
+    }
+}
+
+fn animate_tweens<L: Lens>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Tween<L>, &mut L::Target)>,
+    mut completed: EventWriter<TweenCompleted>,
+) {
+    for (entity, mut tween, mut target) in &mut query {
+        tween.elapsed += time.delta_seconds();
+        let progress = tween.progress();
+        tween.lens.apply(&mut target, tween.ease.apply(progress));
+        if progress >= 1.0 {
+            commands.entity(entity).remove::<Tween<L>>();
+            completed.send(TweenCompleted { entity });
+        }
+    }
+}